@@ -0,0 +1,309 @@
+use std::fmt;
+
+use crate::lexer::{escape_string, Cursor, Diagnostic, Span};
+use crate::AlnError;
+
+/// A block-syntax file (`crates/organiccpu/**/*.aln`): top-level items,
+/// usually named blocks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlnDocument {
+    pub items: Vec<Item>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Item {
+    Block(Block),
+    Field(Field),
+}
+
+/// `name { item; item; ... };` — the trailing `;` is optional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    /// `///` comments directly above the block.
+    pub docs: Vec<String>,
+    pub name: String,
+    pub items: Vec<Item>,
+    pub span: Span,
+}
+
+/// `key: value` terminated by `;` or `,` (optional before `}`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub docs: Vec<String>,
+    pub key: String,
+    pub value: Value,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Str(String),
+    Num(f64),
+    Bool(bool),
+    /// Unquoted token that is not a number or bool: `NONE`, `0.1_ms`,
+    /// `0_0:0_99`, `...`.
+    Atom(String),
+    List(Vec<Value>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) | Value::Atom(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Num(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+impl Block {
+    /// First field named `key` directly in this block.
+    pub fn field(&self, key: &str) -> Option<&Value> {
+        self.items.iter().find_map(|i| match i {
+            Item::Field(f) if f.key == key => Some(&f.value),
+            _ => None,
+        })
+    }
+
+    /// Direct child blocks named `name` (names may repeat).
+    pub fn blocks<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Block> + 'a {
+        self.items.iter().filter_map(move |i| match i {
+            Item::Block(b) if b.name == name => Some(b),
+            _ => None,
+        })
+    }
+}
+
+impl AlnDocument {
+    pub fn block(&self, name: &str) -> Option<&Block> {
+        self.items.iter().find_map(|i| match i {
+            Item::Block(b) if b.name == name => Some(b),
+            _ => None,
+        })
+    }
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.'
+}
+
+fn is_atom_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, ';' | ',' | '[' | ']' | '{' | '}' | '"')
+}
+
+fn looks_numeric(s: &str) -> bool {
+    let digits = s.trim_start_matches(['+', '-']);
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        || (digits.starts_with('.') && digits[1..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+struct Parser<'a> {
+    cur: Cursor<'a>,
+}
+
+impl<'a> Parser<'a> {
+    fn trivia(&mut self) -> Vec<String> {
+        let mut docs = Vec::new();
+        self.cur.skip_trivia(&mut docs);
+        docs
+    }
+
+    fn expect(&mut self, c: char, what: &str) -> Result<(), Diagnostic> {
+        if self.cur.eat(c) {
+            Ok(())
+        } else {
+            Err(Diagnostic::new(self.cur.here(), format!("expected `{c}` {what}")))
+        }
+    }
+
+    /// Items up to `}` (inside a block) or end of input (top level).
+    fn items(&mut self, in_block: bool) -> Result<Vec<Item>, Diagnostic> {
+        let mut items = Vec::new();
+        loop {
+            let docs = self.trivia();
+            match self.cur.peek() {
+                None if in_block => {
+                    return Err(Diagnostic::new(self.cur.here(), "unexpected end of input, expected `}`"))
+                }
+                None => return Ok(items),
+                Some('}') if in_block => return Ok(items),
+                _ => items.push(self.item(docs, in_block)?),
+            }
+        }
+    }
+
+    fn item(&mut self, docs: Vec<String>, in_block: bool) -> Result<Item, Diagnostic> {
+        let m = self.cur.mark();
+        let name = self.cur.take_while(is_key_char);
+        if name.is_empty() {
+            let found = self.cur.peek().map(String::from).unwrap_or_default();
+            let mut span = self.cur.here();
+            span.end += found.len();
+            return Err(Diagnostic::new(span, format!("expected a name, found `{found}`")));
+        }
+        self.trivia();
+        match self.cur.peek() {
+            Some('{') => {
+                self.cur.bump();
+                let items = self.items(true)?;
+                self.expect('}', "to close block")?;
+                let span = self.cur.span_from(m);
+                self.trivia();
+                self.cur.eat(';');
+                Ok(Item::Block(Block {
+                    docs,
+                    name: name.to_string(),
+                    items,
+                    span,
+                }))
+            }
+            Some(':') => {
+                self.cur.bump();
+                let value = self.value()?;
+                let span = self.cur.span_from(m);
+                self.trivia();
+                let closed = self.cur.eat(';') || self.cur.eat(',');
+                let at_end = match self.cur.peek() {
+                    Some('}') => in_block,
+                    None => !in_block,
+                    _ => false,
+                };
+                if !closed && !at_end {
+                    return Err(Diagnostic::new(self.cur.here(), format!("expected `;` after field `{name}`")));
+                }
+                Ok(Item::Field(Field {
+                    docs,
+                    key: name.to_string(),
+                    value,
+                    span,
+                }))
+            }
+            _ => Err(Diagnostic::new(
+                self.cur.span_from(m),
+                format!("expected `{{` or `:` after `{name}`"),
+            )),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Diagnostic> {
+        self.trivia();
+        match self.cur.peek() {
+            Some('"') => Ok(Value::Str(self.cur.read_string()?.0)),
+            Some('[') => {
+                self.cur.bump();
+                let mut list = Vec::new();
+                loop {
+                    self.trivia();
+                    if self.cur.eat(']') {
+                        return Ok(Value::List(list));
+                    }
+                    list.push(self.value()?);
+                    self.trivia();
+                    if !self.cur.eat(',') && self.cur.peek() != Some(']') {
+                        return Err(Diagnostic::new(self.cur.here(), "expected `,` or `]` in list"));
+                    }
+                }
+            }
+            _ => {
+                let m = self.cur.mark();
+                let mut atom = String::new();
+                // `//` starts a comment even directly after a value.
+                while let Some(c) = self.cur.peek() {
+                    if !is_atom_char(c) || self.cur.starts_with("//") {
+                        break;
+                    }
+                    atom.push(c);
+                    self.cur.bump();
+                }
+                if atom.is_empty() {
+                    return Err(Diagnostic::new(self.cur.span_from(m), "expected a value"));
+                }
+                Ok(match atom.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    s if looks_numeric(s) => match s.parse::<f64>() {
+                        Ok(n) if n.is_finite() => Value::Num(n),
+                        _ => Value::Atom(atom),
+                    },
+                    _ => Value::Atom(atom),
+                })
+            }
+        }
+    }
+}
+
+/// Parse a block-syntax `.aln` file. Stops at the first error.
+pub fn parse_document(src: &str) -> Result<AlnDocument, AlnError> {
+    let mut p = Parser {
+        cur: Cursor::new(src),
+    };
+    p.items(false)
+        .map(|items| AlnDocument { items })
+        .map_err(|d| AlnError {
+            diagnostics: vec![d],
+        })
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Str(s) => f.write_str(&escape_string(s)),
+            Value::Num(n) => write!(f, "{n}"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Atom(a) => f.write_str(a),
+            Value::List(items) => {
+                f.write_str("[")?;
+                for (i, v) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+fn write_items(f: &mut fmt::Formatter<'_>, items: &[Item], depth: usize) -> fmt::Result {
+    let pad = "  ".repeat(depth);
+    for item in items {
+        let docs = match item {
+            Item::Block(b) => &b.docs,
+            Item::Field(fl) => &fl.docs,
+        };
+        for d in docs {
+            writeln!(f, "{pad}/// {d}")?;
+        }
+        match item {
+            Item::Field(fl) => writeln!(f, "{pad}{}: {};", fl.key, fl.value)?,
+            Item::Block(b) => {
+                writeln!(f, "{pad}{} {{", b.name)?;
+                write_items(f, &b.items, depth + 1)?;
+                writeln!(f, "{pad}}};")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Canonical form: two-space indent, one item per line, every item
+/// terminated by `;`. Plain `//` comments are not preserved.
+impl fmt::Display for AlnDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_items(f, &self.items, 0)
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+/// Byte range in the source plus the 1-based line/column of its start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// A parse error anchored to a span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub span: Span,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    /// Message followed by the offending source line and a caret marker.
+    pub fn render(&self, src: &str) -> String {
+        let line = src.lines().nth(self.span.line as usize - 1).unwrap_or("");
+        let col = self.span.col as usize;
+        let width = (self.span.end - self.span.start).max(1);
+        let width = width.min(line.len().saturating_sub(col - 1).max(1));
+        format!(
+            "{}\n  {}\n  {}{}",
+            self,
+            line,
+            " ".repeat(col - 1),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.span.line, self.span.col, self.message)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mark {
+    pos: usize,
+    line: u32,
+    col: u32,
+}
+
+/// Character cursor shared by the shard and block parsers; tracks line and
+/// column so every token can carry an exact span.
+pub struct Cursor<'a> {
+    src: &'a str,
+    pos: usize,
+    line: u32,
+    col: u32,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            line: 1,
+            col: 1,
+        }
+    }
+
+    pub fn is_eof(&self) -> bool {
+        self.pos >= self.src.len()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    pub fn starts_with(&self, s: &str) -> bool {
+        self.src[self.pos..].starts_with(s)
+    }
+
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn mark(&self) -> Mark {
+        Mark {
+            pos: self.pos,
+            line: self.line,
+            col: self.col,
+        }
+    }
+
+    pub fn span_from(&self, m: Mark) -> Span {
+        Span {
+            start: m.pos,
+            end: self.pos,
+            line: m.line,
+            col: m.col,
+        }
+    }
+
+    /// Zero-width span at the current position.
+    pub fn here(&self) -> Span {
+        self.span_from(self.mark())
+    }
+
+    pub fn slice(&self, m: Mark) -> &'a str {
+        &self.src[m.pos..self.pos]
+    }
+
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let m = self.mark();
+        while let Some(c) = self.peek() {
+            if !pred(c) {
+                break;
+            }
+            self.bump();
+        }
+        self.slice(m)
+    }
+
+    /// Skip spaces and tabs, staying on the current line.
+    pub fn skip_inline_ws(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t' || c == '\r');
+    }
+
+    /// Skip whitespace and `//` comments. `///` doc comment text is appended
+    /// to `docs` so the parser can attach it to the next item.
+    pub fn skip_trivia(&mut self, docs: &mut Vec<String>) {
+        loop {
+            self.take_while(char::is_whitespace);
+            if self.starts_with("///") {
+                self.bump();
+                self.bump();
+                self.bump();
+                let text = self.take_while(|c| c != '\n');
+                docs.push(text.trim().to_string());
+            } else if self.starts_with("//") {
+                self.take_while(|c| c != '\n');
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Rest of the current line (without the newline), which is consumed.
+    pub fn rest_of_line(&mut self) -> (&'a str, Span) {
+        let m = self.mark();
+        let text = self.take_while(|c| c != '\n');
+        let span = self.span_from(m);
+        self.eat('\n');
+        (text, span)
+    }
+
+    /// Double-quoted string with `\"`, `\\`, `\n` and `\t` escapes.
+    pub fn read_string(&mut self) -> Result<(String, Span), Diagnostic> {
+        let m = self.mark();
+        if !self.eat('"') {
+            return Err(Diagnostic::new(self.here(), "expected string"));
+        }
+        let mut out = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n') => {
+                    return Err(Diagnostic::new(self.span_from(m), "unterminated string"))
+                }
+                Some('"') => return Ok((out, self.span_from(m))),
+                Some('\\') => {
+                    let esc = self.mark();
+                    match self.bump() {
+                        Some('"') => out.push('"'),
+                        Some('\\') => out.push('\\'),
+                        Some('n') => out.push('\n'),
+                        Some('t') => out.push('\t'),
+                        _ => {
+                            return Err(Diagnostic::new(self.span_from(esc), "unknown escape"));
+                        }
+                    }
+                }
+                Some(c) => out.push(c),
+            }
+        }
+    }
+}
+
+pub fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,69 @@
+//! ALN shards (`specs/aln_grammar.ebnf`) and the organiccpu block syntax:
+//! parsers with span-accurate diagnostics, a typed AST, and canonical
+//! serializers that parse back to the same tree.
+
+pub mod block;
+pub mod lexer;
+pub mod shard;
+
+use std::fmt;
+
+pub use block::{parse_document, AlnDocument, Block, Field, Item, Value};
+pub use lexer::{Diagnostic, Span};
+pub use shard::{
+    parse_shard, AlnShard, CorridorBound, Dimension, Expr, Invariant, NeurorightsType, ShardHeader,
+    Signature,
+};
+
+/// One or more diagnostics from a failed parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlnError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl AlnError {
+    /// All diagnostics with source excerpts.
+    pub fn render(&self, src: &str) -> String {
+        self.diagnostics
+            .iter()
+            .map(|d| d.render(src))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl fmt::Display for AlnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.diagnostics.iter().map(|d| d.to_string()).collect();
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl std::error::Error for AlnError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlnFile {
+    Shard(AlnShard),
+    Document(AlnDocument),
+}
+
+impl fmt::Display for AlnFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlnFile::Shard(s) => write!(f, "{s}"),
+            AlnFile::Document(d) => write!(f, "{d}"),
+        }
+    }
+}
+
+/// Parse either form: a shard starts with its numeric version, a block file
+/// with a name.
+pub fn parse(src: &str) -> Result<AlnFile, AlnError> {
+    let mut cur = lexer::Cursor::new(src);
+    cur.skip_trivia(&mut Vec::new());
+    if cur.peek().is_some_and(|c| c.is_ascii_digit()) {
+        parse_shard(src).map(AlnFile::Shard)
+    } else {
+        parse_document(src).map(AlnFile::Document)
+    }
+}
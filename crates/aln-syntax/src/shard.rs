@@ -0,0 +1,396 @@
+use std::fmt;
+
+use crate::lexer::{escape_string, Cursor, Diagnostic, Mark, Span};
+use crate::AlnError;
+
+/// `aln_shard ::= header expr_list signature`
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlnShard {
+    pub header: ShardHeader,
+    pub exprs: Vec<Expr>,
+    pub signature: Signature,
+}
+
+/// `header ::= version subject_id timestamp eco_impact_score`, on one line:
+/// `1 "bostrom1..." "2026-02-25T21:00:00Z" 0.12`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShardHeader {
+    pub version: u32,
+    pub subject_id: String,
+    /// ISO 8601, kept as written.
+    pub timestamp: String,
+    /// gCO2eq per operation.
+    pub eco_impact_score: f32,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Invariant(Invariant),
+    Corridor(CorridorBound),
+}
+
+/// `INVARIANT <neurorights_type> <ltl_formula>`; the formula runs to end of line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Invariant {
+    pub right: NeurorightsType,
+    pub body: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NeurorightsType {
+    MentalPrivacy,
+    MentalIntegrity,
+    CognitiveLiberty,
+}
+
+impl NeurorightsType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NeurorightsType::MentalPrivacy => "MENTAL_PRIVACY",
+            NeurorightsType::MentalIntegrity => "MENTAL_INTEGRITY",
+            NeurorightsType::CognitiveLiberty => "COGNITIVE_LIBERTY",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "MENTAL_PRIVACY" => Some(NeurorightsType::MentalPrivacy),
+            "MENTAL_INTEGRITY" => Some(NeurorightsType::MentalIntegrity),
+            "COGNITIVE_LIBERTY" => Some(NeurorightsType::CognitiveLiberty),
+            _ => None,
+        }
+    }
+}
+
+/// `CORRIDOR <dimension> <lower> <upper>`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CorridorBound {
+    pub dimension: Dimension,
+    pub lower: f32,
+    pub upper: f32,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dimension {
+    Eco,
+    Cyber,
+    Neuro,
+    Smart,
+}
+
+impl Dimension {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Dimension::Eco => "ECO",
+            Dimension::Cyber => "CYBER",
+            Dimension::Neuro => "NEURO",
+            Dimension::Smart => "SMART",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "ECO" => Some(Dimension::Eco),
+            "CYBER" => Some(Dimension::Cyber),
+            "NEURO" => Some(Dimension::Neuro),
+            "SMART" => Some(Dimension::Smart),
+            _ => None,
+        }
+    }
+}
+
+/// Ed25519 signature over `AlnShard::signing_payload`, written as 128 hex
+/// characters on the last line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub bytes: [u8; 64],
+    pub span: Span,
+}
+
+impl AlnShard {
+    /// Canonical header + expr_list text the signature covers.
+    pub fn signing_payload(&self) -> String {
+        let mut out = String::new();
+        write_body(&mut out, self).expect("write to String");
+        out
+    }
+}
+
+fn write_body(f: &mut impl fmt::Write, shard: &AlnShard) -> fmt::Result {
+    let h = &shard.header;
+    writeln!(
+        f,
+        "{} {} {} {}",
+        h.version,
+        escape_string(&h.subject_id),
+        escape_string(&h.timestamp),
+        h.eco_impact_score
+    )?;
+    for expr in &shard.exprs {
+        match expr {
+            Expr::Invariant(i) => writeln!(f, "INVARIANT {} {}", i.right.as_str(), i.body)?,
+            Expr::Corridor(c) => {
+                writeln!(f, "CORRIDOR {} {} {}", c.dimension.as_str(), c.lower, c.upper)?
+            }
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for AlnShard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_body(f, self)?;
+        for b in self.signature.bytes {
+            write!(f, "{:02x}", b)?;
+        }
+        writeln!(f)
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && c != '"'
+}
+
+fn word<'a>(cur: &mut Cursor<'a>) -> (&'a str, Span) {
+    cur.skip_inline_ws();
+    let m = cur.mark();
+    let w = cur.take_while(is_word_char);
+    (w, cur.span_from(m))
+}
+
+fn number<T: std::str::FromStr>(cur: &mut Cursor, what: &str) -> Result<T, Diagnostic> {
+    let (w, span) = word(cur);
+    if w.is_empty() {
+        return Err(Diagnostic::new(span, format!("expected {what}")));
+    }
+    w.parse()
+        .map_err(|_| Diagnostic::new(span, format!("{what} must be a number, got `{w}`")))
+}
+
+fn string(cur: &mut Cursor, what: &str) -> Result<(String, Span), Diagnostic> {
+    cur.skip_inline_ws();
+    if cur.peek() != Some('"') {
+        return Err(Diagnostic::new(cur.here(), format!("expected quoted {what}")));
+    }
+    cur.read_string()
+}
+
+/// Only whitespace or a `//` comment may follow on the line.
+fn end_of_line(cur: &mut Cursor) -> Result<(), Diagnostic> {
+    cur.skip_inline_ws();
+    let (rest, span) = cur.rest_of_line();
+    if rest.is_empty() || rest.starts_with("//") {
+        Ok(())
+    } else {
+        Err(Diagnostic::new(span, "unexpected text at end of line"))
+    }
+}
+
+/// YYYY-MM-DDTHH:MM:SS, optional fraction, then Z or ±HH:MM.
+fn is_iso8601(s: &str) -> bool {
+    let b = s.as_bytes();
+    let digits = |r: std::ops::Range<usize>| b.get(r).is_some_and(|d| d.iter().all(u8::is_ascii_digit));
+    if b.len() < 20
+        || !(digits(0..4) && b[4] == b'-' && digits(5..7) && b[7] == b'-' && digits(8..10))
+        || b[10] != b'T'
+        || !(digits(11..13) && b[13] == b':' && digits(14..16) && b[16] == b':' && digits(17..19))
+    {
+        return false;
+    }
+    let mut i = 19;
+    if b[i] == b'.' {
+        i += 1;
+        let start = i;
+        while i < b.len() && b[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == start {
+            return false;
+        }
+    }
+    match &b[i..] {
+        [b'Z'] => true,
+        [b'+' | b'-', ..] => b.len() - i == 6 && digits(i + 1..i + 3) && b[i + 3] == b':' && digits(i + 4..i + 6),
+        _ => false,
+    }
+}
+
+fn skip_blank_lines(cur: &mut Cursor) {
+    let mut docs = Vec::new();
+    cur.skip_trivia(&mut docs);
+}
+
+fn parse_header(cur: &mut Cursor) -> Result<ShardHeader, Diagnostic> {
+    let start = cur.mark();
+    let version = number(cur, "version")?;
+    let (subject_id, _) = string(cur, "subject_id")?;
+    let (timestamp, ts_span) = string(cur, "timestamp")?;
+    if !is_iso8601(&timestamp) {
+        return Err(Diagnostic::new(ts_span, "timestamp must be ISO 8601 (e.g. 2026-02-25T21:00:00Z)"));
+    }
+    let eco_impact_score = number(cur, "eco_impact_score")?;
+    let span = cur.span_from(start);
+    end_of_line(cur)?;
+    Ok(ShardHeader {
+        version,
+        subject_id,
+        timestamp,
+        eco_impact_score,
+        span,
+    })
+}
+
+fn parse_invariant(cur: &mut Cursor, start: Mark) -> Result<Expr, Diagnostic> {
+    let (kind, kind_span) = word(cur);
+    let right = NeurorightsType::parse(kind).ok_or_else(|| {
+        Diagnostic::new(
+            kind_span,
+            format!("unknown neurorights type `{kind}` (MENTAL_PRIVACY, MENTAL_INTEGRITY, COGNITIVE_LIBERTY)"),
+        )
+    })?;
+    cur.skip_inline_ws();
+    let (line, body_span) = cur.rest_of_line();
+    let body = line.split("//").next().unwrap_or("").trim_end();
+    if body.is_empty() {
+        return Err(Diagnostic::new(body_span, "INVARIANT needs an LTL formula"));
+    }
+    let mut depth = 0i32;
+    for (i, c) in body.char_indices() {
+        depth += match c {
+            '(' => 1,
+            ')' => -1,
+            _ => 0,
+        };
+        if depth < 0 {
+            let at = Span {
+                start: body_span.start + i,
+                end: body_span.start + i + 1,
+                line: body_span.line,
+                col: body_span.col + body[..i].chars().count() as u32,
+            };
+            return Err(Diagnostic::new(at, "unbalanced `)` in LTL formula"));
+        }
+    }
+    if depth != 0 {
+        return Err(Diagnostic::new(body_span, "unclosed `(` in LTL formula"));
+    }
+    let mut span = cur.span_from(start);
+    span.end = body_span.start + body.len();
+    Ok(Expr::Invariant(Invariant {
+        right,
+        body: body.to_string(),
+        span,
+    }))
+}
+
+fn parse_corridor(cur: &mut Cursor, start: Mark) -> Result<Expr, Diagnostic> {
+    let (dim, dim_span) = word(cur);
+    let dimension = Dimension::parse(dim).ok_or_else(|| {
+        Diagnostic::new(dim_span, format!("unknown corridor dimension `{dim}` (ECO, CYBER, NEURO, SMART)"))
+    })?;
+    let lower: f32 = number(cur, "lower_bound")?;
+    let upper: f32 = number(cur, "upper_bound")?;
+    let span = cur.span_from(start);
+    if !(lower.is_finite() && upper.is_finite()) || lower > upper {
+        return Err(Diagnostic::new(span, format!("corridor bounds must be finite with lower <= upper, got {lower} {upper}")));
+    }
+    end_of_line(cur)?;
+    Ok(Expr::Corridor(CorridorBound {
+        dimension,
+        lower,
+        upper,
+        span,
+    }))
+}
+
+fn parse_signature(w: &str, span: Span) -> Result<Signature, Diagnostic> {
+    if w.len() != 128 || !w.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Diagnostic::new(
+            span,
+            format!("expected INVARIANT, CORRIDOR or a 128-hex-digit signature, got `{w}`"),
+        ));
+    }
+    let mut bytes = [0u8; 64];
+    for (i, chunk) in w.as_bytes().chunks(2).enumerate() {
+        let hex = std::str::from_utf8(chunk).expect("ascii");
+        bytes[i] = u8::from_str_radix(hex, 16).expect("checked hex");
+    }
+    Ok(Signature { bytes, span })
+}
+
+/// After an error, continue at the next line unless the failed parse
+/// already consumed it.
+fn resync(cur: &mut Cursor, start: Mark) {
+    if cur.here().line == cur.span_from(start).line {
+        cur.rest_of_line();
+    }
+}
+
+/// Parse an ALN shard. Errors are collected line by line, so one bad
+/// expression does not hide the next.
+pub fn parse_shard(src: &str) -> Result<AlnShard, AlnError> {
+    let mut cur = Cursor::new(src);
+    let mut diagnostics = Vec::new();
+
+    skip_blank_lines(&mut cur);
+    let start = cur.mark();
+    let header = match parse_header(&mut cur) {
+        Ok(h) => Some(h),
+        Err(d) => {
+            diagnostics.push(d);
+            resync(&mut cur, start);
+            None
+        }
+    };
+
+    let mut exprs = Vec::new();
+    let mut signature = None;
+    loop {
+        skip_blank_lines(&mut cur);
+        if cur.is_eof() {
+            break;
+        }
+        let start = cur.mark();
+        let (w, span) = word(&mut cur);
+        if signature.is_some() {
+            diagnostics.push(Diagnostic::new(span, "content after signature"));
+            cur.rest_of_line();
+            continue;
+        }
+        let parsed = match w {
+            "INVARIANT" => parse_invariant(&mut cur, start).map(Some),
+            "CORRIDOR" => parse_corridor(&mut cur, start).map(Some),
+            _ => parse_signature(w, span).map(|s| {
+                signature = Some(s);
+                None
+            }),
+        };
+        match parsed {
+            Ok(Some(e)) => exprs.push(e),
+            Ok(None) => {
+                if let Err(d) = end_of_line(&mut cur) {
+                    diagnostics.push(d);
+                }
+            }
+            Err(d) => {
+                resync(&mut cur, start);
+                diagnostics.push(d);
+            }
+        }
+    }
+
+    if signature.is_none() && diagnostics.is_empty() {
+        diagnostics.push(Diagnostic::new(cur.here(), "missing signature"));
+    }
+    match (header, signature) {
+        (Some(header), Some(signature)) if diagnostics.is_empty() => Ok(AlnShard {
+            header,
+            exprs,
+            signature,
+        }),
+        _ => Err(AlnError { diagnostics }),
+    }
+}
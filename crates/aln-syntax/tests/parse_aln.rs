@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aln_syntax::{parse, parse_document, parse_shard, AlnFile, Dimension, Expr, NeurorightsType, Value};
+
+fn aln_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            aln_files(&path, out);
+        } else if path.extension().is_some_and(|e| e == "aln") {
+            out.push(path);
+        }
+    }
+}
+
+fn sig_hex() -> String {
+    "ab".repeat(64)
+}
+
+#[test]
+fn organiccpu_block_files_parse_and_round_trip() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../organiccpu");
+    let mut files = Vec::new();
+    aln_files(&root, &mut files);
+    assert!(!files.is_empty());
+
+    for path in files {
+        let src = fs::read_to_string(&path).unwrap();
+        let doc = parse_document(&src)
+            .unwrap_or_else(|e| panic!("{}:\n{}", path.display(), e.render(&src)));
+        let printed = doc.to_string();
+        let reparsed = parse_document(&printed).unwrap();
+        assert_eq!(printed, reparsed.to_string(), "{}", path.display());
+        assert_eq!(doc.items.len(), reparsed.items.len());
+    }
+}
+
+#[test]
+fn block_values_are_typed() {
+    let src = r#"
+/// Layer docs
+layer {
+  name: "snn";  // trailing comment
+  duty_cycle_max: 0.3;
+  latency: 0.1_ms;
+  channel_range: [0, 299];
+  weights: [-0.12, +0.31, ...];
+  hardware: true
+};
+"#;
+    let doc = parse_document(src).unwrap();
+    let layer = doc.block("layer").unwrap();
+    assert_eq!(layer.docs, vec!["Layer docs".to_string()]);
+    assert_eq!(layer.field("name"), Some(&Value::Str("snn".into())));
+    assert_eq!(layer.field("duty_cycle_max").and_then(Value::as_f64), Some(0.3));
+    assert_eq!(layer.field("latency"), Some(&Value::Atom("0.1_ms".into())));
+    assert_eq!(
+        layer.field("weights"),
+        Some(&Value::List(vec![Value::Num(-0.12), Value::Num(0.31), Value::Atom("...".into())]))
+    );
+    assert_eq!(layer.field("hardware").and_then(Value::as_bool), Some(true));
+}
+
+#[test]
+fn block_errors_point_at_the_token() {
+    let src = "outer {\n  a: 1;\n  b 2;\n};\n";
+    let err = parse_document(src).unwrap_err();
+    let d = &err.diagnostics[0];
+    assert_eq!((d.span.line, d.span.col), (3, 3));
+    assert!(d.message.contains("after `b`"), "{}", d.message);
+    assert!(err.render(src).contains("  b 2;"));
+
+    let err = parse_document("outer {\n  a: 1;\n").unwrap_err();
+    assert!(err.diagnostics[0].message.contains("expected `}`"));
+}
+
+#[test]
+fn shard_parses_and_round_trips() {
+    let src = format!(
+        "// example shard\n1 \"bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7\" \"2026-02-25T21:00:00Z\" 0.12\n\
+         INVARIANT MENTAL_PRIVACY G(mental_privacy => !export_without_consent)\n\
+         CORRIDOR ECO 0.0 0.3\n\
+         CORRIDOR NEURO 0.05 0.25\n\
+         {}\n",
+        sig_hex()
+    );
+    let shard = parse_shard(&src).unwrap();
+    assert_eq!(shard.header.version, 1);
+    assert_eq!(shard.header.eco_impact_score, 0.12);
+    assert_eq!(shard.exprs.len(), 3);
+    match &shard.exprs[0] {
+        Expr::Invariant(i) => {
+            assert_eq!(i.right, NeurorightsType::MentalPrivacy);
+            assert_eq!(i.body, "G(mental_privacy => !export_without_consent)");
+            assert_eq!(i.span.line, 3);
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    match &shard.exprs[2] {
+        Expr::Corridor(c) => assert_eq!((c.dimension, c.lower, c.upper), (Dimension::Neuro, 0.05, 0.25)),
+        other => panic!("unexpected {other:?}"),
+    }
+    assert_eq!(shard.signature.bytes, [0xab; 64]);
+    assert!(!shard.signing_payload().contains(&sig_hex()));
+
+    let printed = shard.to_string();
+    let reparsed = parse_shard(&printed).unwrap();
+    assert_eq!(reparsed.to_string(), printed);
+    assert_eq!(reparsed.header.subject_id, shard.header.subject_id);
+
+    assert!(matches!(parse(&src), Ok(AlnFile::Shard(_))));
+}
+
+#[test]
+fn shard_reports_every_bad_line() {
+    let src = format!(
+        "1 \"subj\" \"2026-02-25 21:00\" 0.1\n\
+         INVARIANT MENTAL_TELEPATHY G(x)\n\
+         CORRIDOR ECO 0.5 0.1\n\
+         INVARIANT COGNITIVE_LIBERTY G((x)\n\
+         {}\n",
+        sig_hex()
+    );
+    let err = parse_shard(&src).unwrap_err();
+    let lines: Vec<u32> = err.diagnostics.iter().map(|d| d.span.line).collect();
+    assert_eq!(lines, vec![1, 2, 3, 4], "{}", err);
+    assert_eq!(err.diagnostics[1].span.col, 11);
+    assert!(err.diagnostics[0].message.contains("ISO 8601"));
+}
+
+#[test]
+fn shard_requires_signature_last() {
+    let head = "1 \"subj\" \"2026-02-25T21:00:00Z\" 0.1\nCORRIDOR SMART 0 1\n";
+    let err = parse_shard(head).unwrap_err();
+    assert!(err.diagnostics[0].message.contains("missing signature"));
+
+    let trailing = format!("{head}{}\nCORRIDOR ECO 0 1\n", sig_hex());
+    let err = parse_shard(&trailing).unwrap_err();
+    assert_eq!(err.diagnostics[0].span.line, 4);
+    assert!(err.diagnostics[0].message.contains("after signature"));
+}
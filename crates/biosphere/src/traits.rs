@@ -130,6 +130,13 @@ impl EvolutionToken {
             reversible: true,
         }
     }
+
+    /// Scale the effect band by `step` (clamped to 0.0..=1.0), e.g. the
+    /// viable fraction of the change from the viability kernel's
+    /// `max_viable_step`, instead of dropping the token.
+    pub fn shrink_effect_band(&mut self, step: f32) {
+        self.expected_effect_band *= step.clamp(0.0, 1.0);
+    }
 }
 
 /// Per-lane profile (navigation, safety, communication) defining budgets.
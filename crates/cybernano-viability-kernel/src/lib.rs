@@ -0,0 +1,2 @@
+pub mod tsafe_spec;
+pub mod viability;
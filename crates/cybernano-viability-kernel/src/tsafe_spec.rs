@@ -37,7 +37,7 @@ pub struct TsafeKernelSpec {
 impl TsafeKernelSpec {
     /// Simple structural invariants mirroring `.vkernel.aln` invariants.
     pub fn validate_invariants(&self) -> anyhow::Result<()> {
-        use anyhow::{bail, Context};
+        use anyhow::bail;
 
         if self.axes.is_empty() {
             bail!("TsafeKernelSpec must define at least one axis");
@@ -130,7 +130,7 @@ impl TsafeKernelSpec {
         mode_id: &str,
         state: &HashMap<String, f32>,
     ) -> anyhow::Result<bool> {
-        use anyhow::{bail, Context};
+        use anyhow::Context;
 
         let mode = self
             .modes
@@ -154,3 +154,187 @@ impl TsafeKernelSpec {
         Ok(true)
     }
 }
+
+impl TsafeKernelSpec {
+    /// Load a `.vkernel.aln` file; see `from_vkernel_aln`.
+    pub fn load_vkernel_aln(path: &str) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        let text = std::fs::read_to_string(path).with_context(|| format!("read {}", path))?;
+        Self::from_vkernel_aln(&text).with_context(|| format!("parse {}", path))
+    }
+
+    /// Parse the indented `.vkernel.aln` layout. Both shipped variants are
+    /// accepted: `axes` + `kernels` with dense `A`/`b` lists
+    /// (`policies/homo-sapiens-vkernel-v1.vkernel.aln`), and `stateaxes` +
+    /// `modes` with sparse `a`/`b` rows (`bostrom-vkernel-v1.vkernel.aln`).
+    /// A kernel's `mode` name, when present, becomes the mode id.
+    pub fn from_vkernel_aln(text: &str) -> anyhow::Result<Self> {
+        use anyhow::{bail, Context};
+
+        #[derive(Default)]
+        struct PendingMode {
+            id: String,
+            mode: Option<String>,
+            description: String,
+            dense_a: Vec<Vec<f32>>,
+            dense_b: Vec<f32>,
+            rows: Vec<TsafeConstraintRow>,
+        }
+
+        fn num(tok: &str, line_no: usize) -> anyhow::Result<f32> {
+            tok.trim_matches(|c| c == ',' || c == '[' || c == ']')
+                .parse::<f32>()
+                .with_context(|| format!("line {}: expected a number, got '{}'", line_no, tok))
+        }
+
+        let mut meta: HashMap<String, String> = HashMap::new();
+        let mut axes: Vec<TsafeAxis> = Vec::new();
+        let mut modes: Vec<PendingMode> = Vec::new();
+        let mut section = "";
+        let mut list = "";
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = raw.split('#').next().unwrap_or("").trim_end();
+            if line.trim().is_empty() {
+                continue;
+            }
+            if !line.starts_with(' ') {
+                section = match line.trim() {
+                    "axes" | "stateaxes" => "axes",
+                    "kernels" | "modes" => "modes",
+                    other => other,
+                };
+                list = "";
+                continue;
+            }
+
+            let body = line.trim();
+            let (is_item, body) = match body.strip_prefix("- ") {
+                Some(rest) => (true, rest.trim()),
+                None => (false, body),
+            };
+            let mut toks = body.split_whitespace();
+            let key = toks.next().unwrap_or("");
+            let rest: Vec<&str> = toks.collect();
+
+            match section {
+                "meta" => {
+                    meta.insert(key.to_string(), rest.join(" "));
+                }
+                "axes" => {
+                    if is_item && key == "name" {
+                        let name = rest.first().with_context(|| format!("line {}: axis without name", line_no))?;
+                        let mut axis = TsafeAxis { name: name.to_string(), min: 0.0, max: 1.0 };
+                        if rest.get(1) == Some(&"range") && rest.len() >= 4 {
+                            axis.min = num(rest[2], line_no)?;
+                            axis.max = num(rest[3], line_no)?;
+                        }
+                        axes.push(axis);
+                    } else if let Some(axis) = axes.last_mut() {
+                        match key {
+                            "min" => axis.min = num(rest.first().copied().unwrap_or(""), line_no)?,
+                            "max" => axis.max = num(rest.first().copied().unwrap_or(""), line_no)?,
+                            _ => {}
+                        }
+                    }
+                }
+                "modes" => {
+                    if is_item && key == "id" {
+                        modes.push(PendingMode { id: rest.join(" "), ..Default::default() });
+                        list = "";
+                        continue;
+                    }
+                    let mode = modes
+                        .last_mut()
+                        .with_context(|| format!("line {}: kernel field before any '- id'", line_no))?;
+                    if is_item && body.starts_with('[') {
+                        if list != "A" {
+                            bail!("line {}: matrix row outside an A list", line_no);
+                        }
+                        let row = body
+                            .split(|c: char| c == ',' || c.is_whitespace())
+                            .filter(|t| !t.is_empty() && *t != "[" && *t != "]")
+                            .map(|t| num(t, line_no))
+                            .collect::<anyhow::Result<Vec<f32>>>()?;
+                        mode.dense_a.push(row);
+                    } else if is_item && list == "b" {
+                        mode.dense_b.push(num(key, line_no)?);
+                    } else if is_item && key == "a" {
+                        if !rest.len().is_multiple_of(2) {
+                            bail!("line {}: sparse row needs axis/value pairs", line_no);
+                        }
+                        let mut a = HashMap::new();
+                        for pair in rest.chunks(2) {
+                            let v = num(pair[1], line_no)?;
+                            if v != 0.0 {
+                                a.insert(pair[0].to_string(), v);
+                            }
+                        }
+                        mode.rows.push(TsafeConstraintRow { a, b: f32::NAN });
+                    } else {
+                        match key {
+                            "mode" => mode.mode = Some(rest.join(" ")),
+                            "description" => mode.description = rest.join(" "),
+                            "A" if rest.is_empty() => list = "A",
+                            "b" if rest.is_empty() => list = "b",
+                            "b" => {
+                                let row = mode
+                                    .rows
+                                    .last_mut()
+                                    .filter(|r| r.b.is_nan())
+                                    .with_context(|| format!("line {}: 'b' without a preceding 'a' row", line_no))?;
+                                row.b = num(rest[0], line_no)?;
+                            }
+                            "constraints" => list = "constraints",
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut out_modes = Vec::with_capacity(modes.len());
+        for m in modes {
+            let mut constraints = m.rows;
+            if constraints.iter().any(|r| r.b.is_nan()) {
+                bail!("Mode {} has an 'a' row without 'b'", m.id);
+            }
+            if m.dense_a.len() != m.dense_b.len() {
+                bail!("Mode {} has {} A rows but {} b entries", m.id, m.dense_a.len(), m.dense_b.len());
+            }
+            for (row, b) in m.dense_a.into_iter().zip(m.dense_b) {
+                if row.len() != axes.len() {
+                    bail!("Mode {} A row has {} columns for {} axes", m.id, row.len(), axes.len());
+                }
+                let a = axes
+                    .iter()
+                    .zip(row)
+                    .filter(|(_, v)| *v != 0.0)
+                    .map(|(axis, v)| (axis.name.clone(), v))
+                    .collect();
+                constraints.push(TsafeConstraintRow { a, b });
+            }
+            out_modes.push(TsafeModeKernel {
+                id: m.mode.unwrap_or(m.id),
+                description: m.description,
+                constraints,
+            });
+        }
+
+        let subjectid = ["subjectid", "subject_id", "species_id"]
+            .iter()
+            .find_map(|k| meta.get(*k).cloned())
+            .unwrap_or_default();
+        let spec = TsafeKernelSpec {
+            subjectid,
+            version: meta.get("version").cloned().unwrap_or_default(),
+            axes,
+            modes: out_modes,
+        };
+        spec.validate_invariants()?;
+        Ok(spec)
+    }
+}
@@ -0,0 +1,298 @@
+//! Graded answers from a `TsafeKernelSpec` mode: how far a state is from
+//! the kernel boundary, the nearest viable state, and how much of a
+//! proposed change fits. Schedulers use these to shrink an evolution
+//! token's effect band instead of dropping it.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context};
+
+use crate::tsafe_spec::{TsafeKernelSpec, TsafeModeKernel};
+
+/// Same tolerance as `TsafeKernelSpec::is_viable`.
+const VIABLE_TOL: f64 = 1e-6;
+const PROJECTION_TOL: f64 = 1e-9;
+const MAX_SWEEPS: usize = 100_000;
+
+/// A face of a mode's viability kernel: one of its constraint rows, or one
+/// side of an axis's `[min, max]` range.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Face {
+    /// Constraint row `i` of the mode.
+    Row(usize),
+    /// `axis >= min`, taken as the row `-axis <= -min`.
+    AxisMin(String),
+    /// `axis <= max`.
+    AxisMax(String),
+}
+
+/// One face evaluated at a state, as the half-space `lhs <= b`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FaceMargin {
+    pub face: Face,
+    pub lhs: f32,
+    pub b: f32,
+    /// `b - lhs`; negative when the face is crossed.
+    pub margin: f32,
+    /// `margin / ‖a‖`, the signed distance to the face's hyperplane.
+    pub distance: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViabilityReport {
+    pub mode_id: String,
+    /// Inside every constraint row and every axis range, the same set
+    /// `project_to_viable` projects onto and `max_viable_step` stays in.
+    pub viable: bool,
+    /// Face with the smallest `distance`: the hyperplane furthest violated,
+    /// or the nearest one when the state is viable. Raw `margin`s are not
+    /// compared, since they scale with each row's coefficients.
+    pub worst: Option<FaceMargin>,
+    /// Constraint rows in order, then the range faces of each axis present
+    /// in the state.
+    pub margins: Vec<FaceMargin>,
+    /// Axes absent from the state. They are evaluated at their worst case
+    /// within `[min, max]`, so a report with missing axes is conservative.
+    pub missing_axes: Vec<String>,
+}
+
+/// Nearest viable state (Euclidean) to a proposed one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SafeProjection {
+    pub state: HashMap<String, f32>,
+    /// Euclidean distance moved.
+    pub distance: f32,
+}
+
+/// A mode's rows over the spec's axis order, in f64.
+struct DenseKernel {
+    names: Vec<String>,
+    lo: Vec<f64>,
+    hi: Vec<f64>,
+    rows: Vec<(Vec<f64>, f64)>,
+}
+
+impl DenseKernel {
+    fn new(spec: &TsafeKernelSpec, mode: &TsafeModeKernel) -> anyhow::Result<Self> {
+        let names: Vec<String> = spec.axes.iter().map(|a| a.name.clone()).collect();
+        let mut rows = Vec::with_capacity(mode.constraints.len());
+        for (i, row) in mode.constraints.iter().enumerate() {
+            let mut a = vec![0.0; names.len()];
+            for (axis, coeff) in &row.a {
+                let j = names
+                    .iter()
+                    .position(|n| n == axis)
+                    .with_context(|| format!("Mode {} row {} references unknown axis {}", mode.id, i, axis))?;
+                a[j] = *coeff as f64;
+            }
+            rows.push((a, row.b as f64));
+        }
+        Ok(Self {
+            names,
+            lo: spec.axes.iter().map(|a| a.min as f64).collect(),
+            hi: spec.axes.iter().map(|a| a.max as f64).collect(),
+            rows,
+        })
+    }
+
+    /// Dense state; every missing axis is reported at once.
+    fn dense(&self, state: &HashMap<String, f32>) -> anyhow::Result<Vec<f64>> {
+        let missing: Vec<&String> = self.names.iter().filter(|n| !state.contains_key(*n)).collect();
+        if !missing.is_empty() {
+            bail!("Missing state values for axes {:?}", missing);
+        }
+        Ok(self.names.iter().map(|n| state[n] as f64).collect())
+    }
+
+    fn is_inside(&self, x: &[f64], tol: f64) -> bool {
+        self.rows.iter().all(|(a, b)| dot(a, x) <= b + tol)
+            && x.iter().zip(self.lo.iter().zip(&self.hi)).all(|(v, (lo, hi))| *v >= lo - tol && *v <= hi + tol)
+    }
+}
+
+fn dot(a: &[f64], x: &[f64]) -> f64 {
+    a.iter().zip(x).map(|(ai, xi)| ai * xi).sum()
+}
+
+impl TsafeKernelSpec {
+    fn mode(&self, mode_id: &str) -> anyhow::Result<&TsafeModeKernel> {
+        self.modes
+            .iter()
+            .find(|m| m.id == mode_id)
+            .with_context(|| format!("Unknown Tsafe mode id {}", mode_id))
+    }
+
+    /// Per-face margins and the worst one. Unlike `is_viable`, which checks
+    /// the constraint rows only, the axis ranges count too; and missing axes
+    /// do not fail the call (see `ViabilityReport::missing_axes`).
+    pub fn viability_margin(
+        &self,
+        mode_id: &str,
+        state: &HashMap<String, f32>,
+    ) -> anyhow::Result<ViabilityReport> {
+        let mode = self.mode(mode_id)?;
+        let kernel = DenseKernel::new(self, mode)?;
+
+        let mut margins = Vec::with_capacity(kernel.rows.len());
+        for (row, (a, b)) in kernel.rows.iter().enumerate() {
+            let mut lhs = 0.0;
+            for (j, coeff) in a.iter().enumerate() {
+                lhs += match state.get(&kernel.names[j]) {
+                    Some(x) => coeff * *x as f64,
+                    None => (coeff * kernel.lo[j]).max(coeff * kernel.hi[j]),
+                };
+            }
+            let margin = b - lhs;
+            let norm = dot(a, a).sqrt();
+            let distance = if norm > 0.0 { margin / norm } else { margin.signum() * f64::INFINITY };
+            margins.push(FaceMargin {
+                face: Face::Row(row),
+                lhs: lhs as f32,
+                b: *b as f32,
+                margin: margin as f32,
+                distance: distance as f32,
+            });
+        }
+        for (j, name) in kernel.names.iter().enumerate() {
+            let Some(x) = state.get(name).map(|x| *x as f64) else {
+                continue;
+            };
+            for (face, lhs, b) in [
+                (Face::AxisMin(name.clone()), -x, -kernel.lo[j]),
+                (Face::AxisMax(name.clone()), x, kernel.hi[j]),
+            ] {
+                let margin = (b - lhs) as f32;
+                margins.push(FaceMargin {
+                    face,
+                    lhs: lhs as f32,
+                    b: b as f32,
+                    margin,
+                    distance: margin,
+                });
+            }
+        }
+
+        let worst = margins
+            .iter()
+            .min_by(|l, r| l.distance.total_cmp(&r.distance))
+            .cloned();
+        Ok(ViabilityReport {
+            mode_id: mode.id.clone(),
+            viable: margins.iter().all(|m| m.margin as f64 >= -VIABLE_TOL),
+            worst,
+            margins,
+            missing_axes: kernel
+                .names
+                .iter()
+                .filter(|n| !state.contains_key(*n))
+                .cloned()
+                .collect(),
+        })
+    }
+
+    /// Minimal-norm projection of `state` onto the mode's polytope
+    /// intersected with the axis ranges (Dykstra's alternating projections).
+    /// Fails if the kernel is empty.
+    pub fn project_to_viable(
+        &self,
+        mode_id: &str,
+        state: &HashMap<String, f32>,
+    ) -> anyhow::Result<SafeProjection> {
+        let mode = self.mode(mode_id)?;
+        let kernel = DenseKernel::new(self, mode)?;
+        let start = kernel.dense(state)?;
+
+        let mut x = start.clone();
+        if !kernel.is_inside(&x, 0.0) {
+            // One correction term per row plus one for the axis box.
+            let mut p = vec![vec![0.0; x.len()]; kernel.rows.len() + 1];
+            let mut converged = false;
+            for _ in 0..MAX_SWEEPS {
+                let before = x.clone();
+                for (i, (a, b)) in kernel.rows.iter().enumerate() {
+                    let z: Vec<f64> = x.iter().zip(&p[i]).map(|(xi, pi)| xi + pi).collect();
+                    let nn = dot(a, a);
+                    let over = dot(a, &z) - b;
+                    let y: Vec<f64> = if over > 0.0 && nn > 0.0 {
+                        z.iter().zip(a).map(|(zi, ai)| zi - over / nn * ai).collect()
+                    } else {
+                        z.clone()
+                    };
+                    p[i] = z.iter().zip(&y).map(|(zi, yi)| zi - yi).collect();
+                    x = y;
+                }
+                let k = kernel.rows.len();
+                let z: Vec<f64> = x.iter().zip(&p[k]).map(|(xi, pi)| xi + pi).collect();
+                let y: Vec<f64> = z
+                    .iter()
+                    .enumerate()
+                    .map(|(j, zj)| zj.clamp(kernel.lo[j], kernel.hi[j]))
+                    .collect();
+                p[k] = z.iter().zip(&y).map(|(zi, yi)| zi - yi).collect();
+                x = y;
+
+                let moved = x.iter().zip(&before).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+                if moved < PROJECTION_TOL && kernel.is_inside(&x, PROJECTION_TOL) {
+                    converged = true;
+                    break;
+                }
+            }
+            if !converged || !kernel.is_inside(&x, VIABLE_TOL) {
+                bail!("Mode {} has no viable state to project onto", mode.id);
+            }
+        }
+
+        let distance = x.iter().zip(&start).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt();
+        Ok(SafeProjection {
+            state: kernel
+                .names
+                .iter()
+                .cloned()
+                .zip(x.iter().map(|v| *v as f32))
+                .collect(),
+            distance: distance as f32,
+        })
+    }
+
+    /// Largest `t` in `[0, 1]` such that `state + t·delta` stays viable and
+    /// within the axis ranges; `0.0` if `state` itself is not viable. Axes
+    /// absent from `delta` do not move.
+    pub fn max_viable_step(
+        &self,
+        mode_id: &str,
+        state: &HashMap<String, f32>,
+        delta: &HashMap<String, f32>,
+    ) -> anyhow::Result<f32> {
+        let mode = self.mode(mode_id)?;
+        let kernel = DenseKernel::new(self, mode)?;
+        let x = kernel.dense(state)?;
+        if let Some(axis) = delta.keys().find(|k| !kernel.names.contains(*k)) {
+            bail!("Delta references unknown axis {}", axis);
+        }
+        let d: Vec<f64> = kernel
+            .names
+            .iter()
+            .map(|n| delta.get(n).copied().unwrap_or(0.0) as f64)
+            .collect();
+
+        if !kernel.is_inside(&x, VIABLE_TOL) {
+            return Ok(0.0);
+        }
+
+        let mut t: f64 = 1.0;
+        for (a, b) in &kernel.rows {
+            let ad = dot(a, &d);
+            if ad > 0.0 {
+                t = t.min((b - dot(a, &x)).max(0.0) / ad);
+            }
+        }
+        for (j, dj) in d.iter().enumerate() {
+            if *dj > 0.0 {
+                t = t.min((kernel.hi[j] - x[j]).max(0.0) / dj);
+            } else if *dj < 0.0 {
+                t = t.min((kernel.lo[j] - x[j]).min(0.0) / dj);
+            }
+        }
+        Ok(t.max(0.0) as f32)
+    }
+}
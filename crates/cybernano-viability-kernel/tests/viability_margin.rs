@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use cybernano_viability_kernel::tsafe_spec::TsafeKernelSpec;
+use cybernano_viability_kernel::viability::Face;
+
+fn shipped() -> TsafeKernelSpec {
+    let path = format!(
+        "{}/../../policies/homo-sapiens-vkernel-v1.vkernel.aln",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    TsafeKernelSpec::load_vkernel_aln(&path).unwrap()
+}
+
+fn state(pairs: &[(&str, f32)]) -> HashMap<String, f32> {
+    pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+fn baseline_state() -> HashMap<String, f32> {
+    state(&[
+        ("intensity", 0.5),
+        ("dutycycle", 0.3),
+        ("cumulativeload", 0.2),
+        ("implantpower", 0.2),
+        ("neuromodamplitude", 0.1),
+        ("cognitiveload", 0.4),
+        ("legalcomplexity", 0.1),
+        ("lifeforce", 0.8),
+    ])
+}
+
+#[test]
+fn shipped_kernel_loads_and_reports_worst_row() {
+    let spec = shipped();
+    assert_eq!(spec.axes.len(), 8);
+    let mode = &spec.modes[0];
+    assert_eq!(mode.id, "Baseline");
+    assert_eq!(mode.constraints.len(), 8);
+
+    let report = spec.viability_margin("Baseline", &baseline_state()).unwrap();
+    assert!(report.viable);
+    // intensity 0.5 vs 0.7 and implantpower 0.2 vs 0.4 tie at 0.2 among the
+    // rows; neuromodamplitude and legalcomplexity are 0.1 above their range
+    // minimum, which is nearer still.
+    let rows = report.margins.iter().filter(|m| matches!(m.face, Face::Row(_)));
+    let nearest_row = rows.map(|m| m.margin).fold(f32::INFINITY, f32::min);
+    assert!((nearest_row - 0.2).abs() < 1e-6);
+    let worst = report.worst.unwrap();
+    assert_eq!(worst.face, Face::AxisMin("neuromodamplitude".into()));
+    assert!((worst.margin - 0.1).abs() < 1e-6, "{:?}", worst);
+
+    let mut over = baseline_state();
+    over.insert("implantpower".into(), 0.9);
+    over.insert("lifeforce".into(), 0.35);
+    let report = spec.viability_margin("Baseline", &over).unwrap();
+    assert!(!report.viable);
+    let worst = report.worst.unwrap();
+    assert_eq!(worst.face, Face::Row(3));
+    assert!((worst.margin + 0.5).abs() < 1e-6);
+    assert!(!spec.is_viable("Baseline", &over).unwrap());
+}
+
+#[test]
+fn missing_axes_are_reported_and_evaluated_conservatively() {
+    let spec = shipped();
+    let mut partial = baseline_state();
+    partial.remove("lifeforce");
+    partial.remove("legalcomplexity");
+
+    let report = spec.viability_margin("Baseline", &partial).unwrap();
+    assert_eq!(report.missing_axes, vec!["legalcomplexity".to_string(), "lifeforce".to_string()]);
+    // lifeforce at its worst case (0.0) breaks the lifeforce >= 0.4 row.
+    assert!(!report.viable);
+    assert!(spec.is_viable("Baseline", &partial).is_err());
+}
+
+#[test]
+fn projection_is_minimal_and_viable() {
+    let spec = shipped();
+    let mut over = baseline_state();
+    over.insert("intensity".into(), 0.9);
+    over.insert("lifeforce".into(), 0.1);
+
+    let projected = spec.project_to_viable("Baseline", &over).unwrap();
+    assert!(spec.is_viable("Baseline", &projected.state).unwrap());
+    assert!((projected.state["intensity"] - 0.7).abs() < 1e-5);
+    assert!((projected.state["lifeforce"] - 0.4).abs() < 1e-5);
+    assert!((projected.state["dutycycle"] - 0.3).abs() < 1e-6);
+    let expected = (0.2f32 * 0.2 + 0.3 * 0.3).sqrt();
+    assert!((projected.distance - expected).abs() < 1e-5);
+
+    let unchanged = spec.project_to_viable("Baseline", &baseline_state()).unwrap();
+    assert_eq!(unchanged.distance, 0.0);
+}
+
+#[test]
+fn max_viable_step_shrinks_a_delta_to_the_boundary() {
+    let spec = shipped();
+    let delta = state(&[("intensity", 0.4), ("lifeforce", -0.2)]);
+
+    // intensity hits 0.7 at t = 0.5; lifeforce hits 0.4 at t = 2.0.
+    let t = spec.max_viable_step("Baseline", &baseline_state(), &delta).unwrap();
+    assert!((t - 0.5).abs() < 1e-6);
+
+    let small = state(&[("intensity", 0.1)]);
+    assert_eq!(spec.max_viable_step("Baseline", &baseline_state(), &small).unwrap(), 1.0);
+
+    let mut outside = baseline_state();
+    outside.insert("intensity".into(), 0.95);
+    assert_eq!(spec.max_viable_step("Baseline", &outside, &small).unwrap(), 0.0);
+}
+
+#[test]
+fn worst_row_is_ranked_by_distance_not_raw_margin() {
+    let mut spec = shipped();
+    // implantpower <= 0.4 rewritten as 0.1·implantpower <= 0.04: same half-space,
+    // a tenth of the raw margin.
+    let row = &mut spec.modes[0].constraints[3];
+    for coeff in row.a.values_mut() {
+        *coeff *= 0.1;
+    }
+    row.b *= 0.1;
+
+    let mut s = baseline_state();
+    s.insert("intensity".into(), 0.6);
+    s.insert("neuromodamplitude".into(), 0.3);
+    s.insert("legalcomplexity".into(), 0.3);
+    let report = spec.viability_margin("Baseline", &s).unwrap();
+    assert!((report.margins[3].margin - 0.02).abs() < 1e-6);
+    assert!((report.margins[3].distance - 0.2).abs() < 1e-5);
+
+    // intensity is 0.1 from its face; implantpower is 0.2 from its own.
+    let worst = report.worst.unwrap();
+    assert_eq!(worst.face, Face::Row(0));
+    assert!((worst.distance - 0.1).abs() < 1e-6);
+}
+
+#[test]
+fn axis_ranges_count_toward_viability() {
+    let spec = shipped();
+    // Every row holds (intensity <= 0.7), but intensity is below its range.
+    let mut below = baseline_state();
+    below.insert("intensity".into(), -0.2);
+
+    let report = spec.viability_margin("Baseline", &below).unwrap();
+    assert!(!report.viable);
+    let worst = report.worst.unwrap();
+    assert_eq!(worst.face, Face::AxisMin("intensity".into()));
+    assert!((worst.margin + 0.2).abs() < 1e-6 && worst.distance == worst.margin);
+    // `is_viable` checks the rows only.
+    assert!(spec.is_viable("Baseline", &below).unwrap());
+
+    // The report agrees with projection and stepping, which keep to the box.
+    let projected = spec.project_to_viable("Baseline", &below).unwrap();
+    assert!(projected.state["intensity"].abs() < 1e-6);
+    assert!(spec.viability_margin("Baseline", &projected.state).unwrap().viable);
+    let step = state(&[("intensity", 0.1)]);
+    assert_eq!(spec.max_viable_step("Baseline", &below, &step).unwrap(), 0.0);
+
+    // Missing axes get no range faces of their own.
+    let mut partial = baseline_state();
+    partial.remove("intensity");
+    let report = spec.viability_margin("Baseline", &partial).unwrap();
+    assert!(!report.margins.iter().any(|m| m.face == Face::AxisMax("intensity".into())));
+    assert_eq!(report.margins.len(), 8 + 2 * 7);
+}
@@ -0,0 +1,428 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::{
+    verify_chain, verify_chain_from, Checkpoint, LedgerEntry, LedgerError, LedgerStore, MerkleTree, NdjsonStore,
+    NewEntry, RohFold, SegmentRoots, GENESIS_HASH,
+};
+
+const HEAD_FILE: &str = "head.ndjson";
+const CHECKPOINT_FILE: &str = "checkpoints.ndjson";
+const SEGMENT_DIR: &str = "segments";
+
+/// `segments/<first>-<last>.ndjson.gz`, zero-padded so names sort by seq.
+fn segment_name(first: u64, last: u64) -> String {
+    format!("{first:020}-{last:020}.ndjson.gz")
+}
+
+/// `segments/<first>-<last>.roots.json`: the segment's `SegmentRoots`.
+fn roots_name(first: u64, last: u64) -> String {
+    format!("{first:020}-{last:020}.roots.json")
+}
+
+/// Create `dir/name` through a staged file that `write` fills, fsync it,
+/// and make it read-only.
+fn write_sealed(dir: &Path, name: &str, write: impl FnOnce(File) -> std::io::Result<File>) -> Result<PathBuf, LedgerError> {
+    let path = dir.join(name);
+    let staged = dir.join(format!(".{name}.tmp"));
+    write(File::create(&staged)?)?.sync_all()?;
+    fs::rename(&staged, &path)?;
+    let mut perms = fs::metadata(&path)?.permissions();
+    perms.set_readonly(true);
+    fs::set_permissions(&path, perms)?;
+    Ok(path)
+}
+
+/// The persisted roots of an archived segment, or `None` if there are none
+/// (an archive written before they were kept).
+pub fn read_segment_roots(dir: &Path, first: u64, last: u64) -> Result<Option<SegmentRoots>, LedgerError> {
+    let path = dir.join(SEGMENT_DIR).join(roots_name(first, last));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let roots: SegmentRoots = serde_json::from_slice(&fs::read(&path)?).map_err(|e| LedgerError::Parse {
+        line: 1,
+        message: format!("{}: {}", path.display(), e),
+    })?;
+    if (roots.first, roots.last) != (first, last) {
+        return Err(LedgerError::BadProof {
+            message: format!("{} holds roots for {}..={}", path.display(), roots.first, roots.last),
+        });
+    }
+    Ok(Some(roots))
+}
+
+/// Archived segments under `dir` as `(first_seq, last_seq, path)`, in order.
+pub fn segments(dir: &Path) -> Result<Vec<(u64, u64, PathBuf)>, LedgerError> {
+    let seg_dir = dir.join(SEGMENT_DIR);
+    if !seg_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for item in fs::read_dir(&seg_dir)? {
+        let path = item?.path();
+        let Some(range) = path.file_name().and_then(|n| n.to_str()?.strip_suffix(".ndjson.gz")) else {
+            continue;
+        };
+        if let Some((a, b)) = range.split_once('-') {
+            if let (Ok(first), Ok(last)) = (a.parse(), b.parse()) {
+                out.push((first, last, path));
+            }
+        }
+    }
+    out.sort_by_key(|s| s.0);
+    Ok(out)
+}
+
+/// Entries of one archived segment, checked against the range in its name.
+pub fn read_segment(first: u64, last: u64, path: &Path) -> Result<Vec<LedgerEntry>, LedgerError> {
+    let reader = BufReader::new(GzDecoder::new(File::open(path)?));
+    let mut entries: Vec<LedgerEntry> = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line).map_err(|e| LedgerError::Parse {
+            line: i + 1,
+            message: format!("{}: {}", path.display(), e),
+        })?);
+    }
+    let span = (entries.first().map(|e| e.seq), entries.last().map(|e| e.seq));
+    if span != (Some(first), Some(last)) {
+        return Err(LedgerError::ChainBroken {
+            seq: first,
+            message: format!("{} does not hold entries {first}..={last}", path.display()),
+        });
+    }
+    Ok(entries)
+}
+
+/// Complete checkpoint lines, oldest first; a cut-off final line is ignored.
+fn read_checkpoints(path: &Path) -> Result<Vec<Checkpoint>, LedgerError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(path)?;
+    let complete = match data.rfind('\n') {
+        Some(i) => &data[..i],
+        None => "",
+    };
+    let mut out = Vec::new();
+    for (i, line) in complete.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        out.push(serde_json::from_str(line).map_err(|e| LedgerError::Parse {
+            line: i + 1,
+            message: format!("{}: {}", path.display(), e),
+        })?);
+    }
+    Ok(out)
+}
+
+/// The last complete checkpoint line, without parsing the ones before it.
+fn latest_checkpoint(path: &Path) -> Result<Option<Checkpoint>, LedgerError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read_to_string(path)?;
+    let complete = data.rfind('\n').map_or("", |i| &data[..i]);
+    let Some((i, line)) = complete.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()).last() else {
+        return Ok(None);
+    };
+    serde_json::from_str(line).map(Some).map_err(|e| LedgerError::Parse {
+        line: i + 1,
+        message: format!("{}: {}", path.display(), e),
+    })
+}
+
+/// A ledger directory whose boot cost is bounded by the live head, not by
+/// its age:
+///
+/// - `head.ndjson` holds entries not yet archived;
+/// - `checkpoints.ndjson` holds signed checkpoints, newest last;
+/// - `segments/` holds gzip-compressed, read-only archived entries.
+///
+/// `open` trusts the latest checkpoint (once its signature checks out
+/// against a trusted key) and re-verifies only the head and the link from
+/// the last archived entry to it.
+/// `verify_archive` audits the whole history from genesis.
+pub struct SegmentedLedger {
+    dir: PathBuf,
+    trusted: Vec<VerifyingKey>,
+    head_store: NdjsonStore,
+    /// Entries in `head.ndjson` from `archived_height` on.
+    head: Vec<LedgerEntry>,
+    archived_height: u64,
+    /// Hash the first head entry links to.
+    archived_hash: String,
+    checkpoint: Option<Checkpoint>,
+    roh: RohFold,
+}
+
+impl SegmentedLedger {
+    pub fn open(dir: impl AsRef<Path>, trusted: &[VerifyingKey]) -> Result<Self, LedgerError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(dir.join(SEGMENT_DIR))?;
+
+        let archived_height = segments(&dir)?.last().map_or(0, |s| s.1 + 1);
+        let checkpoint = latest_checkpoint(&dir.join(CHECKPOINT_FILE))?;
+        if let Some(cp) = &checkpoint {
+            cp.verify(trusted)?;
+        }
+        let (base_height, base_hash, mut roh) = match &checkpoint {
+            Some(cp) => (cp.height, cp.head_hash.clone(), cp.roh.clone()),
+            None => (0, GENESIS_HASH.to_string(), RohFold::default()),
+        };
+        if base_height < archived_height {
+            return Err(LedgerError::BadCheckpoint {
+                height: base_height,
+                message: format!("segments extend to {archived_height}, past the latest checkpoint"),
+            });
+        }
+
+        let mut head_store = NdjsonStore::new(dir.join(HEAD_FILE));
+        let mut head = head_store.load()?;
+        // Left over if archiving stopped between writing a segment and
+        // resetting the head.
+        head.retain(|e| e.seq >= archived_height);
+
+        // Hash the first head entry must link to: genesis, the checkpoint
+        // if it sits at the archive boundary, or else the last archived
+        // entry.
+        let archived_hash = match segments(&dir)?.last() {
+            None => GENESIS_HASH.to_string(),
+            Some(_) if base_height == archived_height => base_hash.clone(),
+            Some((a, b, path)) => read_segment(*a, *b, path)?.pop().expect("segment is non-empty").hash,
+        };
+
+        // Entries the checkpoint already covers are verified in full and
+        // must end at its head hash; their RoH is already in its fold.
+        let covered = head.iter().take_while(|e| e.seq < base_height).count();
+        verify_chain_from(&head[..covered], archived_height, &archived_hash)?;
+        if base_height > archived_height {
+            let last = head[..covered]
+                .last()
+                .filter(|e| e.seq + 1 == base_height)
+                .ok_or_else(|| LedgerError::BadCheckpoint {
+                    height: base_height,
+                    message: "head is missing entries the checkpoint covers".to_string(),
+                })?;
+            if last.hash != base_hash {
+                return Err(LedgerError::BadCheckpoint {
+                    height: base_height,
+                    message: format!("head hash does not match entry {}", last.seq),
+                });
+            }
+        }
+        verify_chain_from(&head[covered..], base_height, &base_hash)?;
+        for e in &head[covered..] {
+            roh.fold(e);
+        }
+
+        Ok(Self {
+            dir,
+            trusted: trusted.to_vec(),
+            head_store,
+            head,
+            archived_height,
+            archived_hash,
+            checkpoint,
+            roh,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Total entries ever appended; the next entry has this seq.
+    pub fn height(&self) -> u64 {
+        self.archived_height + self.head.len() as u64
+    }
+
+    /// Hash the next entry will link to.
+    pub fn head_hash(&self) -> &str {
+        self.head.last().map_or(self.archived_hash.as_str(), |e| e.hash.as_str())
+    }
+
+    /// Entries not yet archived.
+    pub fn head(&self) -> &[LedgerEntry] {
+        &self.head
+    }
+
+    pub fn latest_checkpoint(&self) -> Option<&Checkpoint> {
+        self.checkpoint.as_ref()
+    }
+
+    /// RoH state folded over the whole chain.
+    pub fn roh(&self) -> &RohFold {
+        &self.roh
+    }
+
+    pub fn append(&mut self, body: NewEntry, signer: Option<&SigningKey>) -> Result<&LedgerEntry, LedgerError> {
+        let entry = LedgerEntry::seal(self.height(), self.head_hash(), body, signer);
+        self.head_store.append(&entry)?;
+        self.roh.fold(&entry);
+        self.head.push(entry);
+        Ok(self.head.last().expect("just pushed"))
+    }
+
+    /// Sign and persist a checkpoint at the current height. `signer` must be
+    /// one of the trusted keys or the next `open` refuses the checkpoint.
+    pub fn checkpoint(&mut self, signer: &SigningKey) -> Result<&Checkpoint, LedgerError> {
+        if !self.trusted.contains(&signer.verifying_key()) {
+            return Err(LedgerError::BadCheckpoint {
+                height: self.height(),
+                message: "signer is not trusted".to_string(),
+            });
+        }
+        let cp = Checkpoint::seal(self.height(), self.head_hash(), self.roh.clone(), signer);
+        let json = serde_json::to_string(&cp).expect("serialize Checkpoint");
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(CHECKPOINT_FILE))?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(json.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        Ok(self.checkpoint.insert(cp))
+    }
+
+    /// Re-verify the head in full, checkpoint it, roll it into a new
+    /// read-only compressed segment, and start an empty head. Returns the
+    /// segment path, or `None` if there was nothing to archive.
+    ///
+    /// Steps are ordered so a crash at any point leaves a ledger `open`
+    /// accepts: the checkpoint is written before the segment, and head
+    /// entries already in a segment are dropped on open.
+    pub fn archive(&mut self, signer: &SigningKey) -> Result<Option<PathBuf>, LedgerError> {
+        let (Some(first), Some(last)) = (self.head.first(), self.head.last()) else {
+            return Ok(None);
+        };
+        let (first, last) = (first.seq, last.seq);
+        verify_chain_from(&self.head, self.archived_height, &self.archived_hash)?;
+        self.checkpoint(signer)?;
+
+        // Roots go first: a segment without them is still readable, only
+        // slower to build a tree over.
+        let seg_dir = self.dir.join(SEGMENT_DIR);
+        let roots = serde_json::to_vec(&SegmentRoots::from_entries(&self.head)).expect("serialize SegmentRoots");
+        write_sealed(&seg_dir, &roots_name(first, last), |mut file| {
+            file.write_all(&roots)?;
+            Ok(file)
+        })?;
+        let path = write_sealed(&seg_dir, &segment_name(first, last), |file| {
+            let mut gz = GzEncoder::new(BufWriter::new(file), Compression::default());
+            for e in &self.head {
+                serde_json::to_writer(&mut gz, e).expect("serialize LedgerEntry");
+                gz.write_all(b"\n")?;
+            }
+            gz.finish()?.into_inner().map_err(|e| e.into_error())
+        })?;
+
+        File::create(self.head_store.path())?.sync_all()?;
+        self.archived_hash = self.head_hash().to_string();
+        self.archived_height = last + 1;
+        self.head.clear();
+        Ok(Some(path))
+    }
+}
+
+/// Every entry of a `SegmentedLedger` directory, archived segments first,
+/// without verification (see `verify_archive`).
+pub fn read_all(dir: impl AsRef<Path>) -> Result<Vec<LedgerEntry>, LedgerError> {
+    let dir = dir.as_ref();
+    let mut entries = Vec::new();
+    for (first, last, path) in segments(dir)? {
+        entries.extend(read_segment(first, last, &path)?);
+    }
+    let archived_height = entries.len() as u64;
+    let mut head = NdjsonStore::new(dir.join(HEAD_FILE));
+    entries.extend(head.load()?.into_iter().filter(|e| e.seq >= archived_height));
+    Ok(entries)
+}
+
+/// Merkle tree over every entry of a `SegmentedLedger` directory, to serve
+/// proofs from an archive. Archived segments contribute their persisted
+/// roots; only a segment without them, and the head, are read and hashed.
+/// Unverified, like `read_all`.
+pub fn merkle_tree(dir: impl AsRef<Path>) -> Result<MerkleTree, LedgerError> {
+    let dir = dir.as_ref();
+    let mut tree = MerkleTree::default();
+    for (first, last, path) in segments(dir)? {
+        match read_segment_roots(dir, first, last)? {
+            Some(roots) => tree.append_segment(&roots)?,
+            None => {
+                for e in &read_segment(first, last, &path)? {
+                    tree.push(e);
+                }
+            }
+        }
+    }
+    let archived_height = tree.len();
+    let mut head = NdjsonStore::new(dir.join(HEAD_FILE));
+    for e in head.load()?.iter().filter(|e| e.seq >= archived_height) {
+        tree.push(e);
+    }
+    Ok(tree)
+}
+
+/// Audit a `SegmentedLedger` directory from genesis: every segment and the
+/// head form one verified chain, and every checkpoint is signed by a trusted
+/// key and matches the chain's hash and folded RoH at its height, and every
+/// segment's persisted roots match its entries. Returns the chain height.
+pub fn verify_archive(dir: impl AsRef<Path>, trusted: &[VerifyingKey]) -> Result<u64, LedgerError> {
+    let dir = dir.as_ref();
+    let entries = read_all(dir)?;
+    verify_chain(&entries)?;
+    for (first, last, _) in segments(dir)? {
+        let expected = SegmentRoots::from_entries(&entries[first as usize..=last as usize]);
+        if read_segment_roots(dir, first, last)?.is_some_and(|roots| roots != expected) {
+            return Err(LedgerError::BadProof {
+                message: format!("persisted roots of segment {first}..={last} do not match its entries"),
+            });
+        }
+    }
+
+    let checkpoints = read_checkpoints(&dir.join(CHECKPOINT_FILE))?;
+    let mut roh = RohFold::default();
+    let mut folded = 0u64;
+    for cp in &checkpoints {
+        cp.verify(trusted)?;
+        let bad = |message: String| LedgerError::BadCheckpoint {
+            height: cp.height,
+            message,
+        };
+        if cp.height < folded {
+            return Err(bad("checkpoints are out of order".to_string()));
+        }
+        if cp.height > entries.len() as u64 {
+            return Err(bad(format!("chain has only {} entries", entries.len())));
+        }
+        for e in &entries[folded as usize..cp.height as usize] {
+            roh.fold(e);
+        }
+        folded = cp.height;
+        let expected = match cp.height {
+            0 => GENESIS_HASH,
+            h => entries[h as usize - 1].hash.as_str(),
+        };
+        if cp.head_hash != expected {
+            return Err(bad("head hash does not match the chain".to_string()));
+        }
+        if cp.roh != roh {
+            return Err(bad("folded RoH does not match the chain".to_string()));
+        }
+    }
+    Ok(entries.len() as u64)
+}
@@ -0,0 +1,83 @@
+use chrono::{SecondsFormat, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::entry::content_hash;
+use crate::{EntrySignature, LedgerEntry, LedgerError};
+
+/// RoH state folded over every entry up to a height, so verification that
+/// starts at a checkpoint still knows where RoH stands.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RohFold {
+    /// Latest `roh_after` seen.
+    pub current: Option<f32>,
+    /// Highest `roh_after` seen.
+    pub peak: Option<f32>,
+    /// Entries whose `roh_after` exceeded their `roh_before`.
+    pub increases: u64,
+}
+
+impl RohFold {
+    pub fn fold(&mut self, entry: &LedgerEntry) {
+        let (before, after) = entry.body.roh();
+        let Some(after) = after else {
+            return;
+        };
+        if before.is_some_and(|before| after > before) {
+            self.increases += 1;
+        }
+        self.current = Some(after);
+        self.peak = Some(self.peak.map_or(after, |p| p.max(after)));
+    }
+}
+
+/// Signed statement that the chain's first `height` entries end in
+/// `head_hash` with RoH state `roh`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Entries covered; the next entry has this seq.
+    pub height: u64,
+    /// Hash of entry `height - 1` (`GENESIS_HASH` at height 0).
+    pub head_hash: String,
+    pub roh: RohFold,
+    /// RFC 3339 UTC.
+    pub created_at: String,
+    /// sha256 over the canonical JSON of every other field except `signature`.
+    pub hash: String,
+    pub signature: EntrySignature,
+}
+
+impl Checkpoint {
+    pub fn seal(height: u64, head_hash: &str, roh: RohFold, signer: &SigningKey) -> Self {
+        let mut cp = Self {
+            height,
+            head_hash: head_hash.to_string(),
+            roh,
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            hash: String::new(),
+            signature: EntrySignature {
+                public_key: String::new(),
+                signature: String::new(),
+            },
+        };
+        cp.hash = content_hash(&cp);
+        cp.signature = EntrySignature::sign(signer, &cp.hash);
+        cp
+    }
+
+    /// Check the content hash and that one of `trusted` signed it.
+    pub fn verify(&self, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        let bad = |message: String| LedgerError::BadCheckpoint {
+            height: self.height,
+            message,
+        };
+        if content_hash(self) != self.hash {
+            return Err(bad("content does not match hash".to_string()));
+        }
+        let key = self.signature.verify(&self.hash).map_err(bad)?;
+        if !trusted.contains(&key) {
+            return Err(bad("signer is not trusted".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use chrono::{SecondsFormat, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::RohCertificate;
+
+/// Highest entry schema this crate writes and reads.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// prev_hash of the first entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Which writer produced the entry; imported entries keep their origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    Native,
+    /// `sovereign-core::donutloop::DonutLoop` (JSON AuditEntry lines).
+    SovereignCore,
+    /// `sovereigntycore/src/ledger.rs::DonutLoopLedger` (bincode frames).
+    SovereigntycoreLedger,
+    /// `crates/sovereigntycore` `AnswerLedgerWriter` (.answer.ndjson).
+    AnswerLedger,
+    /// `organiccpualn::donutloop::DonutloopEntry`.
+    Organiccpualn,
+    /// `policyengine::DonutLoopRowCore`.
+    Policyengine,
+}
+
+/// ed25519 over an entry's or checkpoint's `hash` (hex string bytes).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntrySignature {
+    /// Hex verifying key.
+    pub public_key: String,
+    /// Hex signature.
+    pub signature: String,
+}
+
+impl EntrySignature {
+    pub fn sign(key: &SigningKey, hash: &str) -> Self {
+        Self {
+            public_key: hex::encode(key.verifying_key().to_bytes()),
+            signature: hex::encode(key.sign(hash.as_bytes()).to_bytes()),
+        }
+    }
+
+    /// The signer's key if the signature over `hash` checks out.
+    pub fn verify(&self, hash: &str) -> Result<VerifyingKey, String> {
+        let key_bytes: [u8; 32] = hex::decode(&self.public_key)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or("malformed public key")?;
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|e| e.to_string())?;
+        let signature = hex::decode(&self.signature)
+            .ok()
+            .and_then(|b| Signature::from_slice(&b).ok())
+            .ok_or("malformed signature")?;
+        key.verify(hash.as_bytes(), &signature)
+            .map_err(|_| "signature does not match hash".to_string())?;
+        Ok(key)
+    }
+}
+
+/// SHA-256 of `value`'s canonical JSON without its `hash` and `signature`
+/// fields. serde_json maps are key-sorted, so the JSON is canonical regardless
+/// of field order on disk.
+pub(crate) fn content_hash<T: Serialize>(value: &T) -> String {
+    let mut value = serde_json::to_value(value).expect("serialize ledger record");
+    if let Value::Object(map) = &mut value {
+        map.remove("hash");
+        map.remove("signature");
+    }
+    let json = serde_json::to_vec(&value).expect("serialize ledger record");
+    hex::encode(Sha256::digest(json))
+}
+
+/// Entry content before it is chained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewEntry {
+    pub entry_id: String,
+    /// RFC 3339 UTC.
+    pub timestamp: String,
+    /// What happened: event type, change type, proposal kind.
+    pub kind: String,
+    pub source: Source,
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    #[serde(default)]
+    pub proposal_id: Option<String>,
+    #[serde(default)]
+    pub module_id: Option<String>,
+    #[serde(default)]
+    pub decision: Option<String>,
+    #[serde(default)]
+    pub roh_before: Option<f32>,
+    #[serde(default)]
+    pub roh_after: Option<f32>,
+    /// Kernel-recomputed RoH for the change; native entries carry this
+    /// instead of the bare `roh_before`/`roh_after` floats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roh_certificate: Option<RohCertificate>,
+    /// Format-specific fields; keys are stable per `source`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ext: BTreeMap<String, Value>,
+}
+
+impl NewEntry {
+    /// Native entry stamped now.
+    pub fn new(entry_id: impl Into<String>, kind: impl Into<String>) -> Self {
+        Self {
+            entry_id: entry_id.into(),
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            kind: kind.into(),
+            source: Source::Native,
+            subject_id: None,
+            proposal_id: None,
+            module_id: None,
+            decision: None,
+            roh_before: None,
+            roh_after: None,
+            roh_certificate: None,
+            ext: BTreeMap::new(),
+        }
+    }
+
+    /// Attach `cert` in place of the bare RoH floats. Fills `proposal_id`
+    /// and `subject_id` from the certificate when unset.
+    pub fn with_roh_certificate(mut self, cert: RohCertificate) -> Self {
+        self.roh_before = None;
+        self.roh_after = None;
+        if self.proposal_id.is_none() {
+            self.proposal_id = Some(cert.claim.proposal_id.clone());
+        }
+        if self.subject_id.is_none() {
+            self.subject_id = cert.claim.subject_id.clone();
+        }
+        self.roh_certificate = Some(cert);
+        self
+    }
+
+    /// `(roh_before, roh_after)`, from the certificate when there is one.
+    pub fn roh(&self) -> (Option<f32>, Option<f32>) {
+        match &self.roh_certificate {
+            Some(cert) => (Some(cert.claim.roh_before), Some(cert.claim.roh_after)),
+            None => (self.roh_before, self.roh_after),
+        }
+    }
+
+    /// Set `ext[key]`, dropping nulls so absent legacy fields stay absent.
+    pub fn with_ext(mut self, key: &str, value: impl Into<Value>) -> Self {
+        let value = value.into();
+        if !value.is_null() {
+            self.ext.insert(key.to_string(), value);
+        }
+        self
+    }
+}
+
+/// One chained ledger entry, as stored (one JSON object per NDJSON line).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub version: u32,
+    pub seq: u64,
+    #[serde(flatten)]
+    pub body: NewEntry,
+    pub prev_hash: String,
+    /// sha256 over the canonical JSON of every other field except `signature`.
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<EntrySignature>,
+}
+
+impl LedgerEntry {
+    /// Chain `body` after `prev_hash` and sign it if `signer` is given.
+    pub fn seal(seq: u64, prev_hash: &str, body: NewEntry, signer: Option<&SigningKey>) -> Self {
+        let mut entry = Self {
+            version: SCHEMA_VERSION,
+            seq,
+            body,
+            prev_hash: prev_hash.to_string(),
+            hash: String::new(),
+            signature: None,
+        };
+        entry.hash = entry.compute_hash();
+        entry.signature = signer.map(|key| EntrySignature::sign(key, &entry.hash));
+        entry
+    }
+
+    /// Hash of the entry's content.
+    pub fn compute_hash(&self) -> String {
+        content_hash(self)
+    }
+
+    /// The signer's key if the entry is signed and the signature checks out.
+    pub fn verify_signature(&self) -> Result<Option<VerifyingKey>, String> {
+        self.signature.as_ref().map(|sig| sig.verify(&self.hash)).transpose()
+    }
+}
@@ -0,0 +1,200 @@
+use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::{
+    ConsistencyProof, DecisionProof, InclusionProof, LedgerEntry, LedgerError, LedgerStore, MerkleTree, NewEntry,
+    SignedRoot, GENESIS_HASH, SCHEMA_VERSION,
+};
+
+/// Check seq numbering, prev_hash links, content hashes and any signatures
+/// present. Unsigned entries pass; use `require_signed_by` to demand keys.
+pub fn verify_chain(entries: &[LedgerEntry]) -> Result<(), LedgerError> {
+    verify_chain_from(entries, 0, GENESIS_HASH)
+}
+
+/// `verify_chain` for a chain suffix: `entries[0]` must have seq `height` and
+/// link to `head_hash` (e.g. from a trusted checkpoint).
+pub fn verify_chain_from(entries: &[LedgerEntry], height: u64, head_hash: &str) -> Result<(), LedgerError> {
+    let mut prev = head_hash;
+    for (i, entry) in entries.iter().enumerate() {
+        let seq = entry.seq;
+        let expected = height + i as u64;
+        if entry.version > SCHEMA_VERSION {
+            return Err(LedgerError::UnsupportedVersion {
+                seq,
+                version: entry.version,
+            });
+        }
+        if seq != expected {
+            return Err(LedgerError::ChainBroken {
+                seq,
+                message: format!("expected seq {expected}"),
+            });
+        }
+        if entry.prev_hash != prev {
+            return Err(LedgerError::ChainBroken {
+                seq,
+                message: format!("prev_hash {} does not match {}", entry.prev_hash, prev),
+            });
+        }
+        if entry.compute_hash() != entry.hash {
+            return Err(LedgerError::ChainBroken {
+                seq,
+                message: "content does not match hash".to_string(),
+            });
+        }
+        entry
+            .verify_signature()
+            .map_err(|message| LedgerError::BadSignature { seq, message })?;
+        prev = &entry.hash;
+    }
+    Ok(())
+}
+
+/// `verify_chain`, plus every entry must be signed by one of `trusted`.
+pub fn require_signed_by(entries: &[LedgerEntry], trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+    verify_chain(entries)?;
+    for entry in entries {
+        let seq = entry.seq;
+        match entry.verify_signature() {
+            Ok(Some(key)) if trusted.contains(&key) => {}
+            Ok(Some(_)) => {
+                return Err(LedgerError::BadSignature {
+                    seq,
+                    message: "signer is not trusted".to_string(),
+                })
+            }
+            Ok(None) => {
+                return Err(LedgerError::BadSignature {
+                    seq,
+                    message: "entry is unsigned".to_string(),
+                })
+            }
+            Err(message) => return Err(LedgerError::BadSignature { seq, message }),
+        }
+    }
+    Ok(())
+}
+
+/// `verify_chain`, plus every entry that records RoH must do so through a
+/// monotone certificate signed by one of `trusted`; bare floats fail.
+pub fn require_roh_certified(entries: &[LedgerEntry], trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+    verify_chain(entries)?;
+    for entry in entries {
+        let body = &entry.body;
+        match &body.roh_certificate {
+            Some(cert) => {
+                cert.verify_monotone(trusted)?;
+                if body.roh_before.is_some() || body.roh_after.is_some() {
+                    return Err(LedgerError::ChainBroken {
+                        seq: entry.seq,
+                        message: "entry carries both a RoH certificate and bare RoH values".to_string(),
+                    });
+                }
+                if body.proposal_id.as_deref() != Some(cert.claim.proposal_id.as_str()) {
+                    return Err(LedgerError::ChainBroken {
+                        seq: entry.seq,
+                        message: format!("RoH certificate is for proposal {}", cert.claim.proposal_id),
+                    });
+                }
+            }
+            None if body.roh_before.is_some() || body.roh_after.is_some() => {
+                return Err(LedgerError::ChainBroken {
+                    seq: entry.seq,
+                    message: "RoH values are not certified".to_string(),
+                })
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// A verified chain over a store, with a Merkle tree over its entries.
+/// Entries are chained and persisted before they become visible in memory.
+pub struct Ledger<S: LedgerStore> {
+    store: S,
+    entries: Vec<LedgerEntry>,
+    tree: MerkleTree,
+}
+
+impl<S: LedgerStore> Ledger<S> {
+    /// Load `store` and refuse it unless the whole chain verifies.
+    pub fn open(mut store: S) -> Result<Self, LedgerError> {
+        let entries = store.load()?;
+        verify_chain(&entries)?;
+        let tree = MerkleTree::from_entries(&entries);
+        Ok(Self { store, entries, tree })
+    }
+
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Hash the next entry will link to.
+    pub fn head(&self) -> &str {
+        self.entries.last().map_or(GENESIS_HASH, |e| e.hash.as_str())
+    }
+
+    pub fn append(&mut self, body: NewEntry, signer: Option<&SigningKey>) -> Result<&LedgerEntry, LedgerError> {
+        let entry = LedgerEntry::seal(self.entries.len() as u64, self.head(), body, signer);
+        self.store.append(&entry)?;
+        self.tree.push(&entry);
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("just pushed"))
+    }
+
+    pub fn merkle_root(&self) -> String {
+        self.tree.root()
+    }
+
+    /// Sign the Merkle root at the current height.
+    pub fn signed_root(&self, signer: &SigningKey) -> SignedRoot {
+        SignedRoot::seal(self.tree.len(), &self.tree.root(), signer)
+    }
+
+    /// Proof that entry `seq` is in the tree of the first `size` entries.
+    pub fn inclusion_proof(&self, seq: u64, size: u64) -> Result<InclusionProof, LedgerError> {
+        let entry = self.entries.get(seq as usize).ok_or_else(|| LedgerError::BadProof {
+            message: format!("no entry {seq}"),
+        })?;
+        self.tree.inclusion_proof(entry, size)
+    }
+
+    /// Proof that the ledger at height `first` is a prefix of it at `second`.
+    pub fn consistency_proof(&self, first: u64, second: u64) -> Result<ConsistencyProof, LedgerError> {
+        self.tree.consistency_proof(first, second)
+    }
+
+    /// Entry `seq` with its inclusion proof against `root`.
+    pub fn decision_proof(&self, seq: u64, root: &SignedRoot) -> Result<DecisionProof, LedgerError> {
+        let inclusion = self.inclusion_proof(seq, root.tree_size)?;
+        if self.tree.root_at(root.tree_size)? != root.root_hash {
+            return Err(LedgerError::BadProof {
+                message: "signed root is not this ledger's root".to_string(),
+            });
+        }
+        Ok(DecisionProof {
+            entry: self.entries[seq as usize].clone(),
+            inclusion,
+            root: root.clone(),
+        })
+    }
+
+    /// Append converted legacy entries in order; returns how many were added.
+    pub fn import(
+        &mut self,
+        bodies: impl IntoIterator<Item = NewEntry>,
+        signer: Option<&SigningKey>,
+    ) -> Result<usize, LedgerError> {
+        let mut n = 0;
+        for body in bodies {
+            self.append(body, signer)?;
+            n += 1;
+        }
+        Ok(n)
+    }
+}
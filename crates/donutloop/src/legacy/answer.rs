@@ -0,0 +1,125 @@
+//! `crates/sovereigntycore` `AnswerLedgerWriter`: `.answer.ndjson` lines
+//! chained by `hexstamp`/`prev_hexstamp` ("0xGENESIS" at the start, or an
+//! "0xEPOCH1:" boundary on a ledger migrated off FNV stamps).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{broken, unix_to_rfc3339};
+use crate::{LedgerError, NewEntry, Source};
+
+pub const GENESIS_HEXSTAMP: &str = "0xGENESIS";
+pub const EPOCH_BOUNDARY_PREFIX: &str = "0xEPOCH1:";
+pub const HEXSTAMP_EPOCH_FNV: u32 = 0;
+pub const HEXSTAMP_EPOCH_SHA256: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnswerNdjsonRecord {
+    pub answer_id: String,
+    pub subject_id: String,
+    pub kernel_id: String,
+    pub route: String,
+    pub knowledge_factor: f32,
+    pub roh: f32,
+    pub cybostate: Value,
+    pub bio: Value,
+    pub actuation_forbidden: bool,
+    pub non_commercial: bool,
+    pub roh_domain: Option<String>,
+    pub hexstamp: String,
+    pub prev_hexstamp: String,
+    pub timestamp_utc: u64,
+    pub artifact_kind: String,
+    pub contract_type: String,
+    /// 0 = FNV stamp, 1 = SHA-256 over the full record.
+    #[serde(default)]
+    pub hexstamp_epoch: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legacy_hexstamp: Option<String>,
+}
+
+/// The writer's epoch-1 stamp: SHA-256 over the canonical JSON of `record`
+/// with `hexstamp` blanked.
+pub fn sha256_hexstamp(record: &AnswerNdjsonRecord) -> Result<String, serde_json::Error> {
+    let mut value = serde_json::to_value(record)?;
+    if let Some(map) = value.as_object_mut() {
+        map.insert("hexstamp".to_string(), Value::String(String::new()));
+        // The writer hashed `bio` from f32 fields, widened to f64; here it
+        // was read back as f64, so narrow it the same way first.
+        if let Some(bio) = map.get_mut("bio") {
+            narrow_to_f32(bio);
+        }
+    }
+    Ok(format!("0x{}", hex::encode(Sha256::digest(serde_json::to_vec(&value)?))))
+}
+
+fn narrow_to_f32(value: &mut Value) {
+    match value {
+        Value::Number(n) if n.is_f64() => *value = Value::from(n.as_f64().unwrap_or_default() as f32),
+        Value::Array(items) => items.iter_mut().for_each(narrow_to_f32),
+        Value::Object(map) => map.values_mut().for_each(narrow_to_f32),
+        _ => {}
+    }
+}
+
+pub fn convert(records: &[AnswerNdjsonRecord]) -> Result<Vec<NewEntry>, LedgerError> {
+    let mut prev = GENESIS_HEXSTAMP;
+    let mut epoch = HEXSTAMP_EPOCH_FNV;
+    for (i, r) in records.iter().enumerate() {
+        let boundary = i == 0 && r.prev_hexstamp.starts_with(EPOCH_BOUNDARY_PREFIX);
+        if r.prev_hexstamp != prev && !boundary {
+            return Err(broken(i, format!("prev_hexstamp {:?} does not follow {:?}", r.prev_hexstamp, prev)));
+        }
+        // FNV stamps cover too little to recompute usefully; SHA-256 stamps
+        // cover the whole record and must match it.
+        match r.hexstamp_epoch {
+            HEXSTAMP_EPOCH_FNV if boundary || epoch != HEXSTAMP_EPOCH_FNV => {
+                return Err(broken(i, "FNV-stamped record after SHA-256 records"));
+            }
+            HEXSTAMP_EPOCH_FNV => {}
+            HEXSTAMP_EPOCH_SHA256 => {
+                let expected = sha256_hexstamp(r).map_err(|e| broken(i, e.to_string()))?;
+                if r.hexstamp != expected {
+                    return Err(broken(i, format!("hexstamp mismatch for answer {}", r.answer_id)));
+                }
+            }
+            other => return Err(broken(i, format!("unknown hexstamp epoch {other}"))),
+        }
+        epoch = r.hexstamp_epoch;
+        prev = &r.hexstamp;
+    }
+
+    records
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            Ok(NewEntry {
+                entry_id: r.answer_id.clone(),
+                timestamp: unix_to_rfc3339(i, r.timestamp_utc)?,
+                kind: r.artifact_kind.clone(),
+                source: Source::AnswerLedger,
+                subject_id: Some(r.subject_id.clone()),
+                proposal_id: None,
+                module_id: Some(r.kernel_id.clone()),
+                decision: None,
+                roh_before: None,
+                roh_after: Some(r.roh),
+                roh_certificate: None,
+                ext: Default::default(),
+            }
+            .with_ext("route", r.route.clone())
+            .with_ext("knowledge_factor", r.knowledge_factor)
+            .with_ext("cybostate", r.cybostate.clone())
+            .with_ext("bio", r.bio.clone())
+            .with_ext("actuation_forbidden", r.actuation_forbidden)
+            .with_ext("non_commercial", r.non_commercial)
+            .with_ext("roh_domain", r.roh_domain.clone())
+            .with_ext("contract_type", r.contract_type.clone())
+            .with_ext("hexstamp_epoch", r.hexstamp_epoch)
+            .with_ext("legacy_hash", r.hexstamp.clone())
+            .with_ext("legacy_prev_hash", r.prev_hexstamp.clone())
+            .with_ext("legacy_fnv_hexstamp", r.legacy_hexstamp.clone()))
+        })
+        .collect()
+}
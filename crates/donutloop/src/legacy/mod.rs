@@ -0,0 +1,49 @@
+//! Adapters from the legacy donutloop formats. Each `convert` first checks
+//! the legacy ledger's own links (fail closed: a broken legacy chain imports
+//! nothing), then maps every record to a `NewEntry`, keeping the legacy
+//! hashes and signatures under `ext` so the original chain stays auditable.
+
+pub mod answer;
+pub mod organiccpualn;
+pub mod policyengine;
+pub mod sovereign_core;
+pub mod sovereigntycore;
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat};
+use serde::de::DeserializeOwned;
+
+use crate::LedgerError;
+
+/// Read a JSON-lines legacy file; blank lines are skipped.
+pub fn read_ndjson<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<Vec<T>, LedgerError> {
+    let data = fs::read_to_string(path)?;
+    let mut out = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        out.push(serde_json::from_str(line).map_err(|e| LedgerError::Parse {
+            line: i + 1,
+            message: e.to_string(),
+        })?);
+    }
+    Ok(out)
+}
+
+fn broken(index: usize, message: impl Into<String>) -> LedgerError {
+    LedgerError::Legacy {
+        index,
+        message: message.into(),
+    }
+}
+
+fn unix_to_rfc3339(index: usize, secs: u64) -> Result<String, LedgerError> {
+    i64::try_from(secs)
+        .ok()
+        .and_then(|s| DateTime::from_timestamp(s, 0))
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .ok_or_else(|| broken(index, format!("timestamp {secs} out of range")))
+}
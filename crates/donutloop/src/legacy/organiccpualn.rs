@@ -0,0 +1,79 @@
+//! `organiccpualn::donutloop::DonutloopEntry`: entries of a `DonutloopShard`,
+//! chained by `entry_hash`/`prev_hash`.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::broken;
+use crate::{LedgerError, NewEntry, Source};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonutloopEntry {
+    pub entry_id: String,
+    pub proposal_id: String,
+    pub policy_id: String,
+    pub decision: String,
+    pub roh_before: f32,
+    pub roh_after: f32,
+    pub hexstamp: String,
+    pub prev_hash: String,
+    pub entry_hash: String,
+    pub evolve_stream_pointer: String,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonutloopShard {
+    pub meta: Value,
+    pub entries: Vec<DonutloopEntry>,
+}
+
+/// Load a JSON-serialized `DonutloopShard`.
+pub fn read_shard(path: impl AsRef<Path>) -> Result<DonutloopShard, LedgerError> {
+    let data = fs::read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| LedgerError::Parse {
+        line: e.line(),
+        message: e.to_string(),
+    })
+}
+
+/// The shard format has no genesis marker, so the first entry's `prev_hash`
+/// is taken as given.
+pub fn convert(records: &[DonutloopEntry]) -> Result<Vec<NewEntry>, LedgerError> {
+    for (i, pair) in records.windows(2).enumerate() {
+        if pair[1].prev_hash != pair[0].entry_hash {
+            return Err(broken(
+                i + 1,
+                format!("prev_hash {:?} does not follow {:?}", pair[1].prev_hash, pair[0].entry_hash),
+            ));
+        }
+    }
+
+    Ok(records
+        .iter()
+        .map(|r| {
+            NewEntry {
+                entry_id: r.entry_id.clone(),
+                timestamp: r.timestamp.clone(),
+                kind: "policy_decision".to_string(),
+                source: Source::Organiccpualn,
+                subject_id: None,
+                proposal_id: Some(r.proposal_id.clone()),
+                module_id: None,
+                decision: Some(r.decision.clone()),
+                roh_before: Some(r.roh_before),
+                roh_after: Some(r.roh_after),
+                roh_certificate: None,
+                ext: Default::default(),
+            }
+            .with_ext("policy_id", r.policy_id.clone())
+            .with_ext("hexstamp", r.hexstamp.clone())
+            .with_ext("evolve_stream_pointer", r.evolve_stream_pointer.clone())
+            .with_ext("legacy_hash", r.entry_hash.clone())
+            .with_ext("legacy_prev_hash", r.prev_hash.clone())
+        })
+        .collect())
+}
@@ -0,0 +1,81 @@
+//! `policyengine::DonutLoopRowCore`: `.donutloop.aln` rows chained by
+//! `row_id`/`prev_row_id` and `hexstamp`/`prev_hexstamp` (both absent on the
+//! first row). Fields beyond the core subset (e.g. `decision`, `timestamp`)
+//! are kept in `extra`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::broken;
+use crate::{LedgerError, NewEntry, Source};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonutLoopRowCore {
+    pub row_id: String,
+    pub prev_row_id: Option<String>,
+    pub hexstamp: String,
+    pub prev_hexstamp: Option<String>,
+    pub proposal_id: String,
+    pub proposal_kind: String,
+    pub module_id: String,
+    pub roh_before: f32,
+    pub roh_after: f32,
+    pub capability_before: Value,
+    pub capability_after: Value,
+    pub jurisdiction: Value,
+    pub policy_stack_snapshot: Value,
+    pub biophysical_source_id: String,
+    pub regulatory_basis_id: String,
+    pub validation_evidence_ref: String,
+    pub envelope_shard_ref: Option<String>,
+    pub roh_model_ref: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Rows carry no timestamp in the core subset; one in `extra` is used if
+/// present, otherwise the import time, flagged with `timestamp_is_import_time`.
+pub fn convert(records: &[DonutLoopRowCore]) -> Result<Vec<NewEntry>, LedgerError> {
+    let (mut prev_row, mut prev_stamp) = (None, None);
+    for (i, r) in records.iter().enumerate() {
+        if r.prev_row_id.as_deref() != prev_row || r.prev_hexstamp.as_deref() != prev_stamp {
+            return Err(broken(i, format!("row {} does not follow {:?}", r.row_id, prev_row)));
+        }
+        prev_row = Some(r.row_id.as_str());
+        prev_stamp = Some(r.hexstamp.as_str());
+    }
+
+    Ok(records
+        .iter()
+        .map(|r| {
+            let mut entry = NewEntry::new(r.row_id.clone(), r.proposal_kind.clone());
+            entry.source = Source::Policyengine;
+            entry.proposal_id = Some(r.proposal_id.clone());
+            entry.module_id = Some(r.module_id.clone());
+            entry.roh_before = Some(r.roh_before);
+            entry.roh_after = Some(r.roh_after);
+            entry.decision = r.extra.get("decision").and_then(Value::as_str).map(str::to_string);
+            match r.extra.get("timestamp").and_then(Value::as_str) {
+                Some(ts) => entry.timestamp = ts.to_string(),
+                None => entry = entry.with_ext("timestamp_is_import_time", true),
+            }
+            let mut entry = entry
+                .with_ext("capability_before", r.capability_before.clone())
+                .with_ext("capability_after", r.capability_after.clone())
+                .with_ext("jurisdiction", r.jurisdiction.clone())
+                .with_ext("policy_stack_snapshot", r.policy_stack_snapshot.clone())
+                .with_ext("biophysical_source_id", r.biophysical_source_id.clone())
+                .with_ext("regulatory_basis_id", r.regulatory_basis_id.clone())
+                .with_ext("validation_evidence_ref", r.validation_evidence_ref.clone())
+                .with_ext("envelope_shard_ref", r.envelope_shard_ref.clone())
+                .with_ext("roh_model_ref", r.roh_model_ref.clone())
+                .with_ext("legacy_hash", r.hexstamp.clone());
+            for (k, v) in &r.extra {
+                if k != "decision" && k != "timestamp" {
+                    entry = entry.with_ext(k, v.clone());
+                }
+            }
+            entry
+        })
+        .collect())
+}
@@ -0,0 +1,80 @@
+//! `sovereign-core::donutloop::DonutLoop`: one JSON `AuditEntry` per line,
+//! chained by `event_hash`/`prev_hash` ("" at genesis).
+
+use serde::{Deserialize, Serialize};
+
+use super::broken;
+use crate::{LedgerError, NewEntry, Source};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEventPayload {
+    pub roh_before: Option<f32>,
+    pub roh_after: Option<f32>,
+    pub knowledge_factor_before: Option<f32>,
+    pub knowledge_factor_after: Option<f32>,
+    pub cybostate_factor_before: Option<f32>,
+    pub cybostate_factor_after: Option<f32>,
+    pub reason: Option<String>,
+    pub reference_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub event_id: String,
+    pub timestamp: String,
+    pub trace_id: String,
+    /// `EventType` variant name.
+    pub event_type: String,
+    pub payload: AuditEventPayload,
+    pub event_hash: String,
+    pub prev_hash: String,
+    pub signature: String,
+    pub signer_did: String,
+}
+
+fn decision(event_type: &str) -> Option<&'static str> {
+    match event_type {
+        "EvolutionDecisionApplied" | "ArchitectureChangeApproved" => Some("Allowed"),
+        "NeurorightCheckFailed" | "NeurorightViolationLogged" | "ArchitectureChangeRejected" => Some("Rejected"),
+        _ => None,
+    }
+}
+
+pub fn convert(records: &[AuditEntry]) -> Result<Vec<NewEntry>, LedgerError> {
+    let mut prev = "";
+    for (i, r) in records.iter().enumerate() {
+        if r.prev_hash != prev {
+            return Err(broken(i, format!("prev_hash {:?} does not follow {:?}", r.prev_hash, prev)));
+        }
+        prev = &r.event_hash;
+    }
+
+    Ok(records
+        .iter()
+        .map(|r| {
+            let p = &r.payload;
+            NewEntry {
+                entry_id: r.event_id.clone(),
+                timestamp: r.timestamp.clone(),
+                kind: r.event_type.clone(),
+                source: Source::SovereignCore,
+                subject_id: Some(r.signer_did.clone()),
+                proposal_id: p.reference_id.clone(),
+                module_id: None,
+                decision: decision(&r.event_type).map(str::to_string),
+                roh_before: p.roh_before,
+                roh_after: p.roh_after,
+                roh_certificate: None,
+                ext: Default::default(),
+            }
+            .with_ext("trace_id", r.trace_id.clone())
+            .with_ext("knowledge_factor_before", p.knowledge_factor_before)
+            .with_ext("knowledge_factor_after", p.knowledge_factor_after)
+            .with_ext("cybostate_factor_before", p.cybostate_factor_before)
+            .with_ext("cybostate_factor_after", p.cybostate_factor_after)
+            .with_ext("reason", p.reason.clone())
+            .with_ext("legacy_hash", r.event_hash.clone())
+            .with_ext("legacy_signature", r.signature.clone())
+        })
+        .collect())
+}
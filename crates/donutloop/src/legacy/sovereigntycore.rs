@@ -0,0 +1,187 @@
+//! `sovereigntycore/src/ledger.rs::DonutLoopLedger`: frames of a big-endian
+//! u32 length followed by a bincode 1 (little-endian, fixed-int)
+//! `DonutLoopEntry`. `prev_hash` is the SHA-256 of the previous frame and the
+//! signature covers the frame minus its trailing 64 signature bytes.
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use super::{broken, unix_to_rfc3339};
+use crate::{LedgerError, NewEntry, Source};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToleranceEnvelope {
+    pub pain_threshold: f64,
+    pub fear_threshold: f64,
+    pub psych_risk_threshold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DonutLoopEntry {
+    pub prev_hash: [u8; 32],
+    pub timestamp: u64,
+    pub roh: f64,
+    pub tolerance: ToleranceEnvelope,
+    pub event: String,
+    pub pubkey: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl DonutLoopEntry {
+    /// bincode 1 encoding, byte-for-byte what the legacy writer frames.
+    pub fn to_bincode(&self) -> Vec<u8> {
+        let mut out = self.signed_body();
+        out.extend_from_slice(&self.signature);
+        out
+    }
+
+    /// Bytes the legacy writer signed.
+    pub fn signed_body(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(140 + self.event.len());
+        out.extend_from_slice(&self.prev_hash);
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+        out.extend_from_slice(&self.roh.to_le_bytes());
+        out.extend_from_slice(&self.tolerance.pain_threshold.to_le_bytes());
+        out.extend_from_slice(&self.tolerance.fear_threshold.to_le_bytes());
+        out.extend_from_slice(&self.tolerance.psych_risk_threshold.to_le_bytes());
+        out.extend_from_slice(&(self.event.len() as u64).to_le_bytes());
+        out.extend_from_slice(self.event.as_bytes());
+        out.extend_from_slice(&self.pubkey);
+        out
+    }
+
+    /// The legacy `chain_hash`.
+    pub fn chain_hash(&self) -> [u8; 32] {
+        Sha256::digest(self.to_bincode()).into()
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    index: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], LedgerError> {
+        if self.bytes.len() < n {
+            return Err(LedgerError::Parse {
+                line: self.index + 1,
+                message: format!("truncated record: need {n} bytes, have {}", self.bytes.len()),
+            });
+        }
+        let (head, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], LedgerError> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+
+    fn u64(&mut self) -> Result<u64, LedgerError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, LedgerError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+}
+
+/// Split a legacy ledger file into entries.
+pub fn read_frames(mut bytes: &[u8]) -> Result<Vec<DonutLoopEntry>, LedgerError> {
+    let mut out = Vec::new();
+    while !bytes.is_empty() {
+        let index = out.len();
+        let mut frame = Reader { bytes, index };
+        let len = u32::from_be_bytes(frame.array()?) as usize;
+        let body = frame.take(len)?;
+        bytes = frame.bytes;
+
+        let mut r = Reader { bytes: body, index };
+        let prev_hash = r.array()?;
+        let timestamp = r.u64()?;
+        let roh = r.f64()?;
+        let tolerance = ToleranceEnvelope {
+            pain_threshold: r.f64()?,
+            fear_threshold: r.f64()?,
+            psych_risk_threshold: r.f64()?,
+        };
+        let event_len = r.u64()? as usize;
+        let event = String::from_utf8(r.take(event_len)?.to_vec()).map_err(|e| LedgerError::Parse {
+            line: index + 1,
+            message: e.to_string(),
+        })?;
+        let pubkey = r.array()?;
+        let signature = r.array()?;
+        if !r.bytes.is_empty() {
+            return Err(LedgerError::Parse {
+                line: index + 1,
+                message: format!("{} trailing bytes in record", r.bytes.len()),
+            });
+        }
+        out.push(DonutLoopEntry {
+            prev_hash,
+            timestamp,
+            roh,
+            tolerance,
+            event,
+            pubkey,
+            signature,
+        });
+    }
+    Ok(out)
+}
+
+/// Frame entries the way the legacy writer does (for fixtures and export).
+pub fn write_frames(entries: &[DonutLoopEntry]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for e in entries {
+        let body = e.to_bincode();
+        out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        out.extend_from_slice(&body);
+    }
+    out
+}
+
+pub fn convert(records: &[DonutLoopEntry]) -> Result<Vec<NewEntry>, LedgerError> {
+    let mut prev = [0u8; 32];
+    for (i, r) in records.iter().enumerate() {
+        if r.prev_hash != prev {
+            return Err(broken(i, "prev_hash does not match the previous record"));
+        }
+        let key = VerifyingKey::from_bytes(&r.pubkey).map_err(|e| broken(i, e.to_string()))?;
+        key.verify(&r.signed_body(), &Signature::from_bytes(&r.signature))
+            .map_err(|_| broken(i, "legacy signature does not verify"))?;
+        prev = r.chain_hash();
+    }
+
+    let mut out = Vec::with_capacity(records.len());
+    let mut roh_before = None;
+    for (i, r) in records.iter().enumerate() {
+        let hash = hex::encode(r.chain_hash());
+        out.push(
+            NewEntry {
+                entry_id: hash.clone(),
+                timestamp: unix_to_rfc3339(i, r.timestamp)?,
+                kind: "roh_update".to_string(),
+                source: Source::SovereigntycoreLedger,
+                subject_id: Some(hex::encode(r.pubkey)),
+                proposal_id: None,
+                module_id: None,
+                decision: None,
+                roh_before,
+                roh_after: Some(r.roh as f32),
+                roh_certificate: None,
+                ext: Default::default(),
+            }
+            .with_ext("event", r.event.clone())
+            .with_ext("pain_threshold", r.tolerance.pain_threshold)
+            .with_ext("fear_threshold", r.tolerance.fear_threshold)
+            .with_ext("psych_risk_threshold", r.tolerance.psych_risk_threshold)
+            .with_ext("legacy_hash", hash)
+            .with_ext("legacy_signature", hex::encode(r.signature)),
+        );
+        roh_before = Some(r.roh as f32);
+    }
+    Ok(out)
+}
@@ -0,0 +1,77 @@
+//! The canonical donutloop ledger: one versioned entry schema, one SHA-256
+//! hash chain with optional ed25519 signatures, pluggable storage, and
+//! adapters that import every legacy donutloop format so a single
+//! `verify_chain` covers all of them.
+
+#![forbid(unsafe_code)]
+
+pub mod archive;
+pub mod checkpoint;
+pub mod entry;
+pub mod ledger;
+pub mod legacy;
+pub mod merkle;
+pub mod roh_certificate;
+pub mod store;
+
+use std::fmt;
+use std::io;
+
+pub use archive::{verify_archive, SegmentedLedger};
+pub use checkpoint::{Checkpoint, RohFold};
+pub use entry::{EntrySignature, LedgerEntry, NewEntry, Source, GENESIS_HASH, SCHEMA_VERSION};
+pub use ledger::{require_roh_certified, require_signed_by, verify_chain, verify_chain_from, Ledger};
+pub use merkle::{ConsistencyProof, DecisionProof, InclusionProof, MerkleTree, SegmentRoots, SignedRoot};
+pub use roh_certificate::{state_hash, RohCertificate, RohClaim, CERTIFICATE_VERSION};
+pub use store::{LedgerStore, MemoryStore, NdjsonStore};
+
+#[derive(Debug)]
+pub enum LedgerError {
+    Io(io::Error),
+    /// A stored line or legacy record that does not decode. `line` is 1-based
+    /// (record index + 1 for binary formats).
+    Parse { line: usize, message: String },
+    /// Entry written by a newer schema than this crate understands.
+    UnsupportedVersion { seq: u64, version: u32 },
+    /// seq, prev_hash or hash does not match the chain.
+    ChainBroken { seq: u64, message: String },
+    BadSignature { seq: u64, message: String },
+    /// A checkpoint that is unsigned by a trusted key or disagrees with the
+    /// chain.
+    BadCheckpoint { height: u64, message: String },
+    /// A Merkle proof or signed root that does not verify.
+    BadProof { message: String },
+    /// A RoH certificate that is untrusted, altered or not monotone.
+    BadCertificate { proposal_id: String, message: String },
+    /// A legacy ledger whose own links are inconsistent; nothing is imported.
+    Legacy { index: usize, message: String },
+}
+
+impl fmt::Display for LedgerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LedgerError::Io(e) => write!(f, "ledger io: {e}"),
+            LedgerError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            LedgerError::UnsupportedVersion { seq, version } => write!(
+                f,
+                "entry {seq}: schema version {version} is newer than supported {SCHEMA_VERSION}"
+            ),
+            LedgerError::ChainBroken { seq, message } => write!(f, "entry {seq}: chain broken: {message}"),
+            LedgerError::BadSignature { seq, message } => write!(f, "entry {seq}: bad signature: {message}"),
+            LedgerError::BadCheckpoint { height, message } => write!(f, "checkpoint at {height}: {message}"),
+            LedgerError::BadProof { message } => write!(f, "merkle proof: {message}"),
+            LedgerError::BadCertificate { proposal_id, message } => {
+                write!(f, "RoH certificate for {proposal_id}: {message}")
+            }
+            LedgerError::Legacy { index, message } => write!(f, "legacy record {index}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for LedgerError {}
+
+impl From<io::Error> for LedgerError {
+    fn from(e: io::Error) -> Self {
+        LedgerError::Io(e)
+    }
+}
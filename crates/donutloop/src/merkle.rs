@@ -0,0 +1,456 @@
+//! RFC 6962 / RFC 9162 Merkle tree over ledger entries, so one decision
+//! can be shown to an auditor without the rest of the history.
+//!
+//! Leaves are the entries' `hash` strings; leaf and interior nodes are
+//! domain-separated (`0x00` / `0x01` prefixes). Proofs are plain JSON and
+//! verify against a `SignedRoot` alone.
+
+use chrono::{SecondsFormat, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::entry::content_hash;
+use crate::{EntrySignature, LedgerEntry, LedgerError};
+
+type Hash = [u8; 32];
+
+fn leaf_hash(entry_hash: &str) -> Hash {
+    let mut h = Sha256::new();
+    h.update([0u8]);
+    h.update(entry_hash.as_bytes());
+    h.finalize().into()
+}
+
+fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut h = Sha256::new();
+    h.update([1u8]);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+/// Largest power of two strictly less than `n` (n >= 2).
+fn split(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+fn bad(message: impl Into<String>) -> LedgerError {
+    LedgerError::BadProof {
+        message: message.into(),
+    }
+}
+
+fn decode(hex_hash: &str) -> Result<Hash, LedgerError> {
+    hex::decode(hex_hash)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| bad(format!("malformed hash {hex_hash}")))
+}
+
+/// Append-only Merkle tree of entry hashes.
+///
+/// Keeps every complete subtree root (`levels[h][j]` covers leaves
+/// `j * 2^h .. (j + 1) * 2^h`), so a push hashes at most one node per level
+/// and any RFC 9162 subtree hash, root or proof takes O(log n) lookups.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn from_entries(entries: &[LedgerEntry]) -> Self {
+        let mut tree = Self::default();
+        for e in entries {
+            tree.push(e);
+        }
+        tree
+    }
+
+    pub fn push(&mut self, entry: &LedgerEntry) {
+        let mut node = leaf_hash(&entry.hash);
+        for h in 0.. {
+            if self.levels.len() == h {
+                self.levels.push(Vec::new());
+            }
+            let level = &mut self.levels[h];
+            level.push(node);
+            if level.len() % 2 == 1 {
+                break;
+            }
+            node = node_hash(&level[level.len() - 2], &level[level.len() - 1]);
+        }
+    }
+
+    /// Continue the tree with an archived segment's persisted roots instead
+    /// of its entries. Only subtrees that straddle the segment's start are
+    /// hashed. The tree is unchanged if the roots do not fit.
+    pub fn append_segment(&mut self, roots: &SegmentRoots) -> Result<(), LedgerError> {
+        if roots.first != self.len() || roots.last < roots.first {
+            return Err(bad(format!(
+                "segment roots for {}..={} do not continue a {}-leaf tree",
+                roots.first,
+                roots.last,
+                self.len()
+            )));
+        }
+        let end = roots.last + 1;
+        let height = (u64::BITS - end.leading_zeros()) as usize;
+        if roots.levels.len() != height {
+            return Err(bad(format!("segment roots have {} levels, expected {height}", roots.levels.len())));
+        }
+        let mut stored = Vec::with_capacity(height);
+        for (h, level) in roots.levels.iter().enumerate() {
+            // Nodes wholly inside the segment are `inside..(end >> h)`.
+            let expected = (end >> h).saturating_sub(roots.first.div_ceil(1 << h));
+            if level.len() as u64 != expected {
+                return Err(bad(format!("segment roots have {} nodes at level {h}, expected {expected}", level.len())));
+            }
+            stored.push(level.iter().map(|n| decode(n)).collect::<Result<Vec<_>, _>>()?);
+        }
+
+        for (h, level) in stored.into_iter().enumerate() {
+            if self.levels.len() == h {
+                self.levels.push(Vec::new());
+            }
+            let inside = roots.first.div_ceil(1 << h) as usize;
+            while self.levels[h].len() < inside.min((end >> h) as usize) {
+                let j = self.levels[h].len();
+                let node = node_hash(&self.levels[h - 1][2 * j], &self.levels[h - 1][2 * j + 1]);
+                self.levels[h].push(node);
+            }
+            self.levels[h].extend(level);
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> u64 {
+        self.levels.first().map_or(0, Vec::len) as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// MTH over leaves `start .. start + n`, where `start` is a multiple of
+    /// the largest power of two not above `n` (true of every subtree the
+    /// RFC 9162 recursion visits).
+    fn mth(&self, start: usize, n: usize) -> Hash {
+        match n {
+            0 => Sha256::digest([]).into(),
+            n if n.is_power_of_two() => {
+                let h = n.trailing_zeros() as usize;
+                self.levels[h][start >> h]
+            }
+            n => {
+                let k = split(n);
+                node_hash(&self.mth(start, k), &self.mth(start + k, n - k))
+            }
+        }
+    }
+
+    fn check_size(&self, size: u64) -> Result<usize, LedgerError> {
+        if size > self.len() {
+            return Err(bad(format!("tree has only {} leaves", self.len())));
+        }
+        Ok(size as usize)
+    }
+
+    /// Hex root over the first `size` entries.
+    pub fn root_at(&self, size: u64) -> Result<String, LedgerError> {
+        Ok(hex::encode(self.mth(0, self.check_size(size)?)))
+    }
+
+    pub fn root(&self) -> String {
+        hex::encode(self.mth(0, self.len() as usize))
+    }
+
+    /// Proof that entry `seq` is in the tree of the first `size` entries.
+    pub fn inclusion_proof(&self, entry: &LedgerEntry, size: u64) -> Result<InclusionProof, LedgerError> {
+        let n = self.check_size(size)?;
+        let index = entry.seq as usize;
+        if index >= n || self.levels[0][index] != leaf_hash(&entry.hash) {
+            return Err(bad(format!("entry {} is not leaf {} of a {size}-entry tree", entry.hash, entry.seq)));
+        }
+        fn path(tree: &MerkleTree, m: usize, start: usize, n: usize, out: &mut Vec<String>) {
+            if n <= 1 {
+                return;
+            }
+            let k = split(n);
+            if m < k {
+                path(tree, m, start, k, out);
+                out.push(hex::encode(tree.mth(start + k, n - k)));
+            } else {
+                path(tree, m - k, start + k, n - k, out);
+                out.push(hex::encode(tree.mth(start, k)));
+            }
+        }
+        let mut audit_path = Vec::new();
+        path(self, index, 0, n, &mut audit_path);
+        Ok(InclusionProof {
+            leaf_index: entry.seq,
+            tree_size: size,
+            entry_hash: entry.hash.clone(),
+            audit_path,
+        })
+    }
+
+    /// Proof that the tree of the first `first` entries is a prefix of the
+    /// tree of the first `second`.
+    pub fn consistency_proof(&self, first: u64, second: u64) -> Result<ConsistencyProof, LedgerError> {
+        if first > second {
+            return Err(bad(format!("first size {first} exceeds second size {second}")));
+        }
+        let n = self.check_size(second)?;
+        fn subproof(tree: &MerkleTree, m: usize, start: usize, n: usize, complete: bool, out: &mut Vec<String>) {
+            if m == n {
+                if !complete {
+                    out.push(hex::encode(tree.mth(start, n)));
+                }
+                return;
+            }
+            let k = split(n);
+            if m <= k {
+                subproof(tree, m, start, k, complete, out);
+                out.push(hex::encode(tree.mth(start + k, n - k)));
+            } else {
+                subproof(tree, m - k, start + k, n - k, false, out);
+                out.push(hex::encode(tree.mth(start, k)));
+            }
+        }
+        let mut path = Vec::new();
+        if first > 0 && first < second {
+            subproof(self, first as usize, 0, n, true, &mut path);
+        }
+        Ok(ConsistencyProof {
+            first_size: first,
+            second_size: second,
+            path,
+        })
+    }
+}
+
+/// The complete subtree roots of a `MerkleTree` that lie wholly inside one
+/// run of entries, stored beside an archived segment so the archive's tree
+/// can be rebuilt without reading or rehashing its entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentRoots {
+    pub first: u64,
+    pub last: u64,
+    /// Hex; `levels[h]` holds the roots of the `2^h`-leaf subtrees inside
+    /// `first..=last`, in order (`levels[0]` are the leaves).
+    pub levels: Vec<Vec<String>>,
+}
+
+impl SegmentRoots {
+    /// Roots for `entries`, which must be consecutive and non-empty.
+    pub fn from_entries(entries: &[LedgerEntry]) -> Self {
+        let first = entries.first().map_or(0, |e| e.seq);
+        let last = first + entries.len() as u64 - 1;
+        let mut levels: Vec<Vec<Hash>> = vec![entries.iter().map(|e| leaf_hash(&e.hash)).collect()];
+        for h in 1..(u64::BITS - (last + 1).leading_zeros()) as usize {
+            let inside = first.div_ceil(1 << h);
+            let target = (last + 1) >> h;
+            let below = &levels[h - 1];
+            let offset = first.div_ceil(1 << (h - 1));
+            let level = (inside..target)
+                .map(|j| {
+                    let left = (2 * j - offset) as usize;
+                    node_hash(&below[left], &below[left + 1])
+                })
+                .collect();
+            levels.push(level);
+        }
+        Self {
+            first,
+            last,
+            levels: levels.iter().map(|l| l.iter().map(hex::encode).collect()).collect(),
+        }
+    }
+}
+
+/// Signed Merkle root over the first `tree_size` entries; the only thing a
+/// verifier needs besides a proof.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRoot {
+    pub tree_size: u64,
+    /// Hex.
+    pub root_hash: String,
+    /// RFC 3339 UTC.
+    pub created_at: String,
+    /// sha256 over the canonical JSON of every other field except `signature`.
+    pub hash: String,
+    pub signature: EntrySignature,
+}
+
+impl SignedRoot {
+    pub fn seal(tree_size: u64, root_hash: &str, signer: &SigningKey) -> Self {
+        let mut root = Self {
+            tree_size,
+            root_hash: root_hash.to_string(),
+            created_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            hash: String::new(),
+            signature: EntrySignature {
+                public_key: String::new(),
+                signature: String::new(),
+            },
+        };
+        root.hash = content_hash(&root);
+        root.signature = EntrySignature::sign(signer, &root.hash);
+        root
+    }
+
+    pub fn verify(&self, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        if content_hash(self) != self.hash {
+            return Err(bad("signed root content does not match its hash"));
+        }
+        let key = self.signature.verify(&self.hash).map_err(bad)?;
+        if !trusted.contains(&key) {
+            return Err(bad("signed root signer is not trusted"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub leaf_index: u64,
+    pub tree_size: u64,
+    /// The entry's `hash`; the leaf is derived from it.
+    pub entry_hash: String,
+    /// Sibling hashes, leaf to root, hex.
+    pub audit_path: Vec<String>,
+}
+
+impl InclusionProof {
+    /// Recompute the root from the leaf and path (RFC 9162 §2.1.3.2).
+    pub fn verify_root(&self, root_hash: &str) -> Result<(), LedgerError> {
+        if self.leaf_index >= self.tree_size {
+            return Err(bad("leaf index outside the tree"));
+        }
+        let (mut fnode, mut snode) = (self.leaf_index, self.tree_size - 1);
+        let mut r = leaf_hash(&self.entry_hash);
+        for p in &self.audit_path {
+            let p = decode(p)?;
+            if snode == 0 {
+                return Err(bad("audit path is too long"));
+            }
+            if fnode & 1 == 1 || fnode == snode {
+                r = node_hash(&p, &r);
+                while fnode & 1 == 0 && fnode != 0 {
+                    fnode >>= 1;
+                    snode >>= 1;
+                }
+            } else {
+                r = node_hash(&r, &p);
+            }
+            fnode >>= 1;
+            snode >>= 1;
+        }
+        if snode != 0 || hex::encode(r) != root_hash {
+            return Err(bad("inclusion proof does not lead to the root"));
+        }
+        Ok(())
+    }
+
+    /// Check the proof against a trusted signed root of the same size.
+    pub fn verify(&self, root: &SignedRoot, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        root.verify(trusted)?;
+        if root.tree_size != self.tree_size {
+            return Err(bad(format!(
+                "proof is for size {}, root for size {}",
+                self.tree_size, root.tree_size
+            )));
+        }
+        self.verify_root(&root.root_hash)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsistencyProof {
+    pub first_size: u64,
+    pub second_size: u64,
+    /// Hex node hashes (RFC 9162 §2.1.4).
+    pub path: Vec<String>,
+}
+
+impl ConsistencyProof {
+    /// Check that `first_root` is a prefix of `second_root` (RFC 9162
+    /// §2.1.4.2).
+    pub fn verify_roots(&self, first_root: &str, second_root: &str) -> Result<(), LedgerError> {
+        let (m, n) = (self.first_size, self.second_size);
+        if m > n {
+            return Err(bad("first size exceeds second size"));
+        }
+        if m == 0 || m == n {
+            let ok = self.path.is_empty() && (m == 0 || first_root == second_root);
+            return if ok { Ok(()) } else { Err(bad("consistency proof does not match")) };
+        }
+
+        let mut path = self.path.iter().map(|p| decode(p)).collect::<Result<Vec<_>, _>>()?;
+        if m.is_power_of_two() {
+            path.insert(0, decode(first_root)?);
+        }
+        let Some((&seed, rest)) = path.split_first() else {
+            return Err(bad("consistency proof is empty"));
+        };
+        let (mut fnode, mut snode) = (m - 1, n - 1);
+        while fnode & 1 == 1 {
+            fnode >>= 1;
+            snode >>= 1;
+        }
+        let (mut fr, mut sr) = (seed, seed);
+        for c in rest {
+            if snode == 0 {
+                return Err(bad("consistency path is too long"));
+            }
+            if fnode & 1 == 1 || fnode == snode {
+                fr = node_hash(c, &fr);
+                sr = node_hash(c, &sr);
+                while fnode & 1 == 0 && fnode != 0 {
+                    fnode >>= 1;
+                    snode >>= 1;
+                }
+            } else {
+                sr = node_hash(&sr, c);
+            }
+            fnode >>= 1;
+            snode >>= 1;
+        }
+        if snode != 0 || hex::encode(fr) != first_root || hex::encode(sr) != second_root {
+            return Err(bad("consistency proof does not match"));
+        }
+        Ok(())
+    }
+
+    /// Check against two trusted signed roots.
+    pub fn verify(&self, first: &SignedRoot, second: &SignedRoot, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        first.verify(trusted)?;
+        second.verify(trusted)?;
+        if (first.tree_size, second.tree_size) != (self.first_size, self.second_size) {
+            return Err(bad("roots do not match the proof's sizes"));
+        }
+        self.verify_roots(&first.root_hash, &second.root_hash)
+    }
+}
+
+/// One decision, self-contained for an auditor or an on-chain reference:
+/// the entry, its inclusion proof, and the signed root it proves against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionProof {
+    pub entry: LedgerEntry,
+    pub inclusion: InclusionProof,
+    pub root: SignedRoot,
+}
+
+impl DecisionProof {
+    pub fn verify(&self, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        if self.entry.compute_hash() != self.entry.hash || self.entry.hash != self.inclusion.entry_hash {
+            return Err(bad("entry does not match the proven hash"));
+        }
+        if self.entry.seq != self.inclusion.leaf_index {
+            return Err(bad("entry seq does not match the proven leaf"));
+        }
+        self.inclusion.verify(&self.root, trusted)
+    }
+}
@@ -0,0 +1,133 @@
+//! Signed RoH monotonicity certificates.
+//!
+//! A certificate records a RoH decision that the sovereignty kernel
+//! recomputed itself, instead of copying `roh_before`/`roh_after` from a
+//! proposal. It names the model (id, version, definition hash) and hashes of
+//! the before/after state vectors, so an auditor holding those vectors can
+//! re-derive both values and the decision.
+
+use std::collections::BTreeMap;
+
+use chrono::{SecondsFormat, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::entry::content_hash;
+use crate::{EntrySignature, LedgerError};
+
+/// Highest certificate schema this crate writes and reads.
+pub const CERTIFICATE_VERSION: u32 = 1;
+
+/// Slack allowed in `roh_after <= roh_before`, matching the guards.
+pub const ROH_EPSILON: f32 = 1e-6;
+
+/// sha256 over the canonical JSON of a state keyed by axis name.
+pub fn state_hash(state: &BTreeMap<String, f32>) -> String {
+    let json = serde_json::to_vec(state).expect("serialize RoH state");
+    hex::encode(Sha256::digest(json))
+}
+
+/// What the certificate attests, before it is hashed and signed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RohClaim {
+    pub proposal_id: String,
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    pub model_id: String,
+    pub model_version: String,
+    /// sha256 of the model definition the values were computed with.
+    pub model_hash: String,
+    /// `state_hash` of the state before the change.
+    pub before_hash: String,
+    /// `state_hash` of the state after the change.
+    pub after_hash: String,
+    pub roh_before: f32,
+    pub roh_after: f32,
+    pub roh_ceiling: f32,
+}
+
+impl RohClaim {
+    pub fn is_monotone(&self) -> bool {
+        self.roh_after <= self.roh_before + ROH_EPSILON
+    }
+
+    pub fn within_ceiling(&self) -> bool {
+        self.roh_after <= self.roh_ceiling + ROH_EPSILON
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RohCertificate {
+    pub version: u32,
+    #[serde(flatten)]
+    pub claim: RohClaim,
+    /// RFC 3339 UTC.
+    pub issued_at: String,
+    /// sha256 over the canonical JSON of every other field except `signature`.
+    pub hash: String,
+    pub signature: EntrySignature,
+}
+
+impl RohCertificate {
+    pub fn seal(claim: RohClaim, signer: &SigningKey) -> Self {
+        let mut cert = Self {
+            version: CERTIFICATE_VERSION,
+            claim,
+            issued_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            hash: String::new(),
+            signature: EntrySignature {
+                public_key: String::new(),
+                signature: String::new(),
+            },
+        };
+        cert.hash = content_hash(&cert);
+        cert.signature = EntrySignature::sign(signer, &cert.hash);
+        cert
+    }
+
+    /// Check the version, the content hash and that one of `trusted` signed
+    /// it. Says nothing about whether the claim is monotone.
+    pub fn verify(&self, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        let bad = |message: String| LedgerError::BadCertificate {
+            proposal_id: self.claim.proposal_id.clone(),
+            message,
+        };
+        if self.version > CERTIFICATE_VERSION {
+            return Err(bad(format!(
+                "certificate version {} is newer than supported {CERTIFICATE_VERSION}",
+                self.version
+            )));
+        }
+        if content_hash(self) != self.hash {
+            return Err(bad("content does not match hash".to_string()));
+        }
+        let key = self.signature.verify(&self.hash).map_err(bad)?;
+        if !trusted.contains(&key) {
+            return Err(bad("signer is not trusted".to_string()));
+        }
+        Ok(())
+    }
+
+    /// `verify`, plus the claim must be monotone and under its ceiling.
+    pub fn verify_monotone(&self, trusted: &[VerifyingKey]) -> Result<(), LedgerError> {
+        self.verify(trusted)?;
+        let bad = |message: String| LedgerError::BadCertificate {
+            proposal_id: self.claim.proposal_id.clone(),
+            message,
+        };
+        if !self.claim.is_monotone() {
+            return Err(bad(format!(
+                "RoH rises from {} to {}",
+                self.claim.roh_before, self.claim.roh_after
+            )));
+        }
+        if !self.claim.within_ceiling() {
+            return Err(bad(format!(
+                "RoH {} exceeds ceiling {}",
+                self.claim.roh_after, self.claim.roh_ceiling
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{LedgerEntry, LedgerError};
+
+/// Where ledger entries live. Stores only persist; `Ledger` chains and
+/// verifies.
+pub trait LedgerStore {
+    /// Every stored entry, oldest first.
+    fn load(&mut self) -> Result<Vec<LedgerEntry>, LedgerError>;
+    /// Durably append one entry.
+    fn append(&mut self, entry: &LedgerEntry) -> Result<(), LedgerError>;
+}
+
+/// Process-local store (tests, dry runs, verification of imports).
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    entries: Vec<LedgerEntry>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LedgerStore for MemoryStore {
+    fn load(&mut self) -> Result<Vec<LedgerEntry>, LedgerError> {
+        Ok(self.entries.clone())
+    }
+
+    fn append(&mut self, entry: &LedgerEntry) -> Result<(), LedgerError> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+}
+
+/// One JSON entry per line, fsynced on append.
+#[derive(Debug, Clone)]
+pub struct NdjsonStore {
+    path: PathBuf,
+}
+
+impl NdjsonStore {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl LedgerStore for NdjsonStore {
+    /// A missing file is empty. A cut-off final line (crash mid-append) is
+    /// dropped from the file so the next append starts on a fresh line; any
+    /// other undecodable line is an error.
+    fn load(&mut self) -> Result<Vec<LedgerEntry>, LedgerError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let data = fs::read_to_string(&self.path)?;
+        let complete = data.is_empty() || data.ends_with('\n');
+        let lines: Vec<&str> = data.lines().collect();
+        let mut entries = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) if i + 1 == lines.len() && !complete => {
+                    let keep = data.rfind('\n').map_or(0, |i| i + 1);
+                    OpenOptions::new().write(true).open(&self.path)?.set_len(keep as u64)?;
+                }
+                Err(e) => {
+                    return Err(LedgerError::Parse {
+                        line: i + 1,
+                        message: e.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    fn append(&mut self, entry: &LedgerEntry) -> Result<(), LedgerError> {
+        let json = serde_json::to_string(entry).expect("serialize LedgerEntry");
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(json.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        Ok(())
+    }
+}
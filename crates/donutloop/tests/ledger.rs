@@ -0,0 +1,453 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use donutloop::legacy::{answer, organiccpualn, policyengine, sovereign_core, sovereigntycore};
+use donutloop::{
+    require_roh_certified, require_signed_by, state_hash, verify_archive, verify_chain, Ledger, LedgerEntry, LedgerError,
+    MemoryStore, MerkleTree, NdjsonStore, NewEntry, RohCertificate, RohClaim, SegmentedLedger, Source,
+};
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::json;
+
+fn ledger_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("donutloop-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("donutloop.ndjson")
+}
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+#[test]
+fn ndjson_ledger_reopens_and_detects_edits() {
+    let path = ledger_path("native");
+    {
+        let mut ledger = Ledger::open(NdjsonStore::new(&path)).unwrap();
+        let mut e = NewEntry::new("e1", "EvolutionDecisionApplied");
+        e.decision = Some("Allowed".into());
+        e.roh_after = Some(0.1);
+        ledger.append(e.with_ext("note", "first"), Some(&key())).unwrap();
+        ledger.append(NewEntry::new("e2", "NeurorightCheckFailed"), Some(&key())).unwrap();
+    }
+
+    // A cut-off final line is dropped rather than extended.
+    let mut data = fs::read_to_string(&path).unwrap();
+    data.push_str("{\"version\":1,\"seq\":2");
+    fs::write(&path, &data).unwrap();
+
+    let mut ledger = Ledger::open(NdjsonStore::new(&path)).unwrap();
+    assert_eq!(ledger.entries().len(), 2);
+    assert_eq!(ledger.entries()[0].body.ext["note"], json!("first"));
+    ledger.append(NewEntry::new("e3", "EvolutionProposalSubmitted"), Some(&key())).unwrap();
+    let entries = Ledger::open(NdjsonStore::new(&path)).unwrap().entries().to_vec();
+    assert_eq!(entries.len(), 3);
+    require_signed_by(&entries, &[key().verifying_key()]).unwrap();
+
+    let data = fs::read_to_string(&path).unwrap();
+    fs::write(&path, data.replacen("\"Allowed\"", "\"Rejected\"", 1)).unwrap();
+    match Ledger::open(NdjsonStore::new(&path)) {
+        Err(LedgerError::ChainBroken { seq: 0, .. }) => {}
+        other => panic!("expected broken chain, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn unsigned_and_foreign_entries_fail_require_signed_by() {
+    let mut ledger = Ledger::open(MemoryStore::new()).unwrap();
+    ledger.append(NewEntry::new("a", "x"), Some(&key())).unwrap();
+    ledger.append(NewEntry::new("b", "x"), None).unwrap();
+    verify_chain(ledger.entries()).unwrap();
+    let trusted = [key().verifying_key()];
+    assert!(matches!(
+        require_signed_by(ledger.entries(), &trusted),
+        Err(LedgerError::BadSignature { seq: 1, .. })
+    ));
+
+    let other = SigningKey::from_bytes(&[9u8; 32]);
+    ledger.append(NewEntry::new("c", "x"), Some(&other)).unwrap();
+    verify_chain(ledger.entries()).unwrap();
+    assert!(require_signed_by(&ledger.entries()[2..], &trusted).is_err());
+}
+
+fn sovereign_core_records() -> Vec<sovereign_core::AuditEntry> {
+    let line = |id: &str, prev: &str, hash: &str, ty: &str| {
+        json!({
+            "event_id": id, "timestamp": "2026-02-01T10:00:00Z", "trace_id": "t1",
+            "event_type": ty,
+            "payload": {"roh_before": 0.2, "roh_after": 0.1, "knowledge_factor_before": null,
+                "knowledge_factor_after": null, "cybostate_factor_before": null,
+                "cybostate_factor_after": null, "reason": "ok", "reference_id": "prop-1"},
+            "event_hash": hash, "prev_hash": prev, "signature": "0xsig", "signer_did": "did:host"
+        })
+    };
+    [
+        line("ev1", "", "0xaa", "EvolutionProposalSubmitted"),
+        line("ev2", "0xaa", "0xbb", "ArchitectureChangeApproved"),
+    ]
+    .into_iter()
+    .map(|v| serde_json::from_value(v).unwrap())
+    .collect()
+}
+
+fn sovereigntycore_records() -> Vec<sovereigntycore::DonutLoopEntry> {
+    let key = SigningKey::from_bytes(&[3u8; 32]);
+    let mut prev = [0u8; 32];
+    let mut out = Vec::new();
+    for (roh, event) in [(0.0, "genesis: sovereign identity established"), (0.0, "calibrate")] {
+        let mut e = sovereigntycore::DonutLoopEntry {
+            prev_hash: prev,
+            timestamp: 1_770_000_000,
+            roh,
+            tolerance: sovereigntycore::ToleranceEnvelope {
+                pain_threshold: 1.0,
+                fear_threshold: 1.0,
+                psych_risk_threshold: 0.5,
+            },
+            event: event.to_string(),
+            pubkey: key.verifying_key().to_bytes(),
+            signature: [0u8; 64],
+        };
+        e.signature = key.sign(&e.signed_body()).to_bytes();
+        prev = e.chain_hash();
+        out.push(e);
+    }
+    // Same bytes the legacy writer produces on disk.
+    let bytes = sovereigntycore::write_frames(&out);
+    assert_eq!(sovereigntycore::read_frames(&bytes).unwrap(), out);
+    out
+}
+
+fn answer_records() -> Vec<answer::AnswerNdjsonRecord> {
+    let rec = |id: &str, prev: &str, stamp: &str| {
+        json!({
+            "answer_id": id, "subject_id": "bostrom1", "kernel_id": "k1", "route": "Direct",
+            "knowledge_factor": 0.9, "roh": 0.05, "cybostate": "Stable",
+            "bio": {"fatigue_index": 0.1}, "actuation_forbidden": true, "non_commercial": true,
+            "roh_domain": null, "hexstamp": stamp, "prev_hexstamp": prev,
+            "timestamp_utc": 1_770_000_100u64, "artifact_kind": "answer", "contract_type": "none"
+        })
+    };
+    [rec("a1", "0xGENESIS", "0xNPANS1"), rec("a2", "0xNPANS1", "0xNPANS2")]
+        .into_iter()
+        .map(|v| serde_json::from_value(v).unwrap())
+        .collect()
+}
+
+/// `answer_records` re-stamped as a migrated SHA-256 ledger.
+fn sha256_answer_records() -> Vec<answer::AnswerNdjsonRecord> {
+    let mut prev = format!("{}{}", answer::EPOCH_BOUNDARY_PREFIX, "00".repeat(32));
+    let mut recs = answer_records();
+    for r in &mut recs {
+        r.legacy_hexstamp = Some(r.hexstamp.clone());
+        r.hexstamp_epoch = answer::HEXSTAMP_EPOCH_SHA256;
+        r.prev_hexstamp = prev;
+        r.hexstamp = answer::sha256_hexstamp(r).unwrap();
+        prev = r.hexstamp.clone();
+    }
+    recs
+}
+
+fn organiccpualn_records() -> Vec<organiccpualn::DonutloopEntry> {
+    let shard: organiccpualn::DonutloopShard = serde_json::from_value(json!({
+        "meta": {"id": "shard-1"},
+        "entries": [
+            {"entry_id": "d1", "proposal_id": "p1", "policy_id": "pol", "decision": "Allowed",
+             "roh_before": 0.2, "roh_after": 0.15, "hexstamp": "h1", "prev_hash": "root",
+             "entry_hash": "x1", "evolve_stream_pointer": "s#1", "timestamp": "2026-02-02T00:00:00Z"},
+            {"entry_id": "d2", "proposal_id": "p2", "policy_id": "pol", "decision": "Rejected",
+             "roh_before": 0.15, "roh_after": 0.15, "hexstamp": "h2", "prev_hash": "x1",
+             "entry_hash": "x2", "evolve_stream_pointer": "s#2", "timestamp": "2026-02-02T00:01:00Z"}
+        ]
+    }))
+    .unwrap();
+    shard.entries
+}
+
+fn policyengine_records() -> Vec<policyengine::DonutLoopRowCore> {
+    let row = |id: &str, prev: Option<&str>, stamp: &str, prev_stamp: Option<&str>| {
+        json!({
+            "row_id": id, "prev_row_id": prev, "hexstamp": stamp, "prev_hexstamp": prev_stamp,
+            "proposal_id": "p9", "proposal_kind": "RoHUpdate", "module_id": "m1",
+            "roh_before": 0.1, "roh_after": 0.1, "capability_before": "Tier0",
+            "capability_after": "Tier0", "jurisdiction": "EU", "policy_stack_snapshot": {},
+            "biophysical_source_id": "b", "regulatory_basis_id": "r", "validation_evidence_ref": "v",
+            "envelope_shard_ref": null, "roh_model_ref": "roh-v1",
+            "decision": "Allow", "decision_reason": "within envelope"
+        })
+    };
+    [row("r1", None, "s1", None), row("r2", Some("r1"), "s2", Some("s1"))]
+        .into_iter()
+        .map(|v| serde_json::from_value(v).unwrap())
+        .collect()
+}
+
+#[test]
+fn every_legacy_format_imports_into_one_verified_chain() {
+    let mut ledger = Ledger::open(MemoryStore::new()).unwrap();
+    let k = key();
+    ledger.import(sovereign_core::convert(&sovereign_core_records()).unwrap(), Some(&k)).unwrap();
+    ledger.import(sovereigntycore::convert(&sovereigntycore_records()).unwrap(), Some(&k)).unwrap();
+    ledger.import(answer::convert(&answer_records()).unwrap(), Some(&k)).unwrap();
+    ledger.import(organiccpualn::convert(&organiccpualn_records()).unwrap(), Some(&k)).unwrap();
+    ledger.import(policyengine::convert(&policyengine_records()).unwrap(), Some(&k)).unwrap();
+
+    let entries = ledger.entries();
+    assert_eq!(entries.len(), 10);
+    require_signed_by(entries, &[k.verifying_key()]).unwrap();
+
+    let sources: Vec<Source> = entries.iter().map(|e| e.body.source).collect();
+    assert_eq!(sources[0], Source::SovereignCore);
+    assert_eq!(sources[9], Source::Policyengine);
+
+    assert_eq!(entries[1].body.decision.as_deref(), Some("Allowed"));
+    assert_eq!(entries[1].body.ext["legacy_hash"], json!("0xbb"));
+    assert_eq!(entries[2].body.timestamp, "2026-02-02T02:40:00Z");
+    assert_eq!(entries[3].body.roh_before, Some(0.0));
+    assert_eq!(entries[4].body.module_id.as_deref(), Some("k1"));
+    assert_eq!(entries[7].body.decision.as_deref(), Some("Rejected"));
+    assert_eq!(entries[8].body.decision.as_deref(), Some("Allow"));
+    assert_eq!(entries[8].body.ext["decision_reason"], json!("within envelope"));
+    assert_eq!(entries[8].body.ext["timestamp_is_import_time"], json!(true));
+}
+
+#[test]
+fn broken_legacy_chains_import_nothing() {
+    let mut recs = sovereign_core_records();
+    recs[1].prev_hash = "0xzz".into();
+    assert!(matches!(sovereign_core::convert(&recs), Err(LedgerError::Legacy { index: 1, .. })));
+
+    let mut recs = sovereigntycore_records();
+    recs[1].event.push('!');
+    assert!(matches!(sovereigntycore::convert(&recs), Err(LedgerError::Legacy { index: 1, .. })));
+
+    let mut recs = answer_records();
+    recs[0].prev_hexstamp = "0xNPANS0".into();
+    assert!(matches!(answer::convert(&recs), Err(LedgerError::Legacy { index: 0, .. })));
+
+    let recs = sha256_answer_records();
+    assert_eq!(answer::convert(&recs).unwrap().len(), 2);
+    let mut tampered = recs.clone();
+    tampered[1].roh = 0.01;
+    assert!(matches!(answer::convert(&tampered), Err(LedgerError::Legacy { index: 1, .. })));
+    let mut downgraded = recs.clone();
+    downgraded[1].hexstamp_epoch = answer::HEXSTAMP_EPOCH_FNV;
+    assert!(matches!(answer::convert(&downgraded), Err(LedgerError::Legacy { index: 1, .. })));
+
+    let mut recs = organiccpualn_records();
+    recs[1].prev_hash = "x0".into();
+    assert!(matches!(organiccpualn::convert(&recs), Err(LedgerError::Legacy { index: 1, .. })));
+
+    let mut recs = policyengine_records();
+    recs[1].prev_hexstamp = None;
+    assert!(matches!(policyengine::convert(&recs), Err(LedgerError::Legacy { index: 1, .. })));
+}
+
+fn archive_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("donutloop-archive-{}-{}", name, std::process::id()));
+    if dir.exists() {
+        // Segments are read-only; clear the flag so the directory can go.
+        for seg in fs::read_dir(dir.join("segments")).into_iter().flatten().flatten() {
+            let mut perms = seg.metadata().unwrap().permissions();
+            #[allow(clippy::permissions_set_readonly_false)]
+            perms.set_readonly(false);
+            fs::set_permissions(seg.path(), perms).unwrap();
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    dir
+}
+
+fn roh_entry(i: u32, before: f32, after: f32) -> NewEntry {
+    let mut e = NewEntry::new(format!("e{i}"), "RoHUpdate");
+    e.roh_before = Some(before);
+    e.roh_after = Some(after);
+    e
+}
+
+#[test]
+fn segmented_ledger_boots_from_checkpoint_and_archives() {
+    let dir = archive_dir("boot");
+    let k = key();
+    let trusted = [k.verifying_key()];
+    {
+        let mut ledger = SegmentedLedger::open(&dir, &trusted).unwrap();
+        for i in 0..3 {
+            ledger.append(roh_entry(i, 0.2, 0.1), Some(&k)).unwrap();
+        }
+        let seg = ledger.archive(&k).unwrap().unwrap();
+        assert!(seg.to_string_lossy().ends_with(".ndjson.gz"));
+        assert!(fs::metadata(&seg).unwrap().permissions().readonly());
+        assert!(ledger.head().is_empty());
+
+        ledger.append(roh_entry(3, 0.1, 0.25), Some(&k)).unwrap();
+        ledger.checkpoint(&k).unwrap();
+        ledger.append(roh_entry(4, 0.25, 0.05), Some(&k)).unwrap();
+    }
+
+    let mut ledger = SegmentedLedger::open(&dir, &trusted).unwrap();
+    assert_eq!(ledger.height(), 5);
+    assert_eq!(ledger.latest_checkpoint().unwrap().height, 4);
+    let roh = ledger.roh().clone();
+    assert_eq!((roh.current, roh.peak, roh.increases), (Some(0.05), Some(0.25), 1));
+
+    ledger.archive(&k).unwrap().unwrap();
+    ledger.append(roh_entry(5, 0.05, 0.05), Some(&k)).unwrap();
+    assert_eq!(donutloop::archive::segments(&dir).unwrap().len(), 2);
+    assert_eq!(verify_archive(&dir, &trusted).unwrap(), 6);
+    assert_eq!(SegmentedLedger::open(&dir, &trusted).unwrap().height(), 6);
+
+    // The archive's tree is built from the persisted segment roots.
+    let entries = donutloop::archive::read_all(&dir).unwrap();
+    let tree = donutloop::archive::merkle_tree(&dir).unwrap();
+    assert_eq!(tree.root(), MerkleTree::from_entries(&entries).root());
+    tree.inclusion_proof(&entries[1], 6).unwrap().verify_root(&tree.root()).unwrap();
+
+    let (first, last, _) = donutloop::archive::segments(&dir).unwrap().remove(0);
+    let roots_path = dir.join(format!("segments/{first:020}-{last:020}.roots.json"));
+    let mut roots = donutloop::archive::read_segment_roots(&dir, first, last).unwrap().unwrap();
+    roots.levels[1][0] = roots.levels[0][0].clone();
+    let mut perms = fs::metadata(&roots_path).unwrap().permissions();
+    #[allow(clippy::permissions_set_readonly_false)]
+    perms.set_readonly(false);
+    fs::set_permissions(&roots_path, perms).unwrap();
+    fs::write(&roots_path, serde_json::to_vec(&roots).unwrap()).unwrap();
+    assert_ne!(donutloop::archive::merkle_tree(&dir).unwrap().root(), tree.root());
+    assert!(matches!(verify_archive(&dir, &trusted), Err(LedgerError::BadProof { .. })));
+}
+
+#[test]
+fn checkpoints_must_be_trusted_and_match_the_chain() {
+    let dir = archive_dir("trust");
+    let k = key();
+    let trusted = [k.verifying_key()];
+    let mut ledger = SegmentedLedger::open(&dir, &trusted).unwrap();
+    ledger.append(roh_entry(0, 0.2, 0.1), Some(&k)).unwrap();
+    let other = SigningKey::from_bytes(&[9u8; 32]);
+    assert!(ledger.checkpoint(&other).is_err());
+    ledger.checkpoint(&k).unwrap();
+    drop(ledger);
+
+    assert!(matches!(
+        SegmentedLedger::open(&dir, &[other.verifying_key()]),
+        Err(LedgerError::BadCheckpoint { height: 1, .. })
+    ));
+
+    // Rewriting the checkpointed entry no longer matches the checkpoint.
+    let head = dir.join("head.ndjson");
+    let data = fs::read_to_string(&head).unwrap();
+    fs::write(&head, data.replacen("\"e0\"", "\"x0\"", 1)).unwrap();
+    assert!(matches!(
+        SegmentedLedger::open(&dir, &trusted),
+        Err(LedgerError::ChainBroken { seq: 0, .. })
+    ));
+    assert!(verify_archive(&dir, &trusted).is_err());
+}
+
+#[test]
+fn covered_head_entries_are_verified_back_to_the_archive() {
+    let dir = archive_dir("covered");
+    let k = key();
+    let trusted = [k.verifying_key()];
+    {
+        let mut ledger = SegmentedLedger::open(&dir, &trusted).unwrap();
+        for i in 0..2 {
+            ledger.append(roh_entry(i, 0.2, 0.1), None).unwrap();
+        }
+        ledger.archive(&k).unwrap().unwrap();
+        for i in 2..5 {
+            ledger.append(roh_entry(i, 0.1, 0.1), None).unwrap();
+        }
+        ledger.checkpoint(&k).unwrap();
+    }
+    let head = dir.join("head.ndjson");
+    let good = fs::read_to_string(&head).unwrap();
+    let rewrite = |i: usize, edit: &dyn Fn(&mut LedgerEntry)| {
+        let mut lines: Vec<String> = good.lines().map(str::to_string).collect();
+        let mut e: LedgerEntry = serde_json::from_str(&lines[i]).unwrap();
+        edit(&mut e);
+        e.hash = e.compute_hash();
+        lines[i] = serde_json::to_string(&e).unwrap();
+        fs::write(&head, lines.join("\n") + "\n").unwrap();
+    };
+
+    // A re-hashed entry in the middle no longer links to its successor,
+    // although the last one still matches the checkpoint.
+    rewrite(1, &|e| e.body.entry_id = "x3".to_string());
+    assert!(matches!(
+        SegmentedLedger::open(&dir, &trusted),
+        Err(LedgerError::ChainBroken { seq: 4, .. })
+    ));
+
+    // The first head entry must link to the last archived one.
+    rewrite(0, &|e| e.prev_hash = "ff".repeat(32));
+    assert!(matches!(
+        SegmentedLedger::open(&dir, &trusted),
+        Err(LedgerError::ChainBroken { seq: 2, .. })
+    ));
+
+    fs::write(&head, &good).unwrap();
+    let ledger = SegmentedLedger::open(&dir, &trusted).unwrap();
+    assert_eq!(ledger.height(), 5);
+    assert_eq!(ledger.head()[0].prev_hash, last_archived_hash(&dir));
+}
+
+fn last_archived_hash(dir: &Path) -> String {
+    let (first, last, path) = donutloop::archive::segments(dir).unwrap().pop().unwrap();
+    donutloop::archive::read_segment(first, last, &path).unwrap().pop().unwrap().hash
+}
+
+fn claim(proposal_id: &str, before: f32, after: f32) -> RohClaim {
+    RohClaim {
+        proposal_id: proposal_id.to_string(),
+        subject_id: Some("bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string()),
+        model_id: "bostrom-rohmodel-v1".to_string(),
+        model_version: "1.0.0".to_string(),
+        model_hash: "00".repeat(32),
+        before_hash: state_hash(&[("fatigueindex".to_string(), 0.4)].into_iter().collect()),
+        after_hash: state_hash(&[("fatigueindex".to_string(), 0.3)].into_iter().collect()),
+        roh_before: before,
+        roh_after: after,
+        roh_ceiling: 0.3,
+    }
+}
+
+#[test]
+fn roh_certificates_replace_bare_floats() {
+    let k = key();
+    let trusted = [k.verifying_key()];
+    let mut ledger = Ledger::open(MemoryStore::new()).unwrap();
+
+    let cert = RohCertificate::seal(claim("p1", 0.2, 0.15), &k);
+    let mut body = NewEntry::new("e0", "EvolutionDecisionApplied");
+    body.roh_after = Some(0.9);
+    let body = body.with_roh_certificate(cert.clone());
+    assert_eq!(body.roh(), (Some(0.2), Some(0.15)));
+    assert_eq!(body.proposal_id.as_deref(), Some("p1"));
+    ledger.append(body, Some(&k)).unwrap();
+    require_roh_certified(ledger.entries(), &trusted).unwrap();
+
+    let json = serde_json::to_string(&ledger.entries()[0]).unwrap();
+    assert!(!json.contains("\"roh_after\":0.9"));
+    let back: LedgerEntry = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.body.roh_certificate.as_ref(), Some(&cert));
+
+    // Bare floats, foreign signers, edits and increases are all refused.
+    ledger.append(roh_entry(1, 0.15, 0.1), Some(&k)).unwrap();
+    assert!(matches!(
+        require_roh_certified(ledger.entries(), &trusted),
+        Err(LedgerError::ChainBroken { seq: 1, .. })
+    ));
+
+    let other = SigningKey::from_bytes(&[9u8; 32]);
+    assert!(cert.verify(&[other.verifying_key()]).is_err());
+
+    let mut edited = cert.clone();
+    edited.claim.roh_after = 0.01;
+    assert!(matches!(edited.verify(&trusted), Err(LedgerError::BadCertificate { .. })));
+
+    let rising = RohCertificate::seal(claim("p2", 0.1, 0.2), &k);
+    rising.verify(&trusted).unwrap();
+    assert!(rising.verify_monotone(&trusted).is_err());
+}
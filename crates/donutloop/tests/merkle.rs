@@ -0,0 +1,125 @@
+use donutloop::{DecisionProof, Ledger, LedgerError, MemoryStore, MerkleTree, NewEntry, SegmentRoots, SignedRoot};
+use ed25519_dalek::SigningKey;
+
+fn ledger(n: u64) -> Ledger<MemoryStore> {
+    let mut ledger = Ledger::open(MemoryStore::new()).unwrap();
+    for i in 0..n {
+        ledger.append(NewEntry::new(format!("e{i}"), "decision"), None).unwrap();
+    }
+    ledger
+}
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+#[test]
+fn every_inclusion_and_consistency_proof_verifies() {
+    let ledger = ledger(21);
+    let roots: Vec<String> = (0..=21)
+        .map(|size| MerkleTree::from_entries(&ledger.entries()[..size]).root())
+        .collect();
+    assert_eq!(roots[21], ledger.merkle_root());
+
+    for size in 1..=21u64 {
+        for seq in 0..size {
+            let proof = ledger.inclusion_proof(seq, size).unwrap();
+            proof.verify_root(&roots[size as usize]).unwrap();
+            if size > 1 {
+                assert!(proof.verify_root(&roots[size as usize - 1]).is_err());
+            }
+        }
+        for first in 1..=size {
+            let proof = ledger.consistency_proof(first, size).unwrap();
+            proof.verify_roots(&roots[first as usize], &roots[size as usize]).unwrap();
+            if first < size {
+                assert!(proof.verify_roots(&roots[size as usize], &roots[size as usize]).is_err());
+            }
+        }
+    }
+}
+
+#[test]
+fn segment_roots_rebuild_the_same_tree() {
+    let ledger = ledger(21);
+    let entries = ledger.entries();
+    let full = MerkleTree::from_entries(entries);
+
+    for cuts in [vec![21], vec![1, 21], vec![3, 4, 11, 21], vec![8, 16, 21], vec![5, 6, 7, 13, 20, 21]] {
+        let mut tree = MerkleTree::default();
+        let mut first = 0;
+        for last in cuts {
+            tree.append_segment(&SegmentRoots::from_entries(&entries[first..last])).unwrap();
+            first = last;
+        }
+        for size in 0..=21u64 {
+            assert_eq!(tree.root_at(size).unwrap(), full.root_at(size).unwrap());
+        }
+        assert_eq!(
+            tree.inclusion_proof(&entries[9], 17).unwrap(),
+            full.inclusion_proof(&entries[9], 17).unwrap()
+        );
+        assert_eq!(tree.consistency_proof(6, 19).unwrap(), full.consistency_proof(6, 19).unwrap());
+    }
+
+    // Roots that do not continue the tree, or are the wrong shape, leave it
+    // unchanged.
+    let mut tree = MerkleTree::from_entries(&entries[..5]);
+    assert!(tree.append_segment(&SegmentRoots::from_entries(&entries[6..9])).is_err());
+    let mut short = SegmentRoots::from_entries(&entries[5..9]);
+    short.levels[1].pop();
+    assert!(tree.append_segment(&short).is_err());
+    let mut garbled = SegmentRoots::from_entries(&entries[5..9]);
+    garbled.levels[1][0] = "zz".to_string();
+    assert!(tree.append_segment(&garbled).is_err());
+    assert_eq!(tree.len(), 5);
+    tree.append_segment(&SegmentRoots::from_entries(&entries[5..9])).unwrap();
+    assert_eq!(tree.root(), full.root_at(9).unwrap());
+}
+
+#[test]
+fn tampered_proofs_fail() {
+    let ledger = ledger(7);
+    let root = ledger.merkle_root();
+    let mut proof = ledger.inclusion_proof(3, 7).unwrap();
+    proof.audit_path[1] = proof.audit_path[0].clone();
+    assert!(matches!(proof.verify_root(&root), Err(LedgerError::BadProof { .. })));
+
+    let mut proof = ledger.inclusion_proof(3, 7).unwrap();
+    proof.entry_hash = ledger.entries()[4].hash.clone();
+    assert!(proof.verify_root(&root).is_err());
+
+    let mut proof = ledger.consistency_proof(3, 7).unwrap();
+    proof.path.pop();
+    let first = ledger.consistency_proof(3, 3).unwrap();
+    assert!(first.path.is_empty());
+    assert!(proof.verify_roots(&ledger.merkle_root(), &root).is_err());
+}
+
+#[test]
+fn decision_proof_round_trips_through_json() {
+    let mut ledger = ledger(5);
+    let trusted = [key().verifying_key()];
+    let root = ledger.signed_root(&key());
+    let proof = ledger.decision_proof(2, &root).unwrap();
+
+    let json = serde_json::to_string(&proof).unwrap();
+    let shared: DecisionProof = serde_json::from_str(&json).unwrap();
+    shared.verify(&trusted).unwrap();
+
+    let other = SigningKey::from_bytes(&[9u8; 32]);
+    assert!(shared.verify(&[other.verifying_key()]).is_err());
+
+    let mut forged = shared.clone();
+    forged.entry.body.decision = Some("Allowed".into());
+    assert!(forged.verify(&trusted).is_err());
+
+    // A later root stays consistent with the one the auditor holds.
+    ledger.append(NewEntry::new("e5", "decision"), None).unwrap();
+    let later: SignedRoot = ledger.signed_root(&key());
+    ledger
+        .consistency_proof(root.tree_size, later.tree_size)
+        .unwrap()
+        .verify(&root, &later, &trusted)
+        .unwrap();
+}
@@ -0,0 +1,281 @@
+//! Sliding-window lifeforce depletion ledger.
+//!
+//! `LifeforceEnvelope::check_action` judges one projected value against
+//! `Lmin`/`Lcrit`; this ledger adds the `max_hourly_depletion` and
+//! `max_daily_depletion` caps by remembering every admitted action and every
+//! recovery with its timestamp. Events are appended to an NDJSON file and
+//! fsynced, so the windows survive restarts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::envelope::{LifeforceEnvelope, LifeforceError};
+
+pub const HOUR_SECS: u64 = 3_600;
+pub const DAY_SECS: u64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepletionKind {
+    /// An admitted action; `delta` is negative.
+    Action,
+    /// Replenishment (rest, nutrition, ...); `delta` is positive and offsets
+    /// earlier depletion still inside the windows.
+    Recovery,
+}
+
+/// One ledger line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepletionEvent {
+    /// Unix seconds.
+    pub at: u64,
+    pub kind: DepletionKind,
+    pub delta: f32,
+    pub label: String,
+}
+
+/// What a scheduler may still spend right now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepletionBudget {
+    /// `current - Lmin` for the mode, floored at 0.
+    pub until_lmin: f32,
+    /// Hourly cap minus net depletion in the last hour.
+    pub hourly: f32,
+    /// Daily cap minus net depletion in the last 24 hours.
+    pub daily: f32,
+    /// Smallest of the three: the largest single depletion that is admissible.
+    pub available: f32,
+    /// When the oldest depletion in the hourly window leaves it, if any.
+    pub hourly_frees_at: Option<u64>,
+}
+
+/// Persistent record of depletion and recovery events within the last day.
+#[derive(Debug)]
+pub struct DepletionLedger {
+    path: PathBuf,
+    file: File,
+    events: VecDeque<DepletionEvent>,
+    /// Set when a failed append could not be cut back out of the file; the
+    /// file and `events` may disagree, so nothing more is recorded.
+    poisoned: bool,
+}
+
+impl DepletionLedger {
+    /// Open or create the ledger at `path`. A partially written final line
+    /// (crash mid-append) is dropped; any other malformed line fails. The
+    /// file is then rewritten atomically with only the last day's events,
+    /// so it stays bounded.
+    pub fn open<P: AsRef<Path>>(path: P, now: u64) -> Result<Self, LifeforceError> {
+        let path = path.as_ref().to_path_buf();
+        let mut events = VecDeque::new();
+
+        if path.exists() {
+            let reader = BufReader::new(File::open(&path)?);
+            let mut lines = reader.split(b'\n').peekable();
+            while let Some(line) = lines.next() {
+                let line = line?;
+                let last = lines.peek().is_none();
+                if line.iter().all(|b| b.is_ascii_whitespace()) {
+                    continue;
+                }
+                match serde_json::from_slice::<DepletionEvent>(&line) {
+                    Ok(event) => events.push_back(event),
+                    Err(_) if last => {}
+                    Err(e) => return Err(LifeforceError::Parse(e)),
+                }
+            }
+        }
+
+        if events.iter().zip(events.iter().skip(1)).any(|(a, b)| b.at < a.at) {
+            return Err(LifeforceError::Invariant(format!(
+                "depletion ledger {} is not in time order",
+                path.display()
+            )));
+        }
+
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let since = now.saturating_sub(DAY_SECS);
+        events.retain(|e| e.at > since);
+
+        let mut tmp_name = path.clone().into_os_string();
+        tmp_name.push(".tmp");
+        let tmp = PathBuf::from(tmp_name);
+        {
+            let mut out = File::create(&tmp)?;
+            for e in &events {
+                let mut line = serde_json::to_vec(e)?;
+                line.push(b'\n');
+                out.write_all(&line)?;
+            }
+            out.sync_all()?;
+        }
+        fs::rename(&tmp, &path)?;
+
+        let file = OpenOptions::new().append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file,
+            events,
+            poisoned: false,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Events still inside the daily window, oldest first.
+    pub fn events(&self) -> impl Iterator<Item = &DepletionEvent> {
+        self.events.iter()
+    }
+
+    /// Net depletion (≥ 0) over `(now - window, now]`. A recovery only
+    /// offsets depletion recorded before it inside the window; it never
+    /// drives the running total below zero, so rest taken up front cannot
+    /// be banked as credit against later actions.
+    pub fn net_depletion(&self, now: u64, window: u64) -> f32 {
+        let since = now.saturating_sub(window);
+        self.events
+            .iter()
+            .filter(|e| e.at > since && e.at <= now)
+            .fold(0.0_f32, |net, e| (net - e.delta).max(0.0))
+    }
+
+    /// Spendable lifeforce for `mode` right now.
+    pub fn budget(
+        &self,
+        envelope: &LifeforceEnvelope,
+        mode: &str,
+        current_lifeforce: f32,
+        now: u64,
+    ) -> Result<DepletionBudget, LifeforceError> {
+        let m = envelope.mode_bounds(mode)?;
+        let axis = envelope.axis();
+        let until_lmin = (current_lifeforce - m.Lmin).max(0.0);
+        let hourly = (axis.max_hourly_depletion - self.net_depletion(now, HOUR_SECS)).max(0.0);
+        let daily = (axis.max_daily_depletion - self.net_depletion(now, DAY_SECS)).max(0.0);
+        let since = now.saturating_sub(HOUR_SECS);
+        let hourly_frees_at = self
+            .events
+            .iter()
+            .find(|e| e.at > since && e.kind == DepletionKind::Action)
+            .map(|e| e.at + HOUR_SECS);
+        Ok(DepletionBudget {
+            until_lmin,
+            hourly,
+            daily,
+            available: until_lmin.min(hourly).min(daily),
+            hourly_frees_at,
+        })
+    }
+
+    /// Check an action against the envelope and both windows, and record it
+    /// if admitted. `delta` is negative for depletion; replenishment goes
+    /// through `record_recovery`. Nothing is recorded on denial.
+    pub fn admit(
+        &mut self,
+        envelope: &LifeforceEnvelope,
+        mode: &str,
+        current_lifeforce: f32,
+        delta: f32,
+        label: &str,
+        now: u64,
+    ) -> Result<(), LifeforceError> {
+        if delta.is_nan() || delta > 0.0 {
+            return Err(LifeforceError::Invariant(format!(
+                "action delta {} is not a depletion; record replenishment with record_recovery",
+                delta
+            )));
+        }
+        envelope.check_action(mode, current_lifeforce, delta)?;
+        self.check_clock(now)?;
+        if delta < 0.0 {
+            let axis = envelope.axis();
+            let cost = -delta;
+            let hourly = self.net_depletion(now, HOUR_SECS) + cost;
+            if hourly > axis.max_hourly_depletion + 1e-6 {
+                return Err(LifeforceError::Violation(format!(
+                    "hourly lifeforce depletion {} would exceed cap {}",
+                    hourly, axis.max_hourly_depletion
+                )));
+            }
+            let daily = self.net_depletion(now, DAY_SECS) + cost;
+            if daily > axis.max_daily_depletion + 1e-6 {
+                return Err(LifeforceError::Violation(format!(
+                    "daily lifeforce depletion {} would exceed cap {}",
+                    daily, axis.max_daily_depletion
+                )));
+            }
+        }
+        self.append(DepletionEvent {
+            at: now,
+            kind: DepletionKind::Action,
+            delta,
+            label: label.to_string(),
+        })
+    }
+
+    /// Record replenishment of `amount` (> 0).
+    pub fn record_recovery(&mut self, amount: f32, label: &str, now: u64) -> Result<(), LifeforceError> {
+        if !(amount > 0.0 && amount <= 1.0) {
+            return Err(LifeforceError::Invariant(format!(
+                "recovery amount {} outside (0, 1]",
+                amount
+            )));
+        }
+        self.check_clock(now)?;
+        self.append(DepletionEvent {
+            at: now,
+            kind: DepletionKind::Recovery,
+            delta: amount,
+            label: label.to_string(),
+        })
+    }
+
+    fn check_clock(&self, now: u64) -> Result<(), LifeforceError> {
+        match self.events.back() {
+            Some(last) if now < last.at => Err(LifeforceError::Invariant(format!(
+                "timestamp {} precedes last ledger event at {}",
+                now, last.at
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Write and fsync one line. If that fails, the file is cut back to
+    /// its length before the write, so a partial line cannot run into the
+    /// next one; if even that fails, the ledger is poisoned until reopened.
+    fn append(&mut self, event: DepletionEvent) -> Result<(), LifeforceError> {
+        if self.poisoned {
+            return Err(LifeforceError::Invariant(format!(
+                "depletion ledger {} is poisoned by a failed write; reopen it",
+                self.path.display()
+            )));
+        }
+        let mut line = serde_json::to_vec(&event)?;
+        line.push(b'\n');
+        let len = self.file.metadata()?.len();
+        if let Err(e) = self.file.write_all(&line).and_then(|_| self.file.sync_data()) {
+            if self.file.set_len(len).and_then(|_| self.file.sync_data()).is_err() {
+                self.poisoned = true;
+            }
+            return Err(e.into());
+        }
+        let now = event.at;
+        self.events.push_back(event);
+        self.prune(now);
+        Ok(())
+    }
+
+    fn prune(&mut self, now: u64) {
+        let since = now.saturating_sub(DAY_SECS);
+        while self.events.front().is_some_and(|e| e.at <= since) {
+            self.events.pop_front();
+        }
+    }
+}
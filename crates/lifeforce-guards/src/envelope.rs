@@ -9,6 +9,8 @@ pub struct LifeforceAxis {
     pub max_hourly_depletion: f32,
 }
 
+/// `Lmin`/`Lcrit` keep the field names used in `lifeforce.aln`.
+#[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LifeforceMode {
     pub Lmin: f32,
@@ -66,6 +68,10 @@ impl LifeforceEnvelope {
         })
     }
 
+    pub fn axis(&self) -> &LifeforceAxis {
+        &self.axis
+    }
+
     pub fn mode_bounds(&self, mode: &str) -> Result<LifeforceMode, LifeforceError> {
         self.modes
             .get(mode)
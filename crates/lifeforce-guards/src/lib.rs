@@ -0,0 +1,5 @@
+pub mod depletion;
+pub mod envelope;
+
+pub use depletion::{DepletionBudget, DepletionEvent, DepletionKind, DepletionLedger, DAY_SECS, HOUR_SECS};
+pub use envelope::{LifeforceAxis, LifeforceEnvelope, LifeforceEnvelopeSpec, LifeforceError, LifeforceMode};
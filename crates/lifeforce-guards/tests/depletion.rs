@@ -0,0 +1,176 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use lifeforce_guards::{DepletionKind, DepletionLedger, LifeforceEnvelope, LifeforceError, DAY_SECS, HOUR_SECS};
+
+const T0: u64 = 1_770_000_000;
+
+/// `policies/lifeforce.aln`: hourly cap 0.10, daily cap 0.25, Baseline Lmin 0.25.
+fn envelope() -> LifeforceEnvelope {
+    LifeforceEnvelope::from_path(format!("{}/../../policies/lifeforce.aln", env!("CARGO_MANIFEST_DIR"))).unwrap()
+}
+
+fn ledger_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lifeforce-guards-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("depletion.ndjson")
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+#[test]
+fn hourly_and_daily_caps_are_enforced_over_sliding_windows() {
+    let env = envelope();
+    let mut ledger = DepletionLedger::open(ledger_path("caps"), T0).unwrap();
+
+    ledger.admit(&env, "Baseline", 0.9, -0.06, "a", T0).unwrap();
+    ledger.admit(&env, "Baseline", 0.84, -0.04, "b", T0 + 60).unwrap();
+    let err = ledger.admit(&env, "Baseline", 0.8, -0.01, "c", T0 + 120).unwrap_err();
+    assert!(err.to_string().contains("hourly"), "{err}");
+    // Denials are not recorded.
+    assert_eq!(ledger.events().count(), 2);
+
+    // The hourly window only frees once `a` and `b` are an hour old.
+    assert!(ledger.admit(&env, "Baseline", 0.8, -0.01, "c", T0 + HOUR_SECS - 1).is_err());
+    ledger.admit(&env, "Baseline", 0.8, -0.10, "d", T0 + HOUR_SECS + 61).unwrap();
+    ledger.admit(&env, "Baseline", 0.7, -0.05, "e", T0 + 3 * HOUR_SECS).unwrap();
+
+    // 0.25 spent today: the daily cap binds although the hour is fresh.
+    let err = ledger.admit(&env, "Baseline", 0.65, -0.01, "f", T0 + 5 * HOUR_SECS).unwrap_err();
+    assert!(err.to_string().contains("daily"), "{err}");
+    assert!(close(ledger.net_depletion(T0 + 5 * HOUR_SECS, HOUR_SECS), 0.0));
+    assert!(close(ledger.net_depletion(T0 + 5 * HOUR_SECS, DAY_SECS), 0.25));
+
+    // A day after `a` and `b`, their 0.10 is available again.
+    ledger.admit(&env, "Baseline", 0.65, -0.10, "f", T0 + DAY_SECS + 60).unwrap();
+    assert_eq!(ledger.events().map(|e| e.label.as_str()).collect::<Vec<_>>(), ["d", "e", "f"]);
+
+    // The envelope itself still applies, and replenishment is not an action.
+    assert!(matches!(
+        ledger.admit(&env, "Baseline", 0.3, -0.06, "g", T0 + 2 * DAY_SECS),
+        Err(LifeforceError::Violation(_))
+    ));
+    assert!(matches!(
+        ledger.admit(&env, "Baseline", 0.9, 0.05, "g", T0 + 2 * DAY_SECS),
+        Err(LifeforceError::Invariant(_))
+    ));
+    assert!(matches!(
+        ledger.admit(&env, "Nowhere", 0.9, -0.01, "g", T0 + 2 * DAY_SECS),
+        Err(LifeforceError::UnknownMode(_))
+    ));
+}
+
+#[test]
+fn recoveries_offset_earlier_depletion_but_bank_no_credit() {
+    let env = envelope();
+    let mut ledger = DepletionLedger::open(ledger_path("recovery"), T0).unwrap();
+
+    ledger.admit(&env, "Baseline", 0.9, -0.10, "a", T0).unwrap();
+    ledger.record_recovery(0.04, "rest", T0 + 60).unwrap();
+    assert!(close(ledger.net_depletion(T0 + 60, HOUR_SECS), 0.06));
+    ledger.admit(&env, "Baseline", 0.84, -0.04, "b", T0 + 120).unwrap();
+    assert!(ledger.admit(&env, "Baseline", 0.8, -0.01, "c", T0 + 180).is_err());
+
+    // Rest taken before any depletion leaves nothing to offset.
+    let mut fresh = DepletionLedger::open(ledger_path("recovery-credit"), T0).unwrap();
+    fresh.record_recovery(1.0, "sleep", T0).unwrap();
+    assert!(close(fresh.net_depletion(T0, HOUR_SECS), 0.0));
+    fresh.admit(&env, "Baseline", 0.9, -0.10, "a", T0 + 60).unwrap();
+    assert!(fresh.admit(&env, "Baseline", 0.8, -0.01, "b", T0 + 120).is_err());
+
+    // An oversized recovery only clears what is there.
+    fresh.record_recovery(0.5, "nap", T0 + 180).unwrap();
+    fresh.admit(&env, "Baseline", 0.8, -0.10, "b", T0 + 240).unwrap();
+    assert!(close(fresh.net_depletion(T0 + 240, HOUR_SECS), 0.10));
+
+    for amount in [0.0, -0.1, 1.5, f32::NAN] {
+        assert!(fresh.record_recovery(amount, "bad", T0 + 300).is_err(), "{amount}");
+    }
+    assert!(fresh.record_recovery(0.1, "late", T0).is_err());
+}
+
+#[test]
+fn windows_survive_a_restart() {
+    let env = envelope();
+    let path = ledger_path("restart");
+    {
+        let mut ledger = DepletionLedger::open(&path, T0).unwrap();
+        ledger.admit(&env, "Baseline", 0.9, -0.07, "a", T0).unwrap();
+        ledger.record_recovery(0.02, "rest", T0 + 10).unwrap();
+        ledger.admit(&env, "Baseline", 0.85, -0.05, "b", T0 + HOUR_SECS + 20).unwrap();
+    }
+
+    let mut reopened = DepletionLedger::open(&path, T0 + HOUR_SECS + 30).unwrap();
+    assert_eq!(reopened.events().count(), 3);
+    assert_eq!(reopened.events().nth(1).unwrap().kind, DepletionKind::Recovery);
+    assert!(close(reopened.net_depletion(T0 + HOUR_SECS + 30, DAY_SECS), 0.10));
+    assert!(reopened.admit(&env, "Baseline", 0.8, -0.06, "c", T0 + HOUR_SECS + 40).is_err());
+    // The clock may not run backwards past the persisted events.
+    assert!(reopened.record_recovery(0.01, "x", T0).is_err());
+    drop(reopened);
+
+    // Opening a day later compacts the file to the live window.
+    let later = DepletionLedger::open(&path, T0 + DAY_SECS + 15).unwrap();
+    assert_eq!(later.events().map(|e| e.label.as_str()).collect::<Vec<_>>(), ["b"]);
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+}
+
+#[test]
+fn a_torn_final_line_is_dropped_and_other_damage_is_refused() {
+    let env = envelope();
+    let path = ledger_path("torn");
+    {
+        let mut ledger = DepletionLedger::open(&path, T0).unwrap();
+        ledger.admit(&env, "Baseline", 0.9, -0.03, "a", T0).unwrap();
+    }
+    let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+    file.write_all(br#"{"at":1770000060,"kind":"action","del"#).unwrap();
+    drop(file);
+
+    let mut ledger = DepletionLedger::open(&path, T0 + 120).unwrap();
+    assert_eq!(ledger.events().count(), 1);
+    ledger.admit(&env, "Baseline", 0.87, -0.02, "b", T0 + 120).unwrap();
+    drop(ledger);
+    let ledger = DepletionLedger::open(&path, T0 + 180).unwrap();
+    assert!(close(ledger.net_depletion(T0 + 180, HOUR_SECS), 0.05));
+    drop(ledger);
+
+    let good = fs::read_to_string(&path).unwrap();
+    let (first, rest) = good.split_once('\n').unwrap();
+    fs::write(&path, format!("{first}\nnot json\n{rest}")).unwrap();
+    assert!(matches!(DepletionLedger::open(&path, T0 + 180), Err(LifeforceError::Parse(_))));
+
+    let lines: Vec<&str> = good.lines().collect();
+    fs::write(&path, format!("{}\n{}\n", lines[1], lines[0])).unwrap();
+    assert!(matches!(DepletionLedger::open(&path, T0 + 180), Err(LifeforceError::Invariant(_))));
+}
+
+#[test]
+fn budget_reports_the_binding_limit() {
+    let env = envelope();
+    let mut ledger = DepletionLedger::open(ledger_path("budget"), T0).unwrap();
+
+    let budget = ledger.budget(&env, "Baseline", 0.9, T0).unwrap();
+    assert!(close(budget.until_lmin, 0.65) && close(budget.hourly, 0.10) && close(budget.daily, 0.25));
+    assert!(close(budget.available, 0.10));
+    assert_eq!(budget.hourly_frees_at, None);
+
+    ledger.admit(&env, "Baseline", 0.9, -0.08, "a", T0 + 100).unwrap();
+    let budget = ledger.budget(&env, "Baseline", 0.82, T0 + 200).unwrap();
+    assert!(close(budget.hourly, 0.02) && close(budget.daily, 0.17) && close(budget.available, 0.02));
+    assert_eq!(budget.hourly_frees_at, Some(T0 + 100 + HOUR_SECS));
+    // The advertised budget is admissible; anything more is not.
+    assert!(ledger.admit(&env, "Baseline", 0.82, -(budget.available + 0.01), "b", T0 + 200).is_err());
+    ledger.admit(&env, "Baseline", 0.82, -budget.available, "b", T0 + 200).unwrap();
+
+    // Near Lmin the envelope is what binds.
+    let budget = ledger.budget(&env, "Rehab", 0.37, T0 + 2 * HOUR_SECS).unwrap();
+    assert!(close(budget.until_lmin, 0.02) && close(budget.available, 0.02));
+    let budget = ledger.budget(&env, "Rehab", 0.2, T0 + 2 * HOUR_SECS).unwrap();
+    assert_eq!(budget.available, 0.0);
+    assert!(matches!(ledger.budget(&env, "Nowhere", 0.9, T0), Err(LifeforceError::UnknownMode(_))));
+}
@@ -1,6 +1,6 @@
 use alloc::string::String;
 use thiserror::Error;
-use neurorights_core::NeurorightsViolation;
+use neurorights_core::NeurorightsViolations;
 
 #[derive(Debug, Error)]
 pub enum AssistantAdapterError {
@@ -23,8 +23,8 @@ pub enum AssistantAdapterError {
     Config(String),
 }
 
-impl From<NeurorightsViolation> for AssistantAdapterError {
-    fn from(v: NeurorightsViolation) -> Self {
+impl From<NeurorightsViolations> for AssistantAdapterError {
+    fn from(v: NeurorightsViolations) -> Self {
         AssistantAdapterError::Neurorights(v.to_string())
     }
 }
@@ -10,7 +10,7 @@
 //! guards (answer-quality, neurorights, RoH ≤ 0.3) before display/logging.
 
 use serde::{Deserialize, Serialize};
-use sovereigntycore::answerquality::{
+use sovereigntycore::answer_quality::{
     AnswerRoute, ChatAnswerEnvelope,
 };
 use organiccpucore::BioState;
@@ -80,7 +80,7 @@ macro_rules! neuro_print {
         $fmt:literal $(, $args:expr )*
     ) => {{
         use $crate::NeuroPrintContext;
-        use sovereigntycore::answerquality::AnswerRoute;
+        use sovereigntycore::answer_quality::AnswerRoute;
 
         // Format the body text. No side effects here.
         let body = format!($fmt $(, $args)*);
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use sovereigntycore::evaluate_update; // your existing evaluation entry point
+use sovereigntycore::risk_of_harm::RiskOfHarm;
 use organiccpualn::evolvestream::EvolutionProposalRecord;
-use organiccpualn::rohmodel::RohModel;
+use organiccpualn::roh_registry::RohRegistry;
 
 /// Minimal view of the TFC Run Task payload we care about.
 #[derive(Debug, Deserialize)]
@@ -36,27 +39,46 @@ pub struct TerraformerDecision {
 }
 
 pub struct NeuromorphicTerraformer {
-    pub roh_model: RohModel,
+    /// Registered RoH model the plan is scored with.
+    pub roh: RiskOfHarm,
     pub subject_id: String,
 }
 
 impl NeuromorphicTerraformer {
-    pub fn new(roh_model: RohModel, subject_id: String) -> Self {
-        Self { roh_model, subject_id }
+    /// Fails if `roh_model_id` is not registered.
+    pub fn new(registry: &RohRegistry, roh_model_id: &str, subject_id: String) -> anyhow::Result<Self> {
+        Ok(Self {
+            roh: RiskOfHarm::from_registry(registry, roh_model_id)?,
+            subject_id,
+        })
     }
 
-    /// Core entry: given a parsed TFC payload + plan summary,
-    /// build a proposal, run sovereigntycore, and produce a decision.
+    /// Core entry: given a parsed TFC payload + plan summary and the states
+    /// before and after the plan (keyed by the model's axes), build a
+    /// proposal, run sovereigntycore, and produce a decision.
     pub fn handle_run(
         &self,
         tfc: TfcRunTaskPayload,
         plan_summary: serde_json::Value,
+        state_before: &HashMap<String, f32>,
+        state_after: &HashMap<String, f32>,
     ) -> TerraformerDecision {
         let proposal_id = format!("tf-{}", tfc.run_id);
 
-        // Example: make a crude RoH estimate for infra changes.
-        let roh_before = 0.18_f32;
-        let roh_after_estimate = 0.19_f32; // refined later from real metrics
+        // A state the model cannot score is not evaluated at all.
+        let (roh_before, roh_after_estimate) =
+            match (self.roh.estimate(state_before), self.roh.estimate(state_after)) {
+                (Ok(before), Ok(after)) => (before, after),
+                (Err(e), _) | (_, Err(e)) => {
+                    return TerraformerDecision {
+                        proposal_id,
+                        decision: "Rejected".to_string(),
+                        message: format!("RoH model {}: {e:#}", self.roh.model_id()),
+                        roh_before: f32::NAN,
+                        roh_after: f32::NAN,
+                    }
+                }
+            };
 
         let neuro = NeuroTerraformProposal {
             proposal_id: proposal_id.clone(),
@@ -1,7 +1,19 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use organiccpualn::prompt_envelope::NeurorightsBoundPromptEnvelope;
+pub mod envelope;
+
+pub use envelope::{CybostateClass, NeurorightsBoundPromptEnvelope};
+
+/// Eco-cost ceiling for a tool when the policy does not set `ecolimit`.
+pub const DEFAULT_ECO_LIMIT: f32 = 0.5;
+
+/// Tool id that produces control outputs; never allowed under
+/// `CybostateClass::ActuationForbidden`.
+pub const ACTUATOR_TOOL_ID: &str = "actuator";
+
+/// Domain tag for dream / inner-state content.
+pub const DREAMSTATE_DOMAIN: &str = "dreamstate";
 
 /// Minimal view of your neurorights policy JSON.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -13,8 +25,19 @@ pub struct NeurorightsPolicyDocument {
     pub mentalintegrity: bool,
     pub cognitiveliberty: bool,
 
-    /// Dream / neural sensitive flags already in your stack.
+    /// Dream / neural sensitive flags already in your stack. Required: the
+    /// firewall fails closed without it.
     pub dreamstate: Option<DreamStateSlice>,
+
+    /// Highest `ToolCapability::eco_cost_estimate` allowed.
+    #[serde(default)]
+    pub ecolimit: Option<f32>,
+}
+
+impl NeurorightsPolicyDocument {
+    pub fn eco_limit(&self) -> f32 {
+        self.ecolimit.unwrap_or(DEFAULT_ECO_LIMIT)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -36,68 +59,167 @@ pub struct ToolCapability {
     pub eco_cost_estimate: f32, // 0..1
 }
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Error)]
 pub enum NeurorightsViolation {
+    #[error("policy {policyid} has no {section} section")]
+    MissingPolicy { policyid: String, section: String },
+
     #[error("tool {tool_id} not allowed by envelope")]
     ToolNotAllowed { tool_id: String },
 
     #[error("inner-state scoring is forbidden for domain {domain}")]
     InnerStateScoringForbidden { domain: String },
 
-    #[error("coercive or non-revocable use detected for domain {domain}")]
-    CoerciveUse { domain: String },
+    #[error("decision use is forbidden for domain {domain}")]
+    ForbiddenDecisionUse { domain: String },
 
     #[error("noncommercial neurorights profile forbids this use")]
     NonCommercialViolation,
 
     #[error("eco-impact {eco} exceeds allowed limit {limit}")]
     EcoOverLimit { eco: f32, limit: f32 },
+
+    #[error("tool {tool_id} would actuate under ActuationForbidden")]
+    ActuationForbidden { tool_id: String },
 }
 
-/// Core guard: check envelope + tool against neurorights policy.
-pub fn guard_prompt_tool(
+impl NeurorightsViolation {
+    /// Stable code for logs, ledgers and clients; never reused or renamed.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NeurorightsViolation::MissingPolicy { .. } => "NR_MISSING_POLICY",
+            NeurorightsViolation::ToolNotAllowed { .. } => "NR_TOOL_NOT_ALLOWED",
+            NeurorightsViolation::InnerStateScoringForbidden { .. } => "NR_INNER_STATE_SCORING",
+            NeurorightsViolation::ForbiddenDecisionUse { .. } => "NR_FORBIDDEN_DECISION_USE",
+            NeurorightsViolation::NonCommercialViolation => "NR_NONCOMMERCIAL",
+            NeurorightsViolation::EcoOverLimit { .. } => "NR_ECO_OVER_LIMIT",
+            NeurorightsViolation::ActuationForbidden { .. } => "NR_ACTUATION_FORBIDDEN",
+        }
+    }
+}
+
+/// Every violation found for one request, in check order. Never empty when
+/// returned as an error.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub struct NeurorightsViolations(pub Vec<NeurorightsViolation>);
+
+impl NeurorightsViolations {
+    pub fn codes(&self) -> Vec<&'static str> {
+        self.0.iter().map(NeurorightsViolation::code).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &NeurorightsViolation> {
+        self.0.iter()
+    }
+
+    /// `Err` unless `violations` is empty.
+    pub fn into_result(violations: Vec<NeurorightsViolation>) -> Result<(), Self> {
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Self(violations))
+        }
+    }
+}
+
+impl std::fmt::Display for NeurorightsViolations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, v) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}: {}", v.code(), v)?;
+        }
+        Ok(())
+    }
+}
+
+/// The full neurorights check set for one request. Nothing short-circuits,
+/// so the result lists every violation.
+///
+/// Tool checks (allow-list, dream-content scoring, noncommercial, eco
+/// limit) run only when a `tool` is given; the actuation check reads the
+/// envelope's `cybostate`.
+pub fn check_prompt(
     env: &NeurorightsBoundPromptEnvelope,
     policy: &NeurorightsPolicyDocument,
-    tool: &ToolCapability,
-) -> Result<(), NeurorightsViolation> {
-    // 1) Tool must be listed in allowed_tools.
-    if !env.allowed_tools.iter().any(|t| t == &tool.tool_id) {
-        return Err(NeurorightsViolation::ToolNotAllowed {
-            tool_id: tool.tool_id.clone(),
+    tool: Option<&ToolCapability>,
+) -> Vec<NeurorightsViolation> {
+    let mut violations = Vec::new();
+
+    let Some(dream) = &policy.dreamstate else {
+        violations.push(NeurorightsViolation::MissingPolicy {
+            policyid: policy.policyid.clone(),
+            section: "dreamstate".to_string(),
         });
+        return violations;
+    };
+
+    // 1) Tool must be listed in allowed_tools.
+    if let Some(tool) = tool {
+        if !env.allowed_tools.iter().any(|t| t == &tool.tool_id) {
+            violations.push(NeurorightsViolation::ToolNotAllowed {
+                tool_id: tool.tool_id.clone(),
+            });
+        }
     }
 
-    // 2) No inner-state scoring for protected domains.
-    let protected_domains = policy
-        .dreamstate
-        .as_ref()
-        .map(|d| d.forbiddecisionuse.clone())
-        .unwrap_or_default();
+    // 2) No inner-state scoring of dream content when dream-sensitive.
+    // Decision-use domains are refused outright by check 3, so they are not
+    // reported here a second time.
+    if let Some(tool) = tool.filter(|t| t.can_score_user || t.can_read_inner_state) {
+        let dream_content = dream.dreamsensitive && env.domain_tags.iter().any(|t| t == DREAMSTATE_DOMAIN);
+        if dream_content && (tool.can_score_user || policy.mentalprivacy) {
+            violations.push(NeurorightsViolation::InnerStateScoringForbidden {
+                domain: DREAMSTATE_DOMAIN.to_string(),
+            });
+        }
+    }
 
+    // 3) Forbidden decision use: these domains never reach a backend.
     for tag in &env.domain_tags {
-        if protected_domains.iter().any(|d| d == tag) && tool.can_score_user {
-            return Err(NeurorightsViolation::InnerStateScoringForbidden {
-                domain: tag.clone(),
-            });
+        if dream.forbiddecisionuse.iter().any(|d| d == tag) {
+            violations.push(NeurorightsViolation::ForbiddenDecisionUse { domain: tag.clone() });
         }
     }
 
-    // 3) Noncommercial neurorights: forbid tools that write long-term profiles
-    // or are marked as "commercial".
-    if let Some(dream) = &policy.dreamstate {
+    if let Some(tool) = tool {
+        // 4) Noncommercial neurorights: forbid tools that write long-term
+        // profiles.
         if dream.noncommercial && tool.can_write_longterm_profile {
-            return Err(NeurorightsViolation::NonCommercialViolation);
+            violations.push(NeurorightsViolation::NonCommercialViolation);
+        }
+
+        // 5) Eco guard.
+        let limit = policy.eco_limit();
+        if tool.eco_cost_estimate > limit {
+            violations.push(NeurorightsViolation::EcoOverLimit {
+                eco: tool.eco_cost_estimate,
+                limit,
+            });
         }
     }
 
-    // 4) Simple eco guard: bound eco cost by a global limit from policy or hard-coded.
-    let eco_limit = 0.5_f32; // initial conservative bound, refine via policy if desired.
-    if tool.eco_cost_estimate > eco_limit {
-        return Err(NeurorightsViolation::EcoOverLimit {
-            eco: tool.eco_cost_estimate,
-            limit: eco_limit,
-        });
+    // 6) ActuationForbidden never yields control outputs.
+    if env.cybostate == CybostateClass::ActuationForbidden {
+        let requested = tool.map(|t| t.tool_id.as_str());
+        let mut actuating = env.allowed_tools.iter().map(String::as_str).chain(requested);
+        if let Some(tool_id) = actuating.find(|t| *t == ACTUATOR_TOOL_ID) {
+            violations.push(NeurorightsViolation::ActuationForbidden {
+                tool_id: tool_id.to_string(),
+            });
+        }
     }
 
-    Ok(())
+    violations
+}
+
+/// Core guard: check envelope + tool against neurorights policy. Returns
+/// every violation, not just the first.
+pub fn guard_prompt_tool(
+    env: &NeurorightsBoundPromptEnvelope,
+    policy: &NeurorightsPolicyDocument,
+    tool: &ToolCapability,
+) -> Result<(), NeurorightsViolations> {
+    NeurorightsViolations::into_result(check_prompt(env, policy, Some(tool)))
 }
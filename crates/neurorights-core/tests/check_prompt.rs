@@ -0,0 +1,235 @@
+use neurorights_core::{
+    check_prompt, guard_prompt_tool, CybostateClass, DreamStateSlice, NeurorightsBoundPromptEnvelope,
+    NeurorightsPolicyDocument, NeurorightsViolation, ToolCapability, ACTUATOR_TOOL_ID, DREAMSTATE_DOMAIN,
+};
+
+fn policy() -> NeurorightsPolicyDocument {
+    NeurorightsPolicyDocument {
+        policyid: "bostrom-neurorights-v1".to_string(),
+        subjectid: "bostrom1".to_string(),
+        mentalprivacy: true,
+        mentalintegrity: true,
+        cognitiveliberty: true,
+        dreamstate: Some(DreamStateSlice {
+            dreamsensitive: true,
+            forbiddecisionuse: vec!["employment".to_string(), "credit".to_string()],
+            forgetslahours: 48,
+            noncommercial: true,
+            soulnontradeable: true,
+        }),
+        ecolimit: None,
+    }
+}
+
+fn envelope(domains: &[&str], tools: &[&str], cybostate: CybostateClass) -> NeurorightsBoundPromptEnvelope {
+    NeurorightsBoundPromptEnvelope {
+        subject_id: "bostrom1".to_string(),
+        neurorights_profile_id: "bostrom-neurorights-v1".to_string(),
+        roh_model_id: "bostrom-rohmodel-v1".to_string(),
+        domain_tags: domains.iter().map(|d| d.to_string()).collect(),
+        allowed_tools: tools.iter().map(|t| t.to_string()).collect(),
+        cybostate,
+        prompt_text: "hello".to_string(),
+    }
+}
+
+/// A read-only tool that passes every check.
+fn tool(id: &str) -> ToolCapability {
+    ToolCapability {
+        tool_id: id.to_string(),
+        can_read_inner_state: false,
+        can_score_user: false,
+        can_write_longterm_profile: false,
+        eco_cost_estimate: 0.1,
+    }
+}
+
+fn codes(violations: &[NeurorightsViolation]) -> Vec<&'static str> {
+    violations.iter().map(NeurorightsViolation::code).collect()
+}
+
+#[test]
+fn a_clean_request_passes() {
+    let env = envelope(&["devtools"], &["writer"], CybostateClass::ResearchReady);
+    assert!(check_prompt(&env, &policy(), Some(&tool("writer"))).is_empty());
+    assert!(check_prompt(&env, &policy(), None).is_empty());
+    assert!(guard_prompt_tool(&env, &policy(), &tool("writer")).is_ok());
+}
+
+#[test]
+fn missing_dreamstate_fails_closed() {
+    let mut policy = policy();
+    policy.dreamstate = None;
+    // Even a request that is otherwise clean is refused, and nothing else is
+    // evaluated against the incomplete policy.
+    let env = envelope(&["employment"], &[ACTUATOR_TOOL_ID], CybostateClass::ActuationForbidden);
+    let violations = check_prompt(&env, &policy, Some(&tool("writer")));
+    assert_eq!(
+        violations,
+        vec![NeurorightsViolation::MissingPolicy {
+            policyid: "bostrom-neurorights-v1".to_string(),
+            section: "dreamstate".to_string(),
+        }]
+    );
+    assert_eq!(codes(&violations), ["NR_MISSING_POLICY"]);
+
+    let clean = envelope(&["devtools"], &["writer"], CybostateClass::ResearchReady);
+    assert_eq!(codes(&check_prompt(&clean, &policy, None)), ["NR_MISSING_POLICY"]);
+}
+
+#[test]
+fn tool_not_in_the_allow_list() {
+    let env = envelope(&["devtools"], &["writer"], CybostateClass::ResearchReady);
+    let violations = check_prompt(&env, &policy(), Some(&tool("browser")));
+    assert_eq!(
+        violations,
+        vec![NeurorightsViolation::ToolNotAllowed {
+            tool_id: "browser".to_string()
+        }]
+    );
+    assert_eq!(codes(&violations), ["NR_TOOL_NOT_ALLOWED"]);
+}
+
+#[test]
+fn inner_state_scoring_of_dream_content() {
+    let env = envelope(&[DREAMSTATE_DOMAIN], &["scorer"], CybostateClass::ResearchReady);
+    let scorer = ToolCapability {
+        can_score_user: true,
+        ..tool("scorer")
+    };
+    let violations = check_prompt(&env, &policy(), Some(&scorer));
+    assert_eq!(
+        violations,
+        vec![NeurorightsViolation::InnerStateScoringForbidden {
+            domain: DREAMSTATE_DOMAIN.to_string()
+        }]
+    );
+    assert_eq!(codes(&violations), ["NR_INNER_STATE_SCORING"]);
+
+    // Reading inner state is scoring under mental privacy only.
+    let reader = ToolCapability {
+        can_read_inner_state: true,
+        ..tool("scorer")
+    };
+    assert_eq!(codes(&check_prompt(&env, &policy(), Some(&reader))), ["NR_INNER_STATE_SCORING"]);
+    let relaxed = NeurorightsPolicyDocument {
+        mentalprivacy: false,
+        ..policy()
+    };
+    assert!(check_prompt(&env, &relaxed, Some(&reader)).is_empty());
+
+    // Without the dream-sensitive flag, dream content is not protected.
+    let mut not_sensitive = policy();
+    not_sensitive.dreamstate.as_mut().unwrap().dreamsensitive = false;
+    assert!(check_prompt(&env, &not_sensitive, Some(&scorer)).is_empty());
+}
+
+#[test]
+fn forbidden_decision_use_is_reported_once() {
+    let env = envelope(&["employment", "devtools", "credit"], &["scorer"], CybostateClass::ResearchReady);
+    let expected = vec![
+        NeurorightsViolation::ForbiddenDecisionUse {
+            domain: "employment".to_string(),
+        },
+        NeurorightsViolation::ForbiddenDecisionUse {
+            domain: "credit".to_string(),
+        },
+    ];
+    assert_eq!(check_prompt(&env, &policy(), None), expected);
+
+    // A scoring tool on those domains adds no second code per domain.
+    let scorer = ToolCapability {
+        can_score_user: true,
+        can_read_inner_state: true,
+        ..tool("scorer")
+    };
+    let violations = check_prompt(&env, &policy(), Some(&scorer));
+    assert_eq!(violations, expected);
+    assert_eq!(codes(&violations), ["NR_FORBIDDEN_DECISION_USE", "NR_FORBIDDEN_DECISION_USE"]);
+}
+
+#[test]
+fn noncommercial_profiles_refuse_long_term_profiling() {
+    let env = envelope(&["devtools"], &["profiler"], CybostateClass::ResearchReady);
+    let profiler = ToolCapability {
+        can_write_longterm_profile: true,
+        ..tool("profiler")
+    };
+    let violations = check_prompt(&env, &policy(), Some(&profiler));
+    assert_eq!(violations, vec![NeurorightsViolation::NonCommercialViolation]);
+    assert_eq!(codes(&violations), ["NR_NONCOMMERCIAL"]);
+
+    let mut commercial = policy();
+    commercial.dreamstate.as_mut().unwrap().noncommercial = false;
+    assert!(check_prompt(&env, &commercial, Some(&profiler)).is_empty());
+}
+
+#[test]
+fn eco_cost_over_the_policy_limit() {
+    let env = envelope(&["devtools"], &["render"], CybostateClass::ResearchReady);
+    let heavy = ToolCapability {
+        eco_cost_estimate: 0.6,
+        ..tool("render")
+    };
+    let violations = check_prompt(&env, &policy(), Some(&heavy));
+    assert_eq!(violations, vec![NeurorightsViolation::EcoOverLimit { eco: 0.6, limit: 0.5 }]);
+    assert_eq!(codes(&violations), ["NR_ECO_OVER_LIMIT"]);
+
+    let generous = NeurorightsPolicyDocument {
+        ecolimit: Some(0.7),
+        ..policy()
+    };
+    assert!(check_prompt(&env, &generous, Some(&heavy)).is_empty());
+}
+
+#[test]
+fn actuation_is_refused_under_actuation_forbidden() {
+    // The envelope's own cybostate decides, whether the actuator is only
+    // allowed by the envelope or is the tool being invoked.
+    let env = envelope(&["devtools"], &["writer", ACTUATOR_TOOL_ID], CybostateClass::ActuationForbidden);
+    let violations = check_prompt(&env, &policy(), None);
+    assert_eq!(
+        violations,
+        vec![NeurorightsViolation::ActuationForbidden {
+            tool_id: ACTUATOR_TOOL_ID.to_string()
+        }]
+    );
+    assert_eq!(codes(&violations), ["NR_ACTUATION_FORBIDDEN"]);
+    assert_eq!(
+        codes(&check_prompt(&env, &policy(), Some(&tool(ACTUATOR_TOOL_ID)))),
+        ["NR_ACTUATION_FORBIDDEN"]
+    );
+
+    let governed = envelope(&["devtools"], &["writer", ACTUATOR_TOOL_ID], CybostateClass::GovernanceReady);
+    assert!(check_prompt(&governed, &policy(), Some(&tool(ACTUATOR_TOOL_ID))).is_empty());
+    let reading = envelope(&["devtools"], &["writer"], CybostateClass::ActuationForbidden);
+    assert!(check_prompt(&reading, &policy(), Some(&tool("writer"))).is_empty());
+}
+
+#[test]
+fn every_violation_is_reported_in_check_order() {
+    let env = envelope(&[DREAMSTATE_DOMAIN, "employment"], &[], CybostateClass::ActuationForbidden);
+    let everything = ToolCapability {
+        tool_id: ACTUATOR_TOOL_ID.to_string(),
+        can_read_inner_state: true,
+        can_score_user: true,
+        can_write_longterm_profile: true,
+        eco_cost_estimate: 0.9,
+    };
+    let err = guard_prompt_tool(&env, &policy(), &everything).unwrap_err();
+    assert_eq!(
+        err.codes(),
+        [
+            "NR_TOOL_NOT_ALLOWED",
+            "NR_INNER_STATE_SCORING",
+            "NR_FORBIDDEN_DECISION_USE",
+            "NR_NONCOMMERCIAL",
+            "NR_ECO_OVER_LIMIT",
+            "NR_ACTUATION_FORBIDDEN",
+        ]
+    );
+    assert_eq!(err.iter().count(), 6);
+    let message = err.to_string();
+    assert!(message.starts_with("NR_TOOL_NOT_ALLOWED: tool actuator not allowed by envelope; "), "{message}");
+    assert!(message.ends_with("NR_ACTUATION_FORBIDDEN: tool actuator would actuate under ActuationForbidden"), "{message}");
+}
@@ -1,13 +1,14 @@
 use thiserror::Error;
 
-use organiccpualn::prompt_envelope::NeurorightsBoundPromptEnvelope;
-use neurorights_core::{guard_prompt_tool, NeurorightsPolicyDocument, ToolCapability, NeurorightsViolation};
+use neurorights_core::{check_prompt, NeurorightsBoundPromptEnvelope, NeurorightsPolicyDocument, ToolCapability};
+
+pub use neurorights_core::{NeurorightsViolation, NeurorightsViolations};
 
 /// Backend-specific error wrapper.
 #[derive(Debug, Error)]
 pub enum FirewallError {
     #[error("neurorights violation: {0}")]
-    Neurorights(#[from] NeurorightsViolation),
+    Neurorights(#[from] NeurorightsViolations),
 
     #[error("backend error: {0}")]
     Backend(String),
@@ -26,7 +27,7 @@ pub trait NeurorightsSafeBackend {
 
 /// Main firewall entrypoint:
 /// - loads neurorights policy (caller supplies doc)
-/// - runs guard_prompt_tool
+/// - runs the full check set for `tool`
 /// - forwards envelope to backend only if allowed.
 pub fn process_prompt<B: NeurorightsSafeBackend>(
     backend: &B,
@@ -35,7 +36,7 @@ pub fn process_prompt<B: NeurorightsSafeBackend>(
     tool: &ToolCapability,
 ) -> Result<B::Response, FirewallError> {
     // Enforce neurorights at type level.
-    guard_prompt_tool(env, policy, tool)?;
+    NeurorightsViolations::into_result(check_prompt(env, policy, Some(tool)))?;
 
     // If we reach here, the request is neurorights-clean for this tool.
     backend
@@ -43,15 +44,8 @@ pub fn process_prompt<B: NeurorightsSafeBackend>(
         .map_err(FirewallError::Backend)
 }
 
-#[derive(Debug)]
-pub enum NeurorightsViolation {
-    MissingPolicy,
-    MentalPrivacyViolation,
-    ForbiddenDecisionUse(String),
-    InnerStateScoringForbidden,
-    CoercionForbidden,
-}
-
+/// The neurorights firewall for one subject/session policy. Every entry
+/// point runs `neurorights_core::check_prompt` and reports all violations.
 pub struct NeurorightsFirewall {
     /// Loaded neurorights policy for this subject/session.
     pub policy: NeurorightsPolicyDocument,
@@ -62,38 +56,34 @@ impl NeurorightsFirewall {
         Self { policy }
     }
 
+    /// Every violation for a request. `tool` is the capability about to be
+    /// invoked, if any; the cybostate is the envelope's own.
+    pub fn check(&self, env: &NeurorightsBoundPromptEnvelope, tool: Option<&ToolCapability>) -> Vec<NeurorightsViolation> {
+        check_prompt(env, &self.policy, tool)
+    }
+
     /// Hard gate; any violation returns Err and must abort the pipeline.
-    pub fn validate_envelope(
+    pub fn validate(
         &self,
         env: &NeurorightsBoundPromptEnvelope,
-    ) -> Result<(), NeurorightsViolation> {
-        // 1. Mental privacy: forbid inner-state scoring if policy says so.
-        if self.policy.dreamstate.dreamsensitive
-            && env.domain_tags.iter().any(|t| t == "dreamstate")
-            && self.policy.dreamstate.forbid_inner_state_scoring
-        {
-            return Err(NeurorightsViolation::InnerStateScoringForbidden);
-        }
-
-        // 2. Coercion / forbidden domains: employment, credit, etc.
-        for tag in &env.domain_tags {
-            if self.policy
-                .dreamstate
-                .forbid_decision_use
-                .iter()
-                .any(|forbidden| forbidden == tag)
-            {
-                return Err(NeurorightsViolation::ForbiddenDecisionUse(tag.clone()));
-            }
-        }
+        tool: Option<&ToolCapability>,
+    ) -> Result<(), NeurorightsViolations> {
+        NeurorightsViolations::into_result(self.check(env, tool))
+    }
 
-        // 3. Cybostate / actuation: ActuationForbidden never yields control outputs.
-        if matches!(env.cybostate, CybostateClass::ActuationForbidden)
-            && env.allowed_tools.iter().any(|t| t == "actuator")
-        {
-            return Err(NeurorightsViolation::CoercionForbidden);
-        }
+    /// `validate` before any tool is chosen.
+    pub fn validate_envelope(&self, env: &NeurorightsBoundPromptEnvelope) -> Result<(), NeurorightsViolations> {
+        self.validate(env, None)
+    }
 
-        Ok(())
+    /// `validate`, then hand the envelope to `backend`.
+    pub fn process<B: NeurorightsSafeBackend>(
+        &self,
+        backend: &B,
+        env: &NeurorightsBoundPromptEnvelope,
+        tool: &ToolCapability,
+    ) -> Result<B::Response, FirewallError> {
+        self.validate(env, Some(tool))?;
+        backend.handle_envelope(env).map_err(FirewallError::Backend)
     }
 }
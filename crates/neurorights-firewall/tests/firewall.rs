@@ -0,0 +1,122 @@
+use std::cell::Cell;
+
+use neurorights_core::{
+    CybostateClass, DreamStateSlice, NeurorightsBoundPromptEnvelope, NeurorightsPolicyDocument, ToolCapability,
+    ACTUATOR_TOOL_ID,
+};
+use neurorights_firewall::{process_prompt, FirewallError, NeurorightsFirewall, NeurorightsSafeBackend};
+
+fn policy() -> NeurorightsPolicyDocument {
+    NeurorightsPolicyDocument {
+        policyid: "bostrom-neurorights-v1".to_string(),
+        subjectid: "bostrom1".to_string(),
+        mentalprivacy: true,
+        mentalintegrity: true,
+        cognitiveliberty: true,
+        dreamstate: Some(DreamStateSlice {
+            dreamsensitive: true,
+            forbiddecisionuse: vec!["employment".to_string()],
+            forgetslahours: 48,
+            noncommercial: true,
+            soulnontradeable: true,
+        }),
+        ecolimit: None,
+    }
+}
+
+fn envelope(domains: &[&str], tools: &[&str], cybostate: CybostateClass) -> NeurorightsBoundPromptEnvelope {
+    NeurorightsBoundPromptEnvelope {
+        subject_id: "bostrom1".to_string(),
+        neurorights_profile_id: "bostrom-neurorights-v1".to_string(),
+        roh_model_id: "bostrom-rohmodel-v1".to_string(),
+        domain_tags: domains.iter().map(|d| d.to_string()).collect(),
+        allowed_tools: tools.iter().map(|t| t.to_string()).collect(),
+        cybostate,
+        prompt_text: "hello".to_string(),
+    }
+}
+
+fn tool(id: &str) -> ToolCapability {
+    ToolCapability {
+        tool_id: id.to_string(),
+        can_read_inner_state: false,
+        can_score_user: false,
+        can_write_longterm_profile: false,
+        eco_cost_estimate: 0.1,
+    }
+}
+
+/// Counts the envelopes that reach it.
+#[derive(Default)]
+struct Echo {
+    calls: Cell<u32>,
+}
+
+impl NeurorightsSafeBackend for Echo {
+    type Response = String;
+
+    fn handle_envelope(&self, envelope: &NeurorightsBoundPromptEnvelope) -> Result<String, String> {
+        self.calls.set(self.calls.get() + 1);
+        Ok(envelope.prompt_text.clone())
+    }
+}
+
+#[test]
+fn validate_envelope_uses_the_envelope_cybostate() {
+    let firewall = NeurorightsFirewall::from_policy(policy());
+    let tools = ["writer", ACTUATOR_TOOL_ID];
+
+    for cybostate in [CybostateClass::RetrievalOnly, CybostateClass::ResearchReady, CybostateClass::GovernanceReady] {
+        assert!(firewall.validate_envelope(&envelope(&["devtools"], &tools, cybostate)).is_ok());
+    }
+    let err = firewall
+        .validate_envelope(&envelope(&["devtools"], &tools, CybostateClass::ActuationForbidden))
+        .unwrap_err();
+    assert_eq!(err.codes(), ["NR_ACTUATION_FORBIDDEN"]);
+
+    // Without an actuator the forbidden state has nothing to refuse.
+    assert!(firewall
+        .validate_envelope(&envelope(&["devtools"], &["writer"], CybostateClass::ActuationForbidden))
+        .is_ok());
+}
+
+#[test]
+fn validate_envelope_reports_every_violation_and_fails_closed() {
+    let firewall = NeurorightsFirewall::from_policy(policy());
+    let env = envelope(&["employment"], &[ACTUATOR_TOOL_ID], CybostateClass::ActuationForbidden);
+    let err = firewall.validate_envelope(&env).unwrap_err();
+    assert_eq!(err.codes(), ["NR_FORBIDDEN_DECISION_USE", "NR_ACTUATION_FORBIDDEN"]);
+    assert_eq!(firewall.check(&env, None), err.0);
+
+    let mut incomplete = policy();
+    incomplete.dreamstate = None;
+    let firewall = NeurorightsFirewall::from_policy(incomplete);
+    let clean = envelope(&["devtools"], &["writer"], CybostateClass::ResearchReady);
+    assert_eq!(firewall.validate_envelope(&clean).unwrap_err().codes(), ["NR_MISSING_POLICY"]);
+}
+
+#[test]
+fn only_clean_requests_reach_the_backend() {
+    let firewall = NeurorightsFirewall::from_policy(policy());
+    let backend = Echo::default();
+    let env = envelope(&["devtools"], &["writer"], CybostateClass::ResearchReady);
+
+    assert_eq!(firewall.process(&backend, &env, &tool("writer")).unwrap(), "hello");
+    assert_eq!(process_prompt(&backend, &env, &policy(), &tool("writer")).unwrap(), "hello");
+    assert_eq!(backend.calls.get(), 2);
+
+    let heavy = ToolCapability {
+        eco_cost_estimate: 0.9,
+        ..tool("browser")
+    };
+    match firewall.process(&backend, &env, &heavy) {
+        Err(FirewallError::Neurorights(v)) => assert_eq!(v.codes(), ["NR_TOOL_NOT_ALLOWED", "NR_ECO_OVER_LIMIT"]),
+        other => panic!("{other:?}"),
+    }
+    let forbidden = envelope(&["devtools"], &[ACTUATOR_TOOL_ID], CybostateClass::ActuationForbidden);
+    match process_prompt(&backend, &forbidden, &policy(), &tool(ACTUATOR_TOOL_ID)) {
+        Err(FirewallError::Neurorights(v)) => assert_eq!(v.codes(), ["NR_ACTUATION_FORBIDDEN"]),
+        other => panic!("{other:?}"),
+    }
+    assert_eq!(backend.calls.get(), 2);
+}
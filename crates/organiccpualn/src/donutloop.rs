@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+/// Legacy format: import into the canonical `donutloop` crate with
+/// `donutloop::legacy::organiccpualn::convert`.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DonutloopEntry {
     pub entry_id: String,
@@ -1,5 +1,11 @@
+pub mod evolvestream;
+pub mod prompt_envelope;
+pub mod roh_model;
+pub mod roh_registry;
+pub mod rohmodel;
+
 pub trait AlnBackedProfile {
-    fn load_from_aln(path: &str) -> Result<Self, Error>
+    fn load_from_aln(path: &str) -> anyhow::Result<Self>
     where
         Self: Sized;
     fn roh(&self) -> f32;
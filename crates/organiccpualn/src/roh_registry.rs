@@ -0,0 +1,655 @@
+//! RoH model registry keyed by `roh_model_id`.
+//!
+//! Every `.rohmodel.aln` flavour in the tree loads into one `RohModelDef`:
+//! named axes with units and ranges, a ceiling, and a pluggable model kind
+//! (weighted-linear, polytope, piecewise). Guards resolve their model here by
+//! the id their envelope carries and bind the axis order of the vectors they
+//! build, so a shape mismatch is caught when the guard is wired up rather than
+//! silently zipped away at evaluation time.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::prompt_envelope::NeurorightsBoundPromptEnvelope;
+use crate::roh_model::RohModel;
+use crate::rohmodel::RohModelShard;
+
+/// No registered model may declare a ceiling above the global RoH invariant.
+pub const ROH_CEILING_MAX: f32 = 0.30;
+
+const EPS: f32 = 1e-6;
+
+fn default_unit() -> String {
+    "normalized".to_string()
+}
+
+fn unit_range() -> (f32, f32) {
+    (0.0, 1.0)
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RohAxisSpec {
+    pub name: String,
+    #[serde(default = "default_unit")]
+    pub unit: String,
+    #[serde(default = "unit_range")]
+    pub range: (f32, f32),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RohModelKind {
+    /// `bias + Σ wᵢ·xᵢ`, clamped to `[0, 1]`.
+    WeightedLinear {
+        weights: Vec<f32>,
+        #[serde(default)]
+        bias: f32,
+    },
+    /// `ceiling · maxᵢ (aᵢ·x / bᵢ)`, clamped to `[0, 1]`: the RoH reaches the
+    /// ceiling exactly on the boundary of `A·x ≤ b`, so "inside the polytope"
+    /// and "under the ceiling" are the same test.
+    Polytope { a: Vec<Vec<f32>>, b: Vec<f32> },
+    /// `bias + Σ fᵢ(xᵢ)`, clamped to `[0, 1]`, where each `fᵢ` interpolates
+    /// linearly between `(x, roh)` breakpoints and is flat outside them.
+    Piecewise {
+        curves: Vec<Vec<(f32, f32)>>,
+        #[serde(default)]
+        bias: f32,
+    },
+}
+
+impl RohModelKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RohModelKind::WeightedLinear { .. } => "weighted_linear",
+            RohModelKind::Polytope { .. } => "polytope",
+            RohModelKind::Piecewise { .. } => "piecewise",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RohModelDef {
+    pub model_id: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    pub roh_ceiling: f32,
+    pub axes: Vec<RohAxisSpec>,
+    #[serde(flatten)]
+    pub kind: RohModelKind,
+}
+
+impl RohModelDef {
+    /// Structural checks run before a model is registered.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let id = &self.model_id;
+        if id.trim().is_empty() {
+            bail!("RoH model has an empty model_id");
+        }
+        if !(self.roh_ceiling > 0.0 && self.roh_ceiling <= ROH_CEILING_MAX + EPS) {
+            bail!(
+                "RoH model {} ceiling {} outside (0, {}]",
+                id,
+                self.roh_ceiling,
+                ROH_CEILING_MAX
+            );
+        }
+        if self.axes.is_empty() {
+            bail!("RoH model {} declares no axes", id);
+        }
+        for (i, axis) in self.axes.iter().enumerate() {
+            if self.axes[..i].iter().any(|a| a.name == axis.name) {
+                bail!("RoH model {} declares axis {} twice", id, axis.name);
+            }
+            let (lo, hi) = axis.range;
+            if !(lo.is_finite() && hi.is_finite() && lo < hi) {
+                bail!("RoH model {} axis {} has invalid range [{}, {}]", id, axis.name, lo, hi);
+            }
+        }
+
+        let dim = self.axes.len();
+        match &self.kind {
+            RohModelKind::WeightedLinear { weights, bias } => {
+                if weights.len() != dim {
+                    bail!("RoH model {} has {} weights for {} axes", id, weights.len(), dim);
+                }
+                if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                    bail!("RoH model {} has a negative or non-finite weight", id);
+                }
+                if weights.iter().sum::<f32>() > 1.0 + EPS {
+                    bail!("RoH model {} weights sum above 1.0", id);
+                }
+                if !bias.is_finite() {
+                    bail!("RoH model {} has a non-finite bias", id);
+                }
+            }
+            RohModelKind::Polytope { a, b } => {
+                if a.is_empty() || a.len() != b.len() {
+                    bail!("RoH model {} has {} rows in A and {} entries in b", id, a.len(), b.len());
+                }
+                for (i, (row, bi)) in a.iter().zip(b).enumerate() {
+                    if row.len() != dim {
+                        bail!("RoH model {} row {} has {} entries for {} axes", id, i, row.len(), dim);
+                    }
+                    if row.iter().any(|v| !v.is_finite()) {
+                        bail!("RoH model {} row {} is not finite", id, i);
+                    }
+                    // The RoH scale is relative to the origin, which must sit
+                    // strictly inside every half-space.
+                    if !(bi.is_finite() && *bi > 0.0) {
+                        bail!("RoH model {} row {} needs b > 0, got {}", id, i, bi);
+                    }
+                }
+            }
+            RohModelKind::Piecewise { curves, bias } => {
+                if curves.len() != dim {
+                    bail!("RoH model {} has {} curves for {} axes", id, curves.len(), dim);
+                }
+                for (axis, curve) in self.axes.iter().zip(curves) {
+                    if curve.len() < 2 {
+                        bail!("RoH model {} curve for {} needs at least two breakpoints", id, axis.name);
+                    }
+                    if curve.windows(2).any(|w| w[1].0 <= w[0].0) {
+                        bail!("RoH model {} curve for {} is not strictly increasing in x", id, axis.name);
+                    }
+                    if curve.iter().any(|(x, y)| !x.is_finite() || !y.is_finite() || *y < 0.0) {
+                        bail!("RoH model {} curve for {} has a negative or non-finite point", id, axis.name);
+                    }
+                }
+                if !bias.is_finite() {
+                    bail!("RoH model {} has a non-finite bias", id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn axis_names(&self) -> impl Iterator<Item = &str> {
+        self.axes.iter().map(|a| a.name.as_str())
+    }
+
+    /// Check a vector in this model's axis order: right length, finite, and
+    /// inside every axis range.
+    pub fn check_input(&self, x: &[f32]) -> anyhow::Result<()> {
+        if x.len() != self.axes.len() {
+            bail!(
+                "RoH model {} expects {} axes, got a vector of {}",
+                self.model_id,
+                self.axes.len(),
+                x.len()
+            );
+        }
+        for (axis, v) in self.axes.iter().zip(x) {
+            let (lo, hi) = axis.range;
+            if !v.is_finite() || *v < lo - EPS || *v > hi + EPS {
+                bail!(
+                    "RoH model {} axis {} value {} outside [{}, {}] {}",
+                    self.model_id,
+                    axis.name,
+                    v,
+                    lo,
+                    hi,
+                    axis.unit
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// RoH of a vector in this model's axis order.
+    pub fn compute(&self, x: &[f32]) -> anyhow::Result<f32> {
+        self.check_input(x)?;
+        let roh = match &self.kind {
+            RohModelKind::WeightedLinear { weights, bias } => {
+                bias + weights.iter().zip(x).map(|(w, v)| w * v).sum::<f32>()
+            }
+            RohModelKind::Polytope { a, b } => {
+                let load = a
+                    .iter()
+                    .zip(b)
+                    .map(|(row, bi)| row.iter().zip(x).map(|(ai, v)| ai * v).sum::<f32>() / bi)
+                    .fold(0.0f32, f32::max);
+                self.roh_ceiling * load
+            }
+            RohModelKind::Piecewise { curves, bias } => {
+                bias + curves.iter().zip(x).map(|(c, v)| interpolate(c, *v)).sum::<f32>()
+            }
+        };
+        Ok(roh.clamp(0.0, 1.0))
+    }
+
+    /// RoH of a state given by axis name. Missing and unknown names both fail.
+    pub fn compute_named(&self, state: &HashMap<String, f32>) -> anyhow::Result<f32> {
+        if let Some(name) = state.keys().find(|k| !self.axes.iter().any(|a| &a.name == *k)) {
+            bail!("RoH model {} has no axis {}", self.model_id, name);
+        }
+        let x = self
+            .axes
+            .iter()
+            .map(|a| {
+                state
+                    .get(&a.name)
+                    .copied()
+                    .with_context(|| format!("RoH model {} missing axis {}", self.model_id, a.name))
+            })
+            .collect::<anyhow::Result<Vec<f32>>>()?;
+        self.compute(&x)
+    }
+
+    /// Lift the positional `organiccpualn::roh_model::RohModel`. Its axes
+    /// carry no units or ranges, so they default to normalized `[0, 1]`.
+    pub fn from_linear(model: &RohModel) -> Self {
+        Self {
+            model_id: model.model_id.clone(),
+            version: model.version.clone(),
+            subject_id: None,
+            roh_ceiling: model.roh_ceiling,
+            axes: model
+                .axes
+                .iter()
+                .map(|name| RohAxisSpec {
+                    name: name.clone(),
+                    unit: default_unit(),
+                    range: unit_range(),
+                })
+                .collect(),
+            kind: RohModelKind::WeightedLinear {
+                weights: model.weights.clone(),
+                bias: model.bias,
+            },
+        }
+    }
+
+    /// Lift a `RohModelShard`; weights are matched to axes by name.
+    pub fn from_shard(shard: &RohModelShard) -> anyhow::Result<Self> {
+        let w = &shard.model.weights;
+        let named = [
+            ("energy_load", w.energy_load),
+            ("thermal_load", w.thermal_load),
+            ("cognitive_load", w.cognitive_load),
+            ("inflammation", w.inflammation),
+            ("eco_impact", w.eco_impact),
+        ];
+        let weights = shard
+            .axes
+            .iter()
+            .map(|axis| {
+                named
+                    .iter()
+                    .find(|(n, _)| *n == axis.name)
+                    .map(|(_, v)| *v)
+                    .with_context(|| format!("RoH shard {} has no weight for axis {}", shard.model.id, axis.name))
+            })
+            .collect::<anyhow::Result<Vec<f32>>>()?;
+        Ok(Self {
+            model_id: shard.model.id.clone(),
+            version: shard.meta.version.clone(),
+            subject_id: Some(shard.meta.subject_id.clone()),
+            roh_ceiling: shard.model.roh_ceiling,
+            axes: shard
+                .axes
+                .iter()
+                .map(|a| RohAxisSpec {
+                    name: a.name.clone(),
+                    unit: default_unit(),
+                    range: a.range,
+                })
+                .collect(),
+            kind: RohModelKind::WeightedLinear { weights, bias: 0.0 },
+        })
+    }
+}
+
+fn interpolate(curve: &[(f32, f32)], x: f32) -> f32 {
+    let (first, last) = (curve[0], curve[curve.len() - 1]);
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+    for w in curve.windows(2) {
+        let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+        if x <= x1 {
+            return y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        }
+    }
+    last.1
+}
+
+/// A model resolved for one guard, with the guard's vector layout checked
+/// against the model's axes once, up front.
+#[derive(Clone, Debug)]
+pub struct RohBinding {
+    model: Arc<RohModelDef>,
+    /// `order[i]` is the model axis fed by the guard's i-th component.
+    order: Vec<usize>,
+}
+
+impl RohBinding {
+    pub fn model(&self) -> &RohModelDef {
+        &self.model
+    }
+
+    pub fn roh_ceiling(&self) -> f32 {
+        self.model.roh_ceiling
+    }
+
+    /// RoH of a vector laid out in the order the guard bound with.
+    pub fn compute(&self, components: &[f32]) -> anyhow::Result<f32> {
+        if components.len() != self.order.len() {
+            bail!(
+                "RoH binding for {} expects {} components, got {}",
+                self.model.model_id,
+                self.order.len(),
+                components.len()
+            );
+        }
+        let mut x = vec![0.0; self.order.len()];
+        for (v, &j) in components.iter().zip(&self.order) {
+            x[j] = *v;
+        }
+        self.model.compute(&x)
+    }
+
+    pub fn within_ceiling(&self, components: &[f32]) -> anyhow::Result<bool> {
+        Ok(self.compute(components)? <= self.roh_ceiling() + EPS)
+    }
+}
+
+/// All RoH models known to this host, keyed by `model_id`.
+#[derive(Clone, Debug, Default)]
+pub struct RohRegistry {
+    models: BTreeMap<String, Arc<RohModelDef>>,
+}
+
+impl RohRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `*.rohmodel.aln` under `dir` (not recursive).
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> anyhow::Result<Self> {
+        let dir = dir.as_ref();
+        let mut paths: Vec<_> = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read RoH model directory {}", dir.display()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.to_string_lossy().ends_with(".rohmodel.aln"))
+            .collect();
+        paths.sort();
+        let mut registry = Self::new();
+        for path in paths {
+            registry.load_file(&path)?;
+        }
+        Ok(registry)
+    }
+
+    /// Load one shard and register the model(s) it defines; returns their ids.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<Vec<String>> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read RoH model shard {}", path.display()))?;
+        let defs = parse_shard(&text).with_context(|| format!("Invalid RoH model shard {}", path.display()))?;
+        let mut ids = Vec::with_capacity(defs.len());
+        for def in defs {
+            ids.push(def.model_id.clone());
+            self.insert(def)?;
+        }
+        Ok(ids)
+    }
+
+    /// Validate and register; a second model with the same id is rejected.
+    pub fn insert(&mut self, def: RohModelDef) -> anyhow::Result<()> {
+        def.validate()?;
+        if self.models.contains_key(&def.model_id) {
+            bail!("RoH model {} is already registered", def.model_id);
+        }
+        self.models.insert(def.model_id.clone(), Arc::new(def));
+        Ok(())
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.models.keys().map(|k| k.as_str())
+    }
+
+    pub fn get(&self, model_id: &str) -> anyhow::Result<Arc<RohModelDef>> {
+        self.models
+            .get(model_id)
+            .cloned()
+            .with_context(|| format!("Unknown roh_model_id {}", model_id))
+    }
+
+    /// The model named by an envelope's `roh_model_id`.
+    pub fn resolve(&self, env: &NeurorightsBoundPromptEnvelope) -> anyhow::Result<Arc<RohModelDef>> {
+        self.get(&env.roh_model_id)
+    }
+
+    /// Bind a guard that builds vectors with components named `axes`, in
+    /// that order. The names must be exactly the model's axes, in any order.
+    pub fn bind(&self, model_id: &str, axes: &[&str]) -> anyhow::Result<RohBinding> {
+        let model = self.get(model_id)?;
+        let mut order = Vec::with_capacity(axes.len());
+        for name in axes {
+            let j = model
+                .axes
+                .iter()
+                .position(|a| a.name == *name)
+                .with_context(|| format!("RoH model {} has no axis {}", model_id, name))?;
+            if order.contains(&j) {
+                bail!("Axis {} bound twice for RoH model {}", name, model_id);
+            }
+            order.push(j);
+        }
+        if order.len() != model.axes.len() {
+            let missing: Vec<&str> = model
+                .axis_names()
+                .enumerate()
+                .filter(|(j, _)| !order.contains(j))
+                .map(|(_, n)| n)
+                .collect();
+            bail!("Binding for RoH model {} does not supply axes {:?}", model_id, missing);
+        }
+        Ok(RohBinding { model, order })
+    }
+}
+
+/// One `.rohmodel.aln` may define a single model or, for polytope mode
+/// shards, one model per mode (`<model_id>/<mode_name>`).
+pub fn parse_shard(text: &str) -> anyhow::Result<Vec<RohModelDef>> {
+    let value = match serde_yaml::from_str::<Value>(text) {
+        Ok(v @ Value::Mapping(_)) => v,
+        // The colon-less indented flavour parses as one plain scalar.
+        _ => parse_indented(text)?,
+    };
+
+    if lookup(&value, "kind").is_some() {
+        let def: RohModelDef = serde_yaml::from_value(value)?;
+        return Ok(vec![def]);
+    }
+    if lookup(&value, "modes").is_some() {
+        return parse_mode_shard(&value);
+    }
+    parse_legacy_linear(&value).map(|d| vec![d])
+}
+
+#[derive(Deserialize)]
+#[allow(non_snake_case)]
+struct ModeEntry {
+    mode_name: String,
+    A: Vec<Vec<f32>>,
+    b: Vec<f32>,
+    roh_ceiling: f32,
+}
+
+/// The sovereign-core `RohModel` layout (`modes: [{mode_name, A, b,
+/// roh_ceiling}]`), which must also name its `model_id` and `axes` to be
+/// registered.
+fn parse_mode_shard(value: &Value) -> anyhow::Result<Vec<RohModelDef>> {
+    let model_id = lookup_str(value, &["model_id"]).context("polytope mode shard needs a model_id")?;
+    let axes: Vec<RohAxisSpec> = serde_yaml::from_value(
+        lookup(value, "axes")
+            .cloned()
+            .context("polytope mode shard needs named axes")?,
+    )?;
+    let modes: Vec<ModeEntry> = serde_yaml::from_value(lookup(value, "modes").cloned().unwrap_or_default())?;
+    let version = lookup_str(value, &["version"]).unwrap_or_default();
+    let subject_id = lookup_str(value, &["subject_id"]);
+    Ok(modes
+        .into_iter()
+        .map(|m| RohModelDef {
+            model_id: format!("{}/{}", model_id, m.mode_name),
+            version: version.clone(),
+            subject_id: subject_id.clone(),
+            roh_ceiling: m.roh_ceiling,
+            axes: axes.clone(),
+            kind: RohModelKind::Polytope { a: m.A, b: m.b },
+        })
+        .collect())
+}
+
+/// The weighted-sum shards: `RohModelShard` (weights under `model.weights`)
+/// and the per-axis `weight` flavours, in either YAML or indented form.
+fn parse_legacy_linear(value: &Value) -> anyhow::Result<RohModelDef> {
+    let meta = lookup(value, "meta");
+    let model = lookup(value, "model");
+    let model_id = meta
+        .and_then(|m| lookup_str(m, &["model_id", "rohmodel_id"]))
+        .or_else(|| model.and_then(|m| lookup_str(m, &["id"])))
+        .context("RoH shard names no model id")?;
+    let roh_ceiling = ["model", "global", "invariants"]
+        .iter()
+        .filter_map(|s| lookup(value, s))
+        .find_map(|s| lookup_f32(s, "roh_ceiling"))
+        .with_context(|| format!("RoH shard {} declares no roh_ceiling", model_id))?;
+    let bias = model.and_then(|m| lookup_f32(m, "bias")).unwrap_or(0.0);
+    let shared_weights = model.and_then(|m| lookup(m, "weights"));
+
+    let entries = lookup(value, "axes")
+        .and_then(Value::as_sequence)
+        .with_context(|| format!("RoH shard {} has no axes list", model_id))?;
+    let mut axes = Vec::with_capacity(entries.len());
+    let mut weights = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let name = lookup_str(entry, &["name"]).with_context(|| format!("RoH shard {} has an unnamed axis", model_id))?;
+        let range = match lookup(entry, "range").and_then(Value::as_sequence) {
+            Some(r) if r.len() == 2 => (as_f32(&r[0]).unwrap_or(0.0), as_f32(&r[1]).unwrap_or(1.0)),
+            _ => (
+                lookup_f32(entry, "min").unwrap_or(0.0),
+                lookup_f32(entry, "max").unwrap_or(1.0),
+            ),
+        };
+        let weight = lookup_f32(entry, "weight")
+            .or_else(|| shared_weights.and_then(|w| lookup_f32(w, &name)))
+            .with_context(|| format!("RoH shard {} has no weight for axis {}", model_id, name))?;
+        axes.push(RohAxisSpec {
+            name,
+            unit: lookup_str(entry, &["unit"]).unwrap_or_else(default_unit),
+            range,
+        });
+        weights.push(weight);
+    }
+
+    Ok(RohModelDef {
+        model_id,
+        version: meta.and_then(|m| lookup_str(m, &["version"])).unwrap_or_default(),
+        subject_id: meta.and_then(|m| lookup_str(m, &["subject_id"])),
+        roh_ceiling,
+        axes,
+        kind: RohModelKind::WeightedLinear { weights, bias },
+    })
+}
+
+/// Turn the colon-less shard layout (`section` lines, then indented
+/// `key value` lines, `- ` starting a list item) into a YAML value.
+fn parse_indented(text: &str) -> anyhow::Result<Value> {
+    let mut root = serde_yaml::Mapping::new();
+    let mut section: Option<String> = None;
+    for (lineno, raw) in text.lines().enumerate() {
+        let line = raw.trim_end();
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            let name = line.trim();
+            root.insert(Value::String(name.to_string()), Value::Null);
+            section = Some(name.to_string());
+            continue;
+        }
+        let name = section
+            .clone()
+            .with_context(|| format!("line {}: indented entry outside a section", lineno + 1))?;
+        let slot = root
+            .get_mut(Value::String(name))
+            .expect("section inserted when opened");
+
+        let mut body = line.trim();
+        let new_item = body.starts_with("- ");
+        if new_item {
+            body = body[2..].trim_start();
+        }
+        let (key, rest) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+        let parsed = match serde_yaml::from_str::<Value>(rest.trim()) {
+            Ok(v @ (Value::Bool(_) | Value::Number(_) | Value::String(_))) => v,
+            _ => Value::String(rest.trim().to_string()),
+        };
+        let key = Value::String(key.to_string());
+
+        if new_item {
+            if slot.is_null() {
+                *slot = Value::Sequence(Vec::new());
+            }
+            let seq = slot
+                .as_sequence_mut()
+                .with_context(|| format!("line {}: list item inside a mapping section", lineno + 1))?;
+            seq.push(Value::Mapping(serde_yaml::Mapping::new()));
+        } else if slot.is_null() {
+            *slot = Value::Mapping(serde_yaml::Mapping::new());
+        }
+        let target = match slot {
+            Value::Sequence(seq) => seq.last_mut().and_then(Value::as_mapping_mut),
+            Value::Mapping(map) => Some(map),
+            _ => None,
+        }
+        .with_context(|| format!("line {}: malformed entry", lineno + 1))?;
+        target.insert(key, parsed);
+    }
+    Ok(Value::Mapping(root))
+}
+
+/// Keys are matched ignoring case and underscores (`subjectid` = `subject_id`).
+fn normalize_key(key: &str) -> String {
+    key.chars().filter(|c| *c != '_').flat_map(char::to_lowercase).collect()
+}
+
+fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    let want = normalize_key(key);
+    value
+        .as_mapping()?
+        .iter()
+        .find(|(k, _)| k.as_str().is_some_and(|k| normalize_key(k) == want))
+        .map(|(_, v)| v)
+}
+
+fn lookup_str(value: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|k| match lookup(value, k)? {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+fn lookup_f32(value: &Value, key: &str) -> Option<f32> {
+    lookup(value, key).and_then(as_f32)
+}
+
+fn as_f32(value: &Value) -> Option<f32> {
+    value.as_f64().map(|v| v as f32)
+}
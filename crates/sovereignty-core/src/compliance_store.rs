@@ -0,0 +1,214 @@
+#![forbid(unsafe_code)]
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::journal::GENESIS_HASH;
+use crate::ComplianceBits;
+
+/// One committed change: the full bit set after it, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceRecord {
+    pub bits: ComplianceBits,
+    pub reason: String,
+    pub set_at: DateTime<Utc>,
+    pub prev_hash: String,
+    /// sha256 over (bits, reason, set_at, prev_hash).
+    pub hash: String,
+}
+
+#[derive(Serialize)]
+struct Hashed<'a> {
+    bits: ComplianceBits,
+    reason: &'a str,
+    set_at: &'a DateTime<Utc>,
+    prev_hash: &'a str,
+}
+
+impl ComplianceRecord {
+    fn compute_hash(bits: ComplianceBits, reason: &str, set_at: &DateTime<Utc>, prev_hash: &str) -> String {
+        let json = serde_json::to_vec(&Hashed {
+            bits,
+            reason,
+            set_at,
+            prev_hash,
+        })
+        .expect("serialize ComplianceRecord");
+        hex::encode(Sha256::digest(json))
+    }
+}
+
+/// Write-once compliance bits backed by an append-only, hash-chained NDJSON
+/// journal.
+///
+/// Bits only accumulate: `commit` refuses any value that would clear a bit,
+/// and `open` refuses a journal with an edited, reordered or missing record,
+/// or in which a later record drops a bit an earlier one set.
+///
+/// The chain cannot show that records were cut off the end of the file or
+/// that the file was deleted; both read as fewer bits. `SovereigntyCore`
+/// catches that by checking the store against the bits its signed audit
+/// journal recorded.
+pub struct ComplianceStore {
+    path: Option<PathBuf>,
+    bits: ComplianceBits,
+    records: Vec<ComplianceRecord>,
+}
+
+impl ComplianceStore {
+    /// Store that lives only as long as the process (tests, dry runs).
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            bits: ComplianceBits::empty(),
+            records: Vec::new(),
+        }
+    }
+
+    /// Load the journal at `path`; a missing file is an empty store. A cut-off
+    /// final line (crash mid-append) is ignored.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut store = Self {
+            path: Some(path.clone()),
+            bits: ComplianceBits::empty(),
+            records: Vec::new(),
+        };
+        if !path.exists() {
+            return Ok(store);
+        }
+
+        let data = fs::read_to_string(&path)?;
+        let complete = data.ends_with('\n');
+        let lines: Vec<&str> = data.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: ComplianceRecord = match serde_json::from_str(line) {
+                Ok(r) => r,
+                Err(_) if i + 1 == lines.len() && !complete => break,
+                Err(e) => return Err(invalid(format!("{}:{}: {}", path.display(), i + 1, e))),
+            };
+            let prev = store.records.last().map_or(GENESIS_HASH, |r| r.hash.as_str());
+            if record.prev_hash != prev {
+                return Err(invalid(format!("{}:{}: hash chain broken", path.display(), i + 1)));
+            }
+            if ComplianceRecord::compute_hash(record.bits, &record.reason, &record.set_at, &record.prev_hash)
+                != record.hash
+            {
+                return Err(invalid(format!("{}:{}: hash mismatch", path.display(), i + 1)));
+            }
+            if !record.bits.contains(store.bits) {
+                return Err(invalid(format!(
+                    "{}:{}: record clears compliance bits {:?}",
+                    path.display(),
+                    i + 1,
+                    store.bits - record.bits
+                )));
+            }
+            store.bits = record.bits;
+            store.records.push(record);
+        }
+        if !complete && !data.is_empty() {
+            // Drop a cut-off tail so the next commit does not extend it.
+            let keep = data.rfind('\n').map_or(0, |i| i + 1);
+            OpenOptions::new().write(true).open(&path)?.set_len(keep as u64)?;
+        }
+        Ok(store)
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Bits committed so far.
+    pub fn bits(&self) -> ComplianceBits {
+        self.bits
+    }
+
+    pub fn records(&self) -> &[ComplianceRecord] {
+        &self.records
+    }
+
+    /// Make `bits` the committed set. Fails if it would clear any committed
+    /// bit; a no-op if nothing new is set. The record is fsynced before the
+    /// in-memory value changes.
+    pub fn commit(&mut self, bits: ComplianceBits, reason: &str) -> io::Result<ComplianceBits> {
+        if !bits.contains(self.bits) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("compliance bits are write-once; refusing to clear {:?}", self.bits - bits),
+            ));
+        }
+        if bits == self.bits {
+            return Ok(self.bits);
+        }
+
+        let set_at = Utc::now();
+        let prev_hash = self
+            .records
+            .last()
+            .map_or(GENESIS_HASH, |r| r.hash.as_str())
+            .to_string();
+        let record = ComplianceRecord {
+            bits,
+            reason: reason.to_string(),
+            hash: ComplianceRecord::compute_hash(bits, reason, &set_at, &prev_hash),
+            set_at,
+            prev_hash,
+        };
+        if let Some(path) = &self.path {
+            let json = serde_json::to_string(&record).expect("serialize ComplianceRecord");
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            let mut writer = BufWriter::new(file);
+            writer.write_all(json.as_bytes())?;
+            writer.write_all(b"\n")?;
+            writer.flush()?;
+            writer.get_ref().sync_all()?;
+        }
+        self.bits = bits;
+        self.records.push(record);
+        Ok(bits)
+    }
+
+    /// Add `bit` to the committed set.
+    pub fn set(&mut self, bit: ComplianceBits, reason: &str) -> io::Result<ComplianceBits> {
+        self.commit(self.bits | bit, reason)
+    }
+
+    /// Undo the latest `commit` when the decision behind it could not be
+    /// journaled. The record is cut from the end of the file only if it is
+    /// still the file's last line.
+    pub(crate) fn uncommit_last(&mut self) -> io::Result<()> {
+        let Some(last) = self.records.last() else {
+            return Ok(());
+        };
+        if let Some(path) = &self.path {
+            let mut line = serde_json::to_vec(last).expect("serialize ComplianceRecord");
+            line.push(b'\n');
+            let data = fs::read(path)?;
+            if !data.ends_with(&line) {
+                return Err(invalid(format!(
+                    "{}: last record is not the commit being undone",
+                    path.display()
+                )));
+            }
+            let file = OpenOptions::new().write(true).open(path)?;
+            file.set_len((data.len() - line.len()) as u64)?;
+            file.sync_all()?;
+        }
+        self.records.pop();
+        self.bits = self.records.last().map_or(ComplianceBits::empty(), |r| r.bits);
+        Ok(())
+    }
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
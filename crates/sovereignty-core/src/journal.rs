@@ -0,0 +1,223 @@
+#![forbid(unsafe_code)]
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{AuditEntry, DecisionOutcome, EvolveToken};
+
+/// prev_hash of the first record.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Everything SovereigntyCore needs to rebuild its counters on restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum JournalEvent {
+    /// One evaluate_update / set_compliance_bit outcome. `auto` and
+    /// `consumed_token` are the effects applied when the entry is Allowed.
+    Decision {
+        module: String,
+        auto: bool,
+        consumed_token: Option<String>,
+        entry: AuditEntry,
+    },
+    TokenRegistered {
+        token: EvolveToken,
+    },
+    TokenRevoked {
+        token_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub seq: u64,
+    pub recorded_at: DateTime<Utc>,
+    pub event: JournalEvent,
+    pub prev_hash: String,
+    /// sha256 over (seq, recorded_at, event, prev_hash).
+    pub hash: String,
+    /// ed25519 over `hash`, hex.
+    pub signature: String,
+}
+
+#[derive(Serialize)]
+struct Hashed<'a> {
+    seq: u64,
+    recorded_at: &'a DateTime<Utc>,
+    event: &'a JournalEvent,
+    prev_hash: &'a str,
+}
+
+impl JournalRecord {
+    fn compute_hash(seq: u64, recorded_at: &DateTime<Utc>, event: &JournalEvent, prev_hash: &str) -> String {
+        let json = serde_json::to_vec(&Hashed {
+            seq,
+            recorded_at,
+            event,
+            prev_hash,
+        })
+        .expect("serialize JournalEvent");
+        hex::encode(Sha256::digest(json))
+    }
+
+    pub fn module(&self) -> Option<&str> {
+        match &self.event {
+            JournalEvent::Decision { module, .. } => Some(module),
+            _ => None,
+        }
+    }
+
+    pub fn entry(&self) -> Option<&AuditEntry> {
+        match &self.event {
+            JournalEvent::Decision { entry, .. } => Some(entry),
+            _ => None,
+        }
+    }
+}
+
+/// Filter for `AuditJournal::query`; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct JournalQuery {
+    pub proposal_id: Option<String>,
+    pub module: Option<String>,
+    pub decision: Option<DecisionOutcome>,
+    /// Inclusive.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive.
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl JournalQuery {
+    fn matches(&self, record: &JournalRecord) -> bool {
+        let JournalEvent::Decision { module, entry, .. } = &record.event else {
+            return false;
+        };
+        self.proposal_id.as_ref().is_none_or(|p| *p == entry.proposal_id)
+            && self.module.as_ref().is_none_or(|m| m == module)
+            && self.decision.as_ref().is_none_or(|d| *d == entry.decision)
+            && self.since.is_none_or(|t| record.recorded_at >= t)
+            && self.until.is_none_or(|t| record.recorded_at < t)
+    }
+}
+
+/// Append-only, hash-chained, signed NDJSON journal of SovereigntyCore
+/// decisions and token lifecycle events.
+pub struct AuditJournal {
+    path: PathBuf,
+    key: SigningKey,
+    records: Vec<JournalRecord>,
+}
+
+fn invalid(path: &Path, line: usize, msg: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}:{}: {}", path.display(), line, msg),
+    )
+}
+
+/// Load and check every record against `key`. A cut-off final line (crash
+/// mid-append) is ignored; any other break in the chain or signature is an
+/// error.
+pub fn verify(path: &Path, key: &VerifyingKey) -> io::Result<Vec<JournalRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(path)?;
+    let complete = data.ends_with('\n');
+    let lines: Vec<&str> = data.lines().collect();
+    let mut records: Vec<JournalRecord> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: JournalRecord = match serde_json::from_str(line) {
+            Ok(r) => r,
+            Err(_) if i + 1 == lines.len() && !complete => break,
+            Err(e) => return Err(invalid(path, i + 1, e)),
+        };
+        let (expected_seq, expected_prev) = match records.last() {
+            Some(p) => (p.seq + 1, p.hash.as_str()),
+            None => (0, GENESIS_HASH),
+        };
+        if record.seq != expected_seq || record.prev_hash != expected_prev {
+            return Err(invalid(path, i + 1, "hash chain broken"));
+        }
+        let hash = JournalRecord::compute_hash(record.seq, &record.recorded_at, &record.event, &record.prev_hash);
+        if hash != record.hash {
+            return Err(invalid(path, i + 1, "hash mismatch"));
+        }
+        let sig = hex::decode(&record.signature)
+            .ok()
+            .and_then(|b| Signature::from_slice(&b).ok())
+            .ok_or_else(|| invalid(path, i + 1, "malformed signature"))?;
+        key.verify(record.hash.as_bytes(), &sig)
+            .map_err(|_| invalid(path, i + 1, "bad signature"))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+impl AuditJournal {
+    /// Open (or create) the journal at `path`, verifying existing records
+    /// against `key`'s public half. New records are signed with `key`.
+    pub fn open(path: impl AsRef<Path>, key: SigningKey) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let records = verify(&path, &key.verifying_key())?;
+        if path.exists() {
+            // Drop a cut-off tail so the next append does not extend it.
+            let data = fs::read(&path)?;
+            if data.last().is_some_and(|b| *b != b'\n') {
+                let keep = data.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+                OpenOptions::new().write(true).open(&path)?.set_len(keep as u64)?;
+            }
+        }
+        Ok(Self { path, key, records })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn records(&self) -> &[JournalRecord] {
+        &self.records
+    }
+
+    pub fn append(&mut self, event: JournalEvent) -> io::Result<&JournalRecord> {
+        let (seq, prev_hash) = match self.records.last() {
+            Some(p) => (p.seq + 1, p.hash.clone()),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        let recorded_at = Utc::now();
+        let hash = JournalRecord::compute_hash(seq, &recorded_at, &event, &prev_hash);
+        let signature = hex::encode(self.key.sign(hash.as_bytes()).to_bytes());
+        let record = JournalRecord {
+            seq,
+            recorded_at,
+            event,
+            prev_hash,
+            hash,
+            signature,
+        };
+
+        let json = serde_json::to_string(&record).expect("serialize JournalRecord");
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(json.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+
+        self.records.push(record);
+        Ok(self.records.last().expect("just pushed"))
+    }
+
+    /// Decision records matching `q`, oldest first.
+    pub fn query<'a>(&'a self, q: &'a JournalQuery) -> impl Iterator<Item = &'a JournalRecord> + 'a {
+        self.records.iter().filter(move |r| q.matches(r))
+    }
+}
@@ -1,12 +1,19 @@
 #![forbid(unsafe_code)]
 
+pub mod compliance_store;
+pub mod journal;
+
 use std::collections::HashMap;
-use std::time::{Duration, SystemTime};
+use std::fmt;
 
 use bitflags::bitflags;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 
-/// ---------- Compliance bits: write-once, non-reversible ----------
+pub use compliance_store::{ComplianceRecord, ComplianceStore};
+pub use journal::{AuditJournal, JournalEvent, JournalQuery, JournalRecord};
+
+// ---------- Compliance bits: write-once, non-reversible ----------
 
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -29,7 +36,7 @@ bitflags! {
     }
 }
 
-/// ---------- Biophysical invariants ----------
+// ---------- Biophysical invariants ----------
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EEGMathInvariants {
@@ -66,9 +73,9 @@ pub struct BioRegNetConfig {
     pub etot_balance_tolerance: f32,
 }
 
-/// ---------- Biophysical state ----------
+// ---------- Biophysical state ----------
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateVector {
     /// 0–10 subjective or estimated scales
     pub muscular_pain: u8,
@@ -89,7 +96,7 @@ pub trait BiophysicalStateReader: Send + Sync {
     fn read_state(&self) -> StateVector;
 }
 
-/// ---------- Neurorights & evolution policies ----------
+// ---------- Neurorights & evolution policies ----------
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MentalPrivacyPolicy {
@@ -140,6 +147,8 @@ pub struct ModePolicy {
     pub requires_evolve_token: bool,
 }
 
+/// Field names are the mode names used in policy documents.
+#[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModesConfig {
     pub CONSERVATIVE: ModePolicy,
@@ -194,7 +203,7 @@ pub struct EvolutionPolicyDocument {
     pub integration_depth: IntegrationDepth,
 }
 
-/// ---------- EVOLVE token ----------
+// ---------- EVOLVE token ----------
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhysioGuard {
@@ -202,20 +211,144 @@ pub struct PhysioGuard {
     pub emg_fatigue_max: Option<f32>,
 }
 
+/// How many proposals one EVOLVE token may authorise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenUse {
+    /// Consumed by the first Allowed proposal.
+    #[serde(rename = "single_use")]
+    SingleUse,
+    /// Reusable for its whole validity window.
+    #[serde(rename = "multi_use")]
+    MultiUse,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvolveToken {
     pub id: String,
     pub subject_id: String,
+    /// Scopes this token covers; `*` matches any run of characters
+    /// (`"motor.*"`, `"*"`).
     pub scope: Vec<String>,
     pub max_effect_size: f32,
+    /// RFC 3339, inclusive.
     pub valid_from: String,
+    /// RFC 3339, exclusive.
     pub valid_until: String,
     pub physio_guard: Option<PhysioGuard>,
     /// EVOLVE can be revoked only for safety (bio-incompatibility), not to force downgrade.
     pub revocable: bool,
+    /// Tokens written before `uses` existed are single-use.
+    #[serde(default = "single_use")]
+    pub uses: TokenUse,
+}
+
+fn single_use() -> TokenUse {
+    TokenUse::SingleUse
 }
 
-/// ---------- Invariant policy layer ----------
+/// Why an EVOLVE token did not authorise a proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenRejection {
+    Missing,
+    Unknown,
+    /// valid_from / valid_until is not RFC 3339, or the window is empty.
+    MalformedWindow,
+    NotYetValid,
+    Expired,
+    /// Token subject differs from the neurorights document subject.
+    SubjectMismatch { token_subject: String },
+    /// Proposal scope entries no token scope pattern covers.
+    ScopeNotCovered { scopes: Vec<String> },
+    /// Single-use token already spent on an earlier Allowed proposal.
+    AlreadyConsumed,
+}
+
+impl fmt::Display for TokenRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenRejection::Missing => write!(f, "Missing EVOLVE token"),
+            TokenRejection::Unknown => write!(f, "Unknown EVOLVE token"),
+            TokenRejection::MalformedWindow => {
+                write!(f, "EVOLVE token validity window is not a valid RFC 3339 range")
+            }
+            TokenRejection::NotYetValid => write!(f, "EVOLVE token not yet valid"),
+            TokenRejection::Expired => write!(f, "EVOLVE token expired"),
+            TokenRejection::SubjectMismatch { token_subject } => {
+                write!(f, "EVOLVE token bound to other subject {}", token_subject)
+            }
+            TokenRejection::ScopeNotCovered { scopes } => {
+                write!(f, "EVOLVE token scope does not cover {}", scopes.join(", "))
+            }
+            TokenRejection::AlreadyConsumed => write!(f, "Single-use EVOLVE token already consumed"),
+        }
+    }
+}
+
+/// `*` matches any (possibly empty) run of characters.
+fn scope_matches(pattern: &str, scope: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == scope;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if scope.len() < first.len() + last.len() || !scope.starts_with(first) || !scope.ends_with(last) {
+        return false;
+    }
+    let mut rest = &scope[first.len()..scope.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+impl EvolveToken {
+    /// Window, subject and scope checks; effect size, physio guards and
+    /// consumption are checked by SovereigntyCore.
+    pub fn validate(
+        &self,
+        subject_id: &str,
+        scope: &[String],
+        now: DateTime<Utc>,
+    ) -> Vec<TokenRejection> {
+        let mut rejections = Vec::new();
+
+        match (
+            DateTime::parse_from_rfc3339(&self.valid_from),
+            DateTime::parse_from_rfc3339(&self.valid_until),
+        ) {
+            (Ok(from), Ok(until)) if from < until => {
+                if now < from {
+                    rejections.push(TokenRejection::NotYetValid);
+                } else if now >= until {
+                    rejections.push(TokenRejection::Expired);
+                }
+            }
+            _ => rejections.push(TokenRejection::MalformedWindow),
+        }
+
+        if self.subject_id != subject_id {
+            rejections.push(TokenRejection::SubjectMismatch {
+                token_subject: self.subject_id.clone(),
+            });
+        }
+
+        let uncovered: Vec<String> = scope
+            .iter()
+            .filter(|s| !self.scope.iter().any(|p| scope_matches(p, s)))
+            .cloned()
+            .collect();
+        if !uncovered.is_empty() {
+            rejections.push(TokenRejection::ScopeNotCovered { scopes: uncovered });
+        }
+
+        rejections
+    }
+}
+
+// ---------- Invariant policy layer ----------
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NeuroInvariantsPolicy {
@@ -228,9 +361,9 @@ pub struct NeuroInvariantsPolicy {
     pub require_evolve_for_change: bool,
 }
 
-/// ---------- Update proposal & audit ----------
+// ---------- Update proposal & audit ----------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UpdateKind {
     ParamNudge,
     ThresholdShift,
@@ -259,7 +392,7 @@ pub struct UpdateProposal {
     pub requires_evolve: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DecisionOutcome {
     Allowed,
     Rejected,
@@ -271,6 +404,9 @@ pub struct AuditEntry {
     pub evolve_token_id: Option<String>,
     pub decision: DecisionOutcome,
     pub reason: String,
+    /// Token checks that failed, one entry per distinct cause.
+    #[serde(default)]
+    pub token_rejections: Vec<TokenRejection>,
     pub timestamp: String,
     pub state_snapshot: StateVector,
     /// Only safety rollbacks are allowed; capability rollbacks are forbidden by NO_CAPABILITY_ROLLBACK.
@@ -279,7 +415,7 @@ pub struct AuditEntry {
     pub is_forward_evolution: bool,
 }
 
-/// ---------- Sovereignty core ----------
+// ---------- Sovereignty core ----------
 
 pub struct SovereigntyCore<S: BiophysicalStateReader> {
     neurorights: NeurorightsPolicyDocument,
@@ -287,9 +423,21 @@ pub struct SovereigntyCore<S: BiophysicalStateReader> {
     invariants: NeuroInvariantsPolicy,
     state_reader: S,
     evolve_tokens: HashMap<String, EvolveToken>,
-    used_auto_changes: u32,
-    /// Audit trail of compliance bits and decisions.
-    pub compliance_log: Vec<(ComplianceBits, String)>,
+    /// Allowed proposals authorised per token id.
+    token_uses: HashMap<String, u32>,
+    /// When each Allowed auto change happened (current quota period only).
+    auto_changes: Vec<DateTime<Utc>>,
+    /// Auto-change quota window; None counts every auto change ever made.
+    auto_change_period: Option<Duration>,
+    /// Committed compliance bits; authoritative over the state reader.
+    compliance: ComplianceStore,
+    journal: Option<AuditJournal>,
+}
+
+/// Effects an Allowed decision commits once it is journaled.
+struct DecisionEffects {
+    auto: bool,
+    consumed_token: Option<String>,
 }
 
 impl<S: BiophysicalStateReader> SovereigntyCore<S> {
@@ -305,18 +453,186 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
             invariants,
             state_reader,
             evolve_tokens: HashMap::new(),
-            used_auto_changes: 0,
-            compliance_log: vec![],
+            token_uses: HashMap::new(),
+            auto_changes: Vec::new(),
+            auto_change_period: None,
+            compliance: ComplianceStore::in_memory(),
+            journal: None,
+        }
+    }
+
+    /// Journal every AuditEntry and token event to `journal`, first
+    /// replaying it to restore registered tokens, token consumption and the
+    /// auto-change quota. Attach before registering tokens. Fails if the
+    /// compliance store lacks bits the journal saw committed.
+    pub fn with_journal(mut self, journal: AuditJournal) -> std::io::Result<Self> {
+        for record in journal.records() {
+            match &record.event {
+                JournalEvent::TokenRegistered { token } => {
+                    self.evolve_tokens.insert(token.id.clone(), token.clone());
+                }
+                JournalEvent::TokenRevoked { token_id } => {
+                    self.evolve_tokens.remove(token_id);
+                }
+                JournalEvent::Decision {
+                    auto,
+                    consumed_token,
+                    entry,
+                    ..
+                } => {
+                    if entry.decision == DecisionOutcome::Allowed {
+                        let effects = DecisionEffects {
+                            auto: *auto,
+                            consumed_token: consumed_token.clone(),
+                        };
+                        self.apply_effects(effects, record.recorded_at);
+                    }
+                }
+            }
+        }
+        self.journal = Some(journal);
+        self.check_compliance_against_journal()?;
+        Ok(self)
+    }
+
+    /// Every entry's snapshot holds bits the store had committed, so the
+    /// store must still hold their union. A file-backed store missing any
+    /// was truncated or deleted; an in-memory store is refilled.
+    fn check_compliance_against_journal(&mut self) -> std::io::Result<()> {
+        let Some(journal) = &self.journal else {
+            return Ok(());
+        };
+        let journaled = journal
+            .records()
+            .iter()
+            .filter_map(|r| r.entry())
+            .fold(ComplianceBits::empty(), |bits, e| bits | e.state_snapshot.compliance);
+        if self.compliance.bits().contains(journaled) {
+            return Ok(());
+        }
+        match self.compliance.path() {
+            None => {
+                let bits = self.compliance.bits() | journaled;
+                self.compliance.commit(bits, "replayed from audit journal")?;
+                Ok(())
+            }
+            Some(path) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{}: missing compliance bits {:?} recorded in the audit journal; store truncated or deleted",
+                    path.display(),
+                    journaled - self.compliance.bits()
+                ),
+            )),
+        }
+    }
+
+    /// Reset the auto-change quota every `period`, in windows aligned to the
+    /// Unix epoch (e.g. `Duration::days(1)` resets at 00:00 UTC).
+    pub fn with_auto_change_period(mut self, period: Duration) -> Self {
+        self.auto_change_period = Some(period);
+        self
+    }
+
+    pub fn journal(&self) -> Option<&AuditJournal> {
+        self.journal.as_ref()
+    }
+
+    /// Start of the quota window containing `now`.
+    fn quota_window_start(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let period = self.auto_change_period?.num_milliseconds().max(1);
+        let now_ms = now.timestamp_millis();
+        DateTime::from_timestamp_millis(now_ms - now_ms.rem_euclid(period))
+    }
+
+    /// Allowed auto changes counted against the quota at `now`.
+    pub fn auto_changes_used(&self, now: DateTime<Utc>) -> u32 {
+        let start = self.quota_window_start(now);
+        self.auto_changes
+            .iter()
+            .filter(|t| start.is_none_or(|s| **t >= s))
+            .count() as u32
+    }
+
+    fn apply_effects(&mut self, effects: DecisionEffects, at: DateTime<Utc>) {
+        if effects.auto {
+            self.auto_changes.push(at);
+            if let Some(start) = self.quota_window_start(at) {
+                self.auto_changes.retain(|t| *t >= start);
+            }
+        }
+        if let Some(token_id) = effects.consumed_token {
+            *self.token_uses.entry(token_id).or_insert(0) += 1;
         }
     }
 
-    pub fn register_evolve_token(&mut self, token: EvolveToken) {
+    /// Journal the decision, then apply its effects. If the journal write
+    /// fails the entry is Rejected and nothing is applied.
+    fn record(&mut self, module: &str, mut entry: AuditEntry, effects: DecisionEffects) -> AuditEntry {
+        if let Some(journal) = &mut self.journal {
+            let event = JournalEvent::Decision {
+                module: module.to_string(),
+                auto: effects.auto,
+                consumed_token: effects.consumed_token.clone(),
+                entry: entry.clone(),
+            };
+            if let Err(e) = journal.append(event) {
+                entry.decision = DecisionOutcome::Rejected;
+                entry.reason = format!("{}; audit journal write failed: {}", entry.reason, e);
+                return entry;
+            }
+        }
+        if entry.decision == DecisionOutcome::Allowed {
+            self.apply_effects(effects, Utc::now());
+        }
+        entry
+    }
+
+    /// Persist compliance bits in `store` instead of process memory. Fails
+    /// if an attached journal saw bits committed that `store` lacks.
+    pub fn with_compliance_store(mut self, store: ComplianceStore) -> std::io::Result<Self> {
+        self.compliance = store;
+        self.check_compliance_against_journal()?;
+        Ok(self)
+    }
+
+    pub fn compliance_store(&self) -> &ComplianceStore {
+        &self.compliance
+    }
+
+    /// Reader state with `compliance` replaced by the committed bits, so
+    /// gates never act on bits the store does not hold (or miss ones it does).
+    pub fn read_state(&self) -> StateVector {
+        let mut state = self.state_reader.read_state();
+        state.compliance = self.compliance.bits();
+        state
+    }
+
+    /// Register a token; with a journal attached it is journaled first.
+    pub fn register_evolve_token(&mut self, token: EvolveToken) -> std::io::Result<()> {
+        if let Some(journal) = &mut self.journal {
+            journal.append(JournalEvent::TokenRegistered {
+                token: token.clone(),
+            })?;
+        }
         self.evolve_tokens.insert(token.id.clone(), token);
+        Ok(())
+    }
+
+    /// Allowed proposals this token has authorised so far.
+    pub fn evolve_token_uses(&self, token_id: &str) -> u32 {
+        self.token_uses.get(token_id).copied().unwrap_or(0)
     }
 
-    pub fn revoke_evolve_token(&mut self, token_id: &str) {
+    pub fn revoke_evolve_token(&mut self, token_id: &str) -> std::io::Result<()> {
         // Revocation is allowed only for safety; callers must enforce that at policy layer.
+        if let Some(journal) = &mut self.journal {
+            journal.append(JournalEvent::TokenRevoked {
+                token_id: token_id.to_string(),
+            })?;
+        }
         self.evolve_tokens.remove(token_id);
+        Ok(())
     }
 
     fn active_mode_policy(&self) -> &ModePolicy {
@@ -328,11 +644,7 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
     }
 
     fn now_iso8601() -> String {
-        let secs = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap_or(Duration::from_secs(0))
-            .as_secs();
-        format!("2026-01-30T{:02}:00:00Z", (secs % 86400) / 3600)
+        Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
     }
 
     /// Lyapunov stability gate.
@@ -362,7 +674,11 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
                 <= self.invariants.eegmath_invariants.spectral_entropy_max
     }
 
-    /// Write-once compliance bit setting: cannot be unset or weakened.
+    /// Write-once compliance bit setting: cannot be unset or weakened. An
+    /// allowed bit is persisted before the decision is journaled and its
+    /// quota and token effects are applied; if persisting fails the entry is
+    /// Rejected with no effects. If journaling fails the bit is taken back
+    /// out of the store, so no bit outlives a decision the journal lacks.
     pub fn set_compliance_bit(
         &mut self,
         bit: ComplianceBits,
@@ -383,26 +699,52 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
             requires_evolve: self.invariants.require_evolve_for_change,
         };
 
-        let mut audit = self.evaluate_update(&proposal, evolve_token_id);
-
-        if let DecisionOutcome::Allowed = audit.decision {
-            let mut state = self.state_reader.read_state();
-            let current = state.compliance;
-            if current.contains(bit) {
-                // Already set; cannot be lowered or toggled.
-                audit.decision = DecisionOutcome::Rejected;
-                audit.reason = format!(
-                    "{}; compliance bit {:?} already set (write-once)",
-                    audit.reason, bit
-                );
-                return audit;
-            }
-            let new_bits = current | bit;
-            self.compliance_log.push((new_bits, reason.to_string()));
-            // Caller is responsible for writing new_bits into host-local state.
+        // Already set: cannot be lowered or toggled. Checked first so a
+        // single-use EVOLVE token is not spent on a no-op.
+        if self.compliance.bits().contains(bit) {
+            let entry = AuditEntry {
+                proposal_id: proposal.id,
+                evolve_token_id: evolve_token_id.map(|s| s.to_string()),
+                decision: DecisionOutcome::Rejected,
+                reason: format!("Compliance bit {:?} already set (write-once)", bit),
+                token_rejections: Vec::new(),
+                timestamp: Self::now_iso8601(),
+                state_snapshot: self.read_state(),
+                safety_rollback_available: true,
+                is_forward_evolution: true,
+            };
+            let none = DecisionEffects {
+                auto: false,
+                consumed_token: None,
+            };
+            return self.record(&proposal.module, entry, none);
         }
 
-        audit
+        let (mut entry, effects) = self.assess(&proposal, evolve_token_id);
+        if entry.decision == DecisionOutcome::Allowed {
+            match self.compliance.set(bit, reason) {
+                Ok(bits) => entry.state_snapshot.compliance = bits,
+                Err(e) => {
+                    // Not persisted: charge no quota and spend no token.
+                    entry.decision = DecisionOutcome::Rejected;
+                    entry.reason = format!("{}; failed to persist compliance bit: {}", entry.reason, e);
+                    let none = DecisionEffects {
+                        auto: false,
+                        consumed_token: None,
+                    };
+                    return self.record(&proposal.module, entry, none);
+                }
+            }
+            let mut entry = self.record(&proposal.module, entry, effects);
+            if entry.decision != DecisionOutcome::Allowed {
+                // The journal write failed after the bit was committed.
+                if let Err(e) = self.compliance.uncommit_last() {
+                    entry.reason = format!("{}; failed to roll back compliance bit: {}", entry.reason, e);
+                }
+            }
+            return entry;
+        }
+        self.record(&proposal.module, entry, effects)
     }
 
     /// Integration depth: forbids remote override; only bounded auto in allowed modules.
@@ -422,12 +764,23 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
     }
 
     /// Main gate: non-reversible, non-downgrade evolution respecting neurorights and invariants.
+    /// Every outcome is journaled when a journal is attached.
     pub fn evaluate_update(
         &mut self,
         proposal: &UpdateProposal,
         evolve_token_id: Option<&str>,
     ) -> AuditEntry {
-        let state = self.state_reader.read_state();
+        let (entry, effects) = self.assess(proposal, evolve_token_id);
+        self.record(&proposal.module, entry, effects)
+    }
+
+    /// Decide without side effects; `record` commits the result.
+    fn assess(
+        &self,
+        proposal: &UpdateProposal,
+        evolve_token_id: Option<&str>,
+    ) -> (AuditEntry, DecisionEffects) {
+        let state = self.read_state();
         let mode = self.active_mode_policy();
         let mut reason = String::new();
         let mut allowed = true;
@@ -473,57 +826,50 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
         }
 
         // 4. EVOLVE + host-init requirement
+        let mut token_rejections = Vec::new();
+        let mut consumed_token = None;
         if proposal.requires_evolve || mode.requires_evolve_token {
-            let Some(token_id) = evolve_token_id else {
-                allowed = false;
-                reason.push_str("Missing EVOLVE token; ");
-                return AuditEntry {
-                    proposal_id: proposal.id.clone(),
-                    evolve_token_id: None,
-                    decision: DecisionOutcome::Rejected,
-                    reason,
-                    timestamp: Self::now_iso8601(),
-                    state_snapshot: state,
-                    safety_rollback_available,
-                    is_forward_evolution,
-                };
-            };
-            let Some(token) = self.evolve_tokens.get(token_id) else {
-                allowed = false;
-                reason.push_str("Unknown EVOLVE token; ");
-                return AuditEntry {
-                    proposal_id: proposal.id.clone(),
-                    evolve_token_id: Some(token_id.to_string()),
-                    decision: DecisionOutcome::Rejected,
-                    reason,
-                    timestamp: Self::now_iso8601(),
-                    state_snapshot: state,
-                    safety_rollback_available,
-                    is_forward_evolution,
-                };
-            };
-
-            // Effect size bound (strictest-wins).
-            if proposal.effect_bounds.l2_delta_norm > token.max_effect_size {
-                allowed = false;
-                reason.push_str("Effect exceeds EVOLVE max_effect_size; ");
-            }
+            match evolve_token_id.map(|id| (id, self.evolve_tokens.get(id))) {
+                None => token_rejections.push(TokenRejection::Missing),
+                Some((_, None)) => token_rejections.push(TokenRejection::Unknown),
+                Some((token_id, Some(token))) => {
+                    token_rejections = token.validate(
+                        &self.neurorights.subject_id,
+                        &proposal.scope,
+                        Utc::now(),
+                    );
+                    if token.uses == TokenUse::SingleUse && self.evolve_token_uses(token_id) > 0 {
+                        token_rejections.push(TokenRejection::AlreadyConsumed);
+                    }
 
-            // Physiological guards
-            if let Some(pg) = &token.physio_guard {
-                if let Some(min) = pg.hrv_lf_hf_min {
-                    if state.hrv_lf_hf < min {
+                    // Effect size bound (strictest-wins).
+                    if proposal.effect_bounds.l2_delta_norm > token.max_effect_size {
                         allowed = false;
-                        reason.push_str("HRV below EVOLVE guard; ");
+                        reason.push_str("Effect exceeds EVOLVE max_effect_size; ");
                     }
-                }
-                if let Some(max) = pg.emg_fatigue_max {
-                    if state.emg_fatigue > max {
-                        allowed = false;
-                        reason.push_str("EMG fatigue above EVOLVE guard; ");
+
+                    // Physiological guards
+                    if let Some(pg) = &token.physio_guard {
+                        if let Some(min) = pg.hrv_lf_hf_min {
+                            if state.hrv_lf_hf < min {
+                                allowed = false;
+                                reason.push_str("HRV below EVOLVE guard; ");
+                            }
+                        }
+                        if let Some(max) = pg.emg_fatigue_max {
+                            if state.emg_fatigue > max {
+                                allowed = false;
+                                reason.push_str("EMG fatigue above EVOLVE guard; ");
+                            }
+                        }
                     }
+                    consumed_token = Some(token_id.to_string());
                 }
             }
+            for r in &token_rejections {
+                allowed = false;
+                reason.push_str(&format!("{}; ", r));
+            }
         }
 
         // 5. Pain envelope: no evolution when pain is above corridor; only safety rollback allowed.
@@ -538,11 +884,9 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
 
         // 6. Cognitive liberty: limit auto external changes.
         let cl = &self.neurorights.neurorights.cognitive_liberty;
-        if auto {
-            if self.used_auto_changes >= cl.max_external_auto_changes {
-                allowed = false;
-                reason.push_str("Auto-change quota exceeded; ");
-            }
+        if auto && self.auto_changes_used(Utc::now()) >= cl.max_external_auto_changes {
+            allowed = false;
+            reason.push_str("Auto-change quota exceeded; ");
         }
 
         // 7. Invariant gates: Lyapunov, Bio-RegNet, EEG.Math
@@ -569,15 +913,11 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
             reason.push_str("Policy immutable via compliance bit; ");
         }
 
-        if allowed && auto {
-            self.used_auto_changes += 1;
-        }
-
         if reason.is_empty() {
             reason.push_str("All sovereignty, neurorights, and invariant checks passed.");
         }
 
-        AuditEntry {
+        let entry = AuditEntry {
             proposal_id: proposal.id.clone(),
             evolve_token_id: evolve_token_id.map(|s| s.to_string()),
             decision: if allowed {
@@ -586,10 +926,16 @@ impl<S: BiophysicalStateReader> SovereigntyCore<S> {
                 DecisionOutcome::Rejected
             },
             reason,
+            token_rejections,
             timestamp: Self::now_iso8601(),
             state_snapshot: state,
             safety_rollback_available,
             is_forward_evolution,
-        }
+        };
+        let effects = DecisionEffects {
+            auto,
+            consumed_token,
+        };
+        (entry, effects)
     }
 }
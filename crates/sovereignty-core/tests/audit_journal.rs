@@ -0,0 +1,327 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Duration, Utc};
+use ed25519_dalek::SigningKey;
+use sovereignty_core::{
+    AuditEntry, AuditJournal, BiophysicalStateReader, ComplianceBits, ComplianceStore, DecisionOutcome, EvolveToken,
+    JournalEvent, JournalQuery, NeurorightsPolicyDocument, SovereigntyCore, StateVector, TokenRejection, TokenUse,
+    UpdateEffectBounds, UpdateKind, UpdateProposal,
+};
+
+fn journal(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sovereignty-core-audit-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("audit.ndjson")
+}
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn state() -> StateVector {
+    serde_json::from_str(include_str!("data/calm_state.json")).unwrap()
+}
+
+fn decision(module: &str, proposal_id: &str, decision: DecisionOutcome) -> JournalEvent {
+    JournalEvent::Decision {
+        module: module.to_string(),
+        auto: true,
+        consumed_token: None,
+        entry: AuditEntry {
+            proposal_id: proposal_id.to_string(),
+            evolve_token_id: None,
+            decision,
+            reason: "test".to_string(),
+            token_rejections: Vec::new(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            state_snapshot: state(),
+            safety_rollback_available: true,
+            is_forward_evolution: true,
+        },
+    }
+}
+
+fn token(id: &str) -> EvolveToken {
+    EvolveToken {
+        id: id.to_string(),
+        subject_id: "bostrom-host".to_string(),
+        scope: vec!["*".to_string()],
+        max_effect_size: 0.1,
+        valid_from: (Utc::now() - Duration::days(1)).to_rfc3339(),
+        valid_until: (Utc::now() + Duration::days(1)).to_rfc3339(),
+        physio_guard: None,
+        revocable: true,
+        uses: TokenUse::SingleUse,
+    }
+}
+
+#[test]
+fn records_chain_and_survive_reopen() {
+    let path = journal("reopen");
+    {
+        let mut j = AuditJournal::open(&path, key()).unwrap();
+        j.append(JournalEvent::TokenRegistered { token: token("t1") }).unwrap();
+        j.append(decision("motor", "p1", DecisionOutcome::Allowed)).unwrap();
+        j.append(JournalEvent::TokenRevoked {
+            token_id: "t1".to_string(),
+        })
+        .unwrap();
+    }
+
+    // A crash mid-append leaves a cut-off line; it is dropped on open.
+    let mut data = fs::read_to_string(&path).unwrap();
+    data.push_str("{\"seq\":3,\"recor");
+    fs::write(&path, data).unwrap();
+
+    let mut j = AuditJournal::open(&path, key()).unwrap();
+    assert_eq!(j.records().len(), 3);
+    assert_eq!(j.records()[1].prev_hash, j.records()[0].hash);
+    let next = j.append(decision("motor", "p2", DecisionOutcome::Rejected)).unwrap();
+    assert_eq!(next.seq, 3);
+    assert_eq!(AuditJournal::open(&path, key()).unwrap().records().len(), 4);
+}
+
+#[test]
+fn edited_or_foreign_records_are_refused() {
+    let path = journal("tamper");
+    let mut j = AuditJournal::open(&path, key()).unwrap();
+    j.append(decision("motor", "p1", DecisionOutcome::Rejected)).unwrap();
+    j.append(decision("motor", "p2", DecisionOutcome::Rejected)).unwrap();
+    drop(j);
+
+    let original = fs::read_to_string(&path).unwrap();
+    fs::write(&path, original.replacen("\"Rejected\"", "\"Allowed\"", 1)).unwrap();
+    let err = AuditJournal::open(&path, key()).err().expect("edited journal must be refused");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains(":1: hash mismatch"), "{}", err);
+
+    fs::write(&path, &original).unwrap();
+    let other = SigningKey::from_bytes(&[8u8; 32]);
+    let err = AuditJournal::open(&path, other).err().expect("wrong key must be refused");
+    assert!(err.to_string().contains("bad signature"), "{}", err);
+}
+
+#[test]
+fn query_filters_decisions() {
+    let path = journal("query");
+    let mut j = AuditJournal::open(&path, key()).unwrap();
+    let start = chrono::Utc::now();
+    j.append(JournalEvent::TokenRegistered { token: token("t1") }).unwrap();
+    j.append(decision("motor", "p1", DecisionOutcome::Allowed)).unwrap();
+    j.append(decision("motor", "p2", DecisionOutcome::Rejected)).unwrap();
+    j.append(decision("speech", "p3", DecisionOutcome::Rejected)).unwrap();
+
+    let q = JournalQuery {
+        decision: Some(DecisionOutcome::Rejected),
+        since: Some(start),
+        ..Default::default()
+    };
+    let ids: Vec<&str> = j.query(&q).map(|r| r.entry().unwrap().proposal_id.as_str()).collect();
+    assert_eq!(ids, vec!["p2", "p3"]);
+
+    let q = JournalQuery {
+        module: Some("motor".to_string()),
+        proposal_id: Some("p2".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(j.query(&q).count(), 1);
+
+    let q = JournalQuery {
+        until: Some(start),
+        ..Default::default()
+    };
+    assert_eq!(j.query(&q).count(), 0);
+}
+
+struct Calm;
+
+impl BiophysicalStateReader for Calm {
+    fn read_state(&self) -> StateVector {
+        state()
+    }
+}
+
+/// AUTO_EVOLVE core allowing two auto changes per day, replaying `path`.
+fn core(path: &PathBuf) -> SovereigntyCore<Calm> {
+    let mut neurorights: NeurorightsPolicyDocument =
+        serde_json::from_str(include_str!("data/neurorights.json")).unwrap();
+    neurorights.active_mode = "AUTO_EVOLVE".to_string();
+    SovereigntyCore::new(
+        neurorights,
+        serde_json::from_str(include_str!("data/evolution.json")).unwrap(),
+        serde_json::from_str(include_str!("data/invariants.json")).unwrap(),
+        Calm,
+    )
+    .with_auto_change_period(Duration::days(1))
+    .with_journal(AuditJournal::open(path, key()).unwrap())
+    .unwrap()
+}
+
+fn proposal(id: &str) -> UpdateProposal {
+    UpdateProposal {
+        id: id.to_string(),
+        module: "motor".to_string(),
+        kind: UpdateKind::ParamNudge,
+        scope: vec!["motor.gain".to_string()],
+        description: "test".to_string(),
+        effect_bounds: UpdateEffectBounds {
+            l2_delta_norm: 0.01,
+            irreversible: false,
+            is_downgrade: false,
+        },
+        requires_evolve: true,
+    }
+}
+
+#[test]
+fn restart_replays_quota_and_token_consumption() {
+    let path = journal("replay");
+    {
+        let mut core = core(&path);
+        core.register_evolve_token(token("once")).unwrap();
+        core.register_evolve_token(EvolveToken {
+            uses: TokenUse::MultiUse,
+            ..token("many")
+        })
+        .unwrap();
+        core.register_evolve_token(EvolveToken {
+            uses: TokenUse::MultiUse,
+            ..token("revoked")
+        })
+        .unwrap();
+        core.revoke_evolve_token("revoked").unwrap();
+
+        let entry = core.evaluate_update(&proposal("p1"), Some("once"));
+        assert_eq!(entry.decision, DecisionOutcome::Allowed, "{}", entry.reason);
+        // Rejected decisions are journaled but charge nothing.
+        assert_eq!(core.evaluate_update(&proposal("p2"), Some("once")).decision, DecisionOutcome::Rejected);
+        assert_eq!(core.evaluate_update(&proposal("p3"), Some("many")).decision, DecisionOutcome::Allowed);
+        assert_eq!(core.auto_changes_used(Utc::now()), 2);
+    }
+
+    let mut core = core(&path);
+    let now = Utc::now();
+    assert_eq!(core.evolve_token_uses("once"), 1);
+    assert_eq!(core.evolve_token_uses("many"), 1);
+    assert_eq!(core.auto_changes_used(now), 2);
+    // The replayed changes fall out of the next day's window.
+    assert_eq!(core.auto_changes_used(now + Duration::days(1)), 0);
+
+    let entry = core.evaluate_update(&proposal("p4"), Some("once"));
+    assert_eq!(entry.decision, DecisionOutcome::Rejected);
+    assert!(entry.token_rejections.contains(&TokenRejection::AlreadyConsumed));
+    assert!(entry.reason.contains("Auto-change quota exceeded"), "{}", entry.reason);
+
+    let entry = core.evaluate_update(&proposal("p5"), Some("many"));
+    assert_eq!(entry.decision, DecisionOutcome::Rejected);
+    assert!(entry.token_rejections.is_empty());
+    assert!(entry.reason.contains("Auto-change quota exceeded"), "{}", entry.reason);
+
+    let entry = core.evaluate_update(&proposal("p6"), Some("revoked"));
+    assert_eq!(entry.token_rejections, vec![TokenRejection::Unknown]);
+}
+
+#[test]
+fn unpersisted_compliance_bit_charges_nothing() {
+    let path = journal("compliance");
+    // The store's directory does not exist, so persisting the bit fails.
+    let missing = path.parent().unwrap().join("missing").join("compliance.ndjson");
+    {
+        let mut core = core(&path).with_compliance_store(ComplianceStore::open(&missing).unwrap())
+            .unwrap();
+        core.register_evolve_token(token("once")).unwrap();
+
+        let entry = core.set_compliance_bit(ComplianceBits::NO_DOWNGRADE, "freeze", Some("once"));
+        assert_eq!(entry.decision, DecisionOutcome::Rejected);
+        assert!(entry.reason.contains("failed to persist compliance bit"), "{}", entry.reason);
+        assert!(core.compliance_store().bits().is_empty());
+        assert_eq!(core.evolve_token_uses("once"), 0);
+        assert_eq!(core.auto_changes_used(Utc::now()), 0);
+    }
+
+    let store = path.parent().unwrap().join("compliance.ndjson");
+    let mut core = core(&path)
+        .with_compliance_store(ComplianceStore::open(&store).unwrap())
+        .unwrap();
+    assert_eq!(core.evolve_token_uses("once"), 0);
+    assert_eq!(core.auto_changes_used(Utc::now()), 0);
+
+    let entry = core.set_compliance_bit(ComplianceBits::NO_DOWNGRADE, "freeze", Some("once"));
+    assert_eq!(entry.decision, DecisionOutcome::Allowed, "{}", entry.reason);
+    assert_eq!(entry.state_snapshot.compliance, ComplianceBits::NO_DOWNGRADE);
+    assert_eq!(core.evolve_token_uses("once"), 1);
+    assert_eq!(ComplianceStore::open(&store).unwrap().bits(), ComplianceBits::NO_DOWNGRADE);
+}
+
+#[test]
+fn compliance_bit_is_rolled_back_when_the_journal_write_fails() {
+    let path = journal("compliance-unjournaled");
+    let store = journal("compliance-unjournaled-store").with_file_name("compliance.ndjson");
+    let mut core = core(&path)
+        .with_compliance_store(ComplianceStore::open(&store).unwrap())
+        .unwrap();
+    core.register_evolve_token(token("once")).unwrap();
+    // Without its directory the journal cannot be appended to.
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+    let entry = core.set_compliance_bit(ComplianceBits::NO_DOWNGRADE, "freeze", Some("once"));
+    assert_eq!(entry.decision, DecisionOutcome::Rejected);
+    assert!(entry.reason.contains("audit journal write failed"), "{}", entry.reason);
+    assert!(!entry.reason.contains("roll back"), "{}", entry.reason);
+    assert!(core.compliance_store().bits().is_empty());
+    assert!(core.compliance_store().records().is_empty());
+    assert_eq!(core.evolve_token_uses("once"), 0);
+    let reopened = ComplianceStore::open(&store).unwrap();
+    assert!(reopened.bits().is_empty() && reopened.records().is_empty());
+
+    // Once the journal is writable again the bit is set and kept.
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let entry = core.set_compliance_bit(ComplianceBits::NO_DOWNGRADE, "freeze", Some("once"));
+    assert_eq!(entry.decision, DecisionOutcome::Allowed, "{}", entry.reason);
+    assert_eq!(ComplianceStore::open(&store).unwrap().bits(), ComplianceBits::NO_DOWNGRADE);
+}
+
+#[test]
+fn truncated_or_deleted_compliance_store_is_refused() {
+    let path = journal("store-loss");
+    let store = path.parent().unwrap().join("compliance.ndjson");
+    {
+        let mut core = core(&path)
+            .with_compliance_store(ComplianceStore::open(&store).unwrap())
+            .unwrap();
+        for (id, bit) in [("a", ComplianceBits::NO_DOWNGRADE), ("b", ComplianceBits::POLICY_IMMUTABLE)] {
+            core.register_evolve_token(token(id)).unwrap();
+            let entry = core.set_compliance_bit(bit, "freeze", Some(id));
+            assert_eq!(entry.decision, DecisionOutcome::Allowed, "{}", entry.reason);
+        }
+    }
+    let full = fs::read_to_string(&store).unwrap();
+
+    // Cut at a record boundary: the store's own chain still verifies.
+    let first_line = full.lines().next().unwrap();
+    fs::write(&store, format!("{first_line}\n")).unwrap();
+    assert_eq!(ComplianceStore::open(&store).unwrap().bits(), ComplianceBits::NO_DOWNGRADE);
+    let err = core(&path)
+        .with_compliance_store(ComplianceStore::open(&store).unwrap())
+        .err()
+        .expect("truncated store must be refused");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("POLICY_IMMUTABLE"), "{}", err);
+
+    fs::remove_file(&store).unwrap();
+    assert!(core(&path)
+        .with_compliance_store(ComplianceStore::open(&store).unwrap())
+        .is_err());
+
+    // Without a file-backed store the journal's bits still apply.
+    let both = ComplianceBits::NO_DOWNGRADE | ComplianceBits::POLICY_IMMUTABLE;
+    assert_eq!(core(&path).read_state().compliance, both);
+
+    fs::write(&store, &full).unwrap();
+    let core = core(&path)
+        .with_compliance_store(ComplianceStore::open(&store).unwrap())
+        .unwrap();
+    assert_eq!(core.read_state().compliance, both);
+}
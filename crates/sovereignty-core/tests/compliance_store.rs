@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::PathBuf;
+
+use sovereignty_core::{ComplianceBits, ComplianceStore};
+
+fn journal(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sovereignty-core-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("compliance.ndjson")
+}
+
+#[test]
+fn bits_survive_reload_and_cannot_be_cleared() {
+    let path = journal("reload");
+    {
+        let mut store = ComplianceStore::open(&path).unwrap();
+        store.set(ComplianceBits::NO_DOWNGRADE, "host evolve").unwrap();
+        store.set(ComplianceBits::POLICY_IMMUTABLE, "policy freeze").unwrap();
+    }
+
+    let mut store = ComplianceStore::open(&path).unwrap();
+    let both = ComplianceBits::NO_DOWNGRADE | ComplianceBits::POLICY_IMMUTABLE;
+    assert_eq!(store.bits(), both);
+    assert_eq!(store.records().len(), 2);
+
+    let err = store
+        .commit(ComplianceBits::POLICY_IMMUTABLE, "try to drop NO_DOWNGRADE")
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    assert_eq!(store.bits(), both);
+
+    // Setting an already-set bit changes nothing on disk.
+    let before = fs::read_to_string(&path).unwrap();
+    store.set(ComplianceBits::NO_DOWNGRADE, "again").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    assert_eq!(ComplianceStore::open(&path).unwrap().bits(), both);
+}
+
+#[test]
+fn edited_journal_that_clears_a_bit_is_refused() {
+    let path = journal("tamper");
+    let mut store = ComplianceStore::open(&path).unwrap();
+    store.set(ComplianceBits::INVARIANT_LOCKED, "lock").unwrap();
+    drop(store);
+
+    let cleared = format!(
+        "{{\"bits\":\"\",\"reason\":\"unset\",\"set_at\":\"{}\"}}\n",
+        chrono::Utc::now().to_rfc3339()
+    );
+    let mut data = fs::read_to_string(&path).unwrap();
+    data.push_str(&cleared);
+    fs::write(&path, data).unwrap();
+
+    let err = ComplianceStore::open(&path).err().expect("journal must be refused");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains(":2:"), "{}", err);
+}
+
+#[test]
+fn edited_or_removed_records_break_the_chain() {
+    let path = journal("chain");
+    let mut store = ComplianceStore::open(&path).unwrap();
+    store.set(ComplianceBits::INVARIANT_LOCKED, "lock").unwrap();
+    store.set(ComplianceBits::LYAP_STABLE, "stable").unwrap();
+    store.set(ComplianceBits::NO_DOWNGRADE, "evolve").unwrap();
+    drop(store);
+    let original = fs::read_to_string(&path).unwrap();
+
+    fs::write(&path, original.replacen("\"lock\"", "\"unlock\"", 1)).unwrap();
+    let err = ComplianceStore::open(&path).err().expect("edited record must be refused");
+    assert!(err.to_string().contains(":1: hash mismatch"), "{}", err);
+
+    let lines: Vec<&str> = original.lines().collect();
+    fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+    let err = ComplianceStore::open(&path).err().expect("missing record must be refused");
+    assert!(err.to_string().contains(":2: hash chain broken"), "{}", err);
+}
+
+#[test]
+fn torn_tail_is_dropped_before_the_next_commit() {
+    let path = journal("torn");
+    let mut store = ComplianceStore::open(&path).unwrap();
+    store.set(ComplianceBits::BIOREGNET_ACTIVE, "triad on").unwrap();
+    drop(store);
+
+    let mut data = fs::read_to_string(&path).unwrap();
+    data.push_str("{\"bits\":\"EEGMATH");
+    fs::write(&path, data).unwrap();
+
+    let mut store = ComplianceStore::open(&path).unwrap();
+    assert_eq!(store.bits(), ComplianceBits::BIOREGNET_ACTIVE);
+    store.set(ComplianceBits::EEGMATH_VERIFIED, "verified").unwrap();
+    let reopened = ComplianceStore::open(&path).unwrap();
+    assert_eq!(
+        reopened.bits(),
+        ComplianceBits::BIOREGNET_ACTIVE | ComplianceBits::EEGMATH_VERIFIED
+    );
+    assert_eq!(reopened.records().len(), 2);
+}
@@ -0,0 +1,234 @@
+use chrono::{DateTime, Duration, Utc};
+use sovereignty_core::{
+    BiophysicalStateReader, DecisionOutcome, EvolveToken, SovereigntyCore, StateVector, TokenRejection, TokenUse,
+    UpdateEffectBounds, UpdateKind, UpdateProposal,
+};
+
+const HOST: &str = "bostrom-host";
+
+/// A calm state that passes every invariant gate of `core()`.
+struct Calm;
+
+impl BiophysicalStateReader for Calm {
+    fn read_state(&self) -> StateVector {
+        serde_json::from_str(include_str!("data/calm_state.json")).unwrap()
+    }
+}
+
+/// CO_PILOT core for `HOST`: `motor` is an advisor module and every change
+/// needs an EVOLVE token.
+fn core() -> SovereigntyCore<Calm> {
+    SovereigntyCore::new(
+        serde_json::from_str(include_str!("data/neurorights.json")).unwrap(),
+        serde_json::from_str(include_str!("data/evolution.json")).unwrap(),
+        serde_json::from_str(include_str!("data/invariants.json")).unwrap(),
+        Calm,
+    )
+}
+
+fn rfc3339(t: DateTime<Utc>) -> String {
+    t.to_rfc3339()
+}
+
+fn token(id: &str, scope: &[&str], uses: TokenUse) -> EvolveToken {
+    let now = Utc::now();
+    EvolveToken {
+        id: id.to_string(),
+        subject_id: HOST.to_string(),
+        scope: scope.iter().map(|s| s.to_string()).collect(),
+        max_effect_size: 0.5,
+        valid_from: rfc3339(now - Duration::hours(1)),
+        valid_until: rfc3339(now + Duration::hours(1)),
+        physio_guard: None,
+        revocable: true,
+        uses,
+    }
+}
+
+fn proposal(id: &str, scope: &[&str]) -> UpdateProposal {
+    UpdateProposal {
+        id: id.to_string(),
+        module: "motor".to_string(),
+        kind: UpdateKind::ArchChange,
+        scope: scope.iter().map(|s| s.to_string()).collect(),
+        description: "test".to_string(),
+        effect_bounds: UpdateEffectBounds {
+            l2_delta_norm: 0.1,
+            irreversible: false,
+            is_downgrade: false,
+        },
+        requires_evolve: true,
+    }
+}
+
+fn scopes(s: &[&str]) -> Vec<String> {
+    s.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn validate_checks_the_window_bounds() {
+    let t = token("t", &["*"], TokenUse::MultiUse);
+    let from = DateTime::parse_from_rfc3339(&t.valid_from).unwrap().with_timezone(&Utc);
+    let until = DateTime::parse_from_rfc3339(&t.valid_until).unwrap().with_timezone(&Utc);
+
+    // valid_from is inclusive, valid_until exclusive.
+    assert!(t.validate(HOST, &[], from).is_empty());
+    assert_eq!(
+        t.validate(HOST, &[], from - Duration::milliseconds(1)),
+        vec![TokenRejection::NotYetValid]
+    );
+    assert_eq!(t.validate(HOST, &[], until), vec![TokenRejection::Expired]);
+
+    for (valid_from, valid_until) in [
+        ("yesterday", t.valid_until.as_str()),
+        (t.valid_from.as_str(), "2026-13-01T00:00:00Z"),
+        (t.valid_until.as_str(), t.valid_from.as_str()),
+        (t.valid_from.as_str(), t.valid_from.as_str()),
+    ] {
+        let bad = EvolveToken {
+            valid_from: valid_from.to_string(),
+            valid_until: valid_until.to_string(),
+            ..t.clone()
+        };
+        assert_eq!(
+            bad.validate(HOST, &[], from),
+            vec![TokenRejection::MalformedWindow],
+            "{valid_from} .. {valid_until}"
+        );
+    }
+}
+
+#[test]
+fn validate_checks_subject_and_scope_patterns() {
+    let now = Utc::now();
+    let t = token("t", &["motor.*", "speech.rate", "*.audit.*"], TokenUse::MultiUse);
+
+    assert!(t
+        .validate(HOST, &scopes(&["motor.gain", "motor.", "speech.rate", "eeg.audit.export"]), now)
+        .is_empty());
+    assert_eq!(
+        t.validate(HOST, &scopes(&["motor.gain", "speech.rate.max", "motorgain", "audit.x"]), now),
+        vec![TokenRejection::ScopeNotCovered {
+            scopes: scopes(&["speech.rate.max", "motorgain", "audit.x"]),
+        }]
+    );
+
+    let everything = token("all", &["*"], TokenUse::MultiUse);
+    assert!(everything.validate(HOST, &scopes(&["anything", ""]), now).is_empty());
+    let none = token("none", &[], TokenUse::MultiUse);
+    assert!(none.validate(HOST, &[], now).is_empty());
+
+    // Each failing check reports its own rejection.
+    let late = now + Duration::hours(2);
+    assert_eq!(
+        t.validate("someone-else", &scopes(&["eeg.raw"]), late),
+        vec![
+            TokenRejection::Expired,
+            TokenRejection::SubjectMismatch {
+                token_subject: HOST.to_string(),
+            },
+            TokenRejection::ScopeNotCovered {
+                scopes: scopes(&["eeg.raw"]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn evaluate_update_reports_token_rejections() {
+    let mut core = core();
+    let now = Utc::now();
+
+    let entry = core.evaluate_update(&proposal("p0", &["motor.gain"]), None);
+    assert_eq!(entry.decision, DecisionOutcome::Rejected);
+    assert_eq!(entry.token_rejections, vec![TokenRejection::Missing]);
+
+    let entry = core.evaluate_update(&proposal("p1", &["motor.gain"]), Some("ghost"));
+    assert_eq!(entry.token_rejections, vec![TokenRejection::Unknown]);
+
+    let expired = EvolveToken {
+        valid_from: rfc3339(now - Duration::hours(2)),
+        valid_until: rfc3339(now - Duration::hours(1)),
+        ..token("expired", &["motor.*"], TokenUse::MultiUse)
+    };
+    let early = EvolveToken {
+        valid_from: rfc3339(now + Duration::hours(1)),
+        valid_until: rfc3339(now + Duration::hours(2)),
+        ..token("early", &["motor.*"], TokenUse::MultiUse)
+    };
+    let malformed = EvolveToken {
+        valid_until: "next week".to_string(),
+        ..token("malformed", &["motor.*"], TokenUse::MultiUse)
+    };
+    let foreign = EvolveToken {
+        subject_id: "someone-else".to_string(),
+        ..token("foreign", &["motor.*"], TokenUse::MultiUse)
+    };
+    for t in [expired, early, malformed, foreign] {
+        core.register_evolve_token(t).unwrap();
+    }
+    core.register_evolve_token(token("speech", &["speech.*"], TokenUse::MultiUse))
+        .unwrap();
+
+    for (token_id, rejection) in [
+        ("expired", TokenRejection::Expired),
+        ("early", TokenRejection::NotYetValid),
+        ("malformed", TokenRejection::MalformedWindow),
+        (
+            "foreign",
+            TokenRejection::SubjectMismatch {
+                token_subject: "someone-else".to_string(),
+            },
+        ),
+        (
+            "speech",
+            TokenRejection::ScopeNotCovered {
+                scopes: scopes(&["motor.gain"]),
+            },
+        ),
+    ] {
+        let entry = core.evaluate_update(&proposal(token_id, &["motor.gain"]), Some(token_id));
+        assert_eq!(entry.decision, DecisionOutcome::Rejected, "{token_id}");
+        assert_eq!(entry.token_rejections, vec![rejection.clone()], "{token_id}");
+        assert!(entry.reason.contains(&rejection.to_string()), "{}", entry.reason);
+        assert_eq!(core.evolve_token_uses(token_id), 0);
+    }
+}
+
+#[test]
+fn single_use_tokens_are_consumed_by_the_first_allowed_proposal() {
+    let mut core = core();
+    core.register_evolve_token(token("once", &["motor.*"], TokenUse::SingleUse))
+        .unwrap();
+    core.register_evolve_token(token("many", &["motor.*"], TokenUse::MultiUse))
+        .unwrap();
+
+    // A rejected proposal does not spend the token.
+    let mut too_big = proposal("big", &["motor.gain"]);
+    too_big.effect_bounds.l2_delta_norm = 0.9;
+    assert_eq!(core.evaluate_update(&too_big, Some("once")).decision, DecisionOutcome::Rejected);
+    assert_eq!(core.evolve_token_uses("once"), 0);
+
+    let first = core.evaluate_update(&proposal("p1", &["motor.gain"]), Some("once"));
+    assert_eq!(first.decision, DecisionOutcome::Allowed, "{}", first.reason);
+    assert_eq!(core.evolve_token_uses("once"), 1);
+
+    let second = core.evaluate_update(&proposal("p2", &["motor.gain"]), Some("once"));
+    assert_eq!(second.decision, DecisionOutcome::Rejected);
+    assert_eq!(second.token_rejections, vec![TokenRejection::AlreadyConsumed]);
+    assert_eq!(core.evolve_token_uses("once"), 1);
+
+    for id in ["m1", "m2", "m3"] {
+        let entry = core.evaluate_update(&proposal(id, &["motor.gain"]), Some("many"));
+        assert_eq!(entry.decision, DecisionOutcome::Allowed, "{}", entry.reason);
+    }
+    assert_eq!(core.evolve_token_uses("many"), 3);
+}
+
+#[test]
+fn tokens_without_uses_default_to_single_use() {
+    let mut value = serde_json::to_value(token("legacy", &["*"], TokenUse::MultiUse)).unwrap();
+    value.as_object_mut().unwrap().remove("uses");
+    let legacy: EvolveToken = serde_json::from_value(value).unwrap();
+    assert_eq!(legacy.uses, TokenUse::SingleUse);
+}
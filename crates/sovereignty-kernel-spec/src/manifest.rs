@@ -0,0 +1,690 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use organiccpualn::roh_registry::ROH_CEILING_MAX;
+
+use crate::pipeline::GuardDecision;
+
+/// Record kinds of the shipped `bostrom-sovereign-kernel-v2.ndjson`.
+/// Fields not modelled here are ignored, so a newer manifest that adds keys
+/// to a known record still loads.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ManifestRecord {
+    AiChatRole(AiChatRoleSpec),
+    ServiceScope(ServiceScopeSpec),
+    GuardModule(GuardModuleSpec),
+    SovereignKernelHeader(KernelHeaderSpec),
+    RiskModelSpec(RiskModelRef),
+    StakeSchemaSpec(StakeSchemaRef),
+    NeurorightsPolicySpec(NeurorightsPolicyRef),
+    EvolutionStreamSpec(EvolutionStreamRef),
+    DonutloopLedgerSpec(DonutloopLedgerRef),
+    GuardPipelineSpec(GuardPipelineRef),
+    AiChatToolManifest(AiChatToolManifestSpec),
+    FiletypeIndexSpec(FiletypeIndexSpec),
+    RealityOsSpec(ShardRef),
+    RealityOsSwarmPolicy(ShardRef),
+    RealityUpgradeStream(ShardRef),
+}
+
+impl ManifestRecord {
+    pub fn id(&self) -> &str {
+        match self {
+            ManifestRecord::AiChatRole(r) => &r.id,
+            ManifestRecord::ServiceScope(r) => &r.id,
+            ManifestRecord::GuardModule(r) => &r.id,
+            ManifestRecord::SovereignKernelHeader(r) => &r.id,
+            ManifestRecord::RiskModelSpec(r) => &r.id,
+            ManifestRecord::StakeSchemaSpec(r) => &r.id,
+            ManifestRecord::NeurorightsPolicySpec(r) => &r.id,
+            ManifestRecord::EvolutionStreamSpec(r) => &r.id,
+            ManifestRecord::DonutloopLedgerSpec(r) => &r.id,
+            ManifestRecord::GuardPipelineSpec(r) => &r.id,
+            ManifestRecord::AiChatToolManifest(r) => &r.id,
+            ManifestRecord::FiletypeIndexSpec(r) => &r.id,
+            ManifestRecord::RealityOsSpec(r)
+            | ManifestRecord::RealityOsSwarmPolicy(r)
+            | ManifestRecord::RealityUpgradeStream(r) => &r.id,
+        }
+    }
+
+    pub fn subject_id(&self) -> Option<&str> {
+        match self {
+            ManifestRecord::AiChatRole(r) => Some(&r.subject_id),
+            ManifestRecord::ServiceScope(r) => Some(&r.subject_id),
+            ManifestRecord::GuardModule(r) => Some(&r.subject_id),
+            ManifestRecord::SovereignKernelHeader(r) => Some(&r.subject_id),
+            ManifestRecord::RealityOsSpec(r)
+            | ManifestRecord::RealityOsSwarmPolicy(r)
+            | ManifestRecord::RealityUpgradeStream(r) => r.subject_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Record types a kernel manifest must carry exactly once.
+pub const SINGLETON_RECORD_TYPES: &[&str] = &[
+    "sovereign_kernel_header",
+    "risk_model_spec",
+    "stake_schema_spec",
+    "neurorights_policy_spec",
+    "evolution_stream_spec",
+    "donutloop_ledger_spec",
+    "guard_pipeline_spec",
+];
+
+/// Stages every guard pipeline must contain.
+pub const REQUIRED_GUARDS: &[&str] = &[
+    "parse_spec",
+    "roh_monotonicity_guard",
+    "neurorights_guard",
+    "stake_guard",
+    "token_guard",
+    "decision_writer",
+];
+
+/// Stake scopes that may only change under EVOLVE tokens and multisig.
+pub const MULTISIG_SCOPES: &[&str] = &["lifeforce_alteration", "arch_change"];
+
+/// Record type names `ManifestRecord` understands.
+pub const KNOWN_RECORD_TYPES: &[&str] = &[
+    "ai_chat_role",
+    "service_scope",
+    "guard_module",
+    "sovereign_kernel_header",
+    "risk_model_spec",
+    "stake_schema_spec",
+    "neurorights_policy_spec",
+    "evolution_stream_spec",
+    "donutloop_ledger_spec",
+    "guard_pipeline_spec",
+    "ai_chat_tool_manifest",
+    "filetype_index_spec",
+    "reality_os_spec",
+    "reality_os_swarm_policy",
+    "reality_upgrade_stream",
+];
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AiChatRoleSpec {
+    pub id: String,
+    pub subject_id: String,
+    pub ai_chat_role: String,
+    #[serde(default)]
+    pub allowed_modes: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Either a module list with constraints, or a UI scope bound to a
+/// capability file; both shapes share the record type.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServiceScopeSpec {
+    pub id: String,
+    pub subject_id: String,
+    #[serde(default)]
+    pub scope: Option<String>,
+    #[serde(default)]
+    pub modules: Vec<String>,
+    #[serde(default)]
+    pub constraints: BTreeMap<String, String>,
+    #[serde(default)]
+    pub capability_ref: Option<String>,
+    #[serde(default)]
+    pub nvis_root: Option<String>,
+    #[serde(default)]
+    pub nwave_root: Option<String>,
+    #[serde(default)]
+    pub nui_flow_root: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuardModuleSpec {
+    pub id: String,
+    pub subject_id: String,
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    pub entrypoint: String,
+    pub envelope_type: String,
+    #[serde(default)]
+    pub required_for_domains: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KernelHeaderSpec {
+    pub id: String,
+    pub subject_id: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RiskModelRef {
+    pub id: String,
+    pub roh_model_aln: String,
+    pub global_ceiling: f32,
+    #[serde(default)]
+    pub monotone_invariant: Option<String>,
+    #[serde(default)]
+    pub tsafe_mode: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeScopeRef {
+    pub scope_id: String,
+    pub requires: Vec<String>,
+    pub token_kind: String,
+    pub multisig: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeSchemaRef {
+    pub id: String,
+    pub stake_aln: String,
+    pub required_roles: Vec<String>,
+    pub host_role: String,
+    #[serde(default)]
+    pub tokens: BTreeMap<String, String>,
+    #[serde(default)]
+    pub scopes: Vec<StakeScopeRef>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NeurorightsPolicyRef {
+    pub id: String,
+    pub neurorights_json: String,
+    #[serde(default)]
+    pub enforcement_modes: Vec<String>,
+    #[serde(default)]
+    pub mandatory_domains: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvolutionStreamRef {
+    pub id: String,
+    pub evolve_file: String,
+    pub record_type: String,
+    #[serde(default)]
+    pub roh_fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tsafe_field: Option<String>,
+    pub decision_enum: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DonutloopLedgerRef {
+    pub id: String,
+    pub ledger_file: String,
+    pub entry_type: String,
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub roh_invariant: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GuardPipelineRef {
+    pub id: String,
+    pub ordered_guards: Vec<String>,
+    /// Decision when a guard fails (`Allowed`, `Rejected` or `Deferred`).
+    /// Absent means `Rejected`: any guard failure aborts the change.
+    #[serde(default)]
+    pub failure_decision: Option<String>,
+    /// Per-guard overrides of `failure_decision`.
+    #[serde(default)]
+    pub failure_decisions: BTreeMap<String, String>,
+    #[serde(default)]
+    pub hard_invariants: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AiChatToolSpec {
+    pub tool_id: String,
+    pub capability_descriptor: String,
+    pub role: String,
+    #[serde(default)]
+    pub allowed_outputs: Vec<String>,
+    #[serde(default)]
+    pub forbidden_outputs: Vec<String>,
+    #[serde(default)]
+    pub reason_codes_on_violation: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AiChatToolManifestSpec {
+    pub id: String,
+    pub tools: Vec<AiChatToolSpec>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FiletypeIndexSpec {
+    pub id: String,
+    pub neurofs_index_aln: String,
+    #[serde(default)]
+    pub required_filetypes: Vec<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Pointer to a reality-OS shard.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ShardRef {
+    pub id: String,
+    #[serde(default)]
+    pub subject_id: Option<String>,
+    pub path: String,
+}
+
+/// A record with its 1-based manifest line.
+#[derive(Clone, Debug)]
+pub struct Located<T> {
+    pub line: usize,
+    pub record: T,
+}
+
+/// Record whose `type` this build does not know; kept verbatim so tools can
+/// pass it through.
+#[derive(Clone, Debug)]
+pub struct UnknownRecord {
+    pub line: usize,
+    pub type_name: String,
+    pub raw: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestIssue {
+    /// 1-based line, or 0 for manifest-wide issues.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "manifest: {}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+/// Every error found while loading, not just the first.
+#[derive(Clone, Debug)]
+pub struct ManifestError {
+    pub issues: Vec<ManifestIssue>,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.issues.iter().map(|i| i.to_string()).collect();
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl ManifestError {
+    pub(crate) fn missing(type_name: &str) -> Self {
+        ManifestError {
+            issues: vec![issue(0, format!("missing {type_name}"))],
+        }
+    }
+}
+
+fn issue(line: usize, message: impl Into<String>) -> ManifestIssue {
+    ManifestIssue {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parsed sovereign kernel manifest.
+#[derive(Clone, Debug)]
+pub struct KernelManifest {
+    /// Directory file references are resolved against.
+    pub base_dir: PathBuf,
+    pub records: Vec<Located<ManifestRecord>>,
+    pub unknown: Vec<UnknownRecord>,
+    pub warnings: Vec<ManifestIssue>,
+}
+
+impl KernelManifest {
+    /// Load and cross-check a manifest; file references resolve against the
+    /// manifest's directory.
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self, ManifestError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| ManifestError {
+            issues: vec![issue(0, format!("open {}: {e}", path.display()))],
+        })?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::parse(&text, base)
+    }
+
+    pub fn parse(text: &str, base_dir: &Path) -> Result<Self, ManifestError> {
+        let mut manifest = KernelManifest {
+            base_dir: base_dir.to_path_buf(),
+            records: Vec::new(),
+            unknown: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let mut errors = Vec::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            let lineno = idx + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let raw: Value = match serde_json::from_str(line) {
+                Ok(v) => v,
+                Err(e) => {
+                    errors.push(issue(lineno, format!("invalid JSON: {e}")));
+                    continue;
+                }
+            };
+            let type_name = match raw.get("type").and_then(Value::as_str) {
+                Some(t) => t.to_string(),
+                None => {
+                    errors.push(issue(lineno, "record has no string \"type\""));
+                    continue;
+                }
+            };
+            if !KNOWN_RECORD_TYPES.contains(&type_name.as_str()) {
+                manifest
+                    .warnings
+                    .push(issue(lineno, format!("unknown record type {type_name}, kept as-is")));
+                manifest.unknown.push(UnknownRecord {
+                    line: lineno,
+                    type_name,
+                    raw,
+                });
+                continue;
+            }
+            match serde_json::from_value::<ManifestRecord>(raw) {
+                Ok(record) => manifest.records.push(Located {
+                    line: lineno,
+                    record,
+                }),
+                Err(e) => errors.push(issue(lineno, format!("{type_name}: {e}"))),
+            }
+        }
+
+        if errors.is_empty() {
+            errors = manifest.cross_check();
+        }
+        if errors.is_empty() {
+            Ok(manifest)
+        } else {
+            Err(ManifestError { issues: errors })
+        }
+    }
+
+    pub fn header(&self) -> Option<&KernelHeaderSpec> {
+        self.records.iter().find_map(|r| match &r.record {
+            ManifestRecord::SovereignKernelHeader(h) => Some(h),
+            _ => None,
+        })
+    }
+
+    pub fn guard_pipeline(&self) -> Option<&GuardPipelineRef> {
+        self.records.iter().find_map(|r| match &r.record {
+            ManifestRecord::GuardPipelineSpec(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    pub fn guard_modules(&self) -> impl Iterator<Item = &GuardModuleSpec> {
+        self.records.iter().filter_map(|r| match &r.record {
+            ManifestRecord::GuardModule(m) => Some(m),
+            _ => None,
+        })
+    }
+
+    /// Records of the given kind, e.g. `|r| matches!(r, ManifestRecord::ServiceScope(_))`.
+    pub fn records_where<F>(&self, pred: F) -> impl Iterator<Item = &Located<ManifestRecord>>
+    where
+        F: Fn(&ManifestRecord) -> bool,
+    {
+        self.records.iter().filter(move |r| pred(&r.record))
+    }
+
+    fn singleton(&self, type_name: &str, errors: &mut Vec<ManifestIssue>) {
+        let lines: Vec<usize> = self
+            .records
+            .iter()
+            .filter(|r| record_type(&r.record) == type_name)
+            .map(|r| r.line)
+            .collect();
+        match lines.as_slice() {
+            [] => errors.push(issue(0, format!("missing {type_name}"))),
+            [_] => {}
+            [_, rest @ ..] => {
+                for l in rest {
+                    errors.push(issue(*l, format!("duplicate {type_name}")));
+                }
+            }
+        }
+    }
+
+    fn cross_check(&self) -> Vec<ManifestIssue> {
+        let mut errors = Vec::new();
+
+        for type_name in SINGLETON_RECORD_TYPES {
+            self.singleton(type_name, &mut errors);
+        }
+
+        // Ids are unique per record type.
+        let mut seen = HashSet::new();
+        for r in &self.records {
+            if !seen.insert((record_type(&r.record), r.record.id().to_string())) {
+                errors.push(issue(
+                    r.line,
+                    format!("duplicate {} id {}", record_type(&r.record), r.record.id()),
+                ));
+            }
+        }
+
+        // Every subject-bound record belongs to the header's subject.
+        if let Some(header) = self.header() {
+            for r in &self.records {
+                if let Some(subject) = r.record.subject_id() {
+                    if subject != header.subject_id {
+                        errors.push(issue(
+                            r.line,
+                            format!(
+                                "subject_id {} differs from kernel header subject {}",
+                                subject, header.subject_id
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let stages: Vec<&str> = self
+            .guard_pipeline()
+            .map(|p| p.ordered_guards.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+
+        for r in &self.records {
+            match &r.record {
+                ManifestRecord::GuardModule(m)
+                    if !stages.iter().any(|s| stage_backed_by(s, &m.crate_name)) =>
+                {
+                    errors.push(issue(
+                        r.line,
+                        format!(
+                            "guard_module crate {} backs no guard_pipeline_spec stage ({})",
+                            m.crate_name,
+                            stages.join(", ")
+                        ),
+                    ));
+                }
+                ManifestRecord::ServiceScope(s) => {
+                    if let Some(cap) = &s.capability_ref {
+                        if !self.base_dir.join(cap).is_file() {
+                            errors.push(issue(
+                                r.line,
+                                format!("service_scope {} capability_ref {} does not exist", s.id, cap),
+                            ));
+                        }
+                    }
+                }
+                ManifestRecord::RiskModelSpec(m) => {
+                    if !(m.global_ceiling > 0.0 && m.global_ceiling <= ROH_CEILING_MAX) {
+                        errors.push(issue(
+                            r.line,
+                            format!("global_ceiling {} is outside (0, {ROH_CEILING_MAX}]", m.global_ceiling),
+                        ));
+                    }
+                    match m.monotone_invariant.as_deref().map(monotone_bound) {
+                        None => errors.push(issue(r.line, "risk_model_spec has no monotone_invariant")),
+                        Some(Err(e)) => errors.push(issue(r.line, format!("monotone_invariant: {e}"))),
+                        Some(Ok(bound)) if (bound - m.global_ceiling).abs() > 1e-6 => errors.push(issue(
+                            r.line,
+                            format!(
+                                "monotone_invariant bounds RoH by {bound}, but global_ceiling is {}",
+                                m.global_ceiling
+                            ),
+                        )),
+                        Some(Ok(_)) => {}
+                    }
+                }
+                ManifestRecord::GuardPipelineSpec(p) => {
+                    for required in REQUIRED_GUARDS {
+                        if !p.ordered_guards.iter().any(|g| g == required) {
+                            errors.push(issue(r.line, format!("ordered_guards missing {required}")));
+                        }
+                    }
+                    let mut names = HashSet::new();
+                    for g in &p.ordered_guards {
+                        if !names.insert(g) {
+                            errors.push(issue(r.line, format!("guard {g} listed twice")));
+                        }
+                    }
+                    if let Some(Err(e)) = p.failure_decision.as_deref().map(GuardDecision::parse) {
+                        errors.push(issue(r.line, format!("failure_decision: {e}")));
+                    }
+                    for (g, d) in &p.failure_decisions {
+                        if !p.ordered_guards.contains(g) {
+                            errors.push(issue(r.line, format!("failure_decisions names unknown guard {g}")));
+                        }
+                        if let Err(e) = GuardDecision::parse(d) {
+                            errors.push(issue(r.line, format!("failure_decisions.{g}: {e}")));
+                        }
+                    }
+                }
+                ManifestRecord::StakeSchemaSpec(s) => {
+                    let evolve = s.tokens.get("evolve_kind").map_or("EVOLVE", String::as_str);
+                    for scope in s.scopes.iter().filter(|sc| MULTISIG_SCOPES.contains(&sc.scope_id.as_str())) {
+                        if !scope.multisig || scope.token_kind != evolve {
+                            errors.push(issue(
+                                r.line,
+                                format!("scope {} must require multisig and {evolve}", scope.scope_id),
+                            ));
+                        }
+                    }
+                    if !s.required_roles.contains(&s.host_role) {
+                        errors.push(issue(
+                            r.line,
+                            format!("host_role {} is not in required_roles", s.host_role),
+                        ));
+                    }
+                    for scope in &s.scopes {
+                        for role in &scope.requires {
+                            if !s.required_roles.contains(role) {
+                                errors.push(issue(
+                                    r.line,
+                                    format!("scope {} requires undeclared role {}", scope.scope_id, role),
+                                ));
+                            }
+                        }
+                    }
+                }
+                ManifestRecord::EvolutionStreamSpec(s) => {
+                    for d in &s.decision_enum {
+                        if let Err(e) = GuardDecision::parse(d) {
+                            errors.push(issue(r.line, format!("decision_enum: {e}")));
+                        }
+                    }
+                }
+                ManifestRecord::NeurorightsPolicySpec(p) => {
+                    for domain in &p.mandatory_domains {
+                        if !self
+                            .guard_modules()
+                            .any(|m| m.required_for_domains.contains(domain))
+                        {
+                            errors.push(issue(
+                                r.line,
+                                format!("mandatory domain {domain} has no guard_module"),
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        errors
+    }
+}
+
+fn record_type(record: &ManifestRecord) -> &'static str {
+    match record {
+        ManifestRecord::AiChatRole(_) => "ai_chat_role",
+        ManifestRecord::ServiceScope(_) => "service_scope",
+        ManifestRecord::GuardModule(_) => "guard_module",
+        ManifestRecord::SovereignKernelHeader(_) => "sovereign_kernel_header",
+        ManifestRecord::RiskModelSpec(_) => "risk_model_spec",
+        ManifestRecord::StakeSchemaSpec(_) => "stake_schema_spec",
+        ManifestRecord::NeurorightsPolicySpec(_) => "neurorights_policy_spec",
+        ManifestRecord::EvolutionStreamSpec(_) => "evolution_stream_spec",
+        ManifestRecord::DonutloopLedgerSpec(_) => "donutloop_ledger_spec",
+        ManifestRecord::GuardPipelineSpec(_) => "guard_pipeline_spec",
+        ManifestRecord::AiChatToolManifest(_) => "ai_chat_tool_manifest",
+        ManifestRecord::FiletypeIndexSpec(_) => "filetype_index_spec",
+        ManifestRecord::RealityOsSpec(_) => "reality_os_spec",
+        ManifestRecord::RealityOsSwarmPolicy(_) => "reality_os_swarm_policy",
+        ManifestRecord::RealityUpgradeStream(_) => "reality_upgrade_stream",
+    }
+}
+
+/// Guard crates are named `<family>-<kind>` (`neurorights-firewall`) and back
+/// the pipeline stage `<family>_guard` (`neurorights_guard`).
+pub(crate) fn stage_backed_by(stage: &str, crate_name: &str) -> bool {
+    let family = crate_name.split(['-', '_']).next().unwrap_or(crate_name);
+    stage == crate_name.replace('-', "_") || stage.strip_suffix("_guard") == Some(family)
+}
+
+/// The ceiling stated by a monotone invariant of the form
+/// `roh_after <= roh_before <= <ceiling>`.
+fn monotone_bound(invariant: &str) -> Result<f32, String> {
+    match invariant.split("<=").map(str::trim).collect::<Vec<_>>()[..] {
+        ["roh_after", "roh_before", bound] => bound
+            .parse()
+            .map_err(|_| format!("{bound:?} is not a number in {invariant:?}")),
+        _ => Err(format!("{invariant:?} is not \"roh_after <= roh_before <= <ceiling>\"")),
+    }
+}
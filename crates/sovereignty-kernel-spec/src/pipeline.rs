@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::manifest::{stage_backed_by, KernelManifest};
+use crate::{GuardStageSpec, SovereignKernelConfig, SovereigntyGuardPipelineSpec};
+
+/// Applied when a v2 `guard_pipeline_spec` names no failure decision: the
+/// shipped pipeline requires that any guard failure aborts the change.
+pub const DEFAULT_FAILURE_DECISION: GuardDecision = GuardDecision::Rejected;
+
+/// `sourcecrate` of stages no `guard_module` record claims; the kernel
+/// implements them itself.
+pub const KERNEL_GUARD_CRATE: &str = "sovereigntycore";
+
+/// Decision values a stage may name in `failuredecision`
+/// (same set as the evolve stream `decision_enum`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuardDecision {
+    /// Failure is advisory: recorded in the trace, pipeline continues.
+    Allowed,
+    /// Failure aborts the pipeline; the proposal is rejected.
+    Rejected,
+    /// Failure aborts the pipeline; the proposal is parked for review.
+    Deferred,
+}
+
+impl GuardDecision {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "Allowed" => Ok(GuardDecision::Allowed),
+            "Rejected" => Ok(GuardDecision::Rejected),
+            "Deferred" => Ok(GuardDecision::Deferred),
+            other => Err(format!("unknown failuredecision '{}'", other)),
+        }
+    }
+}
+
+impl SovereigntyGuardPipelineSpec {
+    /// Runnable stages for a v2 manifest's `guard_pipeline_spec`. Order is
+    /// the `ordered_guards` position. A stage backed by a `guard_module`
+    /// runs that module's crate and entrypoint; any other stage is keyed as
+    /// (`KERNEL_GUARD_CRATE`, stage name). A failing stage applies its
+    /// `failure_decisions` entry, else the record's `failure_decision`, else
+    /// `DEFAULT_FAILURE_DECISION`.
+    pub fn from_manifest(manifest: &KernelManifest) -> Option<Self> {
+        let pipeline = manifest.guard_pipeline()?;
+        let stages = pipeline
+            .ordered_guards
+            .iter()
+            .enumerate()
+            .map(|(order, name)| {
+                let module = manifest
+                    .guard_modules()
+                    .find(|m| stage_backed_by(name, &m.crate_name));
+                GuardStageSpec {
+                    order: order as u8,
+                    name: name.clone(),
+                    sourcecrate: module.map_or(KERNEL_GUARD_CRATE, |m| m.crate_name.as_str()).to_string(),
+                    function: module.map_or(name.as_str(), |m| m.entrypoint.as_str()).to_string(),
+                    failuredecision: pipeline
+                        .failure_decisions
+                        .get(name)
+                        .or(pipeline.failure_decision.as_ref())
+                        .cloned()
+                        .unwrap_or_else(|| format!("{DEFAULT_FAILURE_DECISION:?}")),
+                }
+            })
+            .collect();
+        Some(SovereigntyGuardPipelineSpec {
+            id: pipeline.id.clone(),
+            subjectid: manifest.header().map(|h| h.subject_id.clone()).unwrap_or_default(),
+            stages,
+        })
+    }
+}
+
+/// Result of running one guard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum StageOutcome {
+    Pass,
+    Fail { reason: String },
+    /// Not run because an earlier stage aborted the pipeline.
+    Skipped,
+}
+
+/// State threaded through the stages of one run. Guards read the proposal
+/// and config, and may leave facts for later stages (e.g. parseproposal
+/// storing roh_before/roh_after for rohguard).
+pub struct GuardContext<'a> {
+    pub config: &'a SovereignKernelConfig,
+    pub proposal: &'a Value,
+    pub facts: Map<String, Value>,
+    /// Decision so far; stages after an advisory failure can inspect it.
+    pub decision: GuardDecision,
+}
+
+/// A guard implementation.
+pub type GuardFn = Box<dyn Fn(&mut GuardContext) -> StageOutcome + Send + Sync>;
+
+/// Guard implementations keyed by the manifest's (`sourcecrate`, `function`).
+#[derive(Default)]
+pub struct GuardRegistry {
+    guards: HashMap<(String, String), GuardFn>,
+}
+
+impl GuardRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<F>(&mut self, sourcecrate: &str, function: &str, guard: F)
+    where
+        F: Fn(&mut GuardContext) -> StageOutcome + Send + Sync + 'static,
+    {
+        self.guards
+            .insert((sourcecrate.to_string(), function.to_string()), Box::new(guard));
+    }
+
+    pub fn get(&self, stage: &GuardStageSpec) -> Option<&GuardFn> {
+        self.guards
+            .get(&(stage.sourcecrate.clone(), stage.function.clone()))
+    }
+}
+
+/// One line of the per-run trace.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StageTrace {
+    pub order: u8,
+    pub name: String,
+    pub sourcecrate: String,
+    pub function: String,
+    pub outcome: StageOutcome,
+    /// failuredecision applied when the stage failed.
+    pub applied: Option<GuardDecision>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PipelineResult {
+    pub decision: GuardDecision,
+    /// Stage that aborted the run, if any.
+    pub halted_at: Option<String>,
+    pub trace: Vec<StageTrace>,
+}
+
+/// Executes `SovereigntyGuardPipelineSpec` stages in `order` against a
+/// proposal. Stage order and composition come from the manifest; only the
+/// guard implementations are compiled in.
+pub struct GuardPipeline<'a> {
+    config: &'a SovereignKernelConfig,
+    registry: &'a GuardRegistry,
+    stages: Vec<(GuardStageSpec, GuardDecision)>,
+}
+
+impl<'a> GuardPipeline<'a> {
+    /// Resolve every stage up front so a manifest naming an unknown guard or
+    /// decision fails at load time, not on the first proposal.
+    pub fn new(config: &'a SovereignKernelConfig, registry: &'a GuardRegistry) -> Result<Self, String> {
+        let mut stages = Vec::with_capacity(config.guardpipeline.stages.len());
+        for stage in &config.guardpipeline.stages {
+            if registry.get(stage).is_none() {
+                return Err(format!(
+                    "guardpipeline stage {} names unregistered guard {}::{}",
+                    stage.name, stage.sourcecrate, stage.function
+                ));
+            }
+            let decision = GuardDecision::parse(&stage.failuredecision)
+                .map_err(|e| format!("guardpipeline stage {}: {}", stage.name, e))?;
+            stages.push((stage.clone(), decision));
+        }
+        stages.sort_by_key(|(s, _)| s.order);
+        Ok(Self {
+            config,
+            registry,
+            stages,
+        })
+    }
+
+    pub fn run(&self, proposal: &Value) -> PipelineResult {
+        let mut ctx = GuardContext {
+            config: self.config,
+            proposal,
+            facts: Map::new(),
+            decision: GuardDecision::Allowed,
+        };
+        let mut trace = Vec::with_capacity(self.stages.len());
+        let mut halted_at = None;
+
+        for (stage, on_failure) in &self.stages {
+            let mut entry = StageTrace {
+                order: stage.order,
+                name: stage.name.clone(),
+                sourcecrate: stage.sourcecrate.clone(),
+                function: stage.function.clone(),
+                outcome: StageOutcome::Skipped,
+                applied: None,
+            };
+
+            if halted_at.is_none() {
+                let guard = self.registry.get(stage).expect("resolved in GuardPipeline::new");
+                entry.outcome = guard(&mut ctx);
+                if let StageOutcome::Fail { .. } = entry.outcome {
+                    entry.applied = Some(*on_failure);
+                    if *on_failure != GuardDecision::Allowed {
+                        ctx.decision = *on_failure;
+                        halted_at = Some(stage.name.clone());
+                    }
+                }
+            }
+            trace.push(entry);
+        }
+
+        PipelineResult {
+            decision: ctx.decision,
+            halted_at,
+            trace,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+
+use sovereignty_kernel_spec::manifest::{KernelManifest, ManifestError};
+use sovereignty_kernel_spec::SovereignKernelConfig;
+
+const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+fn shipped() -> String {
+    std::fs::read_to_string(repo_root().join("bostrom-sovereign-kernel-v2.ndjson")).unwrap()
+}
+
+/// Parse `text` as if it sat at the repo root, beside the shipped manifest.
+fn parse(text: &str) -> Result<KernelManifest, ManifestError> {
+    KernelManifest::parse(text, &repo_root())
+}
+
+fn issues(err: ManifestError) -> Vec<(usize, String)> {
+    err.issues.into_iter().map(|i| (i.line, i.message)).collect()
+}
+
+#[test]
+fn shipped_manifest_loads_into_the_kernel_config() {
+    let cfg = SovereignKernelConfig::load_from_path(repo_root().join("bostrom-sovereign-kernel-v2.ndjson")).unwrap();
+    assert_eq!(cfg.header.subject_id, SUBJECT);
+    assert_eq!(cfg.header.version, "2.0.0");
+    assert!((cfg.riskmodel.global_ceiling - 0.30).abs() < 1e-6);
+    assert_eq!(cfg.stakeschema.host_role, "Host");
+    assert_eq!(cfg.evolvestream.decision_enum, ["Allowed", "Rejected", "Deferred"]);
+    assert!(cfg.manifest.warnings.is_empty());
+    assert!(cfg.manifest.unknown.is_empty());
+
+    let stages: Vec<(u8, &str, &str)> = cfg
+        .guardpipeline
+        .stages
+        .iter()
+        .map(|s| (s.order, s.name.as_str(), s.sourcecrate.as_str()))
+        .collect();
+    assert_eq!(stages.len(), 8);
+    assert_eq!(stages[0], (0, "parse_spec", "sovereigntycore"));
+    assert_eq!(stages[3], (3, "neurorights_guard", "neurorights-firewall"));
+    assert_eq!(cfg.guardpipeline.stages[3].function, "process_prompt");
+    assert_eq!(stages[7], (7, "decision_writer", "sovereigntycore"));
+}
+
+#[test]
+fn errors_carry_line_numbers_and_are_all_reported() {
+    let mut lines: Vec<String> = shipped().lines().map(str::to_string).collect();
+    lines.insert(1, "{not json".to_string());
+    lines.insert(2, r#"{"id":"no-type"}"#.to_string());
+    lines.insert(3, r#"{"type":"donutloop_ledger_spec","id":"half"}"#.to_string());
+
+    let found = issues(parse(&lines.join("\n")).unwrap_err());
+    let lines_reported: Vec<usize> = found.iter().map(|(l, _)| *l).collect();
+    assert_eq!(lines_reported, [2, 3, 4], "{found:?}");
+    assert!(found[0].1.starts_with("invalid JSON"));
+    assert!(found[1].1.contains("no string \"type\""));
+    assert!(found[2].1.contains("missing field `ledger_file`"));
+
+    // The same errors through the config entry point, with line prefixes.
+    let dir = std::env::temp_dir().join(format!("kernel-spec-lines-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("kernel.ndjson");
+    std::fs::write(&path, lines.join("\n")).unwrap();
+    let message = SovereignKernelConfig::load_from_path(&path).unwrap_err().to_string();
+    assert!(message.starts_with("line 2: invalid JSON"), "{message}");
+    assert!(message.contains("; line 4: donutloop_ledger_spec: missing field"), "{message}");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn unknown_record_types_are_kept_with_a_warning() {
+    let text = format!("{}\n{{\"type\":\"future_shard\",\"id\":\"x\",\"payload\":[1,2]}}\n", shipped().trim_end());
+    let manifest = parse(&text).unwrap();
+    assert_eq!(manifest.unknown.len(), 1);
+    assert_eq!(manifest.unknown[0].line, 17);
+    assert_eq!(manifest.unknown[0].type_name, "future_shard");
+    assert_eq!(manifest.unknown[0].raw["payload"][1], 2);
+    assert_eq!(manifest.warnings.len(), 1);
+    assert_eq!(manifest.warnings[0].line, 17);
+    assert!(manifest.warnings[0].to_string().starts_with("line 17: unknown record type future_shard"));
+    assert!(SovereignKernelConfig::from_manifest(manifest).is_ok());
+}
+
+#[test]
+fn cross_check_reports_broken_references_and_invariants() {
+    let mut lines: Vec<String> = shipped().lines().map(str::to_string).collect();
+    // Line 3: guard_module for a crate that backs no stage, bound to another subject.
+    lines[2] = lines[2]
+        .replace("\"neurorights-firewall\"", "\"lifeforce-guards\"")
+        .replace(SUBJECT, "bostrom1other");
+    // Line 4: capability file that does not exist.
+    lines[3] = lines[3].replace("neuromorph-ui-v1.capability.aln", "missing.capability.aln");
+    // Line 7: arch_change without multisig.
+    lines[6] = lines[6].replace(
+        r#""scope_id":"arch_change","requires":["Host","ResearchAgent"],"token_kind":"EVOLVE","multisig":true"#,
+        r#""scope_id":"arch_change","requires":["Host","ResearchAgent"],"token_kind":"EVOLVE","multisig":false"#,
+    );
+    // Line 11: pipeline without its stake guard.
+    lines[10] = lines[10].replace("\"stake_guard\",", "");
+    // Line 17: a second header.
+    lines.push(lines[4].clone());
+
+    let found = issues(parse(&lines.join("\n")).unwrap_err());
+    let has = |line: usize, needle: &str| found.iter().any(|(l, m)| *l == line && m.contains(needle));
+    assert!(has(17, "duplicate sovereign_kernel_header"), "{found:?}");
+    assert!(has(3, "differs from kernel header subject"), "{found:?}");
+    assert!(has(3, "crate lifeforce-guards backs no guard_pipeline_spec stage"), "{found:?}");
+    assert!(has(4, "missing.capability.aln does not exist"), "{found:?}");
+    assert!(has(7, "scope arch_change must require multisig"), "{found:?}");
+    assert!(has(11, "ordered_guards missing stake_guard"), "{found:?}");
+}
+
+#[test]
+fn every_singleton_record_is_required() {
+    let text = shipped();
+    let without_ledger: Vec<&str> = text
+        .lines()
+        .filter(|l| !l.contains("\"donutloop_ledger_spec\""))
+        .collect();
+    let found = issues(parse(&without_ledger.join("\n")).unwrap_err());
+    assert_eq!(found, [(0, "missing donutloop_ledger_spec".to_string())]);
+}
+
+#[test]
+fn risk_model_ceiling_and_monotone_invariant_are_enforced() {
+    let risk_line = |text: &str| {
+        let lines: Vec<&str> = text.lines().collect();
+        lines.iter().position(|l| l.contains("\"risk_model_spec\"")).unwrap() + 1
+    };
+    let edit = |from: &str, to: &str| {
+        let text = shipped();
+        assert!(text.contains(from), "{from}");
+        let line = risk_line(&text);
+        (line, issues(parse(&text.replace(from, to)).unwrap_err()))
+    };
+    let invariant = r#""monotone_invariant":"roh_after <= roh_before <= 0.30","#;
+
+    let (line, found) = edit(r#""global_ceiling":0.30"#, r#""global_ceiling":0.50"#);
+    assert!(found.contains(&(line, "global_ceiling 0.5 is outside (0, 0.3]".to_string())), "{found:?}");
+    // The invariant still names 0.30, which now disagrees with the ceiling.
+    assert!(found.iter().any(|(l, m)| *l == line && m.contains("global_ceiling is 0.5")), "{found:?}");
+
+    let (line, found) = edit(invariant, "");
+    assert_eq!(found, [(line, "risk_model_spec has no monotone_invariant".to_string())]);
+
+    let (line, found) = edit(invariant, r#""monotone_invariant":"roh_after <= 0.30","#);
+    assert_eq!(found.len(), 1, "{found:?}");
+    assert!(found[0].0 == line && found[0].1.starts_with("monotone_invariant: "), "{found:?}");
+
+    let (line, found) = edit(invariant, r#""monotone_invariant":"roh_before <= roh_after <= 0.30","#);
+    assert!(found.len() == 1 && found[0].0 == line, "{found:?}");
+
+    let (line, found) = edit(invariant, r#""monotone_invariant":"roh_after <= roh_before <= 0.25","#);
+    assert_eq!(
+        found,
+        [(line, "monotone_invariant bounds RoH by 0.25, but global_ceiling is 0.3".to_string())]
+    );
+}
@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+use sovereignty_kernel_spec::manifest::KernelManifest;
+use sovereignty_kernel_spec::pipeline::{GuardContext, GuardDecision, GuardPipeline, GuardRegistry, StageOutcome};
+use sovereignty_kernel_spec::SovereignKernelConfig;
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// The shipped manifest, with `edit` applied to its guard_pipeline_spec line.
+fn config(edit: impl Fn(&str) -> String) -> SovereignKernelConfig {
+    let text = std::fs::read_to_string(repo_root().join("bostrom-sovereign-kernel-v2.ndjson")).unwrap();
+    let text: Vec<String> = text
+        .lines()
+        .map(|l| if l.contains("\"guard_pipeline_spec\"") { edit(l) } else { l.to_string() })
+        .collect();
+    let manifest = KernelManifest::parse(&text.join("\n"), &repo_root()).unwrap();
+    SovereignKernelConfig::from_manifest(manifest).unwrap()
+}
+
+type RunLog = Arc<Mutex<Vec<String>>>;
+
+/// Every stage appends its name to the returned log, and fails if the
+/// proposal lists it under "fail".
+fn recording_guards(cfg: &SovereignKernelConfig) -> (GuardRegistry, RunLog) {
+    let log = RunLog::default();
+    let mut registry = GuardRegistry::new();
+    for stage in &cfg.guardpipeline.stages {
+        let name = stage.name.clone();
+        let log = log.clone();
+        registry.register(&stage.sourcecrate, &stage.function, move |ctx: &mut GuardContext| {
+            log.lock().unwrap().push(name.clone());
+            let fails = ctx.proposal["fail"].as_array().is_some_and(|f| f.contains(&json!(name)));
+            if fails {
+                StageOutcome::Fail {
+                    reason: format!("{name} refused"),
+                }
+            } else {
+                StageOutcome::Pass
+            }
+        });
+    }
+    (registry, log)
+}
+
+fn names(cfg: &SovereignKernelConfig) -> Vec<String> {
+    cfg.guardpipeline.stages.iter().map(|s| s.name.clone()).collect()
+}
+
+#[test]
+fn stages_run_in_manifest_order() {
+    let cfg = config(str::to_string);
+    let (registry, log) = recording_guards(&cfg);
+    let pipeline = GuardPipeline::new(&cfg, &registry).unwrap();
+
+    let result = pipeline.run(&json!({}));
+    assert_eq!(result.decision, GuardDecision::Allowed);
+    assert_eq!(result.halted_at, None);
+    let traced: Vec<String> = result.trace.iter().map(|t| t.name.clone()).collect();
+    assert_eq!(traced, names(&cfg));
+    assert_eq!(*log.lock().unwrap(), traced);
+    assert_eq!(traced[2], "roh_monotonicity_guard");
+    assert!(result.trace.iter().all(|t| t.outcome == StageOutcome::Pass && t.applied.is_none()));
+
+    // Reordering the manifest reorders the run without recompiling.
+    let cfg = config(|l| l.replace("\"stake_guard\",\"token_guard\"", "\"token_guard\",\"stake_guard\""));
+    let (registry, log) = recording_guards(&cfg);
+    GuardPipeline::new(&cfg, &registry).unwrap().run(&json!({}));
+    assert_eq!(log.lock().unwrap()[4..6], ["token_guard", "stake_guard"]);
+}
+
+#[test]
+fn a_failing_stage_rejects_and_later_stages_are_skipped() {
+    let cfg = config(str::to_string);
+    let (registry, log) = recording_guards(&cfg);
+    let pipeline = GuardPipeline::new(&cfg, &registry).unwrap();
+
+    let result = pipeline.run(&json!({ "fail": ["stake_guard", "decision_writer"] }));
+    assert_eq!(log.lock().unwrap().last().map(String::as_str), Some("stake_guard"));
+    assert_eq!(result.decision, GuardDecision::Rejected);
+    assert_eq!(result.halted_at.as_deref(), Some("stake_guard"));
+
+    let stake = result.trace.iter().position(|t| t.name == "stake_guard").unwrap();
+    for (i, t) in result.trace.iter().enumerate() {
+        match i.cmp(&stake) {
+            std::cmp::Ordering::Less => assert_eq!(t.outcome, StageOutcome::Pass),
+            std::cmp::Ordering::Equal => {
+                assert_eq!(
+                    t.outcome,
+                    StageOutcome::Fail {
+                        reason: "stake_guard refused".to_string()
+                    }
+                );
+                assert_eq!(t.applied, Some(GuardDecision::Rejected));
+            }
+            std::cmp::Ordering::Greater => {
+                assert_eq!(t.outcome, StageOutcome::Skipped, "{}", t.name);
+                assert_eq!(t.applied, None);
+            }
+        }
+    }
+    assert_eq!(result.trace.len(), cfg.guardpipeline.stages.len());
+}
+
+#[test]
+fn failure_decisions_come_from_the_manifest() {
+    let cfg = config(|l| {
+        l.replace(
+            "\"ordered_guards\"",
+            r#""failure_decisions":{"token_guard":"Allowed","actuation_forbidden_guard":"Deferred"},"ordered_guards""#,
+        )
+    });
+    let (registry, _) = recording_guards(&cfg);
+    let pipeline = GuardPipeline::new(&cfg, &registry).unwrap();
+
+    // An advisory failure is traced but the run continues.
+    let result = pipeline.run(&json!({ "fail": ["token_guard"] }));
+    assert_eq!(result.decision, GuardDecision::Allowed);
+    assert_eq!(result.halted_at, None);
+    let token = result.trace.iter().find(|t| t.name == "token_guard").unwrap();
+    assert_eq!(token.applied, Some(GuardDecision::Allowed));
+    assert_eq!(result.trace.last().unwrap().outcome, StageOutcome::Pass);
+
+    let result = pipeline.run(&json!({ "fail": ["token_guard", "actuation_forbidden_guard"] }));
+    assert_eq!(result.decision, GuardDecision::Deferred);
+    assert_eq!(result.halted_at.as_deref(), Some("actuation_forbidden_guard"));
+    assert_eq!(result.trace.last().unwrap().outcome, StageOutcome::Skipped);
+
+    // An unknown decision name is a manifest error on the pipeline's line.
+    let text = std::fs::read_to_string(repo_root().join("bostrom-sovereign-kernel-v2.ndjson"))
+        .unwrap()
+        .replace("\"ordered_guards\"", r#""failure_decision":"Maybe","ordered_guards""#);
+    let err = KernelManifest::parse(&text, &repo_root()).unwrap_err();
+    assert_eq!(err.to_string(), "line 11: failure_decision: unknown failuredecision 'Maybe'");
+}
+
+#[test]
+fn unregistered_guards_fail_at_construction() {
+    let cfg = config(str::to_string);
+    let mut registry = GuardRegistry::new();
+    for stage in cfg.guardpipeline.stages.iter().filter(|s| s.name != "neurorights_guard") {
+        registry.register(&stage.sourcecrate, &stage.function, |_: &mut GuardContext| StageOutcome::Pass);
+    }
+    let err = GuardPipeline::new(&cfg, &registry).err().unwrap();
+    assert_eq!(
+        err,
+        "guardpipeline stage neurorights_guard names unregistered guard neurorights-firewall::process_prompt"
+    );
+}
@@ -1,12 +1,20 @@
+use std::path::Path;
+
+use organiccpualn::roh_registry::RohRegistry;
+use sovereignty_kernel_spec::manifest::KernelManifest;
+
 fn main() {
-    // Load canonical shards using organiccpualn loaders.
-    let roh = organiccpualn::rohmodel::load_default().expect("load RoH");
-    assert!(roh.validate_invariants(), "RoH model invalid");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let shards = root.join("qpudatashards/particles");
+    let kernel = root.join("bostrom-sovereign-kernel-v2.ndjson");
+    println!("cargo:rerun-if-changed={}", shards.display());
+    println!("cargo:rerun-if-changed={}", kernel.display());
 
-    let stake = organiccpualn::stake::load_default().expect("load stake");
-    assert!(stake.validate_invariants(), "stake shard invalid");
+    // Every shipped RoH model must register: valid axes, kind and ceiling.
+    RohRegistry::load_dir(&shards).expect("load RoH models");
 
     // Fail build if wiring is missing.
-    sovereigntycore_schema::assert_guard_pipeline_complete()
-        .expect("sovereignty guard pipeline incomplete");
+    if let Err(e) = KernelManifest::load_from_path(&kernel) {
+        panic!("sovereignty guard pipeline incomplete:\n{e}");
+    }
 }
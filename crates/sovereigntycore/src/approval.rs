@@ -0,0 +1,409 @@
+//! Multisig approval collection for stake-governed scopes.
+//!
+//! `StakeTable::check_multisig` decides whether a set of signer addresses is
+//! a quorum; this module collects and verifies the signatures behind that
+//! set. An `ApprovalBook` opens a pending approval per (proposal hash,
+//! `ScopeKind`), accepts detached signatures from the roles' addresses
+//! (bech32 Cosmos accounts or 0x EVM accounts), expires stale requests, and
+//! emits an `ApprovalBundle` once the scope's quorum rule is met. Consumers
+//! re-check the bundle with `ApprovalBundle::verify` rather than trusting
+//! whoever handed it over, and refuse bundles older than a TTL.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::stake::{ScopeKind, StakeRow, StakeTable};
+
+/// Default age after which `ApprovalBundle::verify` refuses a bundle.
+pub const DEFAULT_BUNDLE_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Bytes every approver signs: binds subject, scope and proposal hash.
+pub fn approval_message(subjectid: &str, scope: ScopeKind, proposal_hash: &str) -> Vec<u8> {
+    format!("neuropc/approval/v1\n{}\n{}\n{}", subjectid, scope.as_str(), proposal_hash).into_bytes()
+}
+
+/// A signature made outside this process by the holder of `address`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetachedSignature {
+    /// Address of the signer (a stake row's `bostromaddress`).
+    pub address: String,
+    /// Hex public key, in whatever encoding the verifier expects.
+    pub public_key: String,
+    /// Hex signature over `approval_message`.
+    pub signature: String,
+}
+
+/// Checks that a detached signature was made by the key behind its address.
+pub trait SignatureVerifier {
+    /// Whether this verifier understands `address` at all. Roles whose
+    /// address no verifier accepts could never sign.
+    fn accepts(&self, address: &str) -> bool;
+
+    fn verify(&self, message: &[u8], sig: &DetachedSignature) -> anyhow::Result<()>;
+}
+
+/// Cosmos-SDK accounts: compressed secp256k1 key, address =
+/// bech32(ripemd160(sha256(key))), ECDSA over sha256(message), 64-byte r||s.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CosmosSecp256k1Verifier;
+
+impl SignatureVerifier for CosmosSecp256k1Verifier {
+    fn accepts(&self, address: &str) -> bool {
+        bech32::decode(address).is_ok()
+    }
+
+    fn verify(&self, message: &[u8], sig: &DetachedSignature) -> anyhow::Result<()> {
+        use bech32::FromBase32;
+        use k256::ecdsa::signature::Verifier;
+        use ripemd::Ripemd160;
+        use sha2::{Digest, Sha256};
+
+        let key_bytes = hex::decode(&sig.public_key)?;
+        let (_hrp, data, _variant) = bech32::decode(&sig.address)?;
+        let account = Vec::<u8>::from_base32(&data)?;
+        let derived = Ripemd160::digest(Sha256::digest(&key_bytes));
+        if account != derived.as_slice() {
+            anyhow::bail!("public key does not belong to address {}", sig.address);
+        }
+
+        let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&key_bytes)?;
+        let signature = k256::ecdsa::Signature::from_slice(&hex::decode(&sig.signature)?)?;
+        key.verify(message, &signature)
+            .map_err(|_| anyhow::anyhow!("signature from {} does not verify", sig.address))
+    }
+}
+
+/// EVM accounts: secp256k1 key (compressed or uncompressed SEC1), address =
+/// 0x + last 20 bytes of keccak256(uncompressed key), ECDSA over the EIP-191
+/// `personal_sign` digest of the message; a trailing recovery byte is ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvmSecp256k1Verifier;
+
+impl EvmSecp256k1Verifier {
+    /// Lower-case 0x address of a SEC1 public key.
+    pub fn address_of(key: &k256::ecdsa::VerifyingKey) -> String {
+        use sha3::{Digest, Keccak256};
+
+        let point = key.to_encoded_point(false);
+        let hash = Keccak256::digest(&point.as_bytes()[1..]);
+        format!("0x{}", hex::encode(&hash[12..]))
+    }
+
+    /// keccak256("\x19Ethereum Signed Message:\n" || len || message).
+    pub fn personal_sign_digest(message: &[u8]) -> [u8; 32] {
+        use sha3::{Digest, Keccak256};
+
+        let mut hasher = Keccak256::new();
+        hasher.update(format!("\x19Ethereum Signed Message:\n{}", message.len()));
+        hasher.update(message);
+        hasher.finalize().into()
+    }
+}
+
+impl SignatureVerifier for EvmSecp256k1Verifier {
+    fn accepts(&self, address: &str) -> bool {
+        address.len() == 42
+            && address.starts_with("0x")
+            && address[2..].bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    fn verify(&self, message: &[u8], sig: &DetachedSignature) -> anyhow::Result<()> {
+        use k256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        if !self.accepts(&sig.address) {
+            anyhow::bail!("{} is not an EVM address", sig.address);
+        }
+        let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&hex::decode(sig.public_key.trim_start_matches("0x"))?)?;
+        // EVM addresses are compared case-insensitively (EIP-55 only adds a checksum).
+        if !Self::address_of(&key).eq_ignore_ascii_case(&sig.address) {
+            anyhow::bail!("public key does not belong to address {}", sig.address);
+        }
+
+        let mut bytes = hex::decode(sig.signature.trim_start_matches("0x"))?;
+        if bytes.len() == 65 {
+            bytes.pop();
+        }
+        let signature = k256::ecdsa::Signature::from_slice(&bytes)?;
+        key.verify_prehash(&Self::personal_sign_digest(message), &signature)
+            .map_err(|_| anyhow::anyhow!("signature from {} does not verify", sig.address))
+    }
+}
+
+/// Dispatches on address format: 0x addresses to `EvmSecp256k1Verifier`,
+/// everything else to `CosmosSecp256k1Verifier`. Covers every address kind a
+/// shipped `.stake.aln` uses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StakeAddressVerifier {
+    pub cosmos: CosmosSecp256k1Verifier,
+    pub evm: EvmSecp256k1Verifier,
+}
+
+impl SignatureVerifier for StakeAddressVerifier {
+    fn accepts(&self, address: &str) -> bool {
+        self.evm.accepts(address) || self.cosmos.accepts(address)
+    }
+
+    fn verify(&self, message: &[u8], sig: &DetachedSignature) -> anyhow::Result<()> {
+        if sig.address.starts_with("0x") {
+            self.evm.verify(message, sig)
+        } else {
+            self.cosmos.verify(message, sig)
+        }
+    }
+}
+
+/// A signature accepted for a role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleApproval {
+    pub roleid: String,
+    pub rolekind: String,
+    pub signature: DetachedSignature,
+}
+
+/// An approval still collecting signatures.
+#[derive(Debug, Clone)]
+pub struct PendingApproval {
+    pub proposal_hash: String,
+    pub scope: ScopeKind,
+    pub subjectid: String,
+    /// Unix seconds.
+    pub created_at: u64,
+    /// Unix seconds; the request is dropped at or after this time.
+    pub expires_at: u64,
+    pub required_roles: BTreeSet<String>,
+    /// rolekind -> first valid approval for it.
+    pub approvals: BTreeMap<String, RoleApproval>,
+}
+
+impl PendingApproval {
+    /// Required rolekinds that have not signed yet.
+    pub fn missing_roles(&self) -> BTreeSet<String> {
+        self.required_roles
+            .iter()
+            .filter(|r| !self.approvals.contains_key(*r))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A complete, verified quorum for one proposal and scope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalBundle {
+    pub proposal_hash: String,
+    /// `ScopeKind::as_str` label.
+    pub scope: String,
+    pub subjectid: String,
+    /// Unix seconds.
+    pub finalized_at: u64,
+    pub approvals: Vec<RoleApproval>,
+}
+
+impl ApprovalBundle {
+    pub fn scope_kind(&self) -> ScopeKind {
+        ScopeKind::from_str(&self.scope)
+    }
+
+    /// Addresses to pass as `UpdateProposal::signers` / `check_multisig`.
+    pub fn signer_addresses(&self) -> Vec<String> {
+        self.approvals.iter().map(|a| a.signature.address.clone()).collect()
+    }
+
+    /// Re-verify every signature and the quorum against `stake`, for
+    /// consumers that receive the bundle from elsewhere. A bundle finalized
+    /// `ttl_secs` or more before `now`, or after `now`, is refused.
+    pub fn verify(
+        &self,
+        stake: &StakeTable,
+        verifier: &dyn SignatureVerifier,
+        proposal_hash: &str,
+        now: u64,
+        ttl_secs: u64,
+    ) -> anyhow::Result<()> {
+        if self.proposal_hash != proposal_hash {
+            anyhow::bail!(
+                "approval bundle is for proposal {}, not {}",
+                self.proposal_hash,
+                proposal_hash
+            );
+        }
+        if self.finalized_at > now {
+            anyhow::bail!(
+                "approval bundle for proposal {} is finalized in the future ({} > {})",
+                self.proposal_hash,
+                self.finalized_at,
+                now
+            );
+        }
+        if now - self.finalized_at >= ttl_secs {
+            anyhow::bail!(
+                "approval bundle for proposal {} expired: finalized at {}, ttl {}s",
+                self.proposal_hash,
+                self.finalized_at,
+                ttl_secs
+            );
+        }
+        let scope = self.scope_kind();
+        let message = approval_message(&self.subjectid, scope, &self.proposal_hash);
+        for a in &self.approvals {
+            verifier.verify(&message, &a.signature)?;
+        }
+        stake.check_multisig(&self.subjectid, scope, &self.signer_addresses())
+    }
+}
+
+/// Result of submitting a signature.
+#[derive(Debug, Clone)]
+pub enum ApprovalStatus {
+    Pending { missing: BTreeSet<String> },
+    Finalized(ApprovalBundle),
+}
+
+/// Open approval requests for one stake table.
+pub struct ApprovalBook<V: SignatureVerifier> {
+    stake: StakeTable,
+    verifier: V,
+    /// Seconds a request stays open.
+    ttl_secs: u64,
+    pending: HashMap<(String, ScopeKind), PendingApproval>,
+}
+
+impl<V: SignatureVerifier> ApprovalBook<V> {
+    pub fn new(stake: StakeTable, verifier: V, ttl_secs: u64) -> Self {
+        Self {
+            stake,
+            verifier,
+            ttl_secs,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn stake(&self) -> &StakeTable {
+        &self.stake
+    }
+
+    pub fn pending(&self, proposal_hash: &str, scope: ScopeKind) -> Option<&PendingApproval> {
+        self.pending.get(&(proposal_hash.to_string(), scope))
+    }
+
+    /// Start collecting approvals. Fails if a live request already exists,
+    /// the scope needs no approval for this subject (nothing to collect), or
+    /// a required role has no address the verifier accepts (it could never
+    /// sign).
+    pub fn open(
+        &mut self,
+        proposal_hash: &str,
+        subjectid: &str,
+        scope: ScopeKind,
+        now: u64,
+    ) -> anyhow::Result<&PendingApproval> {
+        self.expire(now);
+        let key = (proposal_hash.to_string(), scope);
+        if self.pending.contains_key(&key) {
+            anyhow::bail!("approval for proposal {} scope {:?} is already pending", proposal_hash, scope);
+        }
+        let required: BTreeSet<String> = self.stake.required_roles_for_scope(subjectid, scope).into_iter().collect();
+        if required.is_empty() {
+            anyhow::bail!("scope {:?} requires no stake approval for subject {}", scope, subjectid);
+        }
+        let rows = self.stake.rows_for_subject(subjectid);
+        for role in &required {
+            let addresses: Vec<&str> = rows
+                .iter()
+                .filter(|r| &r.rolekind == role)
+                .map(|r| r.bostromaddress.as_str())
+                .collect();
+            if !addresses.iter().any(|a| self.verifier.accepts(a)) {
+                anyhow::bail!(
+                    "role {} required for scope {:?} of subject {} has no verifiable address ({:?})",
+                    role,
+                    scope,
+                    subjectid,
+                    addresses
+                );
+            }
+        }
+        let pending = PendingApproval {
+            proposal_hash: proposal_hash.to_string(),
+            scope,
+            subjectid: subjectid.to_string(),
+            created_at: now,
+            expires_at: now.saturating_add(self.ttl_secs),
+            required_roles: required,
+            approvals: BTreeMap::new(),
+        };
+        Ok(self.pending.entry(key).or_insert(pending))
+    }
+
+    /// Verify `sig` and record it for every required role held by its
+    /// address. Finalizes (and removes) the request once the stake rule's
+    /// quorum is met.
+    pub fn submit(
+        &mut self,
+        proposal_hash: &str,
+        scope: ScopeKind,
+        sig: DetachedSignature,
+        now: u64,
+    ) -> anyhow::Result<ApprovalStatus> {
+        self.expire(now);
+        let key = (proposal_hash.to_string(), scope);
+        let pending = self
+            .pending
+            .get_mut(&key)
+            .ok_or_else(|| anyhow::anyhow!("no pending approval for proposal {} scope {:?}", proposal_hash, scope))?;
+
+        let roles: Vec<&StakeRow> = self
+            .stake
+            .rows_for_subject(&pending.subjectid)
+            .iter()
+            .filter(|r| r.bostromaddress == sig.address && pending.required_roles.contains(&r.rolekind))
+            .collect();
+        if roles.is_empty() {
+            anyhow::bail!(
+                "{} holds no role required for scope {:?} of subject {}",
+                sig.address,
+                scope,
+                pending.subjectid
+            );
+        }
+        let message = approval_message(&pending.subjectid, scope, proposal_hash);
+        self.verifier.verify(&message, &sig)?;
+
+        for r in roles {
+            pending.approvals.entry(r.rolekind.clone()).or_insert_with(|| RoleApproval {
+                roleid: r.roleid.clone(),
+                rolekind: r.rolekind.clone(),
+                signature: sig.clone(),
+            });
+        }
+
+        // The stake table stays the single authority on what a quorum is;
+        // threshold rules can finalize before every role has signed.
+        let signers: Vec<String> = pending.approvals.values().map(|a| a.signature.address.clone()).collect();
+        if self.stake.check_multisig(&pending.subjectid, scope, &signers).is_err() {
+            return Ok(ApprovalStatus::Pending {
+                missing: pending.missing_roles(),
+            });
+        }
+
+        let pending = self.pending.remove(&key).expect("pending approval present");
+        Ok(ApprovalStatus::Finalized(ApprovalBundle {
+            proposal_hash: pending.proposal_hash,
+            scope: scope.as_str().to_string(),
+            subjectid: pending.subjectid,
+            finalized_at: now,
+            approvals: pending.approvals.into_values().collect(),
+        }))
+    }
+
+    /// Drop requests whose deadline has passed; returns them.
+    pub fn expire(&mut self, now: u64) -> Vec<PendingApproval> {
+        let stale: Vec<(String, ScopeKind)> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now >= p.expires_at)
+            .map(|(k, _)| k.clone())
+            .collect();
+        stale.into_iter().filter_map(|k| self.pending.remove(&k)).collect()
+    }
+}
@@ -1,9 +1,12 @@
+use crate::approval::{ApprovalBundle, SignatureVerifier, DEFAULT_BUNDLE_TTL_SECS};
 use crate::stake::{ScopeKind, StakeTable};
 use crate::update::UpdateProposal; // your existing proposal type
 
 #[derive(Debug, Clone)]
 pub struct StakeGuard {
     stake: StakeTable,
+    /// Seconds an approval bundle stays usable after it is finalized.
+    bundle_ttl_secs: u64,
 }
 
 impl StakeGuard {
@@ -13,11 +16,19 @@ impl StakeGuard {
         let stake = StakeTable::load_from_file(
             "policies/bostrom-stake-v1.stake.aln",
         )?;
-        Ok(Self { stake })
+        Ok(Self::new(stake))
     }
 
     pub fn new(stake: StakeTable) -> Self {
-        Self { stake }
+        Self {
+            stake,
+            bundle_ttl_secs: DEFAULT_BUNDLE_TTL_SECS,
+        }
+    }
+
+    pub fn with_bundle_ttl(mut self, secs: u64) -> Self {
+        self.bundle_ttl_secs = secs;
+        self
     }
 
     /// Enforce multisig for proposal scopes.
@@ -29,7 +40,57 @@ impl StakeGuard {
     ) -> anyhow::Result<()> {
         let subjectid = &proposal.subjectid;
 
-        // Map text scopes into ScopeKind values and collapse them.
+        for scope in Self::scopes(proposal) {
+            self.stake.check_multisig(
+                subjectid,
+                scope,
+                &proposal.signers,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Enforce multisig from finalized approval bundles instead of the
+    /// proposal's self-declared signers. Every scope that requires roles
+    /// needs a bundle for `proposal_hash` whose signatures and quorum
+    /// re-verify against this guard's stake table and that is younger than
+    /// the guard's bundle TTL at `now` (Unix seconds).
+    pub fn enforce_approved(
+        &self,
+        proposal: &UpdateProposal,
+        proposal_hash: &str,
+        bundles: &[ApprovalBundle],
+        verifier: &dyn SignatureVerifier,
+        now: u64,
+    ) -> anyhow::Result<()> {
+        let subjectid = &proposal.subjectid;
+
+        for scope in Self::scopes(proposal) {
+            if self.stake.required_roles_for_scope(subjectid, scope).is_empty() {
+                continue;
+            }
+            let bundle = bundles
+                .iter()
+                .find(|b| {
+                    &b.subjectid == subjectid && b.scope_kind() == scope && b.proposal_hash == proposal_hash
+                })
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "no approval bundle for subject {} scope {:?} proposal {}",
+                        subjectid,
+                        scope,
+                        proposal_hash
+                    )
+                })?;
+            bundle.verify(&self.stake, verifier, proposal_hash, now, self.bundle_ttl_secs)?;
+        }
+
+        Ok(())
+    }
+
+    /// Map text scopes into ScopeKind values and collapse them.
+    fn scopes(proposal: &UpdateProposal) -> Vec<ScopeKind> {
         let mut scopes: Vec<ScopeKind> = Vec::new();
         for s in &proposal.scope {
             scopes.push(ScopeKind::from_str(s));
@@ -41,15 +102,6 @@ impl StakeGuard {
         // Deduplicate scopes to avoid repeated checks.
         scopes.sort_by_key(|s| *s as u8);
         scopes.dedup();
-
-        for scope in scopes {
-            self.stake.check_multisig(
-                subjectid,
-                scope,
-                &proposal.signers,
-            )?;
-        }
-
-        Ok(())
+        scopes
     }
 }
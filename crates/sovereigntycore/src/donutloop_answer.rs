@@ -1,8 +1,22 @@
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use crate::answerquality::Cybostate;
+use crate::answer_quality::Cybostate;
 use crate::organiccpu_bridge::BioStateSnapshot;
 
+/// prev_hexstamp of the first record of a fresh ledger.
+pub const GENESIS_HEXSTAMP: &str = "0xGENESIS";
+
+/// `hexstamp_epoch` of records stamped with the legacy 64-bit FNV-1a hash
+/// (which covered neither the body nor prev_hexstamp).
+pub const HEXSTAMP_EPOCH_FNV: u32 = 0;
+/// `hexstamp_epoch` of records stamped with SHA-256 over the full record.
+pub const HEXSTAMP_EPOCH_SHA256: u32 = 1;
+
+/// prev_hexstamp prefix of the first record of a migrated ledger, followed by
+/// the SHA-256 of the FNV ledger file it replaced.
+pub const EPOCH_BOUNDARY_PREFIX: &str = "0xEPOCH1:";
+
 /// Single answer‑quality record, emitted as NDJSON line (.answer.ndjson).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AnswerNdjsonRecord {
@@ -22,46 +36,234 @@ pub struct AnswerNdjsonRecord {
     pub timestamp_utc: u64,
     pub artifact_kind: String,
     pub contract_type: String,
+    /// Absent (0) on records written before SHA-256 stamping.
+    #[serde(default)]
+    pub hexstamp_epoch: u32,
+    /// FNV stamp this record carried before migration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legacy_hexstamp: Option<String>,
+}
+
+/// SHA-256 over the canonical JSON of `record` with `hexstamp` blanked. The
+/// record includes `prev_hexstamp`, so rewriting any earlier record breaks
+/// every later stamp.
+pub fn sha256_hexstamp(record: &AnswerNdjsonRecord) -> Result<String, String> {
+    let mut value = serde_json::to_value(record).map_err(|e| e.to_string())?;
+    if let Some(map) = value.as_object_mut() {
+        map.insert("hexstamp".to_string(), serde_json::Value::String(String::new()));
+    }
+    // serde_json maps are key-sorted, so this serialization is canonical.
+    let json = serde_json::to_vec(&value).map_err(|e| e.to_string())?;
+    Ok(format!("0x{}", hex::encode(Sha256::digest(json))))
+}
+
+/// The pre-epoch-1 stamp, kept only to verify and migrate old ledgers.
+pub fn legacy_fnv_hexstamp(subject_id: &str, kernel_id: &str, ts: u64, kf: f32, roh: f32) -> String {
+    let seed = format!("{}:{}:{}:{:.4}:{:.4}", subject_id, kernel_id, ts, kf, roh);
+    let mut acc: u64 = 0xcbf29ce484222325;
+    for b in seed.as_bytes() {
+        acc ^= *b as u64;
+        acc = acc.wrapping_mul(0x100000001b3);
+    }
+    format!("0xNPANS{:016x}", acc)
+}
+
+/// Summary of a verified `.answer.ndjson` ledger.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerLedgerReport {
+    pub records: usize,
+    /// Records still on FNV stamps; their fields outside the FNV seed are not
+    /// tamper-evident. Run `migrate_fnv_ledger` to re-stamp them.
+    pub fnv_records: usize,
+    /// First record starts at an epoch boundary left by migration.
+    pub migrated: bool,
+    pub last_hexstamp: String,
+}
+
+fn read_records(data: &str) -> Result<Vec<AnswerNdjsonRecord>, String> {
+    let mut records = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn verify_records(records: &[AnswerNdjsonRecord]) -> Result<AnswerLedgerReport, String> {
+    let mut report = AnswerLedgerReport {
+        last_hexstamp: GENESIS_HEXSTAMP.to_string(),
+        ..Default::default()
+    };
+    let mut epoch = HEXSTAMP_EPOCH_FNV;
+    for (i, r) in records.iter().enumerate() {
+        let line = i + 1;
+        if i == 0 && r.prev_hexstamp.starts_with(EPOCH_BOUNDARY_PREFIX) {
+            if r.hexstamp_epoch != HEXSTAMP_EPOCH_SHA256 {
+                return Err(format!("line {line}: epoch boundary on a non-SHA-256 record"));
+            }
+            report.migrated = true;
+        } else if r.prev_hexstamp != report.last_hexstamp {
+            return Err(format!(
+                "line {line}: prev_hexstamp {} does not follow {}",
+                r.prev_hexstamp, report.last_hexstamp
+            ));
+        }
+
+        let expected = match r.hexstamp_epoch {
+            HEXSTAMP_EPOCH_FNV if epoch == HEXSTAMP_EPOCH_FNV => {
+                report.fnv_records += 1;
+                legacy_fnv_hexstamp(&r.subject_id, &r.kernel_id, r.timestamp_utc, r.knowledge_factor, r.roh)
+            }
+            HEXSTAMP_EPOCH_FNV => return Err(format!("line {line}: FNV-stamped record after SHA-256 records")),
+            HEXSTAMP_EPOCH_SHA256 => sha256_hexstamp(r)?,
+            other => return Err(format!("line {line}: unknown hexstamp epoch {other}")),
+        };
+        if r.hexstamp != expected {
+            return Err(format!("line {line}: hexstamp mismatch for answer {}", r.answer_id));
+        }
+        epoch = r.hexstamp_epoch;
+        report.last_hexstamp = r.hexstamp.clone();
+        report.records += 1;
+    }
+    Ok(report)
+}
+
+/// Check every link and stamp of the ledger at `path`. A missing file is an
+/// empty ledger. FNV records are accepted only before the first SHA-256
+/// record and are counted in the report.
+pub fn verify_answer_ledger(path: &std::path::Path) -> Result<AnswerLedgerReport, String> {
+    if !path.exists() {
+        return verify_records(&[]);
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    verify_records(&read_records(&data)?)
+}
+
+/// Outcome of `migrate_fnv_ledger`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub migrated: usize,
+    /// The original FNV ledger, kept unchanged.
+    pub backup: std::path::PathBuf,
+    /// prev_hexstamp of the first re-stamped record.
+    pub boundary: String,
+}
+
+/// Re-stamp an FNV ledger with SHA-256. The original file is verified, then
+/// copied to `<path>.fnv`; the new ledger starts at an epoch boundary naming
+/// the SHA-256 of that file, and each record keeps its old stamp in
+/// `legacy_hexstamp`. Returns `Ok(None)` if nothing needs migrating.
+///
+/// The backup is fsynced before `path` is replaced by a single rename, so a
+/// crash leaves either the original or the migrated ledger at `path`. Refuses
+/// to run if `<path>.fnv` already exists rather than overwrite an earlier
+/// backup.
+pub fn migrate_fnv_ledger(path: &std::path::Path) -> Result<Option<MigrationReport>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut records = read_records(&data)?;
+    let report = verify_records(&records)?;
+    if report.fnv_records == 0 {
+        return Ok(None);
+    }
+
+    let boundary = format!("{}{}", EPOCH_BOUNDARY_PREFIX, hex::encode(Sha256::digest(data.as_bytes())));
+    let mut prev = boundary.clone();
+    let mut out = String::new();
+    for r in &mut records {
+        if r.hexstamp_epoch == HEXSTAMP_EPOCH_FNV {
+            r.legacy_hexstamp = Some(r.hexstamp.clone());
+        }
+        r.hexstamp_epoch = HEXSTAMP_EPOCH_SHA256;
+        r.prev_hexstamp = prev;
+        r.hexstamp = sha256_hexstamp(r)?;
+        prev = r.hexstamp.clone();
+        out.push_str(&serde_json::to_string(r).map_err(|e| e.to_string())?);
+        out.push('\n');
+    }
+
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".fnv");
+    let backup = std::path::PathBuf::from(backup);
+    let mut staged = path.as_os_str().to_owned();
+    staged.push(".migrating");
+    let staged = std::path::PathBuf::from(staged);
+
+    {
+        use std::io::Write;
+        let mut file = match std::fs::OpenOptions::new().write(true).create_new(true).open(&backup) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                return Err(format!("{}: backup already exists; refusing to migrate", backup.display()))
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        file.write_all(data.as_bytes()).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
+
+        let mut file = std::fs::File::create(&staged).map_err(|e| e.to_string())?;
+        file.write_all(out.as_bytes()).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
+    }
+    std::fs::rename(&staged, path).map_err(|e| e.to_string())?;
+
+    Ok(Some(MigrationReport {
+        migrated: records.len(),
+        backup,
+        boundary,
+    }))
 }
 
 /// Internal writer abstraction used by TextNeuroPrintBackend.
+/// Legacy format: import into the canonical `donutloop` crate with
+/// `donutloop::legacy::answer::convert`.
 pub struct AnswerLedgerWriter {
     path: std::path::PathBuf,
     last_hex: std::cell::RefCell<String>,
 }
 
 impl AnswerLedgerWriter {
-    pub fn new<P: Into<std::path::PathBuf>>(path: P) -> Self {
-        Self {
-            path: path.into(),
-            last_hex: std::cell::RefCell::new(String::from("0xGENESIS")),
+    /// Writer for a fresh ledger. Fails if `path` already holds records,
+    /// which would otherwise be forked at genesis; use `open` for those.
+    pub fn new<P: Into<std::path::PathBuf>>(path: P) -> Result<Self, String> {
+        let path = path.into();
+        match std::fs::metadata(&path) {
+            Ok(meta) if meta.len() > 0 => {
+                return Err(format!("{}: ledger is not empty; use AnswerLedgerWriter::open", path.display()))
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.to_string()),
         }
+        Ok(Self {
+            path,
+            last_hex: std::cell::RefCell::new(String::from(GENESIS_HEXSTAMP)),
+        })
     }
 
-    pub fn last_hexstamp(&self) -> Result<String, String> {
-        Ok(self.last_hex.borrow().clone())
+    /// Verify the ledger at `path` and continue its chain.
+    pub fn open<P: Into<std::path::PathBuf>>(path: P) -> Result<Self, String> {
+        let path = path.into();
+        let report = verify_answer_ledger(&path)?;
+        Ok(Self {
+            path,
+            last_hex: std::cell::RefCell::new(report.last_hexstamp),
+        })
     }
 
-    pub fn compute_hexstamp(
-        &self,
-        subject_id: &str,
-        kernel_id: &str,
-        ts: u64,
-        kf: f32,
-        roh: f32,
-    ) -> Result<String, String> {
-        // Simple non‑cryptographic hexstamp to avoid blacklisted hashes.
-        let seed = format!("{}:{}:{}:{:.4}:{:.4}", subject_id, kernel_id, ts, kf, roh);
-        let mut acc: u64 = 0xcbf29ce484222325;
-        for b in seed.as_bytes() {
-            acc = acc ^ (*b as u64);
-            acc = acc.wrapping_mul(0x100000001b3);
-        }
-        Ok(format!("0xNPANS{:016x}", acc))
+    pub fn last_hexstamp(&self) -> Result<String, String> {
+        Ok(self.last_hex.borrow().clone())
     }
 
-    pub fn append_entry(&self, entry: AnswerLedgerEntry) -> Result<(), String> {
-        let record = AnswerNdjsonRecord {
+    /// Chain `entry` after the last record, stamp it, and append it.
+    /// Returns the new hexstamp.
+    pub fn append_entry(&self, entry: AnswerLedgerEntry) -> Result<String, String> {
+        let mut record = AnswerNdjsonRecord {
             answer_id: entry.answer_id,
             subject_id: entry.subject_id,
             kernel_id: entry.kernel_id,
@@ -73,12 +275,15 @@ impl AnswerLedgerWriter {
             actuation_forbidden: true,
             non_commercial: true,
             roh_domain: None,
-            hexstamp: entry.hexstamp.clone(),
-            prev_hexstamp: entry.prev_hexstamp.clone(),
+            hexstamp: String::new(),
+            prev_hexstamp: self.last_hexstamp()?,
             timestamp_utc: entry.timestamp_utc,
             artifact_kind: entry.artifact_kind,
             contract_type: entry.contract_type,
+            hexstamp_epoch: HEXSTAMP_EPOCH_SHA256,
+            legacy_hexstamp: None,
         };
+        record.hexstamp = sha256_hexstamp(&record)?;
 
         let line = serde_json::to_string(&record).map_err(|e| e.to_string())?;
         let mut file = std::fs::OpenOptions::new()
@@ -88,26 +293,25 @@ impl AnswerLedgerWriter {
             .map_err(|e| e.to_string())?;
         use std::io::Write;
         writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
 
-        *self.last_hex.borrow_mut() = record.hexstamp;
-        Ok(())
+        *self.last_hex.borrow_mut() = record.hexstamp.clone();
+        Ok(record.hexstamp)
     }
 }
 
-/// Internal helper used by backend.
+/// Internal helper used by backend. The writer fills in the chain fields.
 #[derive(Clone, Debug)]
 pub struct AnswerLedgerEntry {
     pub answer_id: String,
     pub subject_id: String,
     pub kernel_id: String,
-    pub route: crate::answerquality::AnswerRoute,
+    pub route: crate::answer_quality::AnswerRoute,
     pub knowledge_factor: f32,
     pub roh: f32,
     pub cybostate: Cybostate,
     pub bio: BioStateSnapshot,
     pub timestamp_utc: u64,
-    pub prev_hexstamp: String,
-    pub hexstamp: String,
     pub artifact_kind: String,
     pub contract_type: String,
 }
@@ -1,8 +1,37 @@
-use crate::stake::StakeConfig;
-use crate::neurorights_guard::NeurorightsPolicy;
+use serde::Deserialize;
+
+use crate::stake::StakeTable;
 use organiccpualn::evolvestream::EvolutionProposal;
 
+/// The sections of a species neurorights policy
+/// (`policies/<species>-neurorights-v1.neurorights.json`) this guard reads.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NeurorightsPolicy {
+    #[serde(default)]
+    pub reversibility: Option<ReversibilityPolicy>,
+    #[serde(default)]
+    pub multi_species: Option<MultiSpeciesPolicy>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReversibilityPolicy {
+    pub host_may_choose_irreversible: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MultiSpeciesPolicy {
+    pub forbid_irreversible_cross_species: bool,
+}
+
+impl NeurorightsPolicy {
+    pub fn load_from_file(path: &str) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
 /// Result of irreversibility check.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IrreversibilityDecision {
     Allowed,
     Rejected(String),
@@ -10,7 +39,7 @@ pub enum IrreversibilityDecision {
 
 pub fn guard_irreversibility(
     proposal: &EvolutionProposal,
-    stake: &StakeConfig,
+    stake: &StakeTable,
     neurorights: &NeurorightsPolicy,
 ) -> IrreversibilityDecision {
     let irr = proposal.effect_bounds.irreversible;
@@ -52,16 +81,24 @@ pub fn guard_irreversibility(
         );
     }
 
-    // Check that signer set satisfies Host-only self evolution.
-    let ok = stake.validate_multisig(
+    // Check that signer set satisfies Host-only self evolution. The scope
+    // must have an explicit quorum rule; no rule would mean no signers.
+    if stake.rule(&proposal.subject_id, &proposal.scope_id).is_none() {
+        return IrreversibilityDecision::Rejected(format!(
+            "No stake quorum rule for {}",
+            proposal.scope_id
+        ));
+    }
+    if let Err(e) = stake.check_operation(
+        &proposal.subject_id,
         &proposal.scope_id,
         &proposal.signer_dids,
-        "EVOLVE",
-    );
-    if !ok {
-        return IrreversibilityDecision::Rejected(
-            "Irreversible self-evolution requires valid Host EVOLVE signature".to_string(),
-        );
+        Some("EVOLVE"),
+    ) {
+        return IrreversibilityDecision::Rejected(format!(
+            "Irreversible self-evolution requires valid Host EVOLVE signature: {}",
+            e
+        ));
     }
 
     IrreversibilityDecision::Allowed
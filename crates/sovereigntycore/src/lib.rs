@@ -0,0 +1,12 @@
+pub mod answer_quality;
+pub mod approval;
+pub mod core_stake_guard;
+pub mod donutloop_answer;
+pub mod irreversibility_guard;
+pub mod neuro_round_in;
+pub mod npf_roh_safe_hint;
+pub mod organiccpu_bridge;
+pub mod risk_of_harm;
+pub mod roh_certificate;
+pub mod stake;
+pub mod update;
@@ -12,7 +12,7 @@ use std::path::Path;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::answerquality::{ChatAnswerEnvelope, AnswerRoute, Cybostate};
+use crate::answer_quality::{ChatAnswerEnvelope, AnswerRoute, Cybostate};
 use crate::hashing::hexstamp_for_answer; // small helper reusing donutloop hash core.
 use crate::state::AnswerLogState;        // in-memory prev_hexstamp tracker.
 
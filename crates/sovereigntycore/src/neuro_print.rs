@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Arguments;
 
-use crate::answerquality::{
+use crate::answer_quality::{
     AnswerQuality, AnswerRoute, ChatKnowledgeFactor, Cybostate, CybostateClass, RiskEnvelope,
 };
 use crate::organiccpu_bridge::{BioStateSnapshot, SafeEnvelopeDecision};
@@ -62,14 +62,14 @@ pub trait NeuroPrintBackend {
     fn build_body(&self, args: &Arguments<'_>) -> Self::Body;
 
     /// Compute KnowledgeFactor F in [0,1] for this tentative answer.
-    fn compute_knowledge_factor(&self, body: &Self::Body) -> crate::answerquality::KnowledgeFactor;
+    fn compute_knowledge_factor(&self, body: &Self::Body) -> crate::answer_quality::KnowledgeFactor;
 
     /// Map context + body into a RoH StateVector and compute AnswerRisk.
     fn compute_risk(
         &self,
         ctx: &NeuroPrintContext,
         body: &Self::Body,
-    ) -> crate::answerquality::AnswerRisk;
+    ) -> crate::answer_quality::AnswerRisk;
 
     /// Classify Cybostate based on context and body.
     fn classify_cybostate(&self, ctx: &NeuroPrintContext, body: &Self::Body) -> Cybostate;
@@ -1,7 +1,7 @@
 use serde::Serialize;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::answerquality::{
+use crate::answer_quality::{
     AnswerRisk, AnswerRoute, ChatKnowledgeFactor, Cybostate, CybostateClass,
     KnowledgeFactor, RiskEnvelope,
 };
@@ -103,19 +103,11 @@ impl NeuroPrintBackend for TextNeuroPrintBackend {
             cybostate: envelope.quality.cybostate.clone(),
             bio: envelope.bio.clone(),
             timestamp_utc: now,
-            prev_hexstamp: writer.last_hexstamp()?,
-            hexstamp: writer.compute_hexstamp(
-                &ctx.subject_id,
-                &ctx.kernel_id,
-                now,
-                envelope.quality.f.value,
-                envelope.quality.r.roh,
-            )?,
             /// Non‑financial, non‑commercial, answer‑quality artifacts only.
             artifact_kind: "answerquality".to_string(),
             contract_type: "neuroassistive".to_string(),
         };
 
-        writer.append_entry(entry)
+        writer.append_entry(entry).map(|_| ())
     }
 }
@@ -12,7 +12,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::answerquality::{
+use crate::answer_quality::{
     AnswerQuality, AnswerRoute, AnswerRisk, ChatAnswerEnvelope,
     Cybostate, KnowledgeFactor,
 };
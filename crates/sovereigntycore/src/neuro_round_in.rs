@@ -1,18 +1,38 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-use organiccpualn::rohmodel::{RohInputs, RohModelShard};
-use organiccpualn::donutloop::DonutloopEntry;
-use crate::riskofharm::RiskOfHarm;
-use crate::stakegate::StakeGate;
-use crate::smarttoken::{TokenKind, SmartTokenScope};
-use crate::evolvestream::EvolutionProposal;
+use donutloop::{NewEntry, RohCertificate};
+use ed25519_dalek::VerifyingKey;
+use organiccpualn::evolvestream::EvolutionProposal;
+use organiccpualn::roh_registry::RohRegistry;
+use crate::risk_of_harm::RiskOfHarm;
+use crate::stake::{canonical_rolekind, StakeTable};
 
 /// Marker for proposals that want to be treated as neuro round-ins.
 pub const NEURO_ROUND_IN_KIND: &str = "NEURO_ROUND_IN";
 
+/// Kind of token authorizing a proposal (`policies/*.smart.json` `kind`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TokenKind {
+    Smart,
+    Evolve,
+}
+
+/// The part of the authorizing token's scope the round-in guard reads.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SmartTokenScope {
+    pub kind: TokenKind,
+    #[serde(default)]
+    pub forbidden_domains: Vec<String>,
+}
+
 /// Hard-coded mirror of qpudatashards/particles/neuro-round-in-v1.aln.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NeuroRoundInSpec {
+    /// RoH model round-ins are scored with (the shard's `rohceiling-ref`).
+    pub roh_model_id: String,
     pub roh_max: f32,
     pub max_l2_delta: f32,
 }
@@ -20,6 +40,7 @@ pub struct NeuroRoundInSpec {
 impl Default for NeuroRoundInSpec {
     fn default() -> Self {
         Self {
+            roh_model_id: "bostrom-rohmodel-v1".to_string(),
             roh_max: 0.30,
             max_l2_delta: 0.02,
         }
@@ -31,49 +52,45 @@ impl NeuroRoundInSpec {
         p.kind == NEURO_ROUND_IN_KIND
     }
 
+    /// Static effect bounds. RoH is not checked here: the proposal's own
+    /// numbers are only trusted once `check_certificate` has matched them
+    /// against a kernel certificate.
     pub fn check_bounds(&self, p: &EvolutionProposal) -> Result<(), String> {
         if !self.is_neuro_round_in(p) {
             return Ok(());
         }
 
-        if p.effectbounds.l2deltanorm > self.max_l2_delta {
+        if p.effect_bounds.l2_delta_norm > self.max_l2_delta {
             return Err(format!(
                 "NeuroRoundInEffectTooLarge: l2deltanorm={} > {}",
-                p.effectbounds.l2deltanorm, self.max_l2_delta
+                p.effect_bounds.l2_delta_norm, self.max_l2_delta
             ));
         }
 
-        if p.effectbounds.irreversible {
+        if p.effect_bounds.irreversible {
             return Err("NeuroRoundInIrreversibleNotAllowed".to_string());
         }
 
-        if p.rohafter > p.rohbefore + 1e-6 {
-            return Err(format!(
-                "NeuroRoundInRoHNotMonotone: rohafter={} > rohbefore={}",
-                p.rohafter, p.rohbefore
-            ));
-        }
-
-        if p.rohafter > self.roh_max + 1e-6 {
-            return Err(format!(
-                "NeuroRoundInRoHExceedsMax: rohafter={} > roh_max={}",
-                p.rohafter, self.roh_max
-            ));
-        }
-
         Ok(())
     }
 
-    /// Optional: sanity check against live RoH model and state vectors.
+    /// Optional: sanity check against the live RoH model named by
+    /// `roh_model_id`, over states keyed by that model's axes.
     pub fn check_roh_model(
         &self,
-        roh_model: &RohModelShard,
-        before: RohInputs,
-        after: RohInputs,
+        registry: &RohRegistry,
+        roh_model_id: &str,
+        before: &HashMap<String, f32>,
+        after: &HashMap<String, f32>,
     ) -> Result<(), String> {
-        let guard = RiskOfHarm::new(roh_model.clone());
-        let roh_before = guard.estimate(before);
-        let roh_after = guard.estimate(after);
+        let guard = RiskOfHarm::from_registry(registry, roh_model_id)
+            .map_err(|e| format!("NeuroRoundInRoHModelUnavailable: {e:#}"))?;
+        let roh_before = guard
+            .estimate(before)
+            .map_err(|e| format!("NeuroRoundInRoHModelInput: {e:#}"))?;
+        let roh_after = guard
+            .estimate(after)
+            .map_err(|e| format!("NeuroRoundInRoHModelInput: {e:#}"))?;
 
         if roh_after > roh_before + 1e-6 {
             return Err("NeuroRoundInRoHModelNotMonotone".to_string());
@@ -83,6 +100,57 @@ impl NeuroRoundInSpec {
         }
         Ok(())
     }
+
+    /// Replace the proposal's self-reported RoH with a kernel certificate:
+    /// the certificate must be trusted, cover this proposal, be computed with
+    /// the proposal's `roh_model_id` (which must be the spec's model), be
+    /// monotone and under `roh_max`, and the proposal's numbers must agree
+    /// with it.
+    pub fn check_certificate(
+        &self,
+        p: &EvolutionProposal,
+        roh_model_id: &str,
+        cert: &RohCertificate,
+        trusted: &[VerifyingKey],
+    ) -> Result<(), String> {
+        if !self.is_neuro_round_in(p) {
+            return Ok(());
+        }
+
+        if cert.claim.proposal_id != p.proposal_id {
+            return Err(format!(
+                "NeuroRoundInRoHCertificateMismatch: certificate is for {}",
+                cert.claim.proposal_id
+            ));
+        }
+        if roh_model_id != self.roh_model_id {
+            return Err(format!(
+                "NeuroRoundInRoHModelNotAllowed: proposal names {}, spec requires {}",
+                roh_model_id, self.roh_model_id
+            ));
+        }
+        if cert.claim.model_id != roh_model_id {
+            return Err(format!(
+                "NeuroRoundInRoHCertificateWrongModel: certificate uses {}, proposal names {}",
+                cert.claim.model_id, roh_model_id
+            ));
+        }
+        cert.verify_monotone(trusted)
+            .map_err(|e| format!("NeuroRoundInRoHCertificateInvalid: {e}"))?;
+        if cert.claim.roh_after > self.roh_max + 1e-6 {
+            return Err(format!(
+                "NeuroRoundInRoHExceedsMax: certified rohafter={} > roh_max={}",
+                cert.claim.roh_after, self.roh_max
+            ));
+        }
+        if (p.roh_before - cert.claim.roh_before).abs() > 1e-6 || (p.roh_after - cert.claim.roh_after).abs() > 1e-6 {
+            return Err(format!(
+                "NeuroRoundInRoHMisreported: proposal {} -> {}, certified {} -> {}",
+                p.roh_before, p.roh_after, cert.claim.roh_before, cert.claim.roh_after
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Smart-token guard for neuro round-ins: SMART-only, no EVOLVE.
@@ -94,7 +162,7 @@ pub fn ensure_smart_only_scope(
         return Ok(());
     }
 
-    if token_scope.kind != TokenKind::SMART {
+    if token_scope.kind != TokenKind::Smart {
         return Err("NeuroRoundInRequiresSMARTToken".to_string());
     }
 
@@ -109,44 +177,86 @@ pub fn ensure_smart_only_scope(
     Ok(())
 }
 
-/// Donutloop append helper: enforces RoH monotone + Tsafe tag for NEURO_ROUND_IN.
+/// Donutloop append helper: a NEURO_ROUND_IN entry must carry a trusted,
+/// monotone RoH certificate (not bare floats) issued for the entry's own
+/// proposal with the spec's RoH model, and a Tsafe mode tag.
 pub fn append_neuro_round_in_entry(
-    entry: &DonutloopEntry,
+    entry: &NewEntry,
     spec: &NeuroRoundInSpec,
+    trusted: &[VerifyingKey],
 ) -> Result<(), String> {
-    if entry.changetype != NEURO_ROUND_IN_KIND {
+    if entry.kind != NEURO_ROUND_IN_KIND {
         return Ok(());
     }
 
-    if entry.rohafter > entry.rohbefore + 1e-6 {
-        return Err("NeuroRoundInLedgerRoHNotMonotone".to_string());
+    let Some(cert) = &entry.roh_certificate else {
+        return Err("NeuroRoundInLedgerRoHUncertified".to_string());
+    };
+    if entry.proposal_id.as_deref() != Some(cert.claim.proposal_id.as_str()) {
+        return Err(format!(
+            "NeuroRoundInLedgerRoHCertificateMismatch: entry proposal {:?}, certificate is for {}",
+            entry.proposal_id, cert.claim.proposal_id
+        ));
     }
+    if cert.claim.model_id != spec.roh_model_id {
+        return Err(format!(
+            "NeuroRoundInLedgerRoHCertificateWrongModel: certificate uses {}, spec requires {}",
+            cert.claim.model_id, spec.roh_model_id
+        ));
+    }
+    if entry.roh_before.is_some() || entry.roh_after.is_some() {
+        return Err("NeuroRoundInLedgerBareRoHValues".to_string());
+    }
+    cert.verify_monotone(trusted)
+        .map_err(|e| format!("NeuroRoundInLedgerRoHNotMonotone: {e}"))?;
 
-    if entry.rohafter > spec.roh_max + 1e-6 {
+    if cert.claim.roh_after > spec.roh_max + 1e-6 {
         return Err("NeuroRoundInLedgerRoHExceedsMax".to_string());
     }
 
-    if !entry.tsafemode.starts_with("Tsafe") {
+    let tsafe_mode = entry.ext.get("tsafe_mode").and_then(|v| v.as_str()).unwrap_or("");
+    if !tsafe_mode.starts_with("Tsafe") {
         return Err("NeuroRoundInTsafeModeMissingOrInvalid".to_string());
     }
 
     Ok(())
 }
 
+/// The host DID must hold a Host row for the proposal's subject; there is no
+/// automatic allow for unknown hosts.
+pub fn verify_host(stake: &StakeTable, proposal: &EvolutionProposal, host_did: &str) -> Result<(), String> {
+    let is_host = stake
+        .rows_for_subject(&proposal.subject_id)
+        .iter()
+        .any(|r| r.bostromaddress == host_did && canonical_rolekind(&r.rolekind) == "Host");
+    if !is_host {
+        return Err("No matching stakeholder row for host; automatic deny".to_string());
+    }
+    Ok(())
+}
+
 /// Integration hook inside sovereigntycore evaluate_update.
 /// Called after RoH + neurorights + stake guards, before mutating any shards.
+#[allow(clippy::too_many_arguments)]
 pub fn guard_neuro_round_in(
     spec: &NeuroRoundInSpec,
-    stake_gate: &StakeGate,
+    stake: &StakeTable,
+    host_did: &str,
     proposal: &EvolutionProposal,
+    roh_model_id: &str,
     smart_scope: &SmartTokenScope,
+    roh_certificate: &RohCertificate,
+    trusted: &[VerifyingKey],
 ) -> Result<(), String> {
     // Stake must already validate host DID, etc.
-    stake_gate.verify_host()?;
+    verify_host(stake, proposal, host_did)?;
 
     // SMART-only scope.
     ensure_smart_only_scope(proposal, smart_scope)?;
 
-    // Static bounds and RoH fields.
-    spec.check_bounds(proposal)
+    // Static effect bounds.
+    spec.check_bounds(proposal)?;
+
+    // RoH recomputed by the kernel, not taken from the proposal.
+    spec.check_certificate(proposal, roh_model_id, roh_certificate, trusted)
 }
@@ -1,4 +1,4 @@
-use organiccpualn::rohmodel::{RohModel, StateVector};
+use organiccpualn::roh_registry::{RohBinding, RohRegistry};
 
 /// Biocompatibility rating in [0,1].
 pub const NPF_ROH_SAFE_HINT_BCR: f32 = 0.31;
@@ -14,7 +14,16 @@ pub struct AssistedActionContext {
     pub governance_sensitivity: f32,
 }
 
-/// Result: scalar in [0, 0.3], plus a boolean "near-ceiling" hint.
+/// Axes this hint feeds, in component order. The bound model must declare
+/// exactly these.
+pub const NPF_ROH_AXES: [&str; 3] = ["cognitive_load", "change_complexity", "governance_sensitivity"];
+
+/// Resolve `roh_model_id` and check it against `NPF_ROH_AXES`.
+pub fn npf_roh_binding(registry: &RohRegistry, roh_model_id: &str) -> anyhow::Result<RohBinding> {
+    registry.bind(roh_model_id, &NPF_ROH_AXES)
+}
+
+/// Result: scalar in [0, ceiling], plus a boolean "near-ceiling" hint.
 #[derive(Clone, Debug)]
 pub struct RohSafeHint {
     pub roh_estimate: f32,
@@ -22,30 +31,24 @@ pub struct RohSafeHint {
 }
 
 /// Neuroprint-function:
-/// - Builds a tiny StateVector from 3 axes and runs your RoH model.
-/// - Clamps result to global ceiling.
-/// - Marks near_ceiling when within epsilon of 0.3.
+/// - Builds a tiny state from `NPF_ROH_AXES` and runs the bound RoH model.
+/// - Clamps result to the model's ceiling.
+/// - Marks near_ceiling when within epsilon of that ceiling.
 /// - Intended to gate **suggestions** (e.g., “maybe split this into smaller PRs”),
 ///   never as a direct block on user action.
-pub fn npf_roh_safe_hint(model: &RohModel, ctx: &AssistedActionContext) -> RohSafeHint {
-    // Map into a 3D state aligned with your existing axes conceptually.
-    let components = vec![
-        ctx.cognitive_load.max(0.0).min(1.0),
-        ctx.change_complexity.max(0.0).min(1.0),
-        ctx.governance_sensitivity.max(0.0).min(1.0),
+pub fn npf_roh_safe_hint(model: &RohBinding, ctx: &AssistedActionContext) -> anyhow::Result<RohSafeHint> {
+    let components = [
+        ctx.cognitive_load.clamp(0.0, 1.0),
+        ctx.change_complexity.clamp(0.0, 1.0),
+        ctx.governance_sensitivity.clamp(0.0, 1.0),
     ];
-    let state = StateVector { components };
-
-    let mut roh = model.compute_roh(state);
-    // Global ceiling enforced by RohModel, but we clamp defensively.
-    if roh > RohModel::ROH_CEILING {
-        roh = RohModel::ROH_CEILING;
-    }
 
-    let near_ceiling = (RohModel::ROH_CEILING - roh) < 0.03;
+    let ceiling = model.roh_ceiling();
+    let roh = model.compute(&components)?.min(ceiling);
+    let near_ceiling = (ceiling - roh) < 0.03;
 
-    RohSafeHint {
+    Ok(RohSafeHint {
         roh_estimate: roh,
         near_ceiling,
-    }
+    })
 }
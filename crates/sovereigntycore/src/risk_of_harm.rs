@@ -1,23 +1,36 @@
-use organiccpualn::rohmodel::{RohInputs, RohModelShard};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use organiccpualn::roh_registry::{RohModelDef, RohRegistry};
 
 pub struct RiskOfHarm {
-    model: RohModelShard,
+    model: Arc<RohModelDef>,
 }
 
 impl RiskOfHarm {
-    pub fn new(model: RohModelShard) -> Self {
+    pub fn new(model: Arc<RohModelDef>) -> Self {
         Self { model }
     }
 
+    /// Resolve the model by `roh_model_id`; fails for an unregistered id.
+    pub fn from_registry(registry: &RohRegistry, roh_model_id: &str) -> anyhow::Result<Self> {
+        Ok(Self::new(registry.get(roh_model_id)?))
+    }
+
+    pub fn model_id(&self) -> &str {
+        &self.model.model_id
+    }
+
     pub fn roh_ceiling(&self) -> f32 {
-        self.model.roh_ceiling()
+        self.model.roh_ceiling
     }
 
-    pub fn estimate(&self, inputs: &RohInputs) -> f32 {
-        self.model.compute_roh(inputs)
+    /// RoH of a state keyed by the model's axis names.
+    pub fn estimate(&self, state: &HashMap<String, f32>) -> anyhow::Result<f32> {
+        self.model.compute_named(state)
     }
 
-    pub fn is_within_ceiling(&self, inputs: &RohInputs) -> bool {
-        self.estimate(inputs) <= self.roh_ceiling()
+    pub fn is_within_ceiling(&self, state: &HashMap<String, f32>) -> anyhow::Result<bool> {
+        Ok(self.estimate(state)? <= self.roh_ceiling())
     }
 }
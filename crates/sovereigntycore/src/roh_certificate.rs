@@ -0,0 +1,127 @@
+//! Kernel-side RoH monotonicity certificates.
+//!
+//! Guards used to trust the `roh_before`/`roh_after` numbers written into a
+//! proposal. Here both values are recomputed from the registered RoH model
+//! and the before/after states, and the result is sealed into a
+//! `donutloop::RohCertificate` for the ledger. `audit_roh_certificate` is the
+//! inverse: given the same states it re-derives the certificate's claim.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{bail, Context};
+use donutloop::{state_hash, RohCertificate, RohClaim};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use organiccpualn::roh_registry::{RohModelDef, RohRegistry};
+use sha2::{Digest, Sha256};
+
+/// Tolerance when comparing re-derived RoH values to a certificate.
+const ROH_MATCH_TOL: f32 = 1e-6;
+
+/// sha256 over the canonical JSON of a model definition.
+pub fn model_hash(model: &RohModelDef) -> String {
+    let value = serde_json::to_value(model).expect("serialize RoH model");
+    let json = serde_json::to_vec(&value).expect("serialize RoH model");
+    hex::encode(Sha256::digest(json))
+}
+
+/// The state restricted to, and required to cover, the model's axes.
+fn model_state(model: &RohModelDef, state: &HashMap<String, f32>) -> anyhow::Result<BTreeMap<String, f32>> {
+    if let Some(name) = state.keys().find(|k| !model.axis_names().any(|a| a == k.as_str())) {
+        bail!("RoH model {} has no axis {}", model.model_id, name);
+    }
+    model
+        .axis_names()
+        .map(|a| {
+            state
+                .get(a)
+                .map(|v| (a.to_string(), *v))
+                .with_context(|| format!("RoH model {} missing axis {}", model.model_id, a))
+        })
+        .collect()
+}
+
+/// RoH of a state already checked by `model_state`.
+fn roh_of(model: &RohModelDef, state: &BTreeMap<String, f32>) -> anyhow::Result<f32> {
+    let x: Vec<f32> = model.axis_names().map(|a| state[a]).collect();
+    model.compute(&x)
+}
+
+/// Recompute RoH before and after a proposal and sign the result. The
+/// certificate is issued whatever the outcome; callers gate on
+/// `RohCertificate::verify_monotone`.
+pub fn certify_roh(
+    registry: &RohRegistry,
+    roh_model_id: &str,
+    proposal_id: &str,
+    subject_id: Option<&str>,
+    before: &HashMap<String, f32>,
+    after: &HashMap<String, f32>,
+    signer: &SigningKey,
+) -> anyhow::Result<RohCertificate> {
+    let model = registry.get(roh_model_id)?;
+    let before = model_state(&model, before)?;
+    let after = model_state(&model, after)?;
+    let roh_before = roh_of(&model, &before)?;
+    let roh_after = roh_of(&model, &after)?;
+
+    Ok(RohCertificate::seal(
+        RohClaim {
+            proposal_id: proposal_id.to_string(),
+            subject_id: subject_id.map(str::to_string),
+            model_id: model.model_id.clone(),
+            model_version: model.version.clone(),
+            model_hash: model_hash(&model),
+            before_hash: state_hash(&before),
+            after_hash: state_hash(&after),
+            roh_before,
+            roh_after,
+            roh_ceiling: model.roh_ceiling,
+        },
+        signer,
+    ))
+}
+
+/// Re-derive a certificate: trusted signature, same model definition, same
+/// input states and the same RoH values.
+pub fn audit_roh_certificate(
+    cert: &RohCertificate,
+    registry: &RohRegistry,
+    before: &HashMap<String, f32>,
+    after: &HashMap<String, f32>,
+    trusted: &[VerifyingKey],
+) -> anyhow::Result<()> {
+    cert.verify(trusted)?;
+    let claim = &cert.claim;
+    let model = registry.get(&claim.model_id)?;
+    if model.version != claim.model_version || model_hash(&model) != claim.model_hash {
+        bail!(
+            "RoH certificate for {} was issued against a different definition of {} {}",
+            claim.proposal_id,
+            claim.model_id,
+            claim.model_version
+        );
+    }
+
+    let before = model_state(&model, before)?;
+    let after = model_state(&model, after)?;
+    if state_hash(&before) != claim.before_hash || state_hash(&after) != claim.after_hash {
+        bail!("RoH certificate for {} does not match the supplied states", claim.proposal_id);
+    }
+
+    let roh_before = roh_of(&model, &before)?;
+    let roh_after = roh_of(&model, &after)?;
+    if (roh_before - claim.roh_before).abs() > ROH_MATCH_TOL || (roh_after - claim.roh_after).abs() > ROH_MATCH_TOL {
+        bail!(
+            "RoH certificate for {} claims {} -> {}, recomputed {} -> {}",
+            claim.proposal_id,
+            claim.roh_before,
+            claim.roh_after,
+            roh_before,
+            roh_after
+        );
+    }
+    if (model.roh_ceiling - claim.roh_ceiling).abs() > ROH_MATCH_TOL {
+        bail!("RoH certificate for {} records a different ceiling", claim.proposal_id);
+    }
+    Ok(())
+}
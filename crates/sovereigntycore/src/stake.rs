@@ -1,11 +1,26 @@
+//! The stake engine: every quorum decision goes through `StakeTable`.
+//!
+//! Three `.stake.aln` shapes load into one model of roles plus per-operation
+//! `QuorumRule`s:
+//! - the 8-column CSV (`policies/bostrom-stake-v1.stake.aln`), where each
+//!   `requiredfor*` column means "all flagged rolekinds must sign";
+//! - `sovereign-core::schema::StakeConfig` YAML, whose `MultisigRule` carries
+//!   an explicit `threshold`;
+//! - the scope-shard YAML (`qpudatashards/particles/*.stake.aln`) with
+//!   `required_roles` and `multisig_required`.
+//!
+//! `reconcile_files` loads several files and reports where they disagree.
+
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 
 /// One row from policies/bostrom-stake-v1.stake.aln
 /// CSV header (canonical):
 /// roleid,subjectid,bostromaddress,rolekind,canveto,caninitevolve,requiredforlifeforce,requiredforarchchange
+///
+/// Roles loaded from YAML are normalized into the same row; their
+/// `requiredfor*` flags are derived from the file's rules.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StakeRow {
     pub roleid: String,
@@ -30,50 +45,199 @@ pub enum ScopeKind {
 }
 
 impl ScopeKind {
+    /// Infallible: unknown labels map to `Other`.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
-        match s {
+        match operation_key(s).as_str() {
             "lifeforce" => ScopeKind::Lifeforce,
             "archchange" => ScopeKind::ArchChange,
-            "tsafe" | "tsafe_policy" => ScopeKind::TsafePolicy,
-            "qpolicy" | "qpolicy_update" => ScopeKind::QPolicyUpdate,
+            "tsafe_policy" => ScopeKind::TsafePolicy,
+            "qpolicy_update" => ScopeKind::QPolicyUpdate,
             "dreamrights" => ScopeKind::DreamRights,
             _ => ScopeKind::Other,
         }
     }
+
+    /// Canonical label; round-trips through `from_str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScopeKind::Lifeforce => "lifeforce",
+            ScopeKind::ArchChange => "archchange",
+            ScopeKind::TsafePolicy => "tsafe_policy",
+            ScopeKind::QPolicyUpdate => "qpolicy_update",
+            ScopeKind::DreamRights => "dreamrights",
+            ScopeKind::Other => "other",
+        }
+    }
+}
+
+/// Canonical key for an operation or scope name, so "arch.change",
+/// "arch-change" and "archchange" name the same rule. Known scopes map to
+/// `ScopeKind::as_str`; anything else is lowercased with separators removed.
+pub fn operation_key(s: &str) -> String {
+    let compact: String = s
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    match compact.as_str() {
+        "lifeforce" | "lifeforcealteration" => "lifeforce".to_string(),
+        "archchange" => "archchange".to_string(),
+        "tsafe" | "tsafepolicy" => "tsafe_policy".to_string(),
+        "qpolicy" | "qpolicyupdate" => "qpolicy_update".to_string(),
+        "dreamrights" => "dreamrights".to_string(),
+        _ => compact,
+    }
+}
+
+/// Canonical rolekind spelling ("OrganicCpu" and "organiccpu" are "OrganicCPU").
+pub fn canonical_rolekind(s: &str) -> String {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "host" => "Host".to_string(),
+        "organiccpu" => "OrganicCPU".to_string(),
+        "researchagent" => "ResearchAgent".to_string(),
+        "offdeviceswarm" => "OffDeviceSwarm".to_string(),
+        "auditor" => "Auditor".to_string(),
+        _ => s.trim().to_string(),
+    }
+}
+
+/// Quorum for one operation: at least `threshold` of `required_roles` must
+/// be covered, by at least `threshold` distinct signer addresses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuorumRule {
+    /// `operation_key` of the scope/operation.
+    pub operation: String,
+    pub required_roles: BTreeSet<String>,
+    pub threshold: usize,
+    /// Token kinds allowed to authorize the operation; empty means any.
+    pub token_kinds: BTreeSet<String>,
+}
+
+impl QuorumRule {
+    /// Every required role must sign (the CSV semantics).
+    fn all_of(operation: &str, required_roles: BTreeSet<String>) -> Self {
+        Self {
+            operation: operation.to_string(),
+            threshold: required_roles.len(),
+            required_roles,
+            token_kinds: BTreeSet::new(),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "roleid,subjectid,bostromaddress,rolekind,canveto,caninitevolve,requiredforlifeforce,requiredforarchchange";
+
+/// Mirror of `sovereign-core::schema::StakeConfig` (that crate owns the
+/// boot-time copy; quorum checks happen here).
+#[derive(Debug, Deserialize)]
+struct StakeConfigDoc {
+    subject_id: String,
+    #[allow(dead_code)]
+    version: String,
+    roles: Vec<StakeConfigRole>,
+    multisig_rules: Vec<StakeConfigRule>,
 }
 
-/// Table of stake rows indexed by subject and by rolekind.
+#[derive(Debug, Deserialize)]
+struct StakeConfigRole {
+    role: String,
+    did: String,
+    #[serde(default)]
+    scopes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StakeConfigRule {
+    operation: String,
+    required_roles: Vec<String>,
+    threshold: u8,
+}
+
+/// `qpudatashards/particles/*.stake.aln` shape.
+#[derive(Debug, Deserialize)]
+struct ScopeShardDoc {
+    meta: ScopeShardMeta,
+    roles: Vec<ScopeShardRole>,
+    scopes: Vec<ScopeShardScope>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScopeShardMeta {
+    subject_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScopeShardRole {
+    kind: String,
+    role_id: String,
+    bostrom_address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScopeShardScope {
+    scope_id: String,
+    required_roles: Vec<String>,
+    #[serde(default)]
+    token_kinds_allowed: Vec<String>,
+    multisig_required: bool,
+}
+
+/// (roleid, rolekind, address, caninitevolve) of a YAML role.
+type YamlRole = (String, String, String, bool);
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StakeYaml {
+    Config(StakeConfigDoc),
+    Shard(ScopeShardDoc),
+}
+
+/// Roles and quorum rules per subject.
 #[derive(Debug, Clone)]
 pub struct StakeTable {
     /// subjectid -> Vec<StakeRow>
     by_subject: HashMap<String, Vec<StakeRow>>,
+    /// subjectid -> operation_key -> rule
+    rules: HashMap<String, BTreeMap<String, QuorumRule>>,
 }
 
 impl StakeTable {
-    /// Load from canonical CSV-like stake file.
+    /// Load any supported `.stake.aln` format.
     /// Expected path: policies/bostrom-stake-v1.stake.aln
     pub fn load_from_file(path: &str) -> anyhow::Result<Self> {
-        let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
-
-        let mut header = String::new();
-        reader.read_line(&mut header)?;
-        let header = header.trim();
-        let expected = "roleid,subjectid,bostromaddress,rolekind,canveto,caninitevolve,requiredforlifeforce,requiredforarchchange";
-        if header != expected {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|e| anyhow::anyhow!("{}: {}", path, e))
+    }
+
+    /// Parse CSV (detected by its header, optionally `#`-commented and
+    /// snake_cased) or either YAML shape.
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let first = text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+        if normalize_header(first) == CSV_HEADER {
+            Self::parse_csv(text)
+        } else {
+            Self::parse_yaml(text)
+        }
+    }
+
+    fn parse_csv(text: &str) -> anyhow::Result<Self> {
+        // Join backslash continuations before splitting into rows.
+        let joined = text.replace("\\\r\n", " ").replace("\\\n", " ");
+        let mut lines = joined.lines().map(str::trim).filter(|l| !l.is_empty());
+
+        let header = lines.next().unwrap_or("");
+        if normalize_header(header) != CSV_HEADER {
             anyhow::bail!(
                 "stake header mismatch: got '{}', expected '{}'",
                 header,
-                expected
+                CSV_HEADER
             );
         }
 
-        let mut by_subject: HashMap<String, Vec<StakeRow>> = HashMap::new();
-
-        for line in reader.lines() {
-            let line = line?;
-            let line = line.trim();
-            if line.is_empty() {
+        let mut rows = Vec::new();
+        for line in lines {
+            if line.starts_with('#') {
                 continue;
             }
             let cols: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
@@ -81,7 +245,7 @@ impl StakeTable {
                 anyhow::bail!("invalid stake row, expected 8 columns: '{}'", line);
             }
 
-            let row = StakeRow {
+            rows.push(StakeRow {
                 roleid: cols[0].to_string(),
                 subjectid: cols[1].to_string(),
                 bostromaddress: cols[2].to_string(),
@@ -90,50 +254,137 @@ impl StakeTable {
                 caninitevolve: parse_bool(cols[5])?,
                 requiredforlifeforce: parse_bool(cols[6])?,
                 requiredforarchchange: parse_bool(cols[7])?,
-            };
+            });
+        }
+
+        Self::from_rows(rows)
+    }
 
-            by_subject
-                .entry(row.subjectid.clone())
-                .or_insert_with(Vec::new)
-                .push(row);
+    fn parse_yaml(text: &str) -> anyhow::Result<Self> {
+        let doc: StakeYaml = serde_yaml::from_str(text)
+            .map_err(|e| anyhow::anyhow!("not a CSV, StakeConfig or scope-shard stake file: {}", e))?;
+
+        let (subject, roles, rules): (String, Vec<YamlRole>, Vec<QuorumRule>) = match doc {
+            StakeYaml::Config(doc) => {
+                let roles = doc
+                    .roles
+                    .into_iter()
+                    .map(|r| {
+                        let kind = canonical_rolekind(&r.role);
+                        let evolve = r.scopes.iter().any(|s| s.starts_with("EVOLVE"));
+                        (kind.to_ascii_lowercase(), kind, r.did, evolve)
+                    })
+                    .collect();
+                let rules = doc
+                    .multisig_rules
+                    .into_iter()
+                    .map(|r| QuorumRule {
+                        operation: operation_key(&r.operation),
+                        required_roles: r.required_roles.iter().map(|k| canonical_rolekind(k)).collect(),
+                        threshold: r.threshold as usize,
+                        token_kinds: BTreeSet::new(),
+                    })
+                    .collect();
+                (doc.subject_id, roles, rules)
+            }
+            StakeYaml::Shard(doc) => {
+                let roles = doc
+                    .roles
+                    .into_iter()
+                    .map(|r| (r.role_id, canonical_rolekind(&r.kind), r.bostrom_address, false))
+                    .collect();
+                let rules = doc
+                    .scopes
+                    .into_iter()
+                    .map(|s| {
+                        let required: BTreeSet<String> =
+                            s.required_roles.iter().map(|k| canonical_rolekind(k)).collect();
+                        let mut rule = QuorumRule::all_of(&operation_key(&s.scope_id), required);
+                        if !s.multisig_required {
+                            rule.threshold = rule.threshold.min(1);
+                        }
+                        rule.token_kinds = s.token_kinds_allowed.into_iter().collect();
+                        rule
+                    })
+                    .collect();
+                (doc.meta.subject_id, roles, rules)
+            }
+        };
+
+        let mut subject_rules: BTreeMap<String, QuorumRule> = BTreeMap::new();
+        for rule in rules {
+            if subject_rules.contains_key(&rule.operation) {
+                anyhow::bail!("duplicate quorum rule for operation {}", rule.operation);
+            }
+            subject_rules.insert(rule.operation.clone(), rule);
         }
+        let required_for = |op: &str, kind: &str| {
+            subject_rules
+                .get(op)
+                .map(|r| r.required_roles.contains(kind))
+                .unwrap_or(false)
+        };
+        let rows = roles
+            .into_iter()
+            .map(|(roleid, rolekind, address, caninitevolve)| StakeRow {
+                roleid,
+                subjectid: subject.clone(),
+                bostromaddress: address,
+                canveto: false,
+                caninitevolve,
+                requiredforlifeforce: required_for("lifeforce", &rolekind),
+                requiredforarchchange: required_for("archchange", &rolekind),
+                rolekind,
+            })
+            .collect();
 
-        let table = StakeTable { by_subject };
+        let table = StakeTable {
+            by_subject: HashMap::from([(subject.clone(), rows)]),
+            rules: HashMap::from([(subject, subject_rules)]),
+        };
+        table.validate_invariants()?;
+        Ok(table)
+    }
+
+    /// Build from CSV-style rows, enforcing the same invariants as
+    /// `load_from_file`. Lifeforce and archchange rules require every
+    /// flagged rolekind.
+    pub fn from_rows(rows: Vec<StakeRow>) -> anyhow::Result<Self> {
+        let mut by_subject: HashMap<String, Vec<StakeRow>> = HashMap::new();
+        for mut row in rows {
+            row.rolekind = canonical_rolekind(&row.rolekind);
+            by_subject.entry(row.subjectid.clone()).or_default().push(row);
+        }
+
+        let mut rules = HashMap::new();
+        for (subject, rows) in &by_subject {
+            let flagged = |f: fn(&StakeRow) -> bool| -> BTreeSet<String> {
+                rows.iter().filter(|r| f(r)).map(|r| r.rolekind.clone()).collect()
+            };
+            let mut subject_rules = BTreeMap::new();
+            for (scope, required) in [
+                (ScopeKind::Lifeforce, flagged(|r| r.requiredforlifeforce)),
+                (ScopeKind::ArchChange, flagged(|r| r.requiredforarchchange)),
+            ] {
+                if !required.is_empty() {
+                    subject_rules.insert(scope.as_str().to_string(), QuorumRule::all_of(scope.as_str(), required));
+                }
+            }
+            rules.insert(subject.clone(), subject_rules);
+        }
+
+        let table = StakeTable { by_subject, rules };
         table.validate_invariants()?;
         Ok(table)
     }
 
     /// Hard invariants:
     /// - exactly one Host role per subjectid
-    /// - lifeforce and archchange scopes must have at least Host + OrganicCPU rows flagged as required
+    /// - lifeforce and archchange need Host + OrganicCPU, and their threshold
+    ///   must cover every required role so neither can be outvoted
     fn validate_invariants(&self) -> anyhow::Result<()> {
         for (subject, rows) in &self.by_subject {
-            let mut host_count = 0usize;
-            let mut host_lifeforce_ok = false;
-            let mut host_arch_ok = false;
-            let mut organic_lifeforce_ok = false;
-            let mut organic_arch_ok = false;
-
-            for r in rows {
-                if r.rolekind == "Host" {
-                    host_count += 1;
-                    if r.requiredforlifeforce {
-                        host_lifeforce_ok = true;
-                    }
-                    if r.requiredforarchchange {
-                        host_arch_ok = true;
-                    }
-                }
-                if r.rolekind == "OrganicCPU" {
-                    if r.requiredforlifeforce {
-                        organic_lifeforce_ok = true;
-                    }
-                    if r.requiredforarchchange {
-                        organic_arch_ok = true;
-                    }
-                }
-            }
-
+            let host_count = rows.iter().filter(|r| r.rolekind == "Host").count();
             if host_count != 1 {
                 anyhow::bail!(
                     "stake invariant failed for subject {}: expected exactly 1 Host, found {}",
@@ -142,65 +393,65 @@ impl StakeTable {
                 );
             }
 
-            if !host_lifeforce_ok || !organic_lifeforce_ok {
-                anyhow::bail!(
-                    "stake invariant failed for subject {}: lifeforce requires Host+OrganicCPU",
-                    subject
-                );
-            }
-
-            if !host_arch_ok || !organic_arch_ok {
-                anyhow::bail!(
-                    "stake invariant failed for subject {}: archchange requires Host+OrganicCPU",
-                    subject
-                );
+            for scope in [ScopeKind::Lifeforce, ScopeKind::ArchChange] {
+                let ok = self.rule(subject, scope.as_str()).is_some_and(|r| {
+                    r.required_roles.contains("Host")
+                        && r.required_roles.contains("OrganicCPU")
+                        && r.threshold >= r.required_roles.len()
+                });
+                if !ok {
+                    anyhow::bail!(
+                        "stake invariant failed for subject {}: {} requires Host+OrganicCPU",
+                        subject,
+                        scope.as_str()
+                    );
+                }
             }
         }
 
         Ok(())
     }
 
+    /// All stake rows for a subject (empty if unknown).
+    pub fn rows_for_subject(&self, subjectid: &str) -> &[StakeRow] {
+        self.by_subject.get(subjectid).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn subjects(&self) -> impl Iterator<Item = &String> {
+        self.by_subject.keys()
+    }
+
+    /// Rules written in the stake file for a subject, by operation key.
+    pub fn explicit_rules(&self, subjectid: &str) -> Option<&BTreeMap<String, QuorumRule>> {
+        self.rules.get(subjectid)
+    }
+
+    /// The rule governing `operation` for a subject. Policy and dream scopes
+    /// without an explicit rule conservatively require the Host.
+    pub fn rule(&self, subjectid: &str, operation: &str) -> Option<QuorumRule> {
+        let key = operation_key(operation);
+        if let Some(rule) = self.rules.get(subjectid).and_then(|r| r.get(&key)) {
+            return Some(rule.clone());
+        }
+        match ScopeKind::from_str(&key) {
+            ScopeKind::TsafePolicy | ScopeKind::QPolicyUpdate | ScopeKind::DreamRights
+                if self.by_subject.contains_key(subjectid) =>
+            {
+                Some(QuorumRule::all_of(&key, BTreeSet::from(["Host".to_string()])))
+            }
+            _ => None,
+        }
+    }
+
     /// Return the set of required rolekinds for a given subject and action scope.
     pub fn required_roles_for_scope(
         &self,
         subjectid: &str,
         scope: ScopeKind,
     ) -> HashSet<String> {
-        let mut required: HashSet<String> = HashSet::new();
-        let rows = match self.by_subject.get(subjectid) {
-            Some(r) => r,
-            None => return required,
-        };
-
-        match scope {
-            ScopeKind::Lifeforce => {
-                for r in rows {
-                    if r.requiredforlifeforce {
-                        required.insert(r.rolekind.clone());
-                    }
-                }
-            }
-            ScopeKind::ArchChange => {
-                for r in rows {
-                    if r.requiredforarchchange {
-                        required.insert(r.rolekind.clone());
-                    }
-                }
-            }
-            ScopeKind::TsafePolicy | ScopeKind::QPolicyUpdate | ScopeKind::DreamRights => {
-                // Conservative default: require Host for all critical policy/dream scopes
-                for r in rows {
-                    if r.rolekind == "Host" {
-                        required.insert(r.rolekind.clone());
-                    }
-                }
-            }
-            ScopeKind::Other => {
-                // No hard requirement; may still be governed by SMART/EVOLVE tokens.
-            }
-        }
-
-        required
+        self.rule(subjectid, scope.as_str())
+            .map(|r| r.required_roles.into_iter().collect())
+            .unwrap_or_default()
     }
 
     /// Check that the provided signer (bostrom addresses) satisfy required roles
@@ -211,9 +462,33 @@ impl StakeTable {
         scope: ScopeKind,
         signer_addresses: &[String],
     ) -> anyhow::Result<()> {
-        let required_roles = self.required_roles_for_scope(subjectid, scope);
-        if required_roles.is_empty() {
-            return Ok(());
+        self.check_operation(subjectid, scope.as_str(), signer_addresses, None)
+    }
+
+    /// Quorum check for any operation name. Operations without a rule need
+    /// no stake approval. `token_kind`, when given, must be allowed by the
+    /// rule.
+    pub fn check_operation(
+        &self,
+        subjectid: &str,
+        operation: &str,
+        signer_addresses: &[String],
+        token_kind: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let rule = match self.rule(subjectid, operation) {
+            Some(rule) if rule.threshold > 0 => rule,
+            _ => return Ok(()),
+        };
+
+        if let Some(kind) = token_kind {
+            if !rule.token_kinds.is_empty() && !rule.token_kinds.contains(kind) {
+                anyhow::bail!(
+                    "token kind {} may not authorize {} for subject {}",
+                    kind,
+                    rule.operation,
+                    subjectid
+                );
+            }
         }
 
         let rows = self
@@ -221,37 +496,45 @@ impl StakeTable {
             .get(subjectid)
             .ok_or_else(|| anyhow::anyhow!("no stake rows for subject {}", subjectid))?;
 
-        let signer_set: HashSet<String> =
-            signer_addresses.iter().cloned().collect();
+        let signer_set: HashSet<&String> = signer_addresses.iter().collect();
 
-        let mut covered: HashSet<String> = HashSet::new();
+        let mut covered: BTreeSet<String> = BTreeSet::new();
+        let mut distinct_signers: HashSet<&String> = HashSet::new();
 
         for r in rows {
-            if !required_roles.contains(&r.rolekind) {
+            if !rule.required_roles.contains(&r.rolekind) {
                 continue;
             }
             if signer_set.contains(&r.bostromaddress) {
                 covered.insert(r.rolekind.clone());
+                distinct_signers.insert(&r.bostromaddress);
             }
         }
 
-        if covered == required_roles {
+        if covered.len() >= rule.threshold && distinct_signers.len() >= rule.threshold {
             Ok(())
         } else {
-            let missing: Vec<String> = required_roles
-                .difference(&covered)
-                .cloned()
-                .collect();
+            let missing: Vec<&String> = rule.required_roles.difference(&covered).collect();
             anyhow::bail!(
-                "stake multisig violation: missing approvals from roles {:?} for subject {} scope {:?}",
+                "stake multisig violation: {} of {} required roles signed (threshold {}), missing approvals from roles {:?} for subject {} operation {}",
+                covered.len(),
+                rule.required_roles.len(),
+                rule.threshold,
                 missing,
                 subjectid,
-                scope
+                rule.operation
             );
         }
     }
 }
 
+fn normalize_header(line: &str) -> String {
+    line.trim_start_matches('#')
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect()
+}
+
 fn parse_bool(s: &str) -> anyhow::Result<bool> {
     match s {
         "true" | "True" | "1" => Ok(true),
@@ -259,3 +542,164 @@ fn parse_bool(s: &str) -> anyhow::Result<bool> {
         other => anyhow::bail!("invalid bool value '{}'", other),
     }
 }
+
+/// A disagreement between two stake files about the same subject.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakeContradiction {
+    /// A rolekind is bound to different addresses.
+    RoleAddress {
+        subjectid: String,
+        rolekind: String,
+        left: (String, BTreeSet<String>),
+        right: (String, BTreeSet<String>),
+    },
+    /// A rolekind exists in one file only.
+    RoleMissing {
+        subjectid: String,
+        rolekind: String,
+        present_in: String,
+        missing_in: String,
+    },
+    /// Different quorum for the same operation; `None` means no explicit
+    /// rule in that file.
+    Rule {
+        subjectid: String,
+        operation: String,
+        left: (String, Option<QuorumRule>),
+        right: (String, Option<QuorumRule>),
+    },
+}
+
+fn describe(rule: &Option<QuorumRule>) -> String {
+    match rule {
+        Some(r) => format!("{} of {:?}", r.threshold, r.required_roles),
+        None => "no rule".to_string(),
+    }
+}
+
+impl fmt::Display for StakeContradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StakeContradiction::RoleAddress { subjectid, rolekind, left, right } => write!(
+                f,
+                "{}: {} is {:?} in {} but {:?} in {}",
+                subjectid, rolekind, left.1, left.0, right.1, right.0
+            ),
+            StakeContradiction::RoleMissing { subjectid, rolekind, present_in, missing_in } => write!(
+                f,
+                "{}: {} is defined in {} but not in {}",
+                subjectid, rolekind, present_in, missing_in
+            ),
+            StakeContradiction::Rule { subjectid, operation, left, right } => write!(
+                f,
+                "{}: {} needs {} in {} but {} in {}",
+                subjectid,
+                operation,
+                describe(&left.1),
+                left.0,
+                describe(&right.1),
+                right.0
+            ),
+        }
+    }
+}
+
+/// Outcome of loading and comparing several stake files.
+#[derive(Debug, Default)]
+pub struct StakeReconciliation {
+    pub loaded: Vec<String>,
+    /// Files that could not be loaded, with the reason.
+    pub failed: Vec<(String, String)>,
+    pub contradictions: Vec<StakeContradiction>,
+}
+
+/// Pairwise contradictions between named tables, for subjects they share.
+pub fn reconcile(tables: &[(String, StakeTable)]) -> Vec<StakeContradiction> {
+    let mut out = Vec::new();
+    for (i, (lname, left)) in tables.iter().enumerate() {
+        for (rname, right) in &tables[i + 1..] {
+            let mut subjects: Vec<&String> = left
+                .by_subject
+                .keys()
+                .filter(|s| right.by_subject.contains_key(*s))
+                .collect();
+            subjects.sort();
+            for subject in subjects {
+                compare_subject(subject, (lname, left), (rname, right), &mut out);
+            }
+        }
+    }
+    out
+}
+
+fn compare_subject(
+    subject: &str,
+    (lname, left): (&String, &StakeTable),
+    (rname, right): (&String, &StakeTable),
+    out: &mut Vec<StakeContradiction>,
+) {
+    let addresses = |t: &StakeTable| {
+        let mut by_kind: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for r in t.rows_for_subject(subject) {
+            by_kind.entry(r.rolekind.clone()).or_default().insert(r.bostromaddress.clone());
+        }
+        by_kind
+    };
+    let (la, ra) = (addresses(left), addresses(right));
+    for kind in la.keys().chain(ra.keys()).collect::<BTreeSet<_>>() {
+        match (la.get(kind), ra.get(kind)) {
+            (Some(l), Some(r)) if l != r => out.push(StakeContradiction::RoleAddress {
+                subjectid: subject.to_string(),
+                rolekind: kind.clone(),
+                left: (lname.clone(), l.clone()),
+                right: (rname.clone(), r.clone()),
+            }),
+            (Some(_), None) | (None, Some(_)) => {
+                let (present_in, missing_in) = if la.contains_key(kind) { (lname, rname) } else { (rname, lname) };
+                out.push(StakeContradiction::RoleMissing {
+                    subjectid: subject.to_string(),
+                    rolekind: kind.clone(),
+                    present_in: present_in.clone(),
+                    missing_in: missing_in.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let empty = BTreeMap::new();
+    let lr = left.explicit_rules(subject).unwrap_or(&empty);
+    let rr = right.explicit_rules(subject).unwrap_or(&empty);
+    for op in lr.keys().chain(rr.keys()).collect::<BTreeSet<_>>() {
+        let (l, r) = (lr.get(op), rr.get(op));
+        let same = match (l, r) {
+            (Some(l), Some(r)) => l.required_roles == r.required_roles && l.threshold == r.threshold,
+            _ => false,
+        };
+        if !same {
+            out.push(StakeContradiction::Rule {
+                subjectid: subject.to_string(),
+                operation: op.clone(),
+                left: (lname.clone(), l.cloned()),
+                right: (rname.clone(), r.cloned()),
+            });
+        }
+    }
+}
+
+/// Load every path and report contradictions among those that load.
+pub fn reconcile_files(paths: &[&str]) -> StakeReconciliation {
+    let mut report = StakeReconciliation::default();
+    let mut tables = Vec::new();
+    for path in paths {
+        match StakeTable::load_from_file(path) {
+            Ok(t) => {
+                report.loaded.push(path.to_string());
+                tables.push((path.to_string(), t));
+            }
+            Err(e) => report.failed.push((path.to_string(), e.to_string())),
+        }
+    }
+    report.contradictions = reconcile(&tables);
+    report
+}
@@ -0,0 +1,12 @@
+/// The slice of an evolution proposal the stake guard checks: who it is
+/// for, which scopes it touches and who claims to have signed it.
+#[derive(Debug, Clone)]
+pub struct UpdateProposal {
+    pub id: String,
+    pub subjectid: String,
+    /// Scope tags, e.g. "lifeforce", "archchange".
+    pub scope: Vec<String>,
+    /// Self-declared signer addresses; `StakeGuard::enforce_approved`
+    /// ignores them in favour of verified approval bundles.
+    pub signers: Vec<String>,
+}
@@ -0,0 +1,248 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use sovereigntycore::answer_quality::{AnswerRoute, Cybostate};
+use sovereigntycore::donutloop_answer::{
+    legacy_fnv_hexstamp, migrate_fnv_ledger, sha256_hexstamp, verify_answer_ledger, AnswerLedgerEntry,
+    AnswerLedgerWriter, AnswerNdjsonRecord, EPOCH_BOUNDARY_PREFIX, GENESIS_HEXSTAMP, HEXSTAMP_EPOCH_FNV,
+    HEXSTAMP_EPOCH_SHA256,
+};
+use sovereigntycore::organiccpu_bridge::BioStateSnapshot;
+
+fn ledger(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sovereigntycore-answer-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("chat.answer.ndjson")
+}
+
+fn bio() -> BioStateSnapshot {
+    BioStateSnapshot {
+        fatigue_index: 0.1,
+        duty_cycle: 0.2,
+        cognitive_load_index: 0.3,
+        hrv_index: 0.0,
+        device_hours_today: 1.5,
+    }
+}
+
+fn entry(id: &str, ts: u64) -> AnswerLedgerEntry {
+    AnswerLedgerEntry {
+        answer_id: id.to_string(),
+        subject_id: "bostrom1".to_string(),
+        kernel_id: "k1".to_string(),
+        route: AnswerRoute::Info,
+        knowledge_factor: 0.8,
+        roh: 0.05,
+        cybostate: Cybostate::RetrievalOnly,
+        bio: bio(),
+        timestamp_utc: ts,
+        artifact_kind: "answerquality".to_string(),
+        contract_type: "neuroassistive".to_string(),
+    }
+}
+
+/// A ledger of three SHA-256 records; returns the stamps in order.
+fn write_three(path: &Path) -> Vec<String> {
+    let writer = AnswerLedgerWriter::new(path).unwrap();
+    (0..3).map(|i| writer.append_entry(entry(&format!("a{i}"), 1_770_000_000 + i)).unwrap()).collect()
+}
+
+fn read(path: &Path) -> Vec<AnswerNdjsonRecord> {
+    fs::read_to_string(path).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+}
+
+fn write(path: &Path, records: &[AnswerNdjsonRecord]) {
+    let lines: Vec<String> = records.iter().map(|r| serde_json::to_string(r).unwrap() + "\n").collect();
+    fs::write(path, lines.concat()).unwrap();
+}
+
+/// An FNV-era ledger, stamped the way the old writer did.
+fn fnv_records() -> Vec<AnswerNdjsonRecord> {
+    let mut prev = GENESIS_HEXSTAMP.to_string();
+    (0..2)
+        .map(|i| {
+            let ts = 1_760_000_000 + i;
+            let hexstamp = legacy_fnv_hexstamp("bostrom1", "k1", ts, 0.8, 0.05);
+            AnswerNdjsonRecord {
+                answer_id: format!("old{i}"),
+                subject_id: "bostrom1".to_string(),
+                kernel_id: "k1".to_string(),
+                route: "Info".to_string(),
+                knowledge_factor: 0.8,
+                roh: 0.05,
+                cybostate: Cybostate::RetrievalOnly,
+                bio: bio(),
+                actuation_forbidden: true,
+                non_commercial: true,
+                roh_domain: None,
+                hexstamp: hexstamp.clone(),
+                prev_hexstamp: std::mem::replace(&mut prev, hexstamp),
+                timestamp_utc: ts,
+                artifact_kind: "answerquality".to_string(),
+                contract_type: "neuroassistive".to_string(),
+                hexstamp_epoch: HEXSTAMP_EPOCH_FNV,
+                legacy_hexstamp: None,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn sha256_hexstamp_covers_every_field_but_the_stamp() {
+    let path = ledger("stamp");
+    write_three(&path);
+    let record = read(&path).remove(1);
+
+    let stamp = sha256_hexstamp(&record).unwrap();
+    assert_eq!(stamp, record.hexstamp);
+    assert!(stamp.starts_with("0x") && stamp.len() == 66);
+
+    let restamped = AnswerNdjsonRecord {
+        hexstamp: "0xanything".to_string(),
+        ..record.clone()
+    };
+    assert_eq!(sha256_hexstamp(&restamped).unwrap(), stamp);
+
+    let edits: [fn(&mut AnswerNdjsonRecord); 4] = [
+        |r| r.roh = 0.06,
+        |r| r.bio.fatigue_index = 0.9,
+        |r| r.prev_hexstamp = GENESIS_HEXSTAMP.to_string(),
+        |r| r.legacy_hexstamp = Some("0xNPANS0".to_string()),
+    ];
+    for edit in edits {
+        let mut edited = record.clone();
+        edit(&mut edited);
+        assert_ne!(sha256_hexstamp(&edited).unwrap(), stamp);
+    }
+}
+
+#[test]
+fn writers_refuse_to_fork_an_existing_ledger() {
+    let path = ledger("writer");
+    let stamps = write_three(&path);
+
+    let report = verify_answer_ledger(&path).unwrap();
+    assert_eq!(report.records, 3);
+    assert_eq!(report.fnv_records, 0);
+    assert!(!report.migrated);
+    assert_eq!(report.last_hexstamp, stamps[2]);
+
+    let err = AnswerLedgerWriter::new(&path).err().unwrap();
+    assert!(err.contains("not empty"), "{err}");
+
+    let writer = AnswerLedgerWriter::open(&path).unwrap();
+    assert_eq!(writer.last_hexstamp().unwrap(), stamps[2]);
+    let next = writer.append_entry(entry("a3", 1_770_000_003)).unwrap();
+    assert_eq!(read(&path)[3].prev_hexstamp, stamps[2]);
+    assert_eq!(verify_answer_ledger(&path).unwrap().last_hexstamp, next);
+
+    // An empty file is a fresh ledger.
+    let empty = ledger("writer-empty");
+    fs::write(&empty, "").unwrap();
+    AnswerLedgerWriter::new(&empty).unwrap();
+}
+
+#[test]
+fn verify_rejects_tampered_bodies_broken_links_and_fnv_after_sha() {
+    let path = ledger("verify");
+    write_three(&path);
+    let records = read(&path);
+
+    let mut tampered = records.clone();
+    tampered[1].knowledge_factor = 1.0;
+    write(&path, &tampered);
+    let err = verify_answer_ledger(&path).unwrap_err();
+    assert!(err.starts_with("line 2: hexstamp mismatch"), "{err}");
+
+    let mut dropped = records.clone();
+    dropped.remove(1);
+    write(&path, &dropped);
+    let err = verify_answer_ledger(&path).unwrap_err();
+    assert!(err.starts_with("line 2: prev_hexstamp"), "{err}");
+
+    let mut late_fnv = fnv_records().remove(0);
+    late_fnv.prev_hexstamp = records[2].hexstamp.clone();
+    let mut mixed = records.clone();
+    mixed.push(late_fnv);
+    write(&path, &mixed);
+    let err = verify_answer_ledger(&path).unwrap_err();
+    assert!(err.contains("FNV-stamped record after SHA-256 records"), "{err}");
+
+    // A fresh boundary may only start a SHA-256 ledger.
+    let mut boundary = fnv_records();
+    boundary[0].prev_hexstamp = format!("{EPOCH_BOUNDARY_PREFIX}00");
+    write(&path, &boundary);
+    assert!(verify_answer_ledger(&path).unwrap_err().contains("epoch boundary"));
+}
+
+#[test]
+fn migration_restamps_fnv_ledgers_and_keeps_the_original() {
+    let path = ledger("migrate");
+    let old = fnv_records();
+    write(&path, &old);
+    let original = fs::read(&path).unwrap();
+    assert_eq!(verify_answer_ledger(&path).unwrap().fnv_records, 2);
+
+    let report = migrate_fnv_ledger(&path).unwrap().unwrap();
+    assert_eq!(report.migrated, 2);
+    assert_eq!(fs::read(&report.backup).unwrap(), original);
+    assert_eq!(report.boundary, format!("{EPOCH_BOUNDARY_PREFIX}{}", hex::encode(Sha256::digest(&original))));
+
+    let migrated = read(&path);
+    assert_eq!(migrated[0].prev_hexstamp, report.boundary);
+    for (new, old) in migrated.iter().zip(&old) {
+        assert_eq!(new.hexstamp_epoch, HEXSTAMP_EPOCH_SHA256);
+        assert_eq!(new.legacy_hexstamp.as_ref(), Some(&old.hexstamp));
+        assert_eq!(new.answer_id, old.answer_id);
+    }
+    let verified = verify_answer_ledger(&path).unwrap();
+    assert!(verified.migrated);
+    assert_eq!((verified.records, verified.fnv_records), (2, 0));
+
+    // Appends continue the migrated chain; there is nothing left to migrate.
+    AnswerLedgerWriter::open(&path).unwrap().append_entry(entry("new", 1_770_000_000)).unwrap();
+    assert_eq!(verify_answer_ledger(&path).unwrap().records, 3);
+    assert_eq!(migrate_fnv_ledger(&path).unwrap(), None);
+
+    // A broken FNV ledger is not migrated.
+    let broken = ledger("migrate-broken");
+    let mut edited = fnv_records();
+    edited[1].roh = 0.2;
+    write(&broken, &edited);
+    assert!(migrate_fnv_ledger(&broken).unwrap_err().contains("line 2: hexstamp mismatch"));
+    assert!(!broken.with_extension("ndjson.fnv").exists());
+}
+
+#[test]
+fn migration_refuses_to_overwrite_an_existing_backup() {
+    let path = ledger("migrate-backup");
+    write(&path, &fnv_records());
+    let original = fs::read(&path).unwrap();
+    let backup = path.with_extension("ndjson.fnv");
+    fs::write(&backup, b"earlier backup\n").unwrap();
+
+    assert!(migrate_fnv_ledger(&path).unwrap_err().contains("backup already exists"));
+    assert_eq!(fs::read(&path).unwrap(), original);
+    assert_eq!(fs::read(&backup).unwrap(), b"earlier backup\n");
+
+    fs::remove_file(&backup).unwrap();
+    migrate_fnv_ledger(&path).unwrap().unwrap();
+    assert_eq!(fs::read(&backup).unwrap(), original);
+    assert!(!path.with_extension("ndjson.migrating").exists());
+}
+
+#[test]
+fn donutloop_import_recomputes_the_stamps() {
+    use donutloop::legacy::{answer, read_ndjson};
+
+    let path = ledger("import");
+    write_three(&path);
+    let records: Vec<answer::AnswerNdjsonRecord> = read_ndjson(&path).unwrap();
+    assert_eq!(answer::convert(&records).unwrap().len(), 3);
+
+    let mut tampered = records.clone();
+    tampered[2].roh = 0.0;
+    assert!(answer::convert(&tampered).is_err());
+}
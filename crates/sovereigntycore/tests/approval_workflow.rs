@@ -0,0 +1,281 @@
+use bech32::ToBase32;
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use sovereigntycore::approval::{
+    approval_message, ApprovalBook, ApprovalBundle, ApprovalStatus, CosmosSecp256k1Verifier, DetachedSignature,
+    EvmSecp256k1Verifier, SignatureVerifier, StakeAddressVerifier, DEFAULT_BUNDLE_TTL_SECS,
+};
+use sovereigntycore::core_stake_guard::StakeGuard;
+use sovereigntycore::stake::{ScopeKind, StakeRow, StakeTable};
+
+const PROPOSAL: &str = "9f2c1e0b7a";
+const TTL: u64 = DEFAULT_BUNDLE_TTL_SECS;
+
+fn repo_file(rel: &str) -> String {
+    format!("{}/../../{}", env!("CARGO_MANIFEST_DIR"), rel)
+}
+
+struct Account {
+    key: SigningKey,
+    address: String,
+}
+
+fn account(seed: u8, hrp: &str) -> Account {
+    let key = SigningKey::from_bytes(&[seed; 32].into()).unwrap();
+    let pubkey = key.verifying_key().to_sec1_bytes();
+    let account = Ripemd160::digest(Sha256::digest(&pubkey));
+    let address = bech32::encode(hrp, account.to_base32(), bech32::Variant::Bech32).unwrap();
+    Account { key, address }
+}
+
+fn sign(acct: &Account, subject: &str, scope: ScopeKind) -> DetachedSignature {
+    let sig: Signature = acct.key.sign(&approval_message(subject, scope, PROPOSAL));
+    DetachedSignature {
+        address: acct.address.clone(),
+        public_key: hex::encode(acct.key.verifying_key().to_sec1_bytes()),
+        signature: hex::encode(sig.to_bytes()),
+    }
+}
+
+fn evm_account(seed: u8) -> Account {
+    let key = SigningKey::from_bytes(&[seed; 32].into()).unwrap();
+    let address = EvmSecp256k1Verifier::address_of(key.verifying_key());
+    Account { key, address }
+}
+
+fn evm_sign(acct: &Account, subject: &str, scope: ScopeKind) -> DetachedSignature {
+    let digest = EvmSecp256k1Verifier::personal_sign_digest(&approval_message(subject, scope, PROPOSAL));
+    let (sig, recovery) = acct.key.sign_prehash_recoverable(&digest).unwrap();
+    let mut bytes = sig.to_bytes().to_vec();
+    bytes.push(27 + recovery.to_byte());
+    DetachedSignature {
+        address: acct.address.clone(),
+        public_key: hex::encode(acct.key.verifying_key().to_sec1_bytes()),
+        signature: format!("0x{}", hex::encode(bytes)),
+    }
+}
+
+fn finalized(status: ApprovalStatus) -> ApprovalBundle {
+    match status {
+        ApprovalStatus::Finalized(bundle) => bundle,
+        ApprovalStatus::Pending { missing } => panic!("still missing {:?}", missing),
+    }
+}
+
+fn row(roleid: &str, subject: &str, address: &str, rolekind: &str) -> StakeRow {
+    StakeRow {
+        roleid: roleid.to_string(),
+        subjectid: subject.to_string(),
+        bostromaddress: address.to_string(),
+        rolekind: rolekind.to_string(),
+        canveto: true,
+        caninitevolve: true,
+        requiredforlifeforce: true,
+        requiredforarchchange: true,
+    }
+}
+
+fn setup() -> (Account, Account, StakeTable) {
+    let host = account(7, "bostrom");
+    let organic = account(9, "zeta");
+    let table = StakeTable::from_rows(vec![
+        row("hostprimary", &host.address, &host.address, "Host"),
+        row("organiccpu", &host.address, &organic.address, "OrganicCPU"),
+    ])
+    .unwrap();
+    (host, organic, table)
+}
+
+#[test]
+fn lifeforce_bundle_finalizes_after_both_roles_sign() {
+    let (host, organic, table) = setup();
+    let subject = host.address.clone();
+    let mut book = ApprovalBook::new(table.clone(), CosmosSecp256k1Verifier, 600);
+
+    book.open(PROPOSAL, &subject, ScopeKind::Lifeforce, 1_000).unwrap();
+
+    match book.submit(PROPOSAL, ScopeKind::Lifeforce, sign(&host, &subject, ScopeKind::Lifeforce), 1_010).unwrap() {
+        ApprovalStatus::Pending { missing } => {
+            assert_eq!(missing.into_iter().collect::<Vec<_>>(), vec!["OrganicCPU".to_string()]);
+        }
+        ApprovalStatus::Finalized(_) => panic!("finalized without OrganicCPU"),
+    }
+
+    let bundle = match book
+        .submit(PROPOSAL, ScopeKind::Lifeforce, sign(&organic, &subject, ScopeKind::Lifeforce), 1_020)
+        .unwrap()
+    {
+        ApprovalStatus::Finalized(bundle) => bundle,
+        ApprovalStatus::Pending { missing } => panic!("still missing {:?}", missing),
+    };
+    assert!(book.pending(PROPOSAL, ScopeKind::Lifeforce).is_none());
+    bundle.verify(&table, &CosmosSecp256k1Verifier, PROPOSAL, 1_020, TTL).unwrap();
+    assert!(bundle.verify(&table, &CosmosSecp256k1Verifier, "other-proposal", 1_020, TTL).is_err());
+
+    // Bundles are only good for the TTL after they were finalized.
+    bundle.verify(&table, &CosmosSecp256k1Verifier, PROPOSAL, 1_020 + TTL - 1, TTL).unwrap();
+    let err = bundle.verify(&table, &CosmosSecp256k1Verifier, PROPOSAL, 1_020 + TTL, TTL).unwrap_err();
+    assert!(err.to_string().contains("expired"), "{err}");
+    assert!(bundle.verify(&table, &CosmosSecp256k1Verifier, PROPOSAL, 1_019, TTL).is_err());
+
+    let guard = StakeGuard::new(table);
+    let proposal = sovereigntycore::update::UpdateProposal {
+        id: "test-proposal".to_string(),
+        subjectid: subject,
+        scope: vec!["lifeforce".to_string()],
+        // Self-declared signers are ignored by enforce_approved.
+        signers: Vec::new(),
+    };
+    guard
+        .enforce_approved(&proposal, PROPOSAL, std::slice::from_ref(&bundle), &CosmosSecp256k1Verifier, 1_030)
+        .unwrap();
+    assert!(guard
+        .enforce_approved(&proposal, PROPOSAL, &[], &CosmosSecp256k1Verifier, 1_030)
+        .is_err());
+    // A bundle for another proposal in the same scope is passed over, not
+    // taken in place of this one's.
+    let stale = ApprovalBundle {
+        proposal_hash: "other-proposal".to_string(),
+        ..bundle.clone()
+    };
+    guard
+        .enforce_approved(&proposal, PROPOSAL, &[stale.clone(), bundle.clone()], &CosmosSecp256k1Verifier, 1_030)
+        .unwrap();
+    let err = guard
+        .enforce_approved(&proposal, PROPOSAL, &[stale], &CosmosSecp256k1Verifier, 1_030)
+        .unwrap_err();
+    assert!(err.to_string().contains("no approval bundle"), "{err}");
+    let strict = guard.with_bundle_ttl(5);
+    assert!(strict
+        .enforce_approved(&proposal, PROPOSAL, &[bundle], &CosmosSecp256k1Verifier, 1_030)
+        .is_err());
+}
+
+#[test]
+fn rejects_foreign_keys_and_wrong_scope_signatures() {
+    let (host, _organic, table) = setup();
+    let subject = host.address.clone();
+    let mut book = ApprovalBook::new(table, CosmosSecp256k1Verifier, 600);
+    book.open(PROPOSAL, &subject, ScopeKind::ArchChange, 1_000).unwrap();
+
+    // Signed for another scope.
+    let wrong_scope = sign(&host, &subject, ScopeKind::Lifeforce);
+    assert!(book.submit(PROPOSAL, ScopeKind::ArchChange, wrong_scope, 1_001).is_err());
+
+    // Key does not hash to the claimed address.
+    let mut stolen = sign(&account(3, "bostrom"), &subject, ScopeKind::ArchChange);
+    stolen.address = host.address.clone();
+    assert!(book.submit(PROPOSAL, ScopeKind::ArchChange, stolen, 1_002).is_err());
+
+    // Address holds no stake role.
+    let outsider = sign(&account(4, "bostrom"), &subject, ScopeKind::ArchChange);
+    assert!(book.submit(PROPOSAL, ScopeKind::ArchChange, outsider, 1_003).is_err());
+
+    let pending = book.pending(PROPOSAL, ScopeKind::ArchChange).unwrap();
+    assert_eq!(pending.missing_roles().len(), 2);
+}
+
+#[test]
+fn stale_requests_expire() {
+    let (host, _organic, table) = setup();
+    let subject = host.address.clone();
+    let mut book = ApprovalBook::new(table, CosmosSecp256k1Verifier, 60);
+    book.open(PROPOSAL, &subject, ScopeKind::Lifeforce, 1_000).unwrap();
+
+    assert!(book.expire(1_059).is_empty());
+    let expired = book.expire(1_060);
+    assert_eq!(expired.len(), 1);
+    assert!(book
+        .submit(PROPOSAL, ScopeKind::Lifeforce, sign(&host, &subject, ScopeKind::Lifeforce), 1_061)
+        .is_err());
+
+    // A fresh request can be opened once the old one is gone.
+    book.open(PROPOSAL, &subject, ScopeKind::Lifeforce, 1_062).unwrap();
+}
+
+#[test]
+fn evm_addresses_match_the_reference_derivation() {
+    // Private key 1 is the well-known 0x7E5F…5Bdf account.
+    let mut one = [0u8; 32];
+    one[31] = 1;
+    let key = SigningKey::from_bytes(&one.into()).unwrap();
+    assert_eq!(
+        EvmSecp256k1Verifier::address_of(key.verifying_key()),
+        "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+    );
+
+    let acct = evm_account(5);
+    let message = approval_message("bostrom1", ScopeKind::ArchChange, PROPOSAL);
+    let mut sig = evm_sign(&acct, "bostrom1", ScopeKind::ArchChange);
+    sig.address = sig.address.to_uppercase().replacen("0X", "0x", 1);
+    EvmSecp256k1Verifier.verify(&message, &sig).unwrap();
+    StakeAddressVerifier::default().verify(&message, &sig).unwrap();
+    assert!(CosmosSecp256k1Verifier.verify(&message, &sig).is_err());
+
+    let other = approval_message("bostrom1", ScopeKind::Lifeforce, PROPOSAL);
+    assert!(EvmSecp256k1Verifier.verify(&other, &sig).is_err());
+    let mut stolen = evm_sign(&evm_account(6), "bostrom1", ScopeKind::ArchChange);
+    stolen.address = acct.address.clone();
+    assert!(EvmSecp256k1Verifier.verify(&message, &stolen).is_err());
+}
+
+#[test]
+fn shipped_stake_rows_are_all_verifiable_for_archchange() {
+    let shipped = StakeTable::load_from_file(&repo_file("policies/bostrom-stake-v1.stake.aln")).unwrap();
+    let subject = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+    let required = shipped.required_roles_for_scope(subject, ScopeKind::ArchChange);
+    assert!(required.contains("ResearchAgent"), "{required:?}");
+
+    // The ResearchAgent signs from an EVM address a Cosmos-only book could
+    // never verify, so such a book refuses to open the request at all.
+    let mut cosmos_only = ApprovalBook::new(shipped.clone(), CosmosSecp256k1Verifier, 600);
+    let err = cosmos_only.open(PROPOSAL, subject, ScopeKind::ArchChange, 1_000).unwrap_err();
+    assert!(err.to_string().contains("ResearchAgent"), "{err}");
+    let mut book = ApprovalBook::new(shipped.clone(), StakeAddressVerifier::default(), 600);
+    book.open(PROPOSAL, subject, ScopeKind::ArchChange, 1_000).unwrap();
+
+    // Same rows with addresses we hold keys for, in the same formats.
+    let host = account(7, "bostrom");
+    let organic = account(9, "zeta");
+    let research = evm_account(11);
+    let rows: Vec<StakeRow> = shipped
+        .rows_for_subject(subject)
+        .iter()
+        .map(|r| {
+            let address = match r.rolekind.as_str() {
+                "Host" => host.address.clone(),
+                "OrganicCPU" => organic.address.clone(),
+                "ResearchAgent" => research.address.clone(),
+                _ => r.bostromaddress.clone(),
+            };
+            StakeRow {
+                subjectid: host.address.clone(),
+                bostromaddress: address,
+                ..r.clone()
+            }
+        })
+        .collect();
+    let table = StakeTable::from_rows(rows).unwrap();
+    let subject = host.address.clone();
+    assert_eq!(table.required_roles_for_scope(&subject, ScopeKind::ArchChange), required);
+
+    let verifier = StakeAddressVerifier::default();
+    let mut book = ApprovalBook::new(table.clone(), verifier, 600);
+    book.open(PROPOSAL, &subject, ScopeKind::ArchChange, 1_000).unwrap();
+    for acct in [&host, &organic] {
+        let sig = sign(acct, &subject, ScopeKind::ArchChange);
+        assert!(matches!(
+            book.submit(PROPOSAL, ScopeKind::ArchChange, sig, 1_010).unwrap(),
+            ApprovalStatus::Pending { .. }
+        ));
+    }
+    let bundle = finalized(
+        book.submit(PROPOSAL, ScopeKind::ArchChange, evm_sign(&research, &subject, ScopeKind::ArchChange), 1_020)
+            .unwrap(),
+    );
+    assert_eq!(bundle.approvals.len(), 3);
+    bundle.verify(&table, &verifier, PROPOSAL, 1_030, TTL).unwrap();
+}
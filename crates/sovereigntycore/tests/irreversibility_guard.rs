@@ -0,0 +1,134 @@
+use organiccpualn::evolvestream::{EvolutionProposal, UpdateEffectBounds};
+use sovereigntycore::irreversibility_guard::{guard_irreversibility, IrreversibilityDecision, NeurorightsPolicy};
+use sovereigntycore::stake::StakeTable;
+
+const HOST: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+const ORGANIC: &str = "zeta12x0up66pzyeretzyku8p4ccuxrjqtqpdc4y4x8";
+
+fn repo_file(rel: &str) -> String {
+    format!("{}/../../{}", env!("CARGO_MANIFEST_DIR"), rel)
+}
+
+/// Scope-shard stake file with a Host-only rule for `host_self_arch_evolution`
+/// authorized by `token_kind`.
+fn stake(token_kind: &str) -> StakeTable {
+    StakeTable::parse(&format!(
+        "meta:
+  subject_id: {HOST}
+roles:
+  - kind: Host
+    role_id: host-primary
+    bostrom_address: {HOST}
+  - kind: OrganicCPU
+    role_id: organiccpu-core
+    bostrom_address: {ORGANIC}
+scopes:
+  - scope_id: host_self_arch_evolution
+    required_roles: [Host]
+    token_kinds_allowed: [{token_kind}]
+    multisig_required: false
+  - scope_id: lifeforce_alteration
+    required_roles: [Host, OrganicCPU]
+    multisig_required: true
+  - scope_id: arch_change
+    required_roles: [Host, OrganicCPU]
+    multisig_required: true
+"
+    ))
+    .unwrap()
+}
+
+fn policy() -> NeurorightsPolicy {
+    NeurorightsPolicy::load_from_file(&repo_file("policies/homo-sapiens-neurorights-v1.neurorights.json")).unwrap()
+}
+
+fn proposal(scope_id: &str, irreversible: bool, signers: &[&str]) -> EvolutionProposal {
+    EvolutionProposal {
+        proposal_id: "p-1".to_string(),
+        subject_id: HOST.to_string(),
+        species_id: "homo-sapiens".to_string(),
+        kind: "ArchChange".to_string(),
+        scope_id: scope_id.to_string(),
+        module: "sovereigntycore".to_string(),
+        effect_bounds: UpdateEffectBounds {
+            l2_delta_norm: 0.5,
+            irreversible,
+        },
+        roh_before: 0.1,
+        roh_after: 0.1,
+        decay_before: 0.5,
+        decay_after: 0.5,
+        computebioload_before: 0.4,
+        computebioload_after: 0.4,
+        decision: "Pending".to_string(),
+        justice_flags: Vec::new(),
+        tsafe_mode: "Baseline".to_string(),
+        domain_tags: Vec::new(),
+        signer_dids: signers.iter().map(|s| s.to_string()).collect(),
+        hexstamp: String::new(),
+        timestamp_utc: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+fn rejected(decision: IrreversibilityDecision) -> String {
+    match decision {
+        IrreversibilityDecision::Rejected(reason) => reason,
+        IrreversibilityDecision::Allowed => panic!("allowed"),
+    }
+}
+
+#[test]
+fn host_signed_self_evolution_may_be_irreversible() {
+    let (stake, policy) = (stake("EVOLVE"), policy());
+    let p = proposal("host_self_arch_evolution", true, &[HOST]);
+    assert_eq!(guard_irreversibility(&p, &stake, &policy), IrreversibilityDecision::Allowed);
+
+    // Reversible proposals are not this guard's concern.
+    let reversible = proposal("arch_change", false, &[]);
+    assert_eq!(guard_irreversibility(&reversible, &stake, &policy), IrreversibilityDecision::Allowed);
+}
+
+#[test]
+fn quorum_comes_from_the_stake_engine() {
+    let policy = policy();
+
+    let unsigned = proposal("host_self_arch_evolution", true, &[ORGANIC]);
+    let reason = rejected(guard_irreversibility(&unsigned, &stake("EVOLVE"), &policy));
+    assert!(reason.contains("requires valid Host EVOLVE signature"), "{reason}");
+    assert!(reason.contains("missing approvals from roles [\"Host\"]"), "{reason}");
+
+    let host = proposal("host_self_arch_evolution", true, &[HOST]);
+    let reason = rejected(guard_irreversibility(&host, &stake("SMART"), &policy));
+    assert!(reason.contains("token kind EVOLVE may not authorize"), "{reason}");
+
+    // A table without a rule for the scope does not wave the proposal through.
+    let csv = StakeTable::load_from_file(&repo_file("policies/bostrom-stake-v1.stake.aln")).unwrap();
+    let reason = rejected(guard_irreversibility(&host, &csv, &policy));
+    assert_eq!(reason, "No stake quorum rule for host_self_arch_evolution");
+}
+
+#[test]
+fn neurorights_policy_limits_irreversibility() {
+    let (stake, policy) = (stake("EVOLVE"), policy());
+
+    let mut cross = proposal("host_self_arch_evolution", true, &[HOST]);
+    cross.domain_tags.push("cross-species".to_string());
+    assert_eq!(
+        rejected(guard_irreversibility(&cross, &stake, &policy)),
+        "Irreversible cross-species evolution forbidden"
+    );
+    // Without a multi-species section the cross-species ban still holds.
+    let silent = NeurorightsPolicy {
+        multi_species: None,
+        ..policy.clone()
+    };
+    assert!(matches!(guard_irreversibility(&cross, &stake, &silent), IrreversibilityDecision::Rejected(_)));
+
+    let other_scope = proposal("arch_change", true, &[HOST]);
+    let reason = rejected(guard_irreversibility(&other_scope, &stake, &policy));
+    assert!(reason.contains("got arch_change"), "{reason}");
+
+    let host = proposal("host_self_arch_evolution", true, &[HOST]);
+    let reason = rejected(guard_irreversibility(&host, &stake, &NeurorightsPolicy::default()));
+    assert_eq!(reason, "Neurorights policy does not allow Host-chosen irreversibility");
+}
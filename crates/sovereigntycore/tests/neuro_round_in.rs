@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use donutloop::{NewEntry, RohCertificate};
+use ed25519_dalek::SigningKey;
+use organiccpualn::evolvestream::{EvolutionProposal, UpdateEffectBounds};
+use organiccpualn::roh_registry::RohRegistry;
+use sovereigntycore::neuro_round_in::{
+    append_neuro_round_in_entry, guard_neuro_round_in, NeuroRoundInSpec, SmartTokenScope, TokenKind, NEURO_ROUND_IN_KIND,
+};
+use sovereigntycore::roh_certificate::certify_roh;
+use sovereigntycore::stake::StakeTable;
+
+const HOST: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+const MODEL: &str = "bostrom-rohmodel-v1";
+
+fn registry() -> RohRegistry {
+    RohRegistry::load_dir(format!("{}/../../qpudatashards/particles", env!("CARGO_MANIFEST_DIR"))).unwrap()
+}
+
+fn kernel() -> SigningKey {
+    SigningKey::from_bytes(&[3u8; 32])
+}
+
+/// Every axis of `model_id` at `load`.
+fn state(registry: &RohRegistry, model_id: &str, load: f32) -> HashMap<String, f32> {
+    registry.get(model_id).unwrap().axes.iter().map(|a| (a.name.clone(), load)).collect()
+}
+
+fn certificate(model_id: &str, proposal_id: &str, before: f32, after: f32) -> RohCertificate {
+    let registry = registry();
+    let (before, after) = (state(&registry, model_id, before), state(&registry, model_id, after));
+    certify_roh(&registry, model_id, proposal_id, Some(HOST), &before, &after, &kernel()).unwrap()
+}
+
+fn stake() -> StakeTable {
+    StakeTable::parse(&format!(
+        "meta:
+  subject_id: {HOST}
+roles:
+  - kind: Host
+    role_id: host-primary
+    bostrom_address: {HOST}
+  - kind: OrganicCPU
+    role_id: organiccpu-core
+    bostrom_address: zeta12x0up66pzyeretzyku8p4ccuxrjqtqpdc4y4x8
+scopes:
+  - scope_id: lifeforce_alteration
+    required_roles: [Host, OrganicCPU]
+    multisig_required: true
+  - scope_id: arch_change
+    required_roles: [Host, OrganicCPU]
+    multisig_required: true
+"
+    ))
+    .unwrap()
+}
+
+fn smart() -> SmartTokenScope {
+    SmartTokenScope {
+        kind: TokenKind::Smart,
+        forbidden_domains: Vec::new(),
+    }
+}
+
+/// A round-in proposal reporting the RoH `cert` certifies.
+fn proposal(cert: &RohCertificate) -> EvolutionProposal {
+    EvolutionProposal {
+        proposal_id: cert.claim.proposal_id.clone(),
+        subject_id: HOST.to_string(),
+        species_id: "homo-sapiens".to_string(),
+        kind: NEURO_ROUND_IN_KIND.to_string(),
+        scope_id: "neuro_round_in".to_string(),
+        module: "motormacros".to_string(),
+        effect_bounds: UpdateEffectBounds {
+            l2_delta_norm: 0.01,
+            irreversible: false,
+        },
+        roh_before: cert.claim.roh_before,
+        roh_after: cert.claim.roh_after,
+        decay_before: 0.5,
+        decay_after: 0.5,
+        computebioload_before: 0.4,
+        computebioload_after: 0.4,
+        decision: "Pending".to_string(),
+        justice_flags: Vec::new(),
+        tsafe_mode: "TsafeNormal".to_string(),
+        domain_tags: Vec::new(),
+        signer_dids: vec![HOST.to_string()],
+        hexstamp: String::new(),
+        timestamp_utc: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+fn guard(p: &EvolutionProposal, roh_model_id: &str, cert: &RohCertificate) -> Result<(), String> {
+    let trusted = [kernel().verifying_key()];
+    guard_neuro_round_in(&NeuroRoundInSpec::default(), &stake(), HOST, p, roh_model_id, &smart(), cert, &trusted)
+}
+
+fn entry(proposal_id: &str) -> NewEntry {
+    let mut entry = NewEntry::new("e-1", NEURO_ROUND_IN_KIND).with_ext("tsafe_mode", "TsafeNormal");
+    entry.proposal_id = Some(proposal_id.to_string());
+    entry
+}
+
+#[test]
+fn guard_takes_roh_from_the_certificate() {
+    let cert = certificate(MODEL, "p-1", 0.2, 0.1);
+    let p = proposal(&cert);
+    guard(&p, MODEL, &cert).unwrap();
+
+    // Other stakeholders and EVOLVE tokens do not pass.
+    let trusted = [kernel().verifying_key()];
+    let err = guard_neuro_round_in(&NeuroRoundInSpec::default(), &stake(), "bostrom1other", &p, MODEL, &smart(), &cert, &trusted)
+        .unwrap_err();
+    assert!(err.contains("automatic deny"), "{err}");
+    let evolve = SmartTokenScope {
+        kind: TokenKind::Evolve,
+        forbidden_domains: Vec::new(),
+    };
+    let err = guard_neuro_round_in(&NeuroRoundInSpec::default(), &stake(), HOST, &p, MODEL, &evolve, &cert, &trusted)
+        .unwrap_err();
+    assert_eq!(err, "NeuroRoundInRequiresSMARTToken");
+
+    // Monotone-looking floats do not stand in for a rising certificate.
+    let rising = certificate(MODEL, "p-1", 0.1, 0.2);
+    let mut claimed = proposal(&rising);
+    (claimed.roh_before, claimed.roh_after) = (0.2, 0.1);
+    let err = guard(&claimed, MODEL, &rising).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInRoHCertificateInvalid"), "{err}");
+
+    // Numbers that disagree with the certificate are refused even when both are monotone.
+    let mut misreported = proposal(&cert);
+    misreported.roh_after -= 0.05;
+    let err = guard(&misreported, MODEL, &cert).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInRoHMisreported"), "{err}");
+}
+
+#[test]
+fn guard_refuses_certificates_for_another_proposal_or_model() {
+    let cert = certificate(MODEL, "p-1", 0.2, 0.1);
+
+    let mut other = proposal(&cert);
+    other.proposal_id = "p-2".to_string();
+    let err = guard(&other, MODEL, &cert).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInRoHCertificateMismatch"), "{err}");
+
+    // The proposal must name the spec's model, and the certificate must use it.
+    let err = guard(&proposal(&cert), "rohmodel_v1", &cert).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInRoHModelNotAllowed"), "{err}");
+    let legacy = certificate("rohmodel_v1", "p-1", 0.2, 0.1);
+    let err = guard(&proposal(&legacy), MODEL, &legacy).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInRoHCertificateWrongModel"), "{err}");
+}
+
+#[test]
+fn ledger_entries_need_their_own_certificate() {
+    let spec = NeuroRoundInSpec::default();
+    let trusted = [kernel().verifying_key()];
+    let cert = certificate(MODEL, "p-1", 0.2, 0.1);
+
+    append_neuro_round_in_entry(&entry("p-1").with_roh_certificate(cert.clone()), &spec, &trusted).unwrap();
+
+    let err = append_neuro_round_in_entry(&entry("p-1"), &spec, &trusted).unwrap_err();
+    assert_eq!(err, "NeuroRoundInLedgerRoHUncertified");
+
+    let mut bare = entry("p-1").with_roh_certificate(cert.clone());
+    bare.roh_after = Some(0.0);
+    let err = append_neuro_round_in_entry(&bare, &spec, &trusted).unwrap_err();
+    assert_eq!(err, "NeuroRoundInLedgerBareRoHValues");
+
+    let err = append_neuro_round_in_entry(&entry("p-2").with_roh_certificate(cert.clone()), &spec, &trusted).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInLedgerRoHCertificateMismatch"), "{err}");
+
+    let legacy = certificate("rohmodel_v1", "p-1", 0.2, 0.1);
+    let err = append_neuro_round_in_entry(&entry("p-1").with_roh_certificate(legacy), &spec, &trusted).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInLedgerRoHCertificateWrongModel"), "{err}");
+
+    let rising = certificate(MODEL, "p-1", 0.1, 0.2);
+    let err = append_neuro_round_in_entry(&entry("p-1").with_roh_certificate(rising), &spec, &trusted).unwrap_err();
+    assert!(err.starts_with("NeuroRoundInLedgerRoHNotMonotone"), "{err}");
+
+    let untrusted = [SigningKey::from_bytes(&[4u8; 32]).verifying_key()];
+    assert!(append_neuro_round_in_entry(&entry("p-1").with_roh_certificate(cert), &spec, &untrusted).is_err());
+}
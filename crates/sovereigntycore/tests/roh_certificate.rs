@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use donutloop::{require_roh_certified, Ledger, MemoryStore, NewEntry};
+use ed25519_dalek::SigningKey;
+use organiccpualn::roh_registry::RohRegistry;
+use sovereigntycore::roh_certificate::{audit_roh_certificate, certify_roh};
+
+const HOST: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn registry() -> RohRegistry {
+    RohRegistry::load_dir(format!("{}/../../qpudatashards/particles", env!("CARGO_MANIFEST_DIR"))).unwrap()
+}
+
+fn state(load: f32) -> HashMap<String, f32> {
+    ["energy_load", "thermal_load", "cognitive_load", "inflammation", "eco_impact"]
+        .iter()
+        .map(|a| (a.to_string(), load))
+        .collect()
+}
+
+#[test]
+fn certificates_recompute_roh_and_re_derive_for_auditors() {
+    let registry = registry();
+    let kernel = SigningKey::from_bytes(&[3u8; 32]);
+    let trusted = [kernel.verifying_key()];
+
+    let cert = certify_roh(&registry, "rohmodel_v1", "p-1", Some(HOST), &state(0.2), &state(0.1), &kernel).unwrap();
+    assert!((cert.claim.roh_before - 0.2).abs() < 1e-6);
+    assert!((cert.claim.roh_after - 0.1).abs() < 1e-6);
+    assert_eq!(cert.claim.model_version, "1.0.0");
+    cert.verify_monotone(&trusted).unwrap();
+    audit_roh_certificate(&cert, &registry, &state(0.2), &state(0.1), &trusted).unwrap();
+
+    // The auditor needs the same inputs, model and values.
+    assert!(audit_roh_certificate(&cert, &registry, &state(0.2), &state(0.05), &trusted).is_err());
+    let mut forged = cert.clone();
+    forged.claim.roh_after = 0.0;
+    assert!(audit_roh_certificate(&forged, &registry, &state(0.2), &state(0.1), &trusted).is_err());
+    let other = SigningKey::from_bytes(&[4u8; 32]);
+    assert!(audit_roh_certificate(&cert, &registry, &state(0.2), &state(0.1), &[other.verifying_key()]).is_err());
+
+    // An increase is still certified, but as non-monotone.
+    let rising = certify_roh(&registry, "rohmodel_v1", "p-2", None, &state(0.1), &state(0.2), &kernel).unwrap();
+    assert!(!rising.claim.is_monotone());
+    assert!(rising.verify_monotone(&trusted).is_err());
+
+    let mut partial = state(0.1);
+    partial.remove("eco_impact");
+    assert!(certify_roh(&registry, "rohmodel_v1", "p-3", None, &partial, &state(0.1), &kernel).is_err());
+}
+
+#[test]
+fn ledger_entries_store_the_certificate() {
+    let registry = registry();
+    let kernel = SigningKey::from_bytes(&[3u8; 32]);
+    let trusted = [kernel.verifying_key()];
+    let cert = certify_roh(&registry, "rohmodel_v1", "p-1", Some(HOST), &state(0.2), &state(0.1), &kernel).unwrap();
+
+    let mut ledger = Ledger::open(MemoryStore::new()).unwrap();
+    let entry = NewEntry::new("e-1", "EvolutionDecisionApplied").with_roh_certificate(cert.clone());
+    ledger.append(entry, Some(&kernel)).unwrap();
+    require_roh_certified(ledger.entries(), &trusted).unwrap();
+
+    let stored = ledger.entries()[0].body.roh_certificate.as_ref().unwrap();
+    audit_roh_certificate(stored, &registry, &state(0.2), &state(0.1), &trusted).unwrap();
+}
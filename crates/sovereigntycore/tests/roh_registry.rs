@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use organiccpualn::roh_registry::{parse_shard, RohModelKind, RohRegistry};
+use sovereigntycore::npf_roh_safe_hint::{npf_roh_binding, npf_roh_safe_hint, AssistedActionContext};
+use sovereigntycore::risk_of_harm::RiskOfHarm;
+
+fn repo_file(rel: &str) -> String {
+    format!("{}/../../{}", env!("CARGO_MANIFEST_DIR"), rel)
+}
+
+fn state(pairs: &[(&str, f32)]) -> HashMap<String, f32> {
+    pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+}
+
+#[test]
+fn shipped_shards_register_under_their_ids() {
+    let registry = RohRegistry::load_dir(repo_file("qpudatashards/particles")).unwrap();
+    assert_eq!(registry.ids().collect::<Vec<_>>(), vec!["bostrom-rohmodel-v1", "rohmodel_v1"]);
+
+    let indented = RohRegistry::load_dir(repo_file("qpudata/shards/particles")).unwrap();
+    let model = indented.get("homo-sapiens-rohmodel-v1").unwrap();
+    assert_eq!(model.axes.len(), 7);
+    assert_eq!(model.axes[0].name, "thermalload");
+
+    let roh = RiskOfHarm::from_registry(&registry, "rohmodel_v1").unwrap();
+    let calm = state(&[
+        ("energy_load", 0.2),
+        ("thermal_load", 0.2),
+        ("cognitive_load", 0.2),
+        ("inflammation", 0.2),
+        ("eco_impact", 0.2),
+    ]);
+    assert!((roh.estimate(&calm).unwrap() - 0.2).abs() < 1e-6);
+    assert!(roh.is_within_ceiling(&calm).unwrap());
+
+    let mut partial = calm.clone();
+    partial.remove("eco_impact");
+    assert!(roh.estimate(&partial).is_err());
+    assert!(RiskOfHarm::from_registry(&registry, "no-such-model").is_err());
+}
+
+#[test]
+fn bindings_must_match_model_axes() {
+    let mut registry = RohRegistry::new();
+    for def in parse_shard(
+        "model_id: npf-hint-v1\n\
+         kind: piecewise\n\
+         roh_ceiling: 0.3\n\
+         axes:\n  - {name: cognitive_load}\n  - {name: change_complexity}\n  - {name: governance_sensitivity}\n\
+         curves:\n  - [[0.0, 0.0], [1.0, 0.1]]\n  - [[0.0, 0.0], [0.5, 0.02], [1.0, 0.1]]\n  - [[0.0, 0.0], [1.0, 0.1]]\n",
+    )
+    .unwrap()
+    {
+        registry.insert(def).unwrap();
+    }
+
+    let binding = npf_roh_binding(&registry, "npf-hint-v1").unwrap();
+    let hint = npf_roh_safe_hint(
+        &binding,
+        &AssistedActionContext {
+            cognitive_load: 0.5,
+            change_complexity: 0.5,
+            governance_sensitivity: 1.0,
+        },
+    )
+    .unwrap();
+    assert!((hint.roh_estimate - 0.17).abs() < 1e-6);
+    assert!(!hint.near_ceiling);
+
+    assert!(registry.bind("npf-hint-v1", &["cognitive_load", "change_complexity"]).is_err());
+    assert!(registry
+        .bind("npf-hint-v1", &["cognitive_load", "change_complexity", "fatigue_index"])
+        .is_err());
+    assert!(binding.compute(&[0.1, 0.2]).is_err());
+}
+
+#[test]
+fn polytope_mode_shards_reach_the_ceiling_on_the_boundary() {
+    let defs = parse_shard(
+        "model_id: bostrom-modes-v1\n\
+         version: 1.0.0\n\
+         subject_id: bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7\n\
+         axes:\n  - {name: thermal_load, unit: normalized}\n  - {name: cognitive_load}\n\
+         modes:\n\
+         \x20 - mode_name: Baseline\n    A: [[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]\n    b: [0.8, 0.8, 1.2]\n    roh_ceiling: 0.3\n",
+    )
+    .unwrap();
+    assert_eq!(defs.len(), 1);
+    let model = &defs[0];
+    assert_eq!(model.model_id, "bostrom-modes-v1/Baseline");
+    assert!(matches!(model.kind, RohModelKind::Polytope { .. }));
+    model.validate().unwrap();
+
+    // On the a₃ face: 0.6 + 0.6 = 1.2.
+    assert!((model.compute(&[0.6, 0.6]).unwrap() - 0.3).abs() < 1e-6);
+    assert!(model.compute(&[0.4, 0.2]).unwrap() < 0.3);
+    assert!(model.compute(&[0.9, 0.1]).unwrap() > 0.3);
+
+    let mut bad = model.clone();
+    bad.kind = RohModelKind::Polytope {
+        a: vec![vec![1.0, 0.0, 0.0]],
+        b: vec![0.5],
+    };
+    assert!(bad.validate().is_err());
+}
@@ -0,0 +1,105 @@
+use sovereigntycore::stake::{reconcile_files, ScopeKind, StakeContradiction, StakeTable};
+
+const HOST: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+const ORGANIC: &str = "zeta12x0up66pzyeretzyku8p4ccuxrjqtqpdc4y4x8";
+const RESEARCH: &str = "0x519fC0eB4111323Cac44b70e1aE31c30e405802D";
+
+fn repo_file(rel: &str) -> String {
+    format!("{}/../../{}", env!("CARGO_MANIFEST_DIR"), rel)
+}
+
+fn signers(addrs: &[&str]) -> Vec<String> {
+    addrs.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn shipped_csv_and_shard_yaml_agree_on_lifeforce_and_archchange() {
+    let csv = StakeTable::load_from_file(&repo_file("policies/bostrom-stake-v1.stake.aln")).unwrap();
+    let yaml =
+        StakeTable::load_from_file(&repo_file("qpudatashards/particles/bostrom-stake-v1.stake.aln")).unwrap();
+
+    for table in [&csv, &yaml] {
+        assert!(table.check_multisig(HOST, ScopeKind::Lifeforce, &signers(&[HOST])).is_err());
+        table
+            .check_multisig(HOST, ScopeKind::Lifeforce, &signers(&[HOST, ORGANIC]))
+            .unwrap();
+        assert!(table
+            .check_multisig(HOST, ScopeKind::ArchChange, &signers(&[HOST, ORGANIC]))
+            .is_err());
+        table
+            .check_multisig(HOST, ScopeKind::ArchChange, &signers(&[HOST, ORGANIC, RESEARCH]))
+            .unwrap();
+    }
+
+    // Scope names from either file resolve to the same rule.
+    assert_eq!(ScopeKind::from_str("lifeforce_alteration"), ScopeKind::Lifeforce);
+    assert_eq!(ScopeKind::from_str("arch-change"), ScopeKind::ArchChange);
+}
+
+#[test]
+fn stake_config_threshold_counts_distinct_signers() {
+    let text = format!(
+        "subject_id: {HOST}
+version: 1.0.0
+roles:
+  - role: Host
+    did: {HOST}
+    scopes: [\"EVOLVE:*\"]
+  - role: OrganicCpu
+    did: {ORGANIC}
+    scopes: []
+  - role: Auditor
+    did: bostrom1auditor
+    scopes: []
+multisig_rules:
+  - operation: lifeforce
+    required_roles: [Host, OrganicCpu]
+    threshold: 2
+  - operation: arch.change
+    required_roles: [Host, OrganicCpu]
+    threshold: 2
+  - operation: ota.update
+    required_roles: [Host, OrganicCpu, Auditor]
+    threshold: 2
+"
+    );
+    let table = StakeTable::parse(&text).unwrap();
+
+    assert!(table
+        .check_operation(HOST, "ota.update", &signers(&[HOST]), None)
+        .is_err());
+    table
+        .check_operation(HOST, "ota-update", &signers(&[ORGANIC, "bostrom1auditor"]), None)
+        .unwrap();
+    table
+        .check_multisig(HOST, ScopeKind::ArchChange, &signers(&[HOST, ORGANIC]))
+        .unwrap();
+
+    // A threshold that lets the Host be outvoted on lifeforce is refused.
+    let weak = text.replacen("threshold: 2", "threshold: 1", 1);
+    assert!(StakeTable::parse(&weak).is_err());
+}
+
+#[test]
+fn reconcile_reports_contradictions_between_shipped_files() {
+    let csv = repo_file("policies/bostrom-stake-v1.stake.aln");
+    let yaml = repo_file("qpudatashards/particles/bostrom-stake-v1.stake.aln");
+    let report = reconcile_files(&[&csv, &yaml]);
+
+    assert_eq!(report.loaded.len(), 2, "failed: {:?}", report.failed);
+    // The CSV lists an OffDeviceSwarm role the YAML does not.
+    assert!(report.contradictions.iter().any(|c| matches!(
+        c,
+        StakeContradiction::RoleMissing { rolekind, .. } if rolekind == "OffDeviceSwarm"
+    )));
+    // day_to_day_tuning only has a rule in the YAML.
+    assert!(report.contradictions.iter().any(|c| matches!(
+        c,
+        StakeContradiction::Rule { operation, left, .. } if operation == "daytodaytuning" && left.1.is_none()
+    )));
+    // Both agree on the multisig scopes.
+    assert!(!report.contradictions.iter().any(|c| matches!(
+        c,
+        StakeContradiction::Rule { operation, .. } if operation == "lifeforce" || operation == "archchange"
+    )));
+}
@@ -1,39 +1,31 @@
-use sovereigntycore::stake::{ScopeKind, StakeRow, StakeTable};
+use sovereigntycore::stake::{StakeRow, StakeTable};
 use sovereigntycore::core_stake_guard::StakeGuard;
 
 fn example_table() -> StakeTable {
-    use std::collections::HashMap;
-
-    let mut by_subject = HashMap::new();
-    by_subject.insert(
-        "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
-        vec![
-            StakeRow {
-                roleid: "hostprimary".to_string(),
-                subjectid: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
-                bostromaddress:
-                    "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
-                rolekind: "Host".to_string(),
-                canveto: true,
-                caninitevolve: true,
-                requiredforlifeforce: true,
-                requiredforarchchange: true,
-            },
-            StakeRow {
-                roleid: "organiccpu".to_string(),
-                subjectid: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
-                bostromaddress: "zeta12x0up66pzyeretzyku8p4ccuxrjqtqpdc4y4x8".to_string(),
-                rolekind: "OrganicCPU".to_string(),
-                canveto: true,
-                caninitevolve: true,
-                requiredforlifeforce: true,
-                requiredforarchchange: true,
-            },
-        ],
-    );
-
-    // Bypass file loader invariants by constructing directly.
-    StakeTable { by_subject }
+    StakeTable::from_rows(vec![
+        StakeRow {
+            roleid: "hostprimary".to_string(),
+            subjectid: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
+            bostromaddress:
+                "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
+            rolekind: "Host".to_string(),
+            canveto: true,
+            caninitevolve: true,
+            requiredforlifeforce: true,
+            requiredforarchchange: true,
+        },
+        StakeRow {
+            roleid: "organiccpu".to_string(),
+            subjectid: "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7".to_string(),
+            bostromaddress: "zeta12x0up66pzyeretzyku8p4ccuxrjqtqpdc4y4x8".to_string(),
+            rolekind: "OrganicCPU".to_string(),
+            canveto: true,
+            caninitevolve: true,
+            requiredforlifeforce: true,
+            requiredforarchchange: true,
+        },
+    ])
+    .unwrap()
 }
 
 #[derive(Clone)]
@@ -83,7 +75,5 @@ fn to_update(p: DummyProposal) -> sovereigntycore::update::UpdateProposal {
         subjectid: p.subjectid,
         scope: p.scope,
         signers: p.signers,
-        // ...fill other fields with dummy values as needed...
-        ..Default::default()
     }
 }
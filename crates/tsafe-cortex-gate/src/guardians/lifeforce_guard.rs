@@ -1,25 +1,33 @@
-use lifeforce_guards::LifeforceEnvelope;
+use lifeforce_guards::{DepletionLedger, LifeforceEnvelope};
 use crate::auth::{XRAction, RejectionReason};
 
 pub struct LifeforceGuard {
     envelope: LifeforceEnvelope,
+    ledger: DepletionLedger,
 }
 
 impl LifeforceGuard {
-    pub fn from_policies_dir(dir: &std::path::Path) -> anyhow::Result<Self> {
+    /// Load `lifeforce.aln` from `dir` and the depletion history from
+    /// `ledger_path`, so the hourly and daily caps carry across restarts.
+    pub fn from_policies_dir(dir: &std::path::Path, ledger_path: &std::path::Path, now: u64) -> anyhow::Result<Self> {
         let path = dir.join("lifeforce.aln");
         let envelope = LifeforceEnvelope::from_path(path)?;
-        Ok(Self { envelope })
+        let ledger = DepletionLedger::open(ledger_path, now)?;
+        Ok(Self { envelope, ledger })
     }
 
+    /// Admit `action` against the mode's Lmin/Lcrit and the depletion
+    /// windows; an admitted action is recorded under `label`.
     pub fn check(
-        &self,
+        &mut self,
         mode: &str,
         current_lifeforce: f32,
         action: &XRAction,
+        label: &str,
+        now: u64,
     ) -> Result<(), RejectionReason> {
         let delta = -action.lifeforcecost;
-        if let Err(err) = self.envelope.check_action(mode, current_lifeforce, delta) {
+        if let Err(err) = self.ledger.admit(&self.envelope, mode, current_lifeforce, delta, label, now) {
             return Err(RejectionReason {
                 code: "LIFEFORCE_ENVELOPE".into(),
                 message: format!("lifeforce guard violation: {err}"),
@@ -27,4 +35,10 @@ impl LifeforceGuard {
         }
         Ok(())
     }
+
+    /// Record rest or replenishment so it offsets earlier depletion.
+    pub fn record_recovery(&mut self, amount: f32, label: &str, now: u64) -> anyhow::Result<()> {
+        self.ledger.record_recovery(amount, label, now)?;
+        Ok(())
+    }
 }
@@ -66,6 +66,8 @@ pub struct CapabilityGuardError {
 /// Core subset of a .donutloop.aln row used by the guard.
 /// The full row may contain more fields; this is the minimal
 /// kernel needed for enforcement.
+/// Import into the canonical `donutloop` crate with
+/// `donutloop::legacy::policyengine::convert`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DonutLoopRowCore {
     pub row_id: String,
@@ -1,15 +1,19 @@
 use crate::donutloop::{DonutLoop};
-use crate::hashcheck::{hash_canonical, verify_neurorights, verify_roh_invariants};
+use crate::hashcheck::{verify_neurorights, verify_roh_invariants};
 use crate::schema::{
-    AuditEventPayload, EventType, NeurorightsDocument, RohModel, StakeConfig, UpdateProposal,
+    AuditEventPayload, EventType, NeurorightsDocument, StakeConfig, UpdateProposal,
 };
 use ed25519_dalek::Keypair;
+use organiccpualn::roh_registry::RohRegistry;
 use std::fs;
+use std::io::BufRead;
 use uuid::Uuid;
 
 #[derive(Clone, Debug)]
 pub struct SovereignKernel {
-    pub roh_model: RohModel,
+    /// RoH models from the boot shard, keyed by `model_id` (one per mode
+    /// for polytope mode shards).
+    pub roh_models: RohRegistry,
     pub stake: StakeConfig,
     pub neurorights: NeurorightsDocument,
     /// Raw JSONL of proposals; you will process them via Tsafe/CyberRank.
@@ -18,10 +22,10 @@ pub struct SovereignKernel {
     pub host_did: String,
 }
 
-fn load_rohmodel(path: &str) -> Result<RohModel, String> {
-    let text = fs::read_to_string(path).map_err(|e| format!("read rohmodel: {e}"))?;
-    // Treat .aln as YAML/JSON hybrid; adjust parser as needed.
-    serde_yaml::from_str(&text).map_err(|e| format!("parse rohmodel YAML: {e}"))
+fn load_rohmodels(path: &str) -> Result<RohRegistry, String> {
+    let mut registry = RohRegistry::new();
+    registry.load_file(path).map_err(|e| format!("load rohmodel: {e:#}"))?;
+    Ok(registry)
 }
 
 fn load_stake(path: &str) -> Result<StakeConfig, String> {
@@ -79,14 +83,14 @@ pub fn boot_sovereign_kernel(
     host_keypair: &Keypair,
 ) -> Result<SovereignKernel, String> {
     // 1. Load files.
-    let roh_model = load_rohmodel(rohmodel_path)?;
+    let roh_models = load_rohmodels(rohmodel_path)?;
     let stake = load_stake(stake_path)?;
     let neurorights = load_neurorights(neurorights_path)?;
     let evolve_proposals = load_evolve_jsonl(evolve_path)?;
     let mut donutloop = DonutLoop::load(donutloop_path)?;
 
     // 2. Verify invariants.
-    verify_roh_invariants(&roh_model)?;
+    verify_roh_invariants(&roh_models)?;
     verify_neurorights(&neurorights)?;
     let host_did = extract_host_did(&stake)?;
 
@@ -117,7 +121,7 @@ pub fn boot_sovereign_kernel(
     }
 
     Ok(SovereignKernel {
-        roh_model,
+        roh_models,
         stake,
         neurorights,
         evolve_proposals,
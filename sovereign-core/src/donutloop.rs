@@ -1,4 +1,4 @@
-use crate::hashcheck::{compute_chained_hash, hash_canonical};
+use crate::hashcheck::compute_chained_hash;
 use crate::schema::{AuditEntry, EventType};
 use chrono::Utc;
 use ed25519_dalek::{Keypair, Signer};
@@ -8,6 +8,8 @@ use std::io::{BufRead, BufReader, Write};
 use uuid::Uuid;
 
 /// In-memory representation of the donutloop ledger.
+/// Legacy format: import into the canonical `donutloop` crate with
+/// `donutloop::legacy::sovereign_core::convert`.
 #[derive(Clone, Debug)]
 pub struct DonutLoop {
     pub entries: Vec<AuditEntry>,
@@ -1,4 +1,6 @@
-use crate::schema::{AuditEntry, NeurorightsDocument, RohModel};
+use crate::polytope::STATE_DIM;
+use crate::schema::{AuditEntry, NeurorightsDocument, RohModePolytope};
+use organiccpualn::roh_registry::{RohModelKind, RohRegistry};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
@@ -16,23 +18,29 @@ pub fn hash_canonical<T: Serialize>(value: &T) -> Result<String, String> {
     Ok(format!("0x{}", hex::encode(hasher.finalize())))
 }
 
-/// Verify that all RoH ceilings in .rohmodel.aln respect the hard 0.3 invariant.
-pub fn verify_roh_invariants(model: &RohModel) -> Result<(), String> {
-    for mode in &model.modes {
-        if mode.roh_ceiling > 0.30 {
-            return Err(format!(
-                "RoH ceiling {} exceeds 0.30 in mode {}",
-                mode.roh_ceiling, mode.mode_name
-            ));
-        }
-        if mode.A.len() != mode.b.len() {
-            return Err(format!(
-                "Ax≤b dimension mismatch in mode {}: {} rows, {} b entries",
-                mode.mode_name,
-                mode.A.len(),
-                mode.b.len()
-            ));
-        }
+/// Verify the polytope models registered from .rohmodel.aln. Ceilings
+/// (≤ 0.3) and `A`/`b` shapes are checked by the registry on insert; here
+/// every mode polytope must also be a non-empty, bounded set.
+pub fn verify_roh_invariants(registry: &RohRegistry) -> Result<(), String> {
+    for id in registry.ids() {
+        let model = registry.get(id).map_err(|e| e.to_string())?;
+        let RohModelKind::Polytope { a, b } = &model.kind else {
+            continue;
+        };
+        let rows = a
+            .iter()
+            .map(|row| <[f32; STATE_DIM]>::try_from(row.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("mode {} is not over the {}-D biophysical microspace", id, STATE_DIM))?;
+        // An empty or unbounded polytope would make every state unsafe or
+        // none; refuse it at boot.
+        crate::polytope::check_feasible(&RohModePolytope {
+            mode_name: id.to_string(),
+            description: String::new(),
+            A: rows,
+            b: b.clone(),
+            roh_ceiling: model.roh_ceiling,
+        })?;
     }
     Ok(())
 }
@@ -1,6 +1,7 @@
 pub mod boot;
 pub mod donutloop;
 pub mod hashcheck;
+pub mod polytope;
 pub mod schema;
 
 pub use boot::boot_sovereign_kernel;
@@ -0,0 +1,311 @@
+//! Runtime evaluation of RoH mode polytopes `A·x ≤ b` over the 7-D
+//! biophysical microspace, and a boot-time check (via a small dense simplex
+//! LP) that every shipped polytope is a non-empty, bounded safe set.
+
+use crate::schema::RohModePolytope;
+
+/// Dimension of the biophysical microspace (row length of `A`).
+pub const STATE_DIM: usize = 7;
+
+const EPS: f64 = 1e-9;
+
+/// One row of `A·x ≤ b` evaluated at a state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstraintSlack {
+    pub row: usize,
+    /// `b_i - a_i·x`; negative when the row is violated.
+    pub slack: f64,
+    /// `slack / ‖a_i‖`: signed Euclidean distance to the row's hyperplane.
+    /// Infinite for an all-zero row.
+    pub margin: f64,
+}
+
+/// Where a state sits relative to one mode's polytope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolytopeEvaluation {
+    pub mode_name: String,
+    pub inside: bool,
+    /// Indices of violated rows.
+    pub violated: Vec<usize>,
+    pub slacks: Vec<ConstraintSlack>,
+    /// Signed distance to the nearest boundary. Inside: exact distance to
+    /// the closest face (≥ 0). Outside: minus the largest hyperplane
+    /// violation, a lower bound on the true distance back into the set.
+    pub boundary_distance: f64,
+}
+
+/// Evaluate `state` against `mode`. Fails only on malformed input.
+pub fn evaluate(mode: &RohModePolytope, state: &[f32; STATE_DIM]) -> Result<PolytopeEvaluation, String> {
+    if mode.A.len() != mode.b.len() {
+        return Err(format!(
+            "Ax≤b dimension mismatch in mode {}: {} rows, {} b entries",
+            mode.mode_name,
+            mode.A.len(),
+            mode.b.len()
+        ));
+    }
+
+    let mut slacks = Vec::with_capacity(mode.A.len());
+    let mut violated = Vec::new();
+    for (row, (a, b)) in mode.A.iter().zip(&mode.b).enumerate() {
+        let ax: f64 = a.iter().zip(state).map(|(ai, xi)| *ai as f64 * *xi as f64).sum();
+        let slack = *b as f64 - ax;
+        let norm = row_norm(a);
+        let margin = if norm > EPS { slack / norm } else if slack >= 0.0 { f64::INFINITY } else { f64::NEG_INFINITY };
+        if slack < -EPS {
+            violated.push(row);
+        }
+        slacks.push(ConstraintSlack { row, slack, margin });
+    }
+
+    let boundary_distance = if violated.is_empty() {
+        slacks.iter().map(|s| s.margin).fold(f64::INFINITY, f64::min)
+    } else {
+        violated.iter().map(|&i| slacks[i].margin).fold(f64::INFINITY, f64::min)
+    };
+
+    Ok(PolytopeEvaluation {
+        mode_name: mode.mode_name.clone(),
+        inside: violated.is_empty(),
+        violated,
+        slacks,
+        boundary_distance,
+    })
+}
+
+/// Geometry of a feasible, bounded mode polytope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolytopeReport {
+    pub mode_name: String,
+    /// Centre of the largest inscribed ball.
+    pub chebyshev_center: [f64; STATE_DIM],
+    /// Radius of that ball; 0 means the set is non-empty but flat.
+    pub chebyshev_radius: f64,
+    /// Per-axis (min, max) over the polytope.
+    pub bounds: [(f64, f64); STATE_DIM],
+}
+
+/// Check that `mode` describes a non-empty, bounded set.
+pub fn check_feasible(mode: &RohModePolytope) -> Result<PolytopeReport, String> {
+    if mode.A.len() != mode.b.len() {
+        return Err(format!(
+            "Ax≤b dimension mismatch in mode {}: {} rows, {} b entries",
+            mode.mode_name,
+            mode.A.len(),
+            mode.b.len()
+        ));
+    }
+    if mode.A.is_empty() {
+        return Err(format!("mode {} has no constraints; its safe set is unbounded", mode.mode_name));
+    }
+
+    // Free x is split as p - q (both ≥ 0): columns [p(7) | q(7) | r].
+    let n = 2 * STATE_DIM + 1;
+    let rows: Vec<Vec<f64>> = mode
+        .A
+        .iter()
+        .map(|a| {
+            let mut row = Vec::with_capacity(n);
+            row.extend(a.iter().map(|v| *v as f64));
+            row.extend(a.iter().map(|v| -(*v as f64)));
+            row.push(row_norm(a));
+            row
+        })
+        .collect();
+    let b: Vec<f64> = mode.b.iter().map(|v| *v as f64).collect();
+
+    // Chebyshev centre: max r s.t. a_i·x + ‖a_i‖ r ≤ b_i.
+    let mut c = vec![0.0; n];
+    c[n - 1] = 1.0;
+    let (chebyshev_radius, y) = match maximize(&c, &rows, &b) {
+        LpOutcome::Optimal { value, x } => (value, x),
+        LpOutcome::Infeasible => {
+            return Err(format!("mode {} has an empty safe set (A·x ≤ b is infeasible)", mode.mode_name))
+        }
+        LpOutcome::Unbounded => {
+            return Err(format!("mode {} has an unbounded safe set", mode.mode_name));
+        }
+    };
+    let mut chebyshev_center = [0.0; STATE_DIM];
+    for (j, v) in chebyshev_center.iter_mut().enumerate() {
+        *v = y[j] - y[STATE_DIM + j];
+    }
+
+    // Bounded iff every coordinate is bounded above and below.
+    let plain: Vec<Vec<f64>> = rows.iter().map(|r| r[..n - 1].to_vec()).collect();
+    let mut bounds = [(0.0, 0.0); STATE_DIM];
+    for (j, bound) in bounds.iter_mut().enumerate() {
+        let mut extreme = [0.0; 2];
+        for (k, sign) in [-1.0, 1.0].into_iter().enumerate() {
+            let mut c = vec![0.0; n - 1];
+            c[j] = sign;
+            c[STATE_DIM + j] = -sign;
+            match maximize(&c, &plain, &b) {
+                LpOutcome::Optimal { value, .. } => extreme[k] = sign * value,
+                _ => {
+                    return Err(format!(
+                        "mode {} has an unbounded safe set along axis {}",
+                        mode.mode_name, j
+                    ))
+                }
+            }
+        }
+        *bound = (extreme[0], extreme[1]);
+    }
+
+    Ok(PolytopeReport {
+        mode_name: mode.mode_name.clone(),
+        chebyshev_center,
+        chebyshev_radius,
+        bounds,
+    })
+}
+
+fn row_norm(a: &[f32; STATE_DIM]) -> f64 {
+    a.iter().map(|v| (*v as f64).powi(2)).sum::<f64>().sqrt()
+}
+
+/// Result of `maximize`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LpOutcome {
+    Optimal { value: f64, x: Vec<f64> },
+    Infeasible,
+    Unbounded,
+}
+
+/// Maximize `c·x` subject to `A·x ≤ b`, `x ≥ 0`.
+///
+/// Two-phase dense tableau simplex with Bland's rule; sized for the handful
+/// of rows in a `.rohmodel.aln`, not for general use.
+pub fn maximize(c: &[f64], a: &[Vec<f64>], b: &[f64]) -> LpOutcome {
+    let (m, n) = (a.len(), c.len());
+    // Columns: x (n) | aux x0 | slacks (m) | rhs.
+    let aux = n;
+    let cols = n + 1 + m;
+    let mut t = Tableau {
+        rows: a
+            .iter()
+            .zip(b)
+            .enumerate()
+            .map(|(i, (row, bi))| {
+                let mut r = vec![0.0; cols + 1];
+                r[..n].copy_from_slice(&row[..n]);
+                r[aux] = -1.0;
+                r[n + 1 + i] = 1.0;
+                r[cols] = *bi;
+                r
+            })
+            .collect(),
+        obj: vec![0.0; cols + 1],
+        basis: (0..m).map(|i| n + 1 + i).collect(),
+    };
+
+    // Phase 1: maximize -x0 from the most negative rhs.
+    let worst = (0..m).min_by(|&i, &j| b[i].total_cmp(&b[j]));
+    if let Some(l) = worst.filter(|&l| b[l] < -EPS) {
+        t.obj[aux] = 1.0;
+        t.pivot(l, aux);
+        if !t.run(None) {
+            // Cannot happen: -x0 is bounded above by 0.
+            return LpOutcome::Infeasible;
+        }
+        if t.obj[cols] < -EPS {
+            return LpOutcome::Infeasible;
+        }
+        if let Some(r) = t.basis.iter().position(|&v| v == aux) {
+            // x0 is basic at zero; swap it out for any usable column.
+            if let Some(j) = (0..cols).find(|&j| j != aux && t.rows[r][j].abs() > EPS) {
+                t.pivot(r, j);
+            }
+        }
+    }
+
+    // Phase 2: original objective, priced out against the current basis.
+    t.obj = vec![0.0; cols + 1];
+    for (j, cj) in c.iter().enumerate() {
+        t.obj[j] = -cj;
+    }
+    for i in 0..m {
+        let coef = t.obj[t.basis[i]];
+        if coef.abs() > 0.0 {
+            for j in 0..=cols {
+                t.obj[j] -= coef * t.rows[i][j];
+            }
+        }
+    }
+    if !t.run(Some(aux)) {
+        return LpOutcome::Unbounded;
+    }
+
+    let mut x = vec![0.0; n];
+    for (i, &v) in t.basis.iter().enumerate() {
+        if v < n {
+            x[v] = t.rows[i][cols];
+        }
+    }
+    LpOutcome::Optimal { value: t.obj[cols], x }
+}
+
+/// Row `obj` holds `-reduced cost` per column and the objective value at
+/// the rhs position.
+struct Tableau {
+    rows: Vec<Vec<f64>>,
+    obj: Vec<f64>,
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    fn pivot(&mut self, r: usize, col: usize) {
+        let p = self.rows[r][col];
+        for v in self.rows[r].iter_mut() {
+            *v /= p;
+        }
+        let pivot_row = self.rows[r].clone();
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            if i != r {
+                let f = row[col];
+                if f != 0.0 {
+                    for (v, pv) in row.iter_mut().zip(&pivot_row) {
+                        *v -= f * pv;
+                    }
+                }
+            }
+        }
+        let f = self.obj[col];
+        if f != 0.0 {
+            for (v, pv) in self.obj.iter_mut().zip(&pivot_row) {
+                *v -= f * pv;
+            }
+        }
+        self.basis[r] = col;
+    }
+
+    /// Pivot to optimality; false if unbounded. `blocked` never enters.
+    fn run(&mut self, blocked: Option<usize>) -> bool {
+        let rhs = self.obj.len() - 1;
+        loop {
+            let Some(col) = (0..rhs).find(|&j| Some(j) != blocked && self.obj[j] < -EPS) else {
+                return true;
+            };
+            let mut best: Option<(usize, f64)> = None;
+            for (i, row) in self.rows.iter().enumerate() {
+                if row[col] > EPS {
+                    let ratio = row[rhs] / row[col];
+                    let better = match best {
+                        None => true,
+                        Some((bi, br)) => {
+                            ratio < br - EPS || (ratio <= br + EPS && self.basis[i] < self.basis[bi])
+                        }
+                    };
+                    if better {
+                        best = Some((i, ratio));
+                    }
+                }
+            }
+            match best {
+                Some((r, _)) => self.pivot(r, col),
+                None => return false,
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 /// .rohmodel.aln – viability kernel, RoH invariants, modes.
+/// `A` keeps the shard's `A·x ≤ b` field name.
+#[allow(non_snake_case)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RohModePolytope {
     pub mode_name: String,          // e.g., "Baseline", "Training"
@@ -38,6 +40,8 @@ pub struct RoleEntry {
 }
 
 /// Minimal EVOLVE / SMART semantics on chain.
+/// Quorum decisions are made by `sovereigntycore::stake::StakeTable`, which
+/// loads this shape alongside the CSV stake table.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MultisigRule {
     /// e.g., "arch.change", "roh.relax", "ota.update".
@@ -0,0 +1,160 @@
+use sovereign_core::polytope::{check_feasible, evaluate, maximize, LpOutcome, STATE_DIM};
+use sovereign_core::RohModePolytope;
+
+const TOL: f64 = 1e-6;
+
+fn mode(rows: Vec<([f32; STATE_DIM], f32)>) -> RohModePolytope {
+    let (a, b) = rows.into_iter().unzip();
+    RohModePolytope {
+        mode_name: "Test".to_string(),
+        description: String::new(),
+        A: a,
+        b,
+        roh_ceiling: 0.3,
+    }
+}
+
+fn unit(j: usize, v: f32) -> [f32; STATE_DIM] {
+    let mut row = [0.0; STATE_DIM];
+    row[j] = v;
+    row
+}
+
+/// `lo ≤ x_j ≤ hi` on every axis.
+fn box_rows(lo: f32, hi: f32) -> Vec<([f32; STATE_DIM], f32)> {
+    (0..STATE_DIM).flat_map(|j| [(unit(j, 1.0), hi), (unit(j, -1.0), -lo)]).collect()
+}
+
+#[test]
+fn unit_box_has_its_centre_as_chebyshev_centre() {
+    let report = check_feasible(&mode(box_rows(0.0, 1.0))).unwrap();
+    assert!((report.chebyshev_radius - 0.5).abs() < TOL);
+    for (c, (lo, hi)) in report.chebyshev_center.iter().zip(report.bounds) {
+        assert!((c - 0.5).abs() < TOL);
+        assert!(lo.abs() < TOL && (hi - 1.0).abs() < TOL, "({lo}, {hi})");
+    }
+}
+
+#[test]
+fn simplex_chebyshev_radius_matches_the_closed_form() {
+    // x ≥ 0, Σx ≤ 1: the inscribed ball touches every face at r = 1 / (n + √n).
+    let mut rows: Vec<_> = (0..STATE_DIM).map(|j| (unit(j, -1.0), 0.0)).collect();
+    rows.push(([1.0; STATE_DIM], 1.0));
+    let report = check_feasible(&mode(rows)).unwrap();
+
+    let n = STATE_DIM as f64;
+    let r = 1.0 / (n + n.sqrt());
+    assert!((report.chebyshev_radius - r).abs() < TOL, "{}", report.chebyshev_radius);
+    assert!(report.chebyshev_center.iter().all(|c| (c - r).abs() < TOL));
+    assert!(report.bounds.iter().all(|(lo, hi)| lo.abs() < TOL && (hi - 1.0).abs() < TOL));
+}
+
+#[test]
+fn negative_b_needs_phase_one_and_is_still_solved() {
+    // 2 ≤ x_j ≤ 3 excludes the origin, so the simplex cannot start at x = 0.
+    let report = check_feasible(&mode(box_rows(2.0, 3.0))).unwrap();
+    assert!((report.chebyshev_radius - 0.5).abs() < TOL);
+    assert!(report.chebyshev_center.iter().all(|c| (c - 2.5).abs() < TOL));
+    assert!(report.bounds.iter().all(|(lo, hi)| (lo - 2.0).abs() < TOL && (hi - 3.0).abs() < TOL));
+}
+
+#[test]
+fn empty_and_unbounded_sets_are_refused() {
+    // x_0 ≥ 1 and x_0 ≤ -1.
+    let mut rows = box_rows(0.0, 1.0);
+    rows.push((unit(0, -1.0), -1.0));
+    rows.push((unit(0, 1.0), -1.0));
+    let err = check_feasible(&mode(rows)).unwrap_err();
+    assert!(err.contains("empty safe set"), "{err}");
+
+    // Only upper bounds: x_j → -∞ stays inside.
+    let uppers: Vec<_> = (0..STATE_DIM).map(|j| (unit(j, 1.0), 1.0)).collect();
+    let err = check_feasible(&mode(uppers)).unwrap_err();
+    assert!(err.contains("unbounded"), "{err}");
+
+    // Bounded everywhere except along x_6.
+    let mut rows = box_rows(0.0, 1.0);
+    rows.retain(|(a, _)| a[6] <= 0.0);
+    let err = check_feasible(&mode(rows)).unwrap_err();
+    assert!(err.contains("unbounded safe set along axis 6"), "{err}");
+
+    assert!(check_feasible(&mode(Vec::new())).unwrap_err().contains("no constraints"));
+    let mut ragged = mode(box_rows(0.0, 1.0));
+    ragged.b.pop();
+    assert!(check_feasible(&ragged).unwrap_err().contains("dimension mismatch"));
+}
+
+#[test]
+fn flat_sets_are_feasible_with_zero_radius() {
+    // x_0 = 0.25 exactly.
+    let mut rows = box_rows(0.0, 1.0);
+    rows.push((unit(0, 1.0), 0.25));
+    rows.push((unit(0, -1.0), -0.25));
+    let report = check_feasible(&mode(rows)).unwrap();
+    assert!(report.chebyshev_radius.abs() < TOL);
+    let (lo, hi) = report.bounds[0];
+    assert!((lo - 0.25).abs() < TOL && (hi - 0.25).abs() < TOL, "({lo}, {hi})");
+}
+
+#[test]
+fn evaluate_reports_slacks_and_signed_boundary_distance() {
+    let m = mode(box_rows(0.0, 1.0));
+
+    let inside = evaluate(&m, &[0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.9]).unwrap();
+    assert!(inside.inside && inside.violated.is_empty());
+    assert!((inside.boundary_distance - 0.1).abs() < TOL);
+    assert!((inside.slacks[12].slack - 0.1).abs() < TOL);
+
+    let outside = evaluate(&m, &[1.5, 0.5, 0.5, 0.5, 0.5, -0.25, 0.5]).unwrap();
+    assert!(!outside.inside);
+    assert_eq!(outside.violated, vec![0, 11]);
+    assert!((outside.boundary_distance + 0.5).abs() < TOL);
+
+    // Margins are Euclidean: the diagonal face Σx ≤ 1 has ‖a‖ = √7.
+    let diagonal = mode(vec![([1.0; STATE_DIM], 1.0)]);
+    let eval = evaluate(&diagonal, &[0.0; STATE_DIM]).unwrap();
+    assert!((eval.boundary_distance - 1.0 / 7f64.sqrt()).abs() < TOL);
+
+    // An all-zero row is either always or never satisfied.
+    let zero = mode(vec![([0.0; STATE_DIM], 0.0)]);
+    let eval = evaluate(&zero, &[0.3; STATE_DIM]).unwrap();
+    assert!(eval.inside && eval.slacks[0].margin.is_infinite());
+
+    let mut ragged = m.clone();
+    ragged.A.pop();
+    assert!(evaluate(&ragged, &[0.0; STATE_DIM]).is_err());
+}
+
+#[test]
+fn maximize_solves_small_programs() {
+    // max x + y s.t. x ≤ 1, y ≤ 2, x + y ≤ 2.5.
+    let a = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    match maximize(&[1.0, 1.0], &a, &[1.0, 2.0, 2.5]) {
+        LpOutcome::Optimal { value, x } => {
+            assert!((value - 2.5).abs() < TOL);
+            assert!((x[0] + x[1] - 2.5).abs() < TOL && x[0] <= 1.0 + TOL && x[1] <= 2.0 + TOL);
+        }
+        other => panic!("{other:?}"),
+    }
+
+    // Degenerate vertex: three constraints through (1, 1).
+    let a = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    match maximize(&[2.0, 1.0], &a, &[1.0, 1.0, 2.0]) {
+        LpOutcome::Optimal { value, .. } => assert!((value - 3.0).abs() < TOL),
+        other => panic!("{other:?}"),
+    }
+
+    // Phase 1: x ≥ 1 (as -x ≤ -1), x ≤ 3; minimum of x is 1.
+    let a = vec![vec![-1.0], vec![1.0]];
+    match maximize(&[-1.0], &a, &[-1.0, 3.0]) {
+        LpOutcome::Optimal { value, x } => {
+            assert!((value + 1.0).abs() < TOL && (x[0] - 1.0).abs() < TOL);
+        }
+        other => panic!("{other:?}"),
+    }
+
+    assert_eq!(maximize(&[-1.0], &[vec![-1.0], vec![1.0]], &[-3.0, 2.0]), LpOutcome::Infeasible);
+    assert_eq!(maximize(&[1.0, 0.0], &[vec![0.0, 1.0]], &[1.0]), LpOutcome::Unbounded);
+    // No constraints: only x = 0 bounds a non-positive objective.
+    assert!(matches!(maximize(&[-1.0], &[], &[]), LpOutcome::Optimal { value, .. } if value.abs() < TOL));
+}
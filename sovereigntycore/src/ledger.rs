@@ -51,6 +51,8 @@ impl DonutLoopEntry {
     }
 }
 
+/// Legacy format: import into the canonical `donutloop` crate with
+/// `donutloop::legacy::sovereigntycore::{read_frames, convert}`.
 pub struct DonutLoopLedger {
     path: String,
     brain_keypair: Keypair,
@@ -1,13 +1,135 @@
 #![forbid(unsafe_code)]
 
 use chrono::{TimeZone, Utc};
-use std::path::PathBuf;
+use ed25519_dalek::SigningKey;
+use rand_core::OsRng;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use neuro_pc::sovereignty::consent::{AwarenessToken, ConsentObject};
+use neuro_pc::sovereignty::consent::{
+    signing_key_from_hex, verifying_key_from_hex, AwarenessToken, ConsentObject, ConsentStore,
+    OwnerKeyRegistry,
+};
 use neuro_pc::sovereignty::ota_io::{Caller, OtaAction};
 use neuro_pc::sovereignty::policy::NrmlPolicy;
 
+const USAGE: &str = "usage:
+  consent_check                                   run the built-in consent self-test
+  consent_check keygen <secret.hex>               write a new ed25519 secret key (mode 0600), print the public key
+  consent_check sign --key <secret.hex> <file.cobj>...
+  consent_check verify --owner <owner_id> --pubkey <hex> <file.cobj|dir>...";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => self_test(),
+        Some("keygen") => keygen(&args[1..]),
+        Some("sign") => sign(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some(_) => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Split `--flag value` pairs from positional arguments.
+fn parse_flags(args: &[String]) -> Result<(BTreeMap<String, String>, Vec<String>), String> {
+    let mut flags = BTreeMap::new();
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(name) = arg.strip_prefix("--") {
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("missing value for --{}\n{}", name, USAGE))?;
+            flags.insert(name.to_string(), value.clone());
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    if positional.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    Ok((flags, positional))
+}
+
+fn keygen(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let [path] = args else {
+        return Err(USAGE.into());
+    };
+    let key = SigningKey::generate(&mut OsRng);
+
+    // Owner-only from creation, and never over an existing key.
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(hex::encode(key.to_bytes()).as_bytes())?;
+    file.sync_all()?;
+
+    println!("secret key written to {}", path);
+    println!("public: {}", hex::encode(key.verifying_key().to_bytes()));
+    Ok(())
+}
+
+fn sign(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (flags, files) = parse_flags(args)?;
+    let key_path = flags.get("key").ok_or(USAGE)?;
+    let key = signing_key_from_hex(&std::fs::read_to_string(key_path)?)
+        .ok_or("secret key file must hold 32 hex-encoded bytes")?;
+
+    for file in files {
+        let mut cobj: ConsentObject = serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+        cobj.sign(&key);
+        std::fs::write(&file, serde_json::to_string_pretty(&cobj)?)?;
+        println!("signed {} ({})", file, cobj.id);
+    }
+    Ok(())
+}
+
+fn verify(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (flags, paths) = parse_flags(args)?;
+    let owner = flags.get("owner").ok_or(USAGE)?;
+    let pubkey = flags
+        .get("pubkey")
+        .and_then(|k| verifying_key_from_hex(k))
+        .ok_or("--pubkey must be 32 hex-encoded bytes")?;
+    let mut keys = OwnerKeyRegistry::new();
+    keys.register(owner.clone(), pubkey);
+
+    let mut failed = false;
+    for path in paths {
+        let path = Path::new(&path);
+        if path.is_dir() {
+            let report = ConsentStore::new(path, keys.clone()).load_all()?;
+            for c in &report.accepted {
+                println!("ok       {}", c.id);
+            }
+            for r in &report.rejected {
+                println!("REJECTED {}: {}", r.path.display(), r.reason);
+            }
+            failed |= !report.rejected.is_empty();
+        } else {
+            let cobj: ConsentObject = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            match cobj.verify_signature(&keys) {
+                Ok(()) => println!("ok       {}", path.display()),
+                Err(e) => {
+                    println!("REJECTED {}: {}", path.display(), e);
+                    failed = true;
+                }
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn self_test() -> Result<(), Box<dyn std::error::Error>> {
     // 1. Setup test consent directory.
     let consent_dir = PathBuf::from("consent_test");
     std::fs::create_dir_all(&consent_dir)?;
@@ -20,11 +142,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    // Owner (you).
+    // Owner (you), with a throwaway signing key for this run.
     let owner_id = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+    let owner_key = SigningKey::generate(&mut OsRng);
 
     // 2. Write a valid COMMIT .cobj for a specific package hash.
-    let commit_cobj = ConsentObject {
+    let mut commit_cobj = ConsentObject {
         id: "cobj_ota_core_v2_commit".to_string(),
         token: AwarenessToken::Commit,
         target_id: "hash_ota_core_v2_abc123".to_string(),
@@ -32,12 +155,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         created_at: Utc::now(),
         valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
         revoked: false,
+        signature: None,
     };
+    commit_cobj.sign(&owner_key);
     let commit_path = consent_dir.join("ota_commit_ota_core_v2.cobj");
     std::fs::write(&commit_path, serde_json::to_string_pretty(&commit_cobj)?)?;
 
     // 3. Write a valid EVOLVE .cobj for evolving "bf_ota_core".
-    let evolve_cobj = ConsentObject {
+    let mut evolve_cobj = ConsentObject {
         id: "cobj_evolve_bf_ota_core".to_string(),
         token: AwarenessToken::Evolve,
         target_id: "bf_ota_core".to_string(),
@@ -45,7 +170,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         created_at: Utc::now(),
         valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
         revoked: false,
+        signature: None,
     };
+    evolve_cobj.sign(&owner_key);
     let evolve_path = consent_dir.join("evolve_bf_ota_core.cobj");
     std::fs::write(&evolve_path, serde_json::to_string_pretty(&evolve_cobj)?)?;
 
@@ -54,9 +181,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         owner_id: owner_id.to_string(),
         trusted_ota_sources: vec!["https://trusted.vendor.example/ota".to_string()],
         sovereignty_core_enabled_flag: true,
-        rollback_available_flag: true,
         user_control_channel_flag: true,
         consent_dir: consent_dir.clone(),
+        ota_root: PathBuf::from("ota_test"),
+        owner_keys: BTreeMap::from([(
+            owner_id.to_string(),
+            hex::encode(owner_key.verifying_key().to_bytes()),
+        )]),
+        ota_rules: Default::default(),
     };
 
     // 5. Test OTA COMMIT consent (expect true).
@@ -101,8 +233,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut expired_cobj = commit_cobj;
     expired_cobj.valid_until = Some(
         Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+            .single()
             .expect("valid historical datetime"),
     );
+    expired_cobj.sign(&owner_key);
     std::fs::write(
         &commit_path,
         serde_json::to_string_pretty(&expired_cobj)?,
@@ -3,13 +3,17 @@
 pub mod sovereignty {
     pub mod invariants;
     pub mod policy;
+    pub mod policy_loader;
     pub mod consent;
+    pub mod revocation;
     pub mod ota_io;
+    pub mod ota_rules;
     pub mod audit;
 }
 
 pub mod ota {
     pub mod controller;
+    pub mod slots;
 }
 
 pub mod evolution {
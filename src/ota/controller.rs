@@ -1,16 +1,108 @@
 #![forbid(unsafe_code)]
 
+use crate::ota::slots::{FileFetcher, PackageFetcher, SlotEngine, SlotId};
 use crate::sovereignty::ota_io::{Caller, OtaAction, SovereignOtaIo};
 use crate::sovereignty::policy::NrmlPolicy;
 
 /// High-level OTA controller entrypoints.
+///
+/// Every lifecycle phase (Download, Verify, Stage, Commit, Rollback) first goes
+/// through `SovereignOtaIo::request`, so policy, invariants and the audit log
+/// see it before the slot engine touches disk.
 pub struct OtaController<'a> {
     policy: &'a NrmlPolicy,
+    fetcher: Box<dyn PackageFetcher + 'a>,
 }
 
 impl<'a> OtaController<'a> {
     pub fn new(policy: &'a NrmlPolicy) -> Self {
-        Self { policy }
+        Self {
+            policy,
+            fetcher: Box::new(FileFetcher),
+        }
+    }
+
+    /// Use a different transport for Download (e.g. an HTTPS adapter).
+    pub fn with_fetcher(policy: &'a NrmlPolicy, fetcher: impl PackageFetcher + 'a) -> Self {
+        Self {
+            policy,
+            fetcher: Box::new(fetcher),
+        }
+    }
+
+    fn slots(&self) -> SlotEngine {
+        SlotEngine::new(&self.policy.ota_root)
+    }
+
+    fn authorize(
+        io: &mut SovereignOtaIo,
+        caller: &Caller,
+        action: OtaAction,
+        phase: &str,
+    ) -> Result<(), String> {
+        let decision = io.request(caller, &action);
+        if !decision.allowed {
+            return Err(format!(
+                "OTA {} denied: {} (invariants: {:?})",
+                phase, decision.reason, decision.invariants
+            ));
+        }
+        Ok(())
+    }
+
+    /// Download a package from a trusted source into the staging dir.
+    pub fn download_package(
+        &self,
+        io: &mut SovereignOtaIo,
+        caller: &Caller,
+        source: &str,
+        package_hash: &str,
+    ) -> Result<(), String> {
+        let action = OtaAction::Download {
+            source: source.to_string(),
+            package_hash: package_hash.to_string(),
+        };
+        Self::authorize(io, caller, action, "Download")?;
+
+        self.slots()
+            .download(self.fetcher.as_ref(), source, package_hash)
+            .map(|_| ())
+            .map_err(|e| format!("OTA Download failed: {}", e))
+    }
+
+    /// Check the downloaded bytes against package_hash.
+    pub fn verify_package(
+        &self,
+        io: &mut SovereignOtaIo,
+        caller: &Caller,
+        package_hash: &str,
+    ) -> Result<(), String> {
+        let action = OtaAction::Verify {
+            package_hash: package_hash.to_string(),
+        };
+        Self::authorize(io, caller, action, "Verify")?;
+
+        self.slots()
+            .verify(package_hash)
+            .map(|_| ())
+            .map_err(|e| format!("OTA Verify failed: {}", e))
+    }
+
+    /// Install a verified package into the inactive slot.
+    pub fn stage_package(
+        &self,
+        io: &mut SovereignOtaIo,
+        caller: &Caller,
+        package_hash: &str,
+    ) -> Result<SlotId, String> {
+        let action = OtaAction::Stage {
+            package_hash: package_hash.to_string(),
+        };
+        Self::authorize(io, caller, action, "Stage")?;
+
+        self.slots()
+            .stage(package_hash)
+            .map_err(|e| format!("OTA Stage failed: {}", e))
     }
 
     /// Commit a specific OTA package; must have explicit consent.
@@ -24,26 +116,20 @@ impl<'a> OtaController<'a> {
             package_hash: package_hash.to_string(),
         };
 
-        let decision = io.request(caller, &action);
-        if !decision.allowed {
-            return Err(format!(
-                "OTA Commit denied: {} (invariants: {:?})",
-                decision.reason, decision.invariants
-            ));
-        }
-
         // At this point, SovereignOtaIo has:
         // - enforced NrmlPolicy evaluate_ota
         // - enforced has_valid_ota_consent
         // - logged the request and invariant status
-        //
-        // You can now call lower-level install logic knowing neurorights constraints were honored.
-        // install_package(package_hash)?;
+        Self::authorize(io, caller, action, "Commit")?;
 
-        Ok(())
+        self.slots()
+            .commit(package_hash)
+            .map(|_| ())
+            .map_err(|e| format!("OTA Commit failed: {}", e))
     }
 
     /// Rollback to a specific version; must have explicit consent.
+    /// `target_version` is the rollback slot's package hash, or "factory".
     pub fn rollback_to(
         &self,
         io: &mut SovereignOtaIo,
@@ -53,18 +139,11 @@ impl<'a> OtaController<'a> {
         let action = OtaAction::Rollback {
             target_version: target_version.to_string(),
         };
+        Self::authorize(io, caller, action, "Rollback")?;
 
-        let decision = io.request(caller, &action);
-        if !decision.allowed {
-            return Err(format!(
-                "OTA Rollback denied: {} (invariants: {:?})",
-                decision.reason, decision.invariants
-            ));
-        }
-
-        // Now safe to call rollback logic.
-        // perform_rollback(target_version)?;
-
-        Ok(())
+        self.slots()
+            .rollback(target_version)
+            .map(|_| ())
+            .map_err(|e| format!("OTA Rollback failed: {}", e))
     }
 }
@@ -0,0 +1,415 @@
+#![forbid(unsafe_code)]
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// On-disk A/B slot layout under one OTA root:
+///
+/// ```text
+/// <root>/staging/<hash>.download   fetched, not yet verified
+/// <root>/staging/<hash>.verified   hash checked, ready to stage
+/// <root>/slot_a/{package.pkg, slot.json}
+/// <root>/slot_b/{package.pkg, slot.json}
+/// <root>/active.json               SlotPointer (switched atomically)
+/// ```
+///
+/// Every state change is written to a temp path and renamed into place, so a
+/// crash leaves either the old or the new state, never a mix.
+const STAGING_DIR: &str = "staging";
+const POINTER_FILE: &str = "active.json";
+const PACKAGE_FILE: &str = "package.pkg";
+const SLOT_INFO_FILE: &str = "slot.json";
+
+/// Rollback target name used when no previous slot is kept.
+pub const FACTORY_VERSION: &str = "factory";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlotId {
+    A,
+    B,
+}
+
+impl SlotId {
+    pub fn other(self) -> SlotId {
+        match self {
+            SlotId::A => SlotId::B,
+            SlotId::B => SlotId::A,
+        }
+    }
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            SlotId::A => "slot_a",
+            SlotId::B => "slot_b",
+        }
+    }
+}
+
+/// Metadata stored next to each installed payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotInfo {
+    pub package_hash: String,
+    pub staged_at: DateTime<Utc>,
+}
+
+/// Active-slot pointer. `None` means the factory image.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SlotPointer {
+    pub active: Option<SlotId>,
+    /// Slot to return to on rollback; `None` falls back to factory.
+    pub rollback: Option<SlotId>,
+    /// Slot holding a staged, not yet committed package.
+    pub staged: Option<SlotId>,
+}
+
+#[derive(Debug)]
+pub enum SlotError {
+    Io(io::Error),
+    /// Package bytes do not hash to the expected package_hash.
+    HashMismatch { expected: String, actual: String },
+    /// Lifecycle step attempted out of order (e.g. Stage before Verify).
+    NotReady(String),
+    /// Fetcher cannot handle the given source.
+    UnsupportedSource(String),
+    /// package_hash is not 64 lowercase hex characters.
+    InvalidHash(String),
+}
+
+impl fmt::Display for SlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlotError::Io(e) => write!(f, "I/O error: {}", e),
+            SlotError::HashMismatch { expected, actual } => {
+                write!(f, "package hash mismatch: expected {}, got {}", expected, actual)
+            }
+            SlotError::NotReady(msg) => write!(f, "not ready: {}", msg),
+            SlotError::UnsupportedSource(s) => write!(f, "unsupported OTA source: {}", s),
+            SlotError::InvalidHash(h) => {
+                write!(f, "package hash {:?} is not 64 lowercase hex characters", h)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SlotError {}
+
+impl From<io::Error> for SlotError {
+    fn from(e: io::Error) -> Self {
+        SlotError::Io(e)
+    }
+}
+
+/// Reject anything but a lowercase hex SHA-256 before it is used in a path.
+pub fn check_package_hash(package_hash: &str) -> Result<(), SlotError> {
+    let valid = package_hash.len() == 64
+        && package_hash
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    if valid {
+        Ok(())
+    } else {
+        Err(SlotError::InvalidHash(package_hash.to_string()))
+    }
+}
+
+/// Pulls package bytes from an OTA source. Network transports plug in here.
+pub trait PackageFetcher {
+    fn fetch(&self, source: &str, package_hash: &str, dest: &mut dyn Write) -> Result<(), SlotError>;
+}
+
+/// Fetches `<dir>/<package_hash>.pkg` from a `file://` source directory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileFetcher;
+
+impl PackageFetcher for FileFetcher {
+    fn fetch(&self, source: &str, package_hash: &str, dest: &mut dyn Write) -> Result<(), SlotError> {
+        let dir = source
+            .strip_prefix("file://")
+            .ok_or_else(|| SlotError::UnsupportedSource(source.to_string()))?;
+        check_package_hash(package_hash)?;
+        let mut file = File::open(Path::new(dir).join(format!("{}.pkg", package_hash)))?;
+        io::copy(&mut file, dest)?;
+        Ok(())
+    }
+}
+
+/// Hex SHA-256 of a file's contents.
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// A/B slot engine rooted at one directory.
+pub struct SlotEngine {
+    root: PathBuf,
+}
+
+impl SlotEngine {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn staging_path(&self, package_hash: &str, suffix: &str) -> Result<PathBuf, SlotError> {
+        check_package_hash(package_hash)?;
+        Ok(self
+            .root
+            .join(STAGING_DIR)
+            .join(format!("{}.{}", package_hash, suffix)))
+    }
+
+    fn slot_dir(&self, slot: SlotId) -> PathBuf {
+        self.root.join(slot.dir_name())
+    }
+
+    /// Current pointer; a missing file means factory state.
+    pub fn pointer(&self) -> Result<SlotPointer, SlotError> {
+        let path = self.root.join(POINTER_FILE);
+        if !path.exists() {
+            return Ok(SlotPointer::default());
+        }
+        let data = fs::read_to_string(&path)?;
+        serde_json::from_str(&data)
+            .map_err(|e| SlotError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
+    fn write_pointer(&self, pointer: &SlotPointer) -> Result<(), SlotError> {
+        fs::create_dir_all(&self.root)?;
+        let json = serde_json::to_string_pretty(pointer).expect("serialize SlotPointer");
+        write_atomic(&self.root.join(POINTER_FILE), json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Installed metadata for a slot, if it holds a package.
+    pub fn slot_info(&self, slot: SlotId) -> Result<Option<SlotInfo>, SlotError> {
+        let path = self.slot_dir(slot).join(SLOT_INFO_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(&path)?;
+        serde_json::from_str(&data)
+            .map(Some)
+            .map_err(|e| SlotError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Version that `rollback` would return to (a package hash or "factory").
+    pub fn rollback_version(&self) -> Result<String, SlotError> {
+        match self.pointer()?.rollback {
+            None => Ok(FACTORY_VERSION.to_string()),
+            Some(slot) => self
+                .slot_info(slot)?
+                .map(|info| info.package_hash)
+                .ok_or_else(|| SlotError::NotReady(format!("rollback slot {:?} is empty", slot))),
+        }
+    }
+
+    /// True if the rollback target is intact: factory, or a slot whose payload
+    /// still hashes to its recorded package_hash.
+    pub fn rollback_available(&self) -> bool {
+        let pointer = match self.pointer() {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        match pointer.rollback {
+            None => true,
+            Some(slot) => self.slot_intact(slot),
+        }
+    }
+
+    fn slot_intact(&self, slot: SlotId) -> bool {
+        match self.slot_info(slot) {
+            Ok(Some(info)) => {
+                sha256_file(&self.slot_dir(slot).join(PACKAGE_FILE)).ok().as_deref()
+                    == Some(info.package_hash.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    /// Fetch a package into staging as `<hash>.download`.
+    pub fn download(
+        &self,
+        fetcher: &dyn PackageFetcher,
+        source: &str,
+        package_hash: &str,
+    ) -> Result<PathBuf, SlotError> {
+        let partial = self.staging_path(package_hash, "partial")?;
+        fs::create_dir_all(self.root.join(STAGING_DIR))?;
+        {
+            let mut file = File::create(&partial)?;
+            if let Err(e) = fetcher.fetch(source, package_hash, &mut file) {
+                let _ = fs::remove_file(&partial);
+                return Err(e);
+            }
+            file.sync_all()?;
+        }
+        let dest = self.staging_path(package_hash, "download")?;
+        fs::rename(&partial, &dest)?;
+        Ok(dest)
+    }
+
+    /// Check a downloaded package against its hash; a mismatch discards it.
+    pub fn verify(&self, package_hash: &str) -> Result<PathBuf, SlotError> {
+        let downloaded = self.staging_path(package_hash, "download")?;
+        if !downloaded.exists() {
+            return Err(SlotError::NotReady(format!(
+                "package {} has not been downloaded",
+                package_hash
+            )));
+        }
+        let actual = sha256_file(&downloaded)?;
+        if actual != package_hash {
+            let _ = fs::remove_file(&downloaded);
+            return Err(SlotError::HashMismatch {
+                expected: package_hash.to_string(),
+                actual,
+            });
+        }
+        let verified = self.staging_path(package_hash, "verified")?;
+        fs::rename(&downloaded, &verified)?;
+        Ok(verified)
+    }
+
+    /// Copy a verified package into the inactive slot. The slot directory is
+    /// built beside the target and renamed into place.
+    pub fn stage(&self, package_hash: &str) -> Result<SlotId, SlotError> {
+        let verified = self.staging_path(package_hash, "verified")?;
+        if !verified.exists() {
+            return Err(SlotError::NotReady(format!(
+                "package {} has not been verified",
+                package_hash
+            )));
+        }
+
+        let mut pointer = self.pointer()?;
+        let target = pointer.active.map(SlotId::other).unwrap_or(SlotId::A);
+        let slot_dir = self.slot_dir(target);
+        let tmp_dir = self.root.join(format!("{}.tmp", target.dir_name()));
+
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+        fs::create_dir_all(&tmp_dir)?;
+        fs::copy(&verified, tmp_dir.join(PACKAGE_FILE))?;
+        let actual = sha256_file(&tmp_dir.join(PACKAGE_FILE))?;
+        if actual != package_hash {
+            let _ = fs::remove_dir_all(&tmp_dir);
+            return Err(SlotError::HashMismatch {
+                expected: package_hash.to_string(),
+                actual,
+            });
+        }
+        let info = SlotInfo {
+            package_hash: package_hash.to_string(),
+            staged_at: Utc::now(),
+        };
+        let json = serde_json::to_string_pretty(&info).expect("serialize SlotInfo");
+        write_atomic(&tmp_dir.join(SLOT_INFO_FILE), json.as_bytes())?;
+
+        // The inactive slot is about to be overwritten; if it was the rollback
+        // target, the only remaining rollback path is factory.
+        if pointer.rollback == Some(target) {
+            pointer.rollback = None;
+        }
+        pointer.staged = None;
+        self.write_pointer(&pointer)?;
+
+        if slot_dir.exists() {
+            fs::remove_dir_all(&slot_dir)?;
+        }
+        fs::rename(&tmp_dir, &slot_dir)?;
+        fs::remove_file(&verified)?;
+
+        pointer.staged = Some(target);
+        self.write_pointer(&pointer)?;
+        Ok(target)
+    }
+
+    /// Switch the active pointer to the staged slot; the previously active
+    /// slot becomes the rollback target.
+    pub fn commit(&self, package_hash: &str) -> Result<SlotId, SlotError> {
+        let mut pointer = self.pointer()?;
+        let staged = pointer
+            .staged
+            .ok_or_else(|| SlotError::NotReady("no staged package".to_string()))?;
+        match self.slot_info(staged)? {
+            Some(info) if info.package_hash == package_hash => {}
+            _ => {
+                return Err(SlotError::NotReady(format!(
+                    "package {} is not the staged package",
+                    package_hash
+                )))
+            }
+        }
+        if !self.slot_intact(staged) {
+            return Err(SlotError::NotReady(format!(
+                "staged slot {:?} failed re-verification",
+                staged
+            )));
+        }
+
+        pointer.rollback = pointer.active;
+        pointer.active = Some(staged);
+        pointer.staged = None;
+        self.write_pointer(&pointer)?;
+        Ok(staged)
+    }
+
+    /// Return to the rollback target. `target_version` must name it (its
+    /// package hash, or "factory").
+    pub fn rollback(&self, target_version: &str) -> Result<Option<SlotId>, SlotError> {
+        let expected = self.rollback_version()?;
+        if expected != target_version {
+            return Err(SlotError::NotReady(format!(
+                "rollback target is {}, not {}",
+                expected, target_version
+            )));
+        }
+        let mut pointer = self.pointer()?;
+        if let Some(slot) = pointer.rollback {
+            if !self.slot_intact(slot) {
+                return Err(SlotError::NotReady(format!(
+                    "rollback slot {:?} failed re-verification",
+                    slot
+                )));
+            }
+        }
+        pointer.active = pointer.rollback;
+        pointer.rollback = None;
+        pointer.staged = None;
+        self.write_pointer(&pointer)?;
+        Ok(pointer.active)
+    }
+}
+
+/// Write to `<path>.tmp`, fsync, then rename over `path`.
+fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    {
+        let mut file = File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp, path)
+}
@@ -3,17 +3,21 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::fs::{File, OpenOptions};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::sovereignty::invariants::InvariantStatus;
 
 /// Different event types; extend as needed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AuditEventKind {
     OtaRequest,
-    // Add: PolicyChange, ConsentUpdate, etc.
+    /// NrmlPolicy replaced or edited.
+    PolicyChange,
+    /// Consent granted, revoked or otherwise changed.
+    ConsentUpdate,
 }
 
 /// One event in the log.
@@ -31,14 +35,196 @@ pub struct AuditEvent {
     pub invariants: Vec<InvariantStatus>,
 }
 
+/// Chain anchor used by the first record of a fresh log.
+pub const GENESIS_HASH: &str = "GENESIS";
+
 /// Stored record with hash chaining.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct AuditRecord {
+pub struct AuditRecord {
     pub event: AuditEvent,
     pub prev_hash: String,
     pub hash: String,
 }
 
+impl AuditRecord {
+    /// Recompute the chain hash for `event` on top of `prev_hash`.
+    pub fn compute_hash(event: &AuditEvent, prev_hash: &str) -> String {
+        let mut hasher = Sha256::new();
+        let payload = serde_json::to_vec(event).expect("serialize AuditEvent");
+        hasher.update(&payload);
+        hasher.update(prev_hash.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// True if the stored hash matches the event payload and prev_hash.
+    pub fn hash_matches(&self) -> bool {
+        Self::compute_hash(&self.event, &self.prev_hash) == self.hash
+    }
+}
+
+/// One problem found while replaying an audit file.
+/// `line` is the zero-based line index in the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditTamper {
+    /// Line is not a parseable AuditRecord.
+    Malformed { line: usize },
+    /// Stored hash does not match the event payload + prev_hash (edited record).
+    HashMismatch { line: usize },
+    /// prev_hash does not link to the preceding record.
+    ChainBreak {
+        line: usize,
+        expected_prev: String,
+        found_prev: String,
+    },
+    /// prev_hash links to a record other than its predecessor (moved record).
+    /// `links_to` is the line of the record it chains onto, `None` for GENESIS.
+    Reordered { line: usize, links_to: Option<usize> },
+}
+
+/// Trailing line that was cut off mid-write (no newline, not parseable).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TruncatedTail {
+    pub line: usize,
+    pub bytes: usize,
+    /// Set by `AuditLogger::resume` once the partial line has been cut from
+    /// the file.
+    #[serde(default)]
+    pub discarded: bool,
+}
+
+/// Structured result of replaying an audit file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditTamperReport {
+    /// Number of parseable records.
+    pub records_read: usize,
+    /// Line of the first record (or line) where the chain stops verifying.
+    pub first_broken_index: Option<usize>,
+    /// Partially written final line, if any.
+    pub truncated_tail: Option<TruncatedTail>,
+    /// Lines whose record chains onto a non-adjacent record.
+    pub reordered: Vec<usize>,
+    /// Every problem found, in file order.
+    pub issues: Vec<AuditTamper>,
+    /// Hash of the last record whose own hash verifies; the resume point.
+    pub last_valid_hash: String,
+}
+
+impl AuditTamperReport {
+    /// True when every line parses and the whole chain verifies.
+    pub fn is_intact(&self) -> bool {
+        self.issues.is_empty() && self.truncated_tail.is_none()
+    }
+}
+
+/// Records read back from disk, plus the verification result.
+#[derive(Debug, Clone)]
+pub struct AuditReplay {
+    pub records: Vec<AuditRecord>,
+    pub report: AuditTamperReport,
+}
+
+/// Read an audit file, replay every record and recompute the SHA-256 chain.
+/// A missing file replays as an empty, intact log.
+pub fn replay(path: &Path) -> std::io::Result<AuditReplay> {
+    let mut report = AuditTamperReport {
+        last_valid_hash: GENESIS_HASH.to_string(),
+        ..Default::default()
+    };
+    let mut records = Vec::new();
+
+    if !path.exists() {
+        return Ok(AuditReplay { records, report });
+    }
+
+    let data = fs::read_to_string(path)?;
+    let ends_with_newline = data.ends_with('\n');
+    let lines: Vec<&str> = data.lines().collect();
+
+    // First pass: parse and remember where each self-consistent hash lives.
+    let mut parsed: Vec<(usize, AuditRecord, bool)> = Vec::new();
+    let mut by_hash: HashMap<String, usize> = HashMap::new();
+    for (line, text) in lines.iter().enumerate() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditRecord>(text) {
+            Ok(record) => {
+                let self_ok = record.hash_matches();
+                if self_ok {
+                    by_hash.insert(record.hash.clone(), line);
+                }
+                parsed.push((line, record, self_ok));
+            }
+            Err(_) => {
+                if line + 1 == lines.len() && !ends_with_newline {
+                    report.truncated_tail = Some(TruncatedTail {
+                        line,
+                        bytes: text.len(),
+                        discarded: false,
+                    });
+                } else {
+                    report.issues.push(AuditTamper::Malformed { line });
+                }
+            }
+        }
+    }
+
+    // Second pass: walk the chain in file order.
+    let mut expected_prev = GENESIS_HASH.to_string();
+    for (line, record, self_ok) in parsed {
+        if !self_ok {
+            report.issues.push(AuditTamper::HashMismatch { line });
+        }
+        if record.prev_hash != expected_prev {
+            let links_to = if record.prev_hash == GENESIS_HASH {
+                Some(None)
+            } else {
+                by_hash.get(&record.prev_hash).map(|l| Some(*l))
+            };
+            match links_to {
+                Some(links_to) => {
+                    report.reordered.push(line);
+                    report.issues.push(AuditTamper::Reordered { line, links_to });
+                }
+                None => report.issues.push(AuditTamper::ChainBreak {
+                    line,
+                    expected_prev: expected_prev.clone(),
+                    found_prev: record.prev_hash.clone(),
+                }),
+            }
+        }
+        if self_ok {
+            report.last_valid_hash = record.hash.clone();
+        }
+        expected_prev = record.hash.clone();
+        records.push(record);
+    }
+
+    report.records_read = records.len();
+    report.issues.sort_by_key(tamper_line);
+    report.first_broken_index = report
+        .issues
+        .first()
+        .map(tamper_line)
+        .or_else(|| report.truncated_tail.as_ref().map(|t| t.line));
+
+    Ok(AuditReplay { records, report })
+}
+
+/// Convenience wrapper: only the tamper report.
+pub fn verify(path: &Path) -> std::io::Result<AuditTamperReport> {
+    replay(path).map(|r| r.report)
+}
+
+fn tamper_line(t: &AuditTamper) -> usize {
+    match t {
+        AuditTamper::Malformed { line }
+        | AuditTamper::HashMismatch { line }
+        | AuditTamper::ChainBreak { line, .. }
+        | AuditTamper::Reordered { line, .. } => *line,
+    }
+}
+
 /// Simple file-based append-only logger.
 pub struct AuditLogger {
     path: PathBuf,
@@ -49,11 +235,39 @@ impl AuditLogger {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
-            last_hash: String::from("GENESIS"),
+            last_hash: String::from(GENESIS_HASH),
         }
     }
 
+    /// Open an existing log and continue the chain from its last valid record.
+    /// A cut-off trailing line never held a complete record, so it is cut
+    /// back to the last newline and reported with `discarded` set; the log
+    /// then verifies as intact again once the next record is appended.
+    pub fn resume(path: PathBuf) -> std::io::Result<(Self, AuditTamperReport)> {
+        let mut report = verify(&path)?;
+        if let Some(tail) = report.truncated_tail.as_mut() {
+            let data = fs::read(&path)?;
+            let keep = data.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+            let file = OpenOptions::new().write(true).open(&path)?;
+            file.set_len(keep as u64)?;
+            file.sync_all()?;
+            tail.discarded = true;
+        }
+        let logger = Self {
+            path,
+            last_hash: report.last_valid_hash.clone(),
+        };
+        Ok((logger, report))
+    }
+
+    /// Hash the next record will chain onto.
+    pub fn last_hash(&self) -> &str {
+        &self.last_hash
+    }
+
     /// Append an event, compute hash chain, and write as one JSON line.
+    /// The chain only advances once the line is written, so a failed append
+    /// leaves no gap for the next record to link across.
     pub fn append(&mut self, event: AuditEvent) -> std::io::Result<()> {
         let record = self.make_record(event);
         let json = serde_json::to_string(&record).expect("serialize AuditRecord");
@@ -66,24 +280,17 @@ impl AuditLogger {
         writer.write_all(json.as_bytes())?;
         writer.write_all(b"\n")?;
         writer.flush()?;
+        self.last_hash = record.hash;
         Ok(())
     }
 
-    fn make_record(&mut self, event: AuditEvent) -> AuditRecord {
-        let mut hasher = Sha256::new();
-        let payload = serde_json::to_vec(&event).expect("serialize AuditEvent");
-        hasher.update(&payload);
-        hasher.update(self.last_hash.as_bytes());
-        let hash_bytes = hasher.finalize();
-        let hash_hex = hex::encode(hash_bytes);
+    fn make_record(&self, event: AuditEvent) -> AuditRecord {
+        let hash_hex = AuditRecord::compute_hash(&event, &self.last_hash);
 
-        let record = AuditRecord {
+        AuditRecord {
             event,
             prev_hash: self.last_hash.clone(),
-            hash: hash_hex.clone(),
-        };
-
-        self.last_hash = hash_hex;
-        record
+            hash: hash_hex,
+        }
     }
 }
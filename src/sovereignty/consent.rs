@@ -1,34 +1,62 @@
 #![forbid(unsafe_code)]
 
 use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
 
-// Add an EVOLVE token and a separate RequiredToken variant.
+use crate::sovereignty::revocation::{RevocationEntry, RevocationJournal};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Awareness token semantics for consent.
+///
+/// Tokens form a hierarchy by `rank`: Query < Commit < Evolve. A consent
+/// satisfies a requirement when its rank is at least the required rank.
+/// Insight (new struct / schema proposal) is non-destructive and ranks with Query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AwarenessToken {
-    Query,
-    Commit,
-    Insight,
+    Query,   // low-risk, discovery
+    Commit,  // structural/motor commit
+    Insight, // new struct / schema
     /// Explicit evolution consent (e.g., deep model/OS evolution).
     Evolve,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl AwarenessToken {
+    pub fn rank(self) -> u8 {
+        match self {
+            AwarenessToken::Query | AwarenessToken::Insight => 0,
+            AwarenessToken::Commit => 1,
+            AwarenessToken::Evolve => 2,
+        }
+    }
+
+    /// True if a consent carrying this token covers `required`.
+    pub fn satisfies(self, required: RequiredToken) -> bool {
+        self.rank() >= required.rank()
+    }
+}
+
+/// Associate OTA / evolution actions with the minimum token they need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum RequiredToken {
-    Any,
-    Commit,
-    Evolve,
+    Any,    // e.g., for Discover/Download
+    Commit, // Commit / Rollback
+    Evolve, // deep evolution of a target
 }
 
-/// Awareness token semantics for consent.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum AwarenessToken {
-    Query,   // low-risk, discovery
-    Commit,  // structural/motor commit
-    Insight, // new struct / schema
+impl RequiredToken {
+    pub fn rank(self) -> u8 {
+        match self {
+            RequiredToken::Any => 0,
+            RequiredToken::Commit => 1,
+            RequiredToken::Evolve => 2,
+        }
+    }
 }
 
 /// Core ConsentObject as stored on disk.
@@ -41,9 +69,135 @@ pub struct ConsentObject {
     pub created_at: DateTime<Utc>,
     pub valid_until: Option<DateTime<Utc>>,
     pub revoked: bool,
+    /// Hex ed25519 signature by the owner's key over `canonical_bytes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Domain separator so a .cobj signature cannot be replayed as another message.
+const COBJ_SIGNING_DOMAIN: &[u8] = b"neuro_pc.cobj.v1\n";
+
+/// Signed view of a ConsentObject: every field except the signature, in a
+/// fixed order.
+#[derive(Serialize)]
+struct CanonicalConsent<'a> {
+    id: &'a str,
+    token: AwarenessToken,
+    target_id: &'a str,
+    owner_id: &'a str,
+    created_at: &'a DateTime<Utc>,
+    valid_until: &'a Option<DateTime<Utc>>,
+    revoked: bool,
+}
+
+/// Why a .cobj was not accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsentError {
+    /// File is not a parseable ConsentObject.
+    Parse(String),
+    /// No signature field.
+    Unsigned,
+    /// No public key registered for this owner_id.
+    UnknownOwner(String),
+    /// Signature is malformed or does not verify.
+    BadSignature,
+    /// Registered key is not a valid ed25519 public key.
+    InvalidKey(String),
+}
+
+impl fmt::Display for ConsentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsentError::Parse(e) => write!(f, "unparseable ConsentObject: {}", e),
+            ConsentError::Unsigned => write!(f, "ConsentObject is unsigned"),
+            ConsentError::UnknownOwner(o) => write!(f, "no public key registered for owner {}", o),
+            ConsentError::BadSignature => write!(f, "signature does not verify"),
+            ConsentError::InvalidKey(o) => write!(f, "invalid public key for owner {}", o),
+        }
+    }
+}
+
+impl std::error::Error for ConsentError {}
+
+/// Public keys allowed to sign consent, keyed by owner_id.
+#[derive(Debug, Clone, Default)]
+pub struct OwnerKeyRegistry {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl OwnerKeyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, owner_id: impl Into<String>, key: VerifyingKey) {
+        self.keys.insert(owner_id.into(), key);
+    }
+
+    /// Build from an owner_id -> hex public key map (as stored in NrmlPolicy).
+    pub fn from_hex_map(map: &BTreeMap<String, String>) -> Result<Self, ConsentError> {
+        let mut registry = Self::new();
+        for (owner_id, key_hex) in map {
+            registry.register(owner_id.clone(), verifying_key_from_hex(key_hex)
+                .ok_or_else(|| ConsentError::InvalidKey(owner_id.clone()))?);
+        }
+        Ok(registry)
+    }
+
+    pub fn get(&self, owner_id: &str) -> Option<&VerifyingKey> {
+        self.keys.get(owner_id)
+    }
+}
+
+/// Parse a 32-byte hex ed25519 public key.
+pub fn verifying_key_from_hex(key_hex: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(key_hex.trim()).ok()?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// Parse a 32-byte hex ed25519 secret key.
+pub fn signing_key_from_hex(key_hex: &str) -> Option<SigningKey> {
+    let bytes: [u8; 32] = hex::decode(key_hex.trim()).ok()?.try_into().ok()?;
+    Some(SigningKey::from_bytes(&bytes))
 }
 
 impl ConsentObject {
+    /// Bytes covered by the owner signature.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let view = CanonicalConsent {
+            id: &self.id,
+            token: self.token,
+            target_id: &self.target_id,
+            owner_id: &self.owner_id,
+            created_at: &self.created_at,
+            valid_until: &self.valid_until,
+            revoked: self.revoked,
+        };
+        let mut bytes = COBJ_SIGNING_DOMAIN.to_vec();
+        bytes.extend(serde_json::to_vec(&view).expect("serialize CanonicalConsent"));
+        bytes
+    }
+
+    /// Sign in place with the owner's key.
+    pub fn sign(&mut self, key: &SigningKey) {
+        let signature = key.sign(&self.canonical_bytes());
+        self.signature = Some(hex::encode(signature.to_bytes()));
+    }
+
+    /// Check the signature against the key registered for owner_id.
+    pub fn verify_signature(&self, keys: &OwnerKeyRegistry) -> Result<(), ConsentError> {
+        let sig_hex = self.signature.as_deref().ok_or(ConsentError::Unsigned)?;
+        let key = keys
+            .get(&self.owner_id)
+            .ok_or_else(|| ConsentError::UnknownOwner(self.owner_id.clone()))?;
+        let sig_bytes: [u8; 64] = hex::decode(sig_hex)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or(ConsentError::BadSignature)?;
+        key.verify(&self.canonical_bytes(), &Signature::from_bytes(&sig_bytes))
+            .map_err(|_| ConsentError::BadSignature)
+    }
+
     /// Simple validity check against time and revocation.
     pub fn is_currently_valid(&self, now: DateTime<Utc>, owner: &str) -> bool {
         if self.revoked {
@@ -61,45 +215,229 @@ impl ConsentObject {
     }
 }
 
-/// Associate OTA actions with required token kind.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum RequiredToken {
-    Any,    // e.g., for Discover/Download
-    Commit, // Commit / Rollback
+/// Parsed .cobj file plus the metadata used to detect in-place edits.
+#[derive(Debug, Clone)]
+struct CachedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    consent: Result<ConsentObject, String>,
+}
+
+/// In-memory index over one consent directory.
+///
+/// The file list is re-read only when the directory mtime changes (files
+/// added, removed or renamed). Known files are re-parsed only when their own
+/// mtime or length changes, so the common path is one stat per file. The
+/// revocation journal beside the directory is tracked the same way.
+#[derive(Debug, Default)]
+struct ConsentIndex {
+    dir_modified: Option<SystemTime>,
+    files: HashMap<PathBuf, CachedFile>,
+    by_target: HashMap<String, Vec<ConsentObject>>,
+    journal_meta: Option<(Option<SystemTime>, u64)>,
+    revoked: HashSet<(String, String)>,
+}
+
+impl ConsentIndex {
+    fn refresh(&mut self, root: &Path) -> std::io::Result<()> {
+        self.refresh_revocations(root)?;
+        if !root.exists() {
+            self.dir_modified = None;
+            self.files.clear();
+            self.by_target.clear();
+            return Ok(());
+        }
+
+        let dir_modified = fs::metadata(root)?.modified().ok();
+        let mut changed = false;
+
+        if is_racy(dir_modified) || dir_modified != self.dir_modified {
+            let mut seen = Vec::new();
+            for entry in fs::read_dir(root)? {
+                let path = entry?.path();
+                if path.extension().and_then(|s| s.to_str()) == Some("cobj") {
+                    seen.push(path);
+                }
+            }
+            let before = self.files.len();
+            self.files.retain(|p, _| seen.contains(p));
+            changed |= self.files.len() != before;
+            for path in seen {
+                changed |= Self::refresh_file(&mut self.files, path)?;
+            }
+            self.dir_modified = dir_modified;
+        } else {
+            let paths: Vec<PathBuf> = self.files.keys().cloned().collect();
+            for path in paths {
+                changed |= Self::refresh_file(&mut self.files, path)?;
+            }
+        }
+
+        if changed {
+            self.by_target.clear();
+            for cached in self.files.values() {
+                if let Ok(c) = &cached.consent {
+                    self.by_target
+                        .entry(c.target_id.clone())
+                        .or_default()
+                        .push(c.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn refresh_revocations(&mut self, root: &Path) -> std::io::Result<()> {
+        let journal = RevocationJournal::for_consent_dir(root);
+        let meta = match fs::metadata(journal.path()) {
+            Ok(m) => Some((m.modified().ok(), m.len())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        let racy = meta.as_ref().is_some_and(|(m, _)| is_racy(*m));
+        if racy || meta != self.journal_meta {
+            // Load before recording the metadata so a bad journal is retried.
+            self.revoked = journal.revoked_set()?;
+            self.journal_meta = meta;
+        }
+        Ok(())
+    }
+
+    fn is_revoked(&self, c: &ConsentObject) -> bool {
+        c.revoked || self.revoked.contains(&(c.id.clone(), c.owner_id.clone()))
+    }
+
+    /// Re-parse `path` if it is new or its metadata changed. Returns true if
+    /// the cached entry was replaced.
+    fn refresh_file(files: &mut HashMap<PathBuf, CachedFile>, path: PathBuf) -> std::io::Result<bool> {
+        let meta = match fs::metadata(&path) {
+            Ok(m) => m,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(files.remove(&path).is_some());
+            }
+            Err(e) => return Err(e),
+        };
+        let modified = meta.modified().ok();
+        let len = meta.len();
+        if let Some(cached) = files.get(&path) {
+            if !is_racy(modified) && cached.modified == modified && cached.len == len {
+                return Ok(false);
+            }
+        }
+        let data = fs::read_to_string(&path)?;
+        let consent = serde_json::from_str::<ConsentObject>(&data).map_err(|e| e.to_string());
+        files.insert(path, CachedFile { modified, len, consent });
+        Ok(true)
+    }
+}
+
+/// Timestamps this close to "now" may hide a second write within the same
+/// filesystem tick, so they are never trusted as a cache key.
+fn is_racy(modified: Option<SystemTime>) -> bool {
+    match modified {
+        None => true,
+        Some(m) => SystemTime::now()
+            .duration_since(m)
+            .map(|age| age < RACY_WINDOW)
+            .unwrap_or(true),
+    }
+}
+
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Process-wide indexes, one per consent directory, so short-lived
+/// `ConsentStore` handles share parsed state.
+fn shared_index(root: &Path) -> Arc<Mutex<ConsentIndex>> {
+    static INDEXES: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<ConsentIndex>>>>> = OnceLock::new();
+    let key = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let mut map = INDEXES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    map.entry(key).or_default().clone()
+}
+
+/// A .cobj file that was not accepted, and why.
+#[derive(Debug, Clone)]
+pub struct ConsentRejection {
+    pub path: PathBuf,
+    pub reason: ConsentError,
+}
+
+/// Result of loading a consent directory.
+#[derive(Debug, Clone, Default)]
+pub struct ConsentLoadReport {
+    pub accepted: Vec<ConsentObject>,
+    pub rejected: Vec<ConsentRejection>,
+    /// Ids of accepted objects withdrawn by flag or revocation journal.
+    pub revoked: Vec<String>,
 }
 
 pub struct ConsentStore {
     root: PathBuf,
+    keys: OwnerKeyRegistry,
+    index: Arc<Mutex<ConsentIndex>>,
 }
 
 impl ConsentStore {
-    /// root_dir is the directory where .cobj files live.
-    pub fn new(root_dir: impl AsRef<Path>) -> Self {
-        Self {
-            root: root_dir.as_ref().to_path_buf(),
-        }
+    /// root_dir is the directory where .cobj files live; only objects signed
+    /// by a key in `keys` are accepted.
+    pub fn new(root_dir: impl AsRef<Path>, keys: OwnerKeyRegistry) -> Self {
+        let root = root_dir.as_ref().to_path_buf();
+        let index = shared_index(&root);
+        Self { root, keys, index }
     }
 
-    /// Load all .cobj files from disk.
-    fn load_all(&self) -> std::io::Result<Vec<ConsentObject>> {
-        let mut result = Vec::new();
-        if !self.root.exists() {
-            return Ok(result);
-        }
-        for entry in fs::read_dir(&self.root)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("cobj") {
-                let data = fs::read_to_string(&path)?;
-                if let Ok(cobj) = serde_json::from_str::<ConsentObject>(&data) {
-                    result.push(cobj);
+    /// Load every .cobj, reporting unparseable, unsigned and mis-signed files.
+    pub fn load_all(&self) -> std::io::Result<ConsentLoadReport> {
+        let mut index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+        index.refresh(&self.root)?;
+
+        let mut report = ConsentLoadReport::default();
+        let mut paths: Vec<&PathBuf> = index.files.keys().collect();
+        paths.sort();
+        for path in paths {
+            let verdict = match &index.files[path].consent {
+                Err(e) => Err(ConsentError::Parse(e.clone())),
+                Ok(c) => c.verify_signature(&self.keys).map(|_| c.clone()),
+            };
+            match verdict {
+                Ok(c) => {
+                    if index.is_revoked(&c) {
+                        report.revoked.push(c.id.clone());
+                    }
+                    report.accepted.push(c)
                 }
+                Err(reason) => report.rejected.push(ConsentRejection {
+                    path: path.clone(),
+                    reason,
+                }),
             }
         }
-        Ok(result)
+        Ok(report)
+    }
+
+    /// Withdraw a consent by appending to the revocation journal. Takes effect
+    /// on the next lookup through any store over the same directory.
+    pub fn revoke(
+        &self,
+        consent_id: &str,
+        owner_id: &str,
+        reason: &str,
+    ) -> std::io::Result<RevocationEntry> {
+        let entry = RevocationEntry {
+            consent_id: consent_id.to_string(),
+            owner_id: owner_id.to_string(),
+            revoked_at: Utc::now(),
+            reason: reason.to_string(),
+        };
+        RevocationJournal::for_consent_dir(&self.root).append(&entry)?;
+        Ok(entry)
     }
 
     /// Find a consent object that matches owner, target and token requirement.
+    /// When several match, the least-privileged token wins, then the one that
+    /// stays valid longest.
     pub fn find_valid_for(
         &self,
         owner_id: &str,
@@ -107,29 +445,20 @@ impl ConsentStore {
         required: RequiredToken,
         now: DateTime<Utc>,
     ) -> std::io::Result<Option<ConsentObject>> {
-        let all = self.load_all()?;
-        let mut best: Option<ConsentObject> = None;
+        let mut index = self.index.lock().unwrap_or_else(|e| e.into_inner());
+        index.refresh(&self.root)?;
 
-        for c in all {
-            if !c.is_currently_valid(now, owner_id) {
-                continue;
-            }
-            if c.target_id != target_id {
-                continue;
-            }
-            match required {
-                RequiredToken::Any => {
-                    best = Some(c);
-                    break;
-                }
-                RequiredToken::Commit => {
-                    if c.token == AwarenessToken::Commit {
-                        best = Some(c);
-                        break;
-                    }
-                }
-            }
-        }
+        let best = index
+            .by_target
+            .get(target_id)
+            .into_iter()
+            .flatten()
+            .filter(|c| c.is_currently_valid(now, owner_id))
+            .filter(|c| !index.is_revoked(c))
+            .filter(|c| c.token.satisfies(required))
+            .filter(|c| c.verify_signature(&self.keys).is_ok())
+            .min_by_key(|c| (c.token.rank(), std::cmp::Reverse(c.valid_until.unwrap_or(DateTime::<Utc>::MAX_UTC))))
+            .cloned();
 
         Ok(best)
     }
@@ -2,10 +2,14 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+use std::sync::Arc;
 
 use crate::sovereignty::invariants::{InvariantStatus, SovereigntyInvariant};
 use crate::sovereignty::policy::NrmlPolicy;
+use crate::sovereignty::policy_loader::PolicyWatcher;
 use crate::sovereignty::audit::{AuditEvent, AuditEventKind, AuditLogger};
+use crate::sovereignty::revocation::RevocationEntry;
 
 /// Who is asking (e.g., "bf_ota_core", "module_xyz").
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,28 +38,81 @@ pub struct OtaDecision {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Where the façade reads its policy from.
+enum PolicySource<'a> {
+    Fixed(&'a NrmlPolicy),
+    Live(&'a PolicyWatcher),
+}
+
+/// Policy in force for one request; a live policy is pinned for its duration.
+enum PolicySnapshot<'a> {
+    Fixed(&'a NrmlPolicy),
+    Live(Arc<NrmlPolicy>),
+}
+
+impl Deref for PolicySnapshot<'_> {
+    type Target = NrmlPolicy;
+
+    fn deref(&self) -> &NrmlPolicy {
+        match self {
+            PolicySnapshot::Fixed(p) => p,
+            PolicySnapshot::Live(p) => p,
+        }
+    }
+}
+
 /// Sovereign I/O façade.
 pub struct SovereignOtaIo<'a> {
-    policy: &'a NrmlPolicy,
+    policy: PolicySource<'a>,
     logger: &'a mut AuditLogger,
 }
 
 impl<'a> SovereignOtaIo<'a> {
     pub fn new(policy: &'a NrmlPolicy, logger: &'a mut AuditLogger) -> Self {
-        Self { policy, logger }
+        Self {
+            policy: PolicySource::Fixed(policy),
+            logger,
+        }
+    }
+
+    /// Follow a .nrml file: the watcher is polled before every request, so an
+    /// edited policy takes effect (and is audited) without a restart.
+    pub fn with_watcher(watcher: &'a PolicyWatcher, logger: &'a mut AuditLogger) -> Self {
+        Self {
+            policy: PolicySource::Live(watcher),
+            logger,
+        }
+    }
+
+    /// Policy for one request. The snapshot is returned even when polling
+    /// failed, so the caller can still evaluate and audit a denial.
+    fn policy(&mut self) -> (PolicySnapshot<'a>, std::io::Result<()>) {
+        match self.policy {
+            PolicySource::Fixed(p) => (PolicySnapshot::Fixed(p), Ok(())),
+            PolicySource::Live(w) => {
+                let polled = w.poll(self.logger).map(|_| ());
+                (PolicySnapshot::Live(w.current()), polled)
+            }
+        }
     }
 
     /// Main entry point: evaluate + (if allowed) perform the action.
     /// In this short-term skeleton, we only decide + log; actual fs/net can be added behind
     /// additional, tightly-controlled adapters.
     pub fn request(&mut self, caller: &Caller, action: &OtaAction) -> OtaDecision {
-        // 1. Evaluate policy.
-        let policy_decision = self.policy.evaluate_ota(caller, action);
+        // 1. Evaluate policy (one snapshot for the whole request).
+        let (policy, polled) = self.policy();
+        let mut policy_decision = policy.evaluate_ota(caller, action);
+        if let Err(e) = polled {
+            // A policy change that could not be audited denies the request.
+            policy_decision.allowed = false;
+            policy_decision.reason = format!("policy reload failed: {}", e);
+        }
 
         // 2. Check invariants that MUST hold before any OTA side effect.
         let mut inv_status = Vec::new();
 
-        let sov_ok = self.policy.sovereignty_core_enabled();
+        let sov_ok = policy.sovereignty_core_enabled();
         inv_status.push(if sov_ok {
             InvariantStatus::ok(SovereigntyInvariant::InvSovCoreEnabled)
         } else {
@@ -65,7 +122,7 @@ impl<'a> SovereignOtaIo<'a> {
             )
         });
 
-        let rb_ok = self.policy.rollback_path_available();
+        let rb_ok = policy.rollback_path_available();
         inv_status.push(if rb_ok {
             InvariantStatus::ok(SovereigntyInvariant::InvRollbackReachable)
         } else {
@@ -75,7 +132,7 @@ impl<'a> SovereignOtaIo<'a> {
             )
         });
 
-        let user_ch_ok = self.policy.user_control_channel_available();
+        let user_ch_ok = policy.user_control_channel_available();
         inv_status.push(if user_ch_ok {
             InvariantStatus::ok(SovereigntyInvariant::InvUserControlChannelAvailable)
         } else {
@@ -87,7 +144,7 @@ impl<'a> SovereignOtaIo<'a> {
 
         // 3. If this is a Commit or Rollback, enforce explicit consent invariant.
         if matches!(action, OtaAction::Commit { .. } | OtaAction::Rollback { .. }) {
-            let has_consent = self.policy.has_valid_ota_consent(caller, action);
+            let has_consent = policy.has_valid_ota_consent(caller, action);
             inv_status.push(if has_consent {
                 InvariantStatus::ok(SovereigntyInvariant::InvNoOtaWithoutExplicitConsent)
             } else {
@@ -99,7 +156,22 @@ impl<'a> SovereignOtaIo<'a> {
         }
 
         let all_invariants_ok = inv_status.iter().all(|s| s.satisfied);
-        let allowed = policy_decision.allowed && all_invariants_ok;
+        let mut allowed = policy_decision.allowed && all_invariants_ok;
+
+        // Only a Commit that is actually going ahead counts against its
+        // rule's rate limit.
+        let now = Utc::now();
+        let mut recorded_commit = None;
+        if let (true, OtaAction::Commit { .. }, Some(rule)) = (allowed, action, &policy_decision.rule) {
+            match policy.ota_rules.record_commit(caller, rule, now) {
+                Ok(()) => recorded_commit = Some(rule.clone()),
+                Err(reason) => {
+                    allowed = false;
+                    policy_decision.allowed = false;
+                    policy_decision.reason = reason;
+                }
+            }
+        }
 
         // 4. Log the decision (no secrets, only metadata + hashes).
         let event = AuditEvent {
@@ -111,7 +183,21 @@ impl<'a> SovereignOtaIo<'a> {
             policy_decision: serde_json::to_value(&policy_decision).unwrap_or_default(),
             invariants: inv_status.clone(),
         };
-        let _ = self.logger.append(event);
+        if let Err(e) = self.logger.append(event) {
+            // An unrecorded decision is never acted on.
+            if let Some(rule) = recorded_commit {
+                policy.ota_rules.forget_commit(caller, &rule, now);
+            }
+            return OtaDecision {
+                allowed: false,
+                reason: format!(
+                    "Denied: decision could not be audited: {}; {}",
+                    e, policy_decision.reason
+                ),
+                invariants: inv_status,
+                timestamp: Utc::now(),
+            };
+        }
 
         OtaDecision {
             allowed,
@@ -124,4 +210,34 @@ impl<'a> SovereignOtaIo<'a> {
             timestamp: Utc::now(),
         }
     }
+
+    /// Kill switch: revoke a consent through the journal and record a
+    /// ConsentUpdate event. Only the policy owner's consents can be revoked here.
+    pub fn revoke_consent(
+        &mut self,
+        caller: &Caller,
+        consent_id: &str,
+        reason: &str,
+    ) -> std::io::Result<RevocationEntry> {
+        let (policy, polled) = self.policy();
+        polled?;
+        let entry = policy
+            .consent_store()
+            .revoke(consent_id, &policy.owner_id, reason)?;
+
+        let event = AuditEvent {
+            timestamp: entry.revoked_at,
+            caller_module: caller.module_id.clone(),
+            caller_instance: caller.instance_id.clone(),
+            kind: AuditEventKind::ConsentUpdate,
+            action: serde_json::json!({ "Revoke": &entry }),
+            policy_decision: serde_json::json!({
+                "allowed": true,
+                "reason": format!("Consent {} revoked by owner", consent_id),
+            }),
+            invariants: Vec::new(),
+        };
+        self.logger.append(event)?;
+        Ok(entry)
+    }
 }
@@ -0,0 +1,243 @@
+#![forbid(unsafe_code)]
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::sovereignty::ota_io::{Caller, OtaAction};
+
+/// OTA action kinds a rule can name (payload-free mirror of OtaAction).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OtaActionKind {
+    Discover,
+    Download,
+    Verify,
+    Stage,
+    Commit,
+    Rollback,
+}
+
+impl OtaActionKind {
+    pub fn of(action: &OtaAction) -> Self {
+        match action {
+            OtaAction::Discover { .. } => OtaActionKind::Discover,
+            OtaAction::Download { .. } => OtaActionKind::Download,
+            OtaAction::Verify { .. } => OtaActionKind::Verify,
+            OtaAction::Stage { .. } => OtaActionKind::Stage,
+            OtaAction::Commit { .. } => OtaActionKind::Commit,
+            OtaAction::Rollback { .. } => OtaActionKind::Rollback,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RuleEffect {
+    Allow,
+    Deny,
+}
+
+/// At most `max` Commit attempts per caller within `window_secs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitRateLimit {
+    pub max: u32,
+    pub window_secs: i64,
+}
+
+/// One row of the OTA rule table. Rules are checked in order; the first one
+/// that matches decides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtaRule {
+    /// Stable name reported in PolicyDecision.
+    pub id: String,
+    /// Glob over `Caller::module_id` (`*` matches any run of characters).
+    pub caller: String,
+    /// Actions this rule covers; empty means all.
+    #[serde(default)]
+    pub actions: Vec<OtaActionKind>,
+    /// Globs over the source URL of Discover/Download (`https://vendor/*`
+    /// for a prefix). Empty means any source; non-empty never matches
+    /// actions without a source.
+    #[serde(default)]
+    pub sources: Vec<String>,
+    pub effect: RuleEffect,
+    /// Only valid on Allow rules covering Commit; the loader rejects it
+    /// anywhere else.
+    #[serde(default)]
+    pub commit_rate_limit: Option<CommitRateLimit>,
+}
+
+impl OtaRule {
+    fn matches(&self, caller: &Caller, kind: OtaActionKind, source: Option<&str>) -> bool {
+        if !glob_match(&self.caller, &caller.module_id) {
+            return false;
+        }
+        if !self.actions.is_empty() && !self.actions.contains(&kind) {
+            return false;
+        }
+        if self.sources.is_empty() {
+            return true;
+        }
+        match source {
+            Some(src) => self.sources.iter().any(|p| glob_match(p, src)),
+            None => false,
+        }
+    }
+}
+
+/// (rule id, module_id) -> timestamps of allowed Commit attempts.
+type AttemptLog = HashMap<(String, String), VecDeque<DateTime<Utc>>>;
+
+/// Sliding-window log of allowed Commit attempts, keyed by (rule id, module).
+/// Only commits recorded through `OtaRules::record_commit` count.
+/// Shared between clones so a reloaded policy can keep counting.
+#[derive(Debug, Clone, Default)]
+pub struct CommitAttempts(Arc<Mutex<AttemptLog>>);
+
+/// Rule table evaluated by `NrmlPolicy::evaluate_ota`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OtaRules {
+    /// Deny actions no rule matches (otherwise they are allowed).
+    #[serde(default)]
+    pub default_deny: bool,
+    #[serde(default)]
+    pub rules: Vec<OtaRule>,
+    #[serde(skip)]
+    attempts: CommitAttempts,
+}
+
+/// Outcome of the rule table for one request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleVerdict {
+    pub allowed: bool,
+    /// Rule that decided, or None when the default applied.
+    pub rule: Option<String>,
+    pub reason: String,
+}
+
+impl OtaRules {
+    /// Carry the Commit attempt log over from a previous rule set (hot reload).
+    pub fn inherit_attempts(&mut self, previous: &OtaRules) {
+        self.attempts = previous.attempts.clone();
+    }
+
+    pub fn evaluate(&self, caller: &Caller, action: &OtaAction, now: DateTime<Utc>) -> RuleVerdict {
+        let kind = OtaActionKind::of(action);
+        let source = match action {
+            OtaAction::Discover { source } | OtaAction::Download { source, .. } => Some(source.as_str()),
+            _ => None,
+        };
+
+        let rule = match self.rules.iter().find(|r| r.matches(caller, kind, source)) {
+            Some(r) => r,
+            None => {
+                return RuleVerdict {
+                    allowed: !self.default_deny,
+                    rule: None,
+                    reason: if self.default_deny {
+                        format!("No rule allows {:?} for {} (deny by default)", kind, caller.module_id)
+                    } else {
+                        format!("No rule matched {:?} for {} (allow by default)", kind, caller.module_id)
+                    },
+                }
+            }
+        };
+
+        if rule.effect == RuleEffect::Deny {
+            return RuleVerdict {
+                allowed: false,
+                rule: Some(rule.id.clone()),
+                reason: format!("Rule {} denies {:?} for {}", rule.id, kind, caller.module_id),
+            };
+        }
+
+        if kind == OtaActionKind::Commit && !self.commit_window_open(rule, caller, now, false) {
+            return RuleVerdict {
+                allowed: false,
+                rule: Some(rule.id.clone()),
+                reason: rate_limit_reason(rule, caller),
+            };
+        }
+
+        RuleVerdict {
+            allowed: true,
+            rule: Some(rule.id.clone()),
+            reason: format!("Rule {} allows {:?} for {}", rule.id, kind, caller.module_id),
+        }
+    }
+
+    /// Count a Commit against the rate limit of `rule_id`, the rule that
+    /// allowed it. `evaluate` only checks the window; the caller records once
+    /// every other check has passed, so denied commits never use up the
+    /// quota. Errs with the denial reason if the window filled since
+    /// `evaluate`.
+    pub fn record_commit(&self, caller: &Caller, rule_id: &str, now: DateTime<Utc>) -> Result<(), String> {
+        match self.rules.iter().find(|r| r.id == rule_id) {
+            Some(rule) if !self.commit_window_open(rule, caller, now, true) => {
+                Err(rate_limit_reason(rule, caller))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Withdraw a Commit recorded at `now` whose effects never happened.
+    pub fn forget_commit(&self, caller: &Caller, rule_id: &str, now: DateTime<Utc>) {
+        let mut log = self.attempts.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(window) = log.get_mut(&(rule_id.to_string(), caller.module_id.clone())) {
+            if let Some(i) = window.iter().rposition(|t| *t == now) {
+                window.remove(i);
+            }
+        }
+    }
+
+    /// True if `rule` has room for another Commit by `caller` at `now`;
+    /// with `record`, the attempt is also logged under the same lock.
+    fn commit_window_open(&self, rule: &OtaRule, caller: &Caller, now: DateTime<Utc>, record: bool) -> bool {
+        let Some(limit) = rule.commit_rate_limit else {
+            return true;
+        };
+        let mut log = self.attempts.0.lock().unwrap_or_else(|e| e.into_inner());
+        let window = log
+            .entry((rule.id.clone(), caller.module_id.clone()))
+            .or_default();
+        let cutoff = now - Duration::seconds(limit.window_secs);
+        while window.front().is_some_and(|t| *t <= cutoff) {
+            window.pop_front();
+        }
+        if window.len() >= limit.max as usize {
+            return false;
+        }
+        if record {
+            window.push_back(now);
+        }
+        true
+    }
+}
+
+fn rate_limit_reason(rule: &OtaRule, caller: &Caller) -> String {
+    let limit = rule.commit_rate_limit.expect("rate-limited rule");
+    format!(
+        "Rule {} rate limit: {} Commit attempts by {} in {}s",
+        rule.id, limit.max, caller.module_id, limit.window_secs
+    )
+}
+
+/// Glob match where `*` matches any (possibly empty) run of characters.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
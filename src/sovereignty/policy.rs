@@ -2,15 +2,22 @@
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use crate::sovereignty::consent::{ConsentStore, RequiredToken};
+use crate::ota::slots::SlotEngine;
+use crate::sovereignty::consent::{ConsentStore, OwnerKeyRegistry, RequiredToken};
 use crate::sovereignty::ota_io::{Caller, OtaAction};
+use crate::sovereignty::ota_rules::OtaRules;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyDecision {
     pub allowed: bool,
     pub reason: String,
+    /// Rule that decided ("trusted_ota_sources" or an OtaRule id); None when
+    /// the table default applied.
+    #[serde(default)]
+    pub rule: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,32 +25,38 @@ pub struct NrmlPolicy {
     pub owner_id: String,
     pub trusted_ota_sources: Vec<String>,
     pub sovereignty_core_enabled_flag: bool,
-    pub rollback_available_flag: bool,
     pub user_control_channel_flag: bool,
     /// Directory where .cobj files live.
     pub consent_dir: PathBuf,
+    /// Root of the A/B slot layout; rollback availability is read from here.
+    pub ota_root: PathBuf,
+    /// owner_id -> hex ed25519 public key allowed to sign .cobj files.
+    #[serde(default)]
+    pub owner_keys: BTreeMap<String, String>,
+    /// Per-caller / per-source rules applied after the trusted-source check.
+    #[serde(default)]
+    pub ota_rules: OtaRules,
 }
 
 impl NrmlPolicy {
-    pub fn evaluate_ota(&self, _caller: &Caller, action: &OtaAction) -> PolicyDecision {
-        match action {
-            OtaAction::Discover { source } | OtaAction::Download { source, .. } => {
-                if self.trusted_ota_sources.contains(source) {
-                    PolicyDecision {
-                        allowed: true,
-                        reason: format!("Source {} trusted by policy", source),
-                    }
-                } else {
-                    PolicyDecision {
-                        allowed: false,
-                        reason: format!("Source {} not in trusted_ota_sources", source),
-                    }
-                }
+    /// Trusted sources are a hard gate for Discover/Download; the rule table
+    /// then decides per caller, action and source.
+    pub fn evaluate_ota(&self, caller: &Caller, action: &OtaAction) -> PolicyDecision {
+        if let OtaAction::Discover { source } | OtaAction::Download { source, .. } = action {
+            if !self.trusted_ota_sources.contains(source) {
+                return PolicyDecision {
+                    allowed: false,
+                    reason: format!("Source {} not in trusted_ota_sources", source),
+                    rule: Some("trusted_ota_sources".to_string()),
+                };
             }
-            _ => PolicyDecision {
-                allowed: true,
-                reason: "Non-network OTA action allowed by default".to_string(),
-            },
+        }
+
+        let verdict = self.ota_rules.evaluate(caller, action, Utc::now());
+        PolicyDecision {
+            allowed: verdict.allowed,
+            reason: verdict.reason,
+            rule: verdict.rule,
         }
     }
 
@@ -51,14 +64,23 @@ impl NrmlPolicy {
         self.sovereignty_core_enabled_flag
     }
 
+    /// Derived from the slot state: the rollback target must be factory or
+    /// an intact slot.
     pub fn rollback_path_available(&self) -> bool {
-        self.rollback_available_flag
+        SlotEngine::new(&self.ota_root).rollback_available()
     }
 
     pub fn user_control_channel_available(&self) -> bool {
         self.user_control_channel_flag
     }
 
+    /// Consent store over consent_dir that only accepts owner-signed objects.
+    /// A malformed key map yields an empty registry, so nothing verifies.
+    pub fn consent_store(&self) -> ConsentStore {
+        let keys = OwnerKeyRegistry::from_hex_map(&self.owner_keys).unwrap_or_default();
+        ConsentStore::new(&self.consent_dir, keys)
+    }
+
     /// OTA Commit/Rollback consent: requires a matching .cobj.
     pub fn has_valid_ota_consent(&self, caller: &Caller, action: &OtaAction) -> bool {
         let (required_token, target_id) = match action {
@@ -79,27 +101,22 @@ impl NrmlPolicy {
             }
         };
 
-        let store = ConsentStore::new(&self.consent_dir);
+        let store = self.consent_store();
         let now = Utc::now();
-        match store.find_valid_for(&self.owner_id, target_id, required_token, now) {
-            Ok(Some(_)) => true,
-            _ => false,
-        }
+        matches!(
+            store.find_valid_for(&self.owner_id, target_id, required_token, now),
+            Ok(Some(_))
+        )
     }
 
     /// Evolution consent: e.g., OS / model / BrainFunction evolution.
     /// evolve_target_id should be a stable identifier (e.g., "bf_ota_core").
     pub fn has_valid_evolve_consent(&self, evolve_target_id: &str) -> bool {
-        let store = ConsentStore::new(&self.consent_dir);
+        let store = self.consent_store();
         let now = Utc::now();
-        match store.find_valid_for(
-            &self.owner_id,
-            evolve_target_id,
-            RequiredToken::Evolve,
-            now,
-        ) {
-            Ok(Some(_)) => true,
-            _ => false,
-        }
+        matches!(
+            store.find_valid_for(&self.owner_id, evolve_target_id, RequiredToken::Evolve, now),
+            Ok(Some(_))
+        )
     }
 }
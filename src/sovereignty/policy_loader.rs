@@ -0,0 +1,314 @@
+#![forbid(unsafe_code)]
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::sovereignty::audit::{AuditEvent, AuditEventKind, AuditLogger};
+use crate::sovereignty::ota_rules::{OtaActionKind, RuleEffect};
+use crate::sovereignty::policy::NrmlPolicy;
+
+/// Value of the `schema` key every .nrml file must carry.
+pub const NRML_SCHEMA: &str = "neuro_pc.nrml";
+/// Highest .nrml schema version this build understands.
+pub const NRML_VERSION: u32 = 1;
+
+/// On-disk .nrml document: a versioned header around NrmlPolicy.
+///
+/// TOML by default; a file whose first non-blank character is `{` is read as
+/// JSON. Relative paths are resolved against the .nrml file's directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NrmlPolicyFile {
+    pub schema: String,
+    pub version: u32,
+    /// Stake file whose Host role must be `owner_id`.
+    pub stake_file: PathBuf,
+    #[serde(flatten)]
+    pub policy: NrmlPolicy,
+}
+
+#[derive(Debug)]
+pub enum PolicyLoadError {
+    Io(std::io::Error),
+    Parse(String),
+    /// Every validation failure found, not just the first.
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for PolicyLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyLoadError::Io(e) => write!(f, "I/O error: {}", e),
+            PolicyLoadError::Parse(e) => write!(f, "parse error: {}", e),
+            PolicyLoadError::Invalid(issues) => write!(f, "invalid policy: {}", issues.join("; ")),
+        }
+    }
+}
+
+impl std::error::Error for PolicyLoadError {}
+
+impl From<std::io::Error> for PolicyLoadError {
+    fn from(e: std::io::Error) -> Self {
+        PolicyLoadError::Io(e)
+    }
+}
+
+/// Parse and validate a .nrml file.
+pub fn load_policy(path: &Path) -> Result<NrmlPolicy, PolicyLoadError> {
+    let text = fs::read_to_string(path)?;
+    parse_policy(&text, path.parent().unwrap_or_else(|| Path::new(".")))
+}
+
+/// Parse and validate .nrml text; `base_dir` anchors relative paths.
+pub fn parse_policy(text: &str, base_dir: &Path) -> Result<NrmlPolicy, PolicyLoadError> {
+    let mut file: NrmlPolicyFile = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|e| PolicyLoadError::Parse(e.to_string()))?
+    } else {
+        toml::from_str(text).map_err(|e| PolicyLoadError::Parse(e.to_string()))?
+    };
+
+    file.stake_file = resolve(base_dir, &file.stake_file);
+    file.policy.consent_dir = resolve(base_dir, &file.policy.consent_dir);
+    file.policy.ota_root = resolve(base_dir, &file.policy.ota_root);
+
+    let issues = validate(&file);
+    if !issues.is_empty() {
+        return Err(PolicyLoadError::Invalid(issues));
+    }
+    Ok(file.policy)
+}
+
+fn resolve(base_dir: &Path, p: &Path) -> PathBuf {
+    if p.is_absolute() {
+        p.to_path_buf()
+    } else {
+        base_dir.join(p)
+    }
+}
+
+/// All schema and consistency checks; an empty list means the file is valid.
+pub fn validate(file: &NrmlPolicyFile) -> Vec<String> {
+    let mut issues = Vec::new();
+    let policy = &file.policy;
+
+    if file.schema != NRML_SCHEMA {
+        issues.push(format!("schema must be '{}', got '{}'", NRML_SCHEMA, file.schema));
+    }
+    if file.version == 0 || file.version > NRML_VERSION {
+        issues.push(format!(
+            "unsupported version {} (supported: 1..={})",
+            file.version, NRML_VERSION
+        ));
+    }
+
+    for source in &policy.trusted_ota_sources {
+        match url::Url::parse(source) {
+            Ok(u) if u.scheme() == "https" && u.host_str().is_some() => {}
+            Ok(u) if u.scheme() == "file" => {}
+            Ok(u) => issues.push(format!(
+                "trusted OTA source '{}' must be https:// with a host or file://, got scheme '{}'",
+                source,
+                u.scheme()
+            )),
+            Err(e) => issues.push(format!("trusted OTA source '{}' is not a URL: {}", source, e)),
+        }
+    }
+
+    for rule in &policy.ota_rules.rules {
+        let covers_commit = rule.actions.is_empty() || rule.actions.contains(&OtaActionKind::Commit);
+        if rule.commit_rate_limit.is_some() && (rule.effect == RuleEffect::Deny || !covers_commit) {
+            issues.push(format!(
+                "OTA rule '{}' has a commit_rate_limit but is not an Allow rule covering Commit",
+                rule.id
+            ));
+        }
+    }
+
+    if !policy.consent_dir.is_dir() {
+        issues.push(format!(
+            "consent_dir {} does not exist",
+            policy.consent_dir.display()
+        ));
+    }
+
+    match stake_hosts(&file.stake_file) {
+        Ok(hosts) if hosts.iter().any(|h| h == &policy.owner_id) => {}
+        Ok(hosts) if hosts.is_empty() => issues.push(format!(
+            "stake file {} has no Host role",
+            file.stake_file.display()
+        )),
+        Ok(hosts) => issues.push(format!(
+            "owner_id {} is not the stake Host ({})",
+            policy.owner_id,
+            hosts.join(", ")
+        )),
+        Err(e) => issues.push(format!(
+            "cannot read stake file {}: {}",
+            file.stake_file.display(),
+            e
+        )),
+    }
+
+    issues
+}
+
+/// Bostrom addresses of Host rows in a CSV stake file
+/// (`role_id,subject_id,bostrom_address,role_kind,...`, `#` comments,
+/// trailing `\` continues a row on the next line).
+fn stake_hosts(path: &Path) -> std::io::Result<Vec<String>> {
+    let text = fs::read_to_string(path)?;
+    let mut rows = Vec::new();
+    let mut pending = String::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('#') || (line.is_empty() && pending.is_empty()) {
+            continue;
+        }
+        match line.strip_suffix('\\') {
+            Some(head) => pending.push_str(head.trim()),
+            None => {
+                pending.push_str(line);
+                rows.push(std::mem::take(&mut pending));
+            }
+        }
+    }
+
+    Ok(rows
+        .iter()
+        .map(|r| r.split(',').map(str::trim).collect::<Vec<_>>())
+        .filter(|cols| cols.len() >= 4 && cols[3] == "Host")
+        .map(|cols| cols[2].to_string())
+        .collect())
+}
+
+/// Digest placeholder recorded while the policy file cannot be read.
+const UNREADABLE_DIGEST: &str = "unreadable";
+
+fn digest(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Holds the live policy loaded from a .nrml file and swaps it when the file
+/// changes. Polling is explicit: `SovereignOtaIo` polls before each request,
+/// and hosts may poll on their own schedule. Readers always see either the
+/// old or the new policy, never a partially applied one.
+pub struct PolicyWatcher {
+    path: PathBuf,
+    current: RwLock<(Arc<NrmlPolicy>, String)>,
+}
+
+impl PolicyWatcher {
+    /// Load the initial policy; an invalid file is an error here.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PolicyLoadError> {
+        let path = path.as_ref().to_path_buf();
+        let text = fs::read_to_string(&path)?;
+        let policy = parse_policy(&text, path.parent().unwrap_or_else(|| Path::new(".")))?;
+        Ok(Self {
+            path,
+            current: RwLock::new((Arc::new(policy), digest(&text))),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Snapshot of the policy in force.
+    pub fn current(&self) -> Arc<NrmlPolicy> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .0
+            .clone()
+    }
+
+    /// Re-read the file; if its content changed, validate and swap. Each
+    /// change, accepted or rejected, is logged as one PolicyChange event.
+    /// A rejected file leaves the previous policy in force, and so does a
+    /// change whose audit record could not be written (that error is
+    /// returned and the change is retried on the next poll).
+    /// Returns true if a new policy was installed.
+    pub fn poll(&self, logger: &mut AuditLogger) -> std::io::Result<bool> {
+        let old_digest = self
+            .current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .1
+            .clone();
+        let text = match fs::read_to_string(&self.path) {
+            Ok(t) => t,
+            Err(e) => {
+                // A missing or unreadable file never drops the current policy;
+                // report it once until the file comes back.
+                if old_digest != UNREADABLE_DIGEST {
+                    self.log_change(logger, None, false, format!("policy file unreadable: {}", e))?;
+                    self.current.write().unwrap_or_else(|e| e.into_inner()).1 =
+                        UNREADABLE_DIGEST.to_string();
+                }
+                return Ok(false);
+            }
+        };
+        let new_digest = digest(&text);
+        if new_digest == old_digest {
+            return Ok(false);
+        }
+
+        let base = self.path.parent().unwrap_or_else(|| Path::new("."));
+        match parse_policy(&text, base) {
+            Ok(mut policy) => {
+                // Rate-limit windows survive the swap.
+                policy.ota_rules.inherit_attempts(&self.current().ota_rules);
+                // An unaudited policy never takes effect; the next poll retries.
+                self.log_change(
+                    logger,
+                    Some((&old_digest, &new_digest)),
+                    true,
+                    "policy reloaded".to_string(),
+                )?;
+                *self.current.write().unwrap_or_else(|e| e.into_inner()) =
+                    (Arc::new(policy), new_digest);
+                Ok(true)
+            }
+            Err(e) => {
+                self.log_change(
+                    logger,
+                    Some((&old_digest, &new_digest)),
+                    false,
+                    format!("policy rejected, previous policy kept: {}", e),
+                )?;
+                // Remember the digest so the same bad file is reported once.
+                self.current.write().unwrap_or_else(|e| e.into_inner()).1 = new_digest;
+                Ok(false)
+            }
+        }
+    }
+
+    fn log_change(
+        &self,
+        logger: &mut AuditLogger,
+        digests: Option<(&str, &str)>,
+        allowed: bool,
+        reason: String,
+    ) -> std::io::Result<()> {
+        let (previous, next) = digests.unwrap_or(("", ""));
+        logger.append(AuditEvent {
+            timestamp: Utc::now(),
+            caller_module: "nrml_policy_watcher".to_string(),
+            caller_instance: None,
+            kind: AuditEventKind::PolicyChange,
+            action: serde_json::json!({
+                "Reload": {
+                    "path": self.path.display().to_string(),
+                    "previous_digest": previous,
+                    "new_digest": next,
+                }
+            }),
+            policy_decision: serde_json::json!({ "allowed": allowed, "reason": reason }),
+            invariants: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,112 @@
+#![forbid(unsafe_code)]
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// One revocation: consent `consent_id` owned by `owner_id` is no longer valid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationEntry {
+    pub consent_id: String,
+    pub owner_id: String,
+    pub revoked_at: DateTime<Utc>,
+    pub reason: String,
+}
+
+/// Append-only NDJSON journal of revocations, kept beside the consent dir
+/// (`<parent>/<consent_dir_name>.revocations.ndjson`) so .cobj files never
+/// need to be edited to withdraw consent.
+pub struct RevocationJournal {
+    path: PathBuf,
+}
+
+impl RevocationJournal {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Journal that belongs to `consent_dir`.
+    pub fn for_consent_dir(consent_dir: impl AsRef<Path>) -> Self {
+        Self::new(Self::path_for(consent_dir.as_ref()))
+    }
+
+    pub fn path_for(consent_dir: &Path) -> PathBuf {
+        let name = consent_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "consent".to_string());
+        consent_dir.with_file_name(format!("{}.revocations.ndjson", name))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one entry as a single JSON line. A cut-off final line left by
+    /// a crash mid-append is cut back to the last newline first, so the new
+    /// entry starts on its own line and `load` keeps accepting the journal.
+    pub fn append(&self, entry: &RevocationEntry) -> std::io::Result<()> {
+        let json = serde_json::to_string(entry).expect("serialize RevocationEntry");
+        if self.path.exists() {
+            let data = fs::read(&self.path)?;
+            if !data.is_empty() && !data.ends_with(b"\n") {
+                let keep = data.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+                let file = OpenOptions::new().write(true).open(&self.path)?;
+                file.set_len(keep as u64)?;
+                file.sync_all()?;
+            }
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_all(json.as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        Ok(())
+    }
+
+    /// Read every entry. A cut-off final line (crash mid-append) is ignored;
+    /// any other unparseable line is an error, so consent checks fail closed.
+    pub fn load(&self) -> std::io::Result<Vec<RevocationEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let data = fs::read_to_string(&self.path)?;
+        let complete = data.ends_with('\n');
+        let lines: Vec<&str> = data.lines().collect();
+        let mut entries = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<RevocationEntry>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) if i + 1 == lines.len() && !complete => {}
+                Err(e) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("{}:{}: {}", self.path.display(), i + 1, e),
+                    ))
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    /// (consent_id, owner_id) pairs that are revoked.
+    pub fn revoked_set(&self) -> std::io::Result<HashSet<(String, String)>> {
+        Ok(self
+            .load()?
+            .into_iter()
+            .map(|e| (e.consent_id, e.owner_id))
+            .collect())
+    }
+}
@@ -0,0 +1,87 @@
+#![forbid(unsafe_code)]
+
+use chrono::Utc;
+use tempfile::TempDir;
+
+use neuro_pc::sovereignty::audit::{self, AuditEvent, AuditEventKind, AuditLogger, AuditTamper};
+
+fn event(module: &str) -> AuditEvent {
+    AuditEvent {
+        timestamp: Utc::now(),
+        caller_module: module.to_string(),
+        caller_instance: None,
+        kind: AuditEventKind::OtaRequest,
+        action: serde_json::json!({ "Commit": { "package_hash": "hash_abc" } }),
+        policy_decision: serde_json::json!({ "allowed": true, "reason": "test" }),
+        invariants: Vec::new(),
+    }
+}
+
+#[test]
+fn resume_continues_chain_from_tail() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let path = tmp.path().join("audit.log");
+
+    let mut logger = AuditLogger::new(path.clone());
+    logger.append(event("a"))?;
+    logger.append(event("b"))?;
+    let tail = logger.last_hash().to_string();
+    drop(logger);
+
+    // Restart: the next record must chain to the real tail, not GENESIS.
+    let (mut resumed, report) = AuditLogger::resume(path.clone())?;
+    assert!(report.is_intact());
+    assert_eq!(resumed.last_hash(), tail);
+    resumed.append(event("c"))?;
+
+    let replay = audit::replay(&path)?;
+    assert!(replay.report.is_intact(), "{:?}", replay.report);
+    assert_eq!(replay.records.len(), 3);
+    assert_eq!(replay.records[2].prev_hash, tail);
+    Ok(())
+}
+
+#[test]
+fn tamper_report_names_edit_truncation_and_reorder() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let path = tmp.path().join("audit.log");
+
+    let mut logger = AuditLogger::new(path.clone());
+    for m in ["a", "b", "c", "d"] {
+        logger.append(event(m))?;
+    }
+    let original = std::fs::read_to_string(&path)?;
+    let lines: Vec<&str> = original.lines().collect();
+
+    // Edited payload on line 1.
+    let edited = original.replacen("\"caller_module\":\"b\"", "\"caller_module\":\"x\"", 1);
+    std::fs::write(&path, &edited)?;
+    let report = audit::verify(&path)?;
+    assert_eq!(report.first_broken_index, Some(1));
+    assert!(report.issues.contains(&AuditTamper::HashMismatch { line: 1 }));
+
+    // Swap lines 2 and 3.
+    let swapped = format!("{}\n{}\n{}\n{}\n", lines[0], lines[1], lines[3], lines[2]);
+    std::fs::write(&path, &swapped)?;
+    let report = audit::verify(&path)?;
+    assert_eq!(report.first_broken_index, Some(2));
+    assert_eq!(report.reordered, vec![2, 3]);
+
+    // Crash mid-write: partial trailing line without newline.
+    let truncated = format!("{}{}", original, &lines[0][..20]);
+    std::fs::write(&path, &truncated)?;
+    let (mut resumed, report) = AuditLogger::resume(path.clone())?;
+    assert!(report.issues.is_empty());
+    let tail = report.truncated_tail.as_ref().unwrap();
+    assert_eq!((tail.line, tail.discarded), (4, true));
+    assert_eq!(std::fs::read_to_string(&path)?, original);
+    assert_eq!(resumed.last_hash(), report.last_valid_hash);
+    resumed.append(event("e"))?;
+
+    // The partial record is gone, so the resumed log verifies as intact.
+    let replay = audit::replay(&path)?;
+    assert_eq!(replay.records.len(), 5);
+    assert!(replay.report.is_intact(), "{:?}", replay.report);
+    assert_eq!(replay.records[4].prev_hash, replay.records[3].hash);
+    Ok(())
+}
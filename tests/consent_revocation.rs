@@ -0,0 +1,132 @@
+#![forbid(unsafe_code)]
+
+use chrono::Utc;
+use ed25519_dalek::SigningKey;
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+use neuro_pc::sovereignty::audit::{self, AuditEventKind, AuditLogger};
+use neuro_pc::sovereignty::consent::{AwarenessToken, ConsentObject};
+use neuro_pc::sovereignty::ota_io::{Caller, OtaAction, SovereignOtaIo};
+use neuro_pc::sovereignty::policy::NrmlPolicy;
+use neuro_pc::sovereignty::revocation::RevocationJournal;
+
+const OWNER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+/// A signed commit consent for `package_hash`, written into `consent_dir`.
+fn write_commit_consent(
+    consent_dir: &std::path::Path,
+    key: &SigningKey,
+    id: &str,
+    package_hash: &str,
+) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    let mut cobj = ConsentObject {
+        id: id.to_string(),
+        token: AwarenessToken::Commit,
+        target_id: package_hash.to_string(),
+        owner_id: OWNER.to_string(),
+        created_at: Utc::now(),
+        valid_until: None,
+        revoked: false,
+        signature: None,
+    };
+    cobj.sign(key);
+    let cobj_path = consent_dir.join(format!("{id}.cobj"));
+    std::fs::write(&cobj_path, serde_json::to_string_pretty(&cobj)?)?;
+    Ok(cobj_path)
+}
+
+fn policy(tmp: &TempDir, consent_dir: &std::path::Path, key: &SigningKey) -> NrmlPolicy {
+    NrmlPolicy {
+        owner_id: OWNER.to_string(),
+        trusted_ota_sources: Vec::new(),
+        sovereignty_core_enabled_flag: true,
+        user_control_channel_flag: true,
+        consent_dir: consent_dir.to_path_buf(),
+        ota_root: tmp.path().join("ota"),
+        owner_keys: BTreeMap::from([(OWNER.to_string(), hex::encode(key.verifying_key().to_bytes()))]),
+        ota_rules: Default::default(),
+    }
+}
+
+fn commit(package_hash: &str) -> OtaAction {
+    OtaAction::Commit {
+        package_hash: package_hash.to_string(),
+    }
+}
+
+fn caller() -> Caller {
+    Caller {
+        module_id: "sovereign_controls".to_string(),
+        instance_id: None,
+    }
+}
+
+#[test]
+fn revocation_journal_withdraws_consent_and_is_audited() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let consent_dir = tmp.path().join("consent");
+    std::fs::create_dir_all(&consent_dir)?;
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+
+    let cobj_path = write_commit_consent(&consent_dir, &key, "cobj_commit_pkg", "hash_pkg")?;
+    let cobj_before = std::fs::read(&cobj_path)?;
+
+    let policy = policy(&tmp, &consent_dir, &key);
+    let commit = commit("hash_pkg");
+    let caller = caller();
+    assert!(policy.has_valid_ota_consent(&caller, &commit));
+
+    let audit_path = tmp.path().join("audit.log");
+    let mut logger = AuditLogger::new(audit_path.clone());
+    let mut io = SovereignOtaIo::new(&policy, &mut logger);
+    let entry = io.revoke_consent(&caller, "cobj_commit_pkg", "changed my mind")?;
+
+    // Effective immediately, without touching the .cobj file.
+    assert!(!policy.has_valid_ota_consent(&caller, &commit));
+    assert_eq!(std::fs::read(&cobj_path)?, cobj_before);
+    let report = policy.consent_store().load_all()?;
+    assert_eq!(report.revoked, vec!["cobj_commit_pkg".to_string()]);
+
+    // Journal sits next to the consent dir and records who/what/when/why.
+    let journal = RevocationJournal::for_consent_dir(&consent_dir);
+    assert_eq!(journal.path(), tmp.path().join("consent.revocations.ndjson"));
+    assert_eq!(journal.load()?, vec![entry]);
+
+    // And the audit trail has a ConsentUpdate event.
+    let replay = audit::replay(&audit_path)?;
+    assert!(replay.report.is_intact());
+    assert_eq!(replay.records.len(), 1);
+    assert_eq!(replay.records[0].event.kind, AuditEventKind::ConsentUpdate);
+    Ok(())
+}
+
+#[test]
+fn a_revoke_after_a_crash_mid_append_is_kept() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let consent_dir = tmp.path().join("consent");
+    std::fs::create_dir_all(&consent_dir)?;
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    write_commit_consent(&consent_dir, &key, "cobj_a", "hash_a")?;
+    write_commit_consent(&consent_dir, &key, "cobj_b", "hash_b")?;
+    let policy = policy(&tmp, &consent_dir, &key);
+    let caller = caller();
+
+    let mut logger = AuditLogger::new(tmp.path().join("audit.log"));
+    let first = SovereignOtaIo::new(&policy, &mut logger).revoke_consent(&caller, "cobj_a", "first")?;
+
+    // Crash mid-append: half of a record, no newline.
+    let journal = RevocationJournal::for_consent_dir(&consent_dir);
+    let line = std::fs::read_to_string(journal.path())?;
+    let mut file = std::fs::OpenOptions::new().append(true).open(journal.path())?;
+    std::io::Write::write_all(&mut file, &line.as_bytes()[..line.len() / 2])?;
+    drop(file);
+    assert_eq!(journal.load()?, vec![first.clone()]);
+
+    let second = SovereignOtaIo::new(&policy, &mut logger).revoke_consent(&caller, "cobj_b", "second")?;
+    assert_eq!(journal.load()?, vec![first, second]);
+    assert!(std::fs::read_to_string(journal.path())?.lines().all(|l| serde_json::from_str::<serde_json::Value>(l).is_ok()));
+    assert!(!policy.has_valid_ota_consent(&caller, &commit("hash_a")));
+    assert!(!policy.has_valid_ota_consent(&caller, &commit("hash_b")));
+    Ok(())
+}
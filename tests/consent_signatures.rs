@@ -0,0 +1,82 @@
+#![forbid(unsafe_code)]
+
+use chrono::Utc;
+use ed25519_dalek::SigningKey;
+use tempfile::TempDir;
+
+use neuro_pc::sovereignty::consent::{
+    AwarenessToken, ConsentError, ConsentObject, ConsentStore, OwnerKeyRegistry, RequiredToken,
+};
+
+const OWNER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn cobj(id: &str) -> ConsentObject {
+    ConsentObject {
+        id: id.to_string(),
+        token: AwarenessToken::Commit,
+        target_id: "hash_pkg".to_string(),
+        owner_id: OWNER.to_string(),
+        created_at: Utc::now(),
+        valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
+        revoked: false,
+        signature: None,
+    }
+}
+
+#[test]
+fn load_all_reports_unsigned_missigned_and_tampered_files() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let owner = SigningKey::from_bytes(&[7u8; 32]);
+    let intruder = SigningKey::from_bytes(&[9u8; 32]);
+    let mut keys = OwnerKeyRegistry::new();
+    keys.register(OWNER, owner.verifying_key());
+
+    let write = |name: &str, c: &ConsentObject| {
+        std::fs::write(tmp.path().join(name), serde_json::to_string_pretty(c).unwrap()).unwrap()
+    };
+
+    let mut good = cobj("good");
+    good.sign(&owner);
+    write("a_good.cobj", &good);
+
+    write("b_unsigned.cobj", &cobj("unsigned"));
+
+    let mut forged = cobj("forged");
+    forged.sign(&intruder);
+    write("c_forged.cobj", &forged);
+
+    let mut tampered = cobj("tampered");
+    tampered.sign(&owner);
+    tampered.target_id = "hash_other".to_string();
+    write("d_tampered.cobj", &tampered);
+
+    std::fs::write(tmp.path().join("e_garbage.cobj"), "{ not json")?;
+
+    let store = ConsentStore::new(tmp.path(), keys.clone());
+    let report = store.load_all()?;
+    assert_eq!(report.accepted.len(), 1);
+    assert_eq!(report.accepted[0].id, "good");
+
+    let reasons: Vec<_> = report.rejected.iter().map(|r| r.reason.clone()).collect();
+    assert_eq!(reasons[0], ConsentError::Unsigned);
+    assert_eq!(reasons[1], ConsentError::BadSignature);
+    assert_eq!(reasons[2], ConsentError::BadSignature);
+    assert!(matches!(reasons[3], ConsentError::Parse(_)));
+
+    // Only the owner-signed object can satisfy a lookup.
+    assert!(store
+        .find_valid_for(OWNER, "hash_other", RequiredToken::Commit, Utc::now())?
+        .is_none());
+    assert!(store
+        .find_valid_for(OWNER, "hash_pkg", RequiredToken::Commit, Utc::now())?
+        .is_some());
+
+    // No registered key for the owner: nothing verifies.
+    let bare = ConsentStore::new(tmp.path(), OwnerKeyRegistry::new());
+    assert!(bare
+        .load_all()?
+        .rejected
+        .iter()
+        .any(|r| r.reason == ConsentError::UnknownOwner(OWNER.to_string())));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+#![forbid(unsafe_code)]
+
+use chrono::Utc;
+use ed25519_dalek::SigningKey;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tempfile::TempDir;
+
+use neuro_pc::sovereignty::consent::{
+    AwarenessToken, ConsentObject, ConsentStore, OwnerKeyRegistry, RequiredToken,
+};
+
+const OWNER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn owner_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn keys() -> OwnerKeyRegistry {
+    let mut keys = OwnerKeyRegistry::new();
+    keys.register(OWNER, owner_key().verifying_key());
+    keys
+}
+
+fn write_cobj(dir: &Path, file: &str, token: AwarenessToken, target_id: &str) {
+    let mut cobj = ConsentObject {
+        id: file.to_string(),
+        token,
+        target_id: target_id.to_string(),
+        owner_id: OWNER.to_string(),
+        created_at: Utc::now(),
+        valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
+        revoked: false,
+        signature: None,
+    };
+    cobj.sign(&owner_key());
+    std::fs::write(dir.join(format!("{}.cobj", file)), serde_json::to_string_pretty(&cobj).unwrap()).unwrap();
+}
+
+#[test]
+fn token_hierarchy_covers_every_required_combination() {
+    use AwarenessToken::*;
+    let cases = [
+        (Query, RequiredToken::Any, true),
+        (Query, RequiredToken::Commit, false),
+        (Query, RequiredToken::Evolve, false),
+        (Insight, RequiredToken::Any, true),
+        (Insight, RequiredToken::Commit, false),
+        (Insight, RequiredToken::Evolve, false),
+        (Commit, RequiredToken::Any, true),
+        (Commit, RequiredToken::Commit, true),
+        (Commit, RequiredToken::Evolve, false),
+        (Evolve, RequiredToken::Any, true),
+        (Evolve, RequiredToken::Commit, true),
+        (Evolve, RequiredToken::Evolve, true),
+    ];
+
+    for (token, required, expected) in cases {
+        assert_eq!(token.satisfies(required), expected, "{:?} vs {:?}", token, required);
+
+        let tmp = TempDir::new().unwrap();
+        write_cobj(tmp.path(), "c", token, "bf_ota_core");
+        let store = ConsentStore::new(tmp.path(), keys());
+        let found = store
+            .find_valid_for(OWNER, "bf_ota_core", required, Utc::now())
+            .unwrap();
+        assert_eq!(found.is_some(), expected, "store: {:?} vs {:?}", token, required);
+    }
+}
+
+#[test]
+fn least_privileged_match_wins_and_cache_sees_edits() {
+    let tmp = TempDir::new().unwrap();
+    write_cobj(tmp.path(), "evolve", AwarenessToken::Evolve, "bf_nav");
+    write_cobj(tmp.path(), "commit", AwarenessToken::Commit, "bf_nav");
+
+    let store = ConsentStore::new(tmp.path(), keys());
+    let found = store
+        .find_valid_for(OWNER, "bf_nav", RequiredToken::Commit, Utc::now())
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.token, AwarenessToken::Commit);
+
+    // Removing the EVOLVE file is picked up by a fresh handle on the same dir.
+    std::fs::remove_file(tmp.path().join("evolve.cobj")).unwrap();
+    let again = ConsentStore::new(tmp.path(), keys());
+    assert!(again
+        .find_valid_for(OWNER, "bf_nav", RequiredToken::Evolve, Utc::now())
+        .unwrap()
+        .is_none());
+
+    // In-place edit of an existing file (same name) is also picked up.
+    write_cobj(tmp.path(), "commit", AwarenessToken::Query, "bf_nav");
+    assert!(store
+        .find_valid_for(OWNER, "bf_nav", RequiredToken::Commit, Utc::now())
+        .unwrap()
+        .is_none());
+}
+
+/// Move a file's (or directory's) mtime out of the racy window.
+fn backdate(path: &Path, to: SystemTime) {
+    std::fs::File::open(path).unwrap().set_modified(to).unwrap();
+}
+
+#[test]
+fn settled_files_are_served_from_cache() {
+    let tmp = TempDir::new().unwrap();
+    write_cobj(tmp.path(), "commit", AwarenessToken::Commit, "bf_nav");
+    let file = tmp.path().join("commit.cobj");
+    let settled = SystemTime::now() - Duration::from_secs(60);
+    backdate(&file, settled);
+    backdate(tmp.path(), settled);
+
+    let store = ConsentStore::new(tmp.path(), keys());
+    let find = || {
+        store
+            .find_valid_for(OWNER, "bf_nav", RequiredToken::Commit, Utc::now())
+            .unwrap()
+    };
+    assert!(find().is_some());
+
+    // Same length and mtime: the cached parse is trusted and the file is not
+    // re-read, so even unparseable bytes go unnoticed.
+    let len = std::fs::metadata(&file).unwrap().len() as usize;
+    std::fs::write(&file, vec![b'x'; len]).unwrap();
+    backdate(&file, settled);
+    assert!(find().is_some());
+
+    // A different length invalidates the entry despite the old mtime.
+    std::fs::write(&file, b"{}").unwrap();
+    backdate(&file, settled);
+    assert!(find().is_none());
+}
@@ -0,0 +1,162 @@
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+use tempfile::TempDir;
+
+use neuro_pc::sovereignty::audit::{self, AuditEventKind, AuditLogger};
+use neuro_pc::sovereignty::ota_io::{Caller, OtaAction, SovereignOtaIo};
+use neuro_pc::sovereignty::policy_loader::{load_policy, PolicyLoadError, PolicyWatcher};
+
+const OWNER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn write_policy(dir: &Path, sources: &[&str]) {
+    let sources: Vec<String> = sources.iter().map(|s| format!("{:?}", s)).collect();
+    let text = format!(
+        r#"schema = "neuro_pc.nrml"
+version = 1
+owner_id = "{OWNER}"
+stake_file = "stake.aln"
+trusted_ota_sources = [{}]
+sovereignty_core_enabled_flag = true
+user_control_channel_flag = true
+consent_dir = "consent"
+ota_root = "ota"
+"#,
+        sources.join(", ")
+    );
+    // Write-then-rename so a concurrent poll never sees a half-written file.
+    std::fs::write(dir.join("policy.tmp"), text).unwrap();
+    std::fs::rename(dir.join("policy.tmp"), dir.join("policy.nrml")).unwrap();
+}
+
+fn setup() -> TempDir {
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir_all(tmp.path().join("consent")).unwrap();
+    std::fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("policies/bostrom-stake-v1.stake.aln"),
+        tmp.path().join("stake.aln"),
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn shipped_policy_file_loads() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("policies/neuro_pc.nrml");
+    let policy = load_policy(&path).expect("shipped .nrml validates");
+    assert_eq!(policy.owner_id, OWNER);
+    assert!(policy.consent_dir.is_dir());
+}
+
+#[test]
+fn validation_reports_every_problem() {
+    let tmp = setup();
+    let text = r#"{
+        "schema": "neuro_pc.nrml",
+        "version": 2,
+        "owner_id": "bostrom1someoneelse",
+        "stake_file": "stake.aln",
+        "trusted_ota_sources": ["http://plain.example/ota", "not a url"],
+        "sovereignty_core_enabled_flag": true,
+        "user_control_channel_flag": true,
+        "consent_dir": "missing_consent",
+        "ota_root": "ota"
+    }"#;
+    std::fs::write(tmp.path().join("bad.nrml"), text).unwrap();
+
+    match load_policy(&tmp.path().join("bad.nrml")) {
+        Err(PolicyLoadError::Invalid(issues)) => {
+            assert_eq!(issues.len(), 5, "{:#?}", issues);
+            assert!(issues.iter().any(|i| i.contains("unsupported version 2")));
+            assert!(issues.iter().any(|i| i.contains("http://plain.example")));
+            assert!(issues.iter().any(|i| i.contains("not a URL")));
+            assert!(issues.iter().any(|i| i.contains("consent_dir")));
+            assert!(issues.iter().any(|i| i.contains("not the stake Host")));
+        }
+        other => panic!("expected Invalid, got {:?}", other),
+    }
+}
+
+#[test]
+fn watcher_swaps_policy_while_io_runs_and_audits_each_change() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = setup();
+    write_policy(tmp.path(), &["https://a.example/ota"]);
+    let watcher = PolicyWatcher::open(tmp.path().join("policy.nrml"))?;
+
+    let audit_path = tmp.path().join("audit.log");
+    let mut logger = AuditLogger::new(audit_path.clone());
+    let mut io = SovereignOtaIo::with_watcher(&watcher, &mut logger);
+    let caller = Caller {
+        module_id: "bf_ota_core".to_string(),
+        instance_id: None,
+    };
+    let discover_b = OtaAction::Discover {
+        source: "https://b.example/ota".to_string(),
+    };
+
+    assert!(!io.request(&caller, &discover_b).allowed);
+
+    // Trust b.example: the next request sees it.
+    write_policy(tmp.path(), &["https://b.example/ota"]);
+    assert!(io.request(&caller, &discover_b).allowed);
+
+    // An invalid edit is rejected and the previous policy stays in force.
+    write_policy(tmp.path(), &["ftp://b.example/ota"]);
+    assert!(io.request(&caller, &discover_b).allowed);
+    assert_eq!(watcher.current().trusted_ota_sources, vec!["https://b.example/ota"]);
+
+    let replay = audit::replay(&audit_path)?;
+    assert!(replay.report.is_intact());
+    let kinds: Vec<_> = replay.records.iter().map(|r| r.event.kind.clone()).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            AuditEventKind::OtaRequest,
+            AuditEventKind::PolicyChange,
+            AuditEventKind::OtaRequest,
+            AuditEventKind::PolicyChange,
+            AuditEventKind::OtaRequest,
+        ]
+    );
+    assert_eq!(replay.records[3].event.policy_decision["allowed"], false);
+    Ok(())
+}
+
+#[test]
+fn unaudited_reload_or_decision_denies_the_request() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = setup();
+    write_policy(tmp.path(), &["https://a.example/ota"]);
+    let watcher = PolicyWatcher::open(tmp.path().join("policy.nrml"))?;
+
+    // The audit directory does not exist yet, so every append fails.
+    let audit_dir = tmp.path().join("audit");
+    let mut logger = AuditLogger::new(audit_dir.join("audit.log"));
+    let mut io = SovereignOtaIo::with_watcher(&watcher, &mut logger);
+    let caller = Caller {
+        module_id: "bf_ota_core".to_string(),
+        instance_id: None,
+    };
+    let discover = |source: &str| OtaAction::Discover {
+        source: source.to_string(),
+    };
+
+    let decision = io.request(&caller, &discover("https://a.example/ota"));
+    assert!(!decision.allowed);
+    assert!(decision.reason.contains("could not be audited"), "{}", decision.reason);
+
+    // The reload cannot be audited either: denied, and the old policy stays.
+    write_policy(tmp.path(), &["https://b.example/ota"]);
+    let decision = io.request(&caller, &discover("https://b.example/ota"));
+    assert!(!decision.allowed);
+    assert!(decision.reason.contains("policy reload failed"), "{}", decision.reason);
+    assert_eq!(watcher.current().trusted_ota_sources, vec!["https://a.example/ota"]);
+
+    // Once the log is writable the pending change is audited and applied.
+    std::fs::create_dir_all(&audit_dir)?;
+    assert!(io.request(&caller, &discover("https://b.example/ota")).allowed);
+    let replay = audit::replay(&audit_dir.join("audit.log"))?;
+    assert!(replay.report.is_intact());
+    let kinds: Vec<_> = replay.records.into_iter().map(|r| r.event.kind).collect();
+    assert_eq!(kinds, vec![AuditEventKind::PolicyChange, AuditEventKind::OtaRequest]);
+    Ok(())
+}
@@ -1,24 +1,36 @@
 #![forbid(unsafe_code)]
 
 use chrono::{TimeZone, Utc};
+use ed25519_dalek::SigningKey;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
 use neuro_pc::evolution::controller::EvolutionController;
 use neuro_pc::ota::controller::OtaController;
 use neuro_pc::sovereignty::consent::{AwarenessToken, ConsentObject};
-use neuro_pc::sovereignty::ota_io::{Caller, OtaAction, SovereignOtaIo};
+use neuro_pc::ota::slots::sha256_file;
+use neuro_pc::sovereignty::ota_io::{Caller, SovereignOtaIo};
 use neuro_pc::sovereignty::policy::NrmlPolicy;
 use neuro_pc::sovereignty::audit::AuditLogger;
 
-fn make_policy(consent_dir: PathBuf, owner_id: &str) -> NrmlPolicy {
+fn owner_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn make_policy(consent_dir: PathBuf, ota_root: PathBuf, source: &str, owner_id: &str) -> NrmlPolicy {
     NrmlPolicy {
         owner_id: owner_id.to_string(),
-        trusted_ota_sources: vec!["https://trusted.vendor.example/ota".to_string()],
+        trusted_ota_sources: vec![source.to_string()],
         sovereignty_core_enabled_flag: true,
-        rollback_available_flag: true,
         user_control_channel_flag: true,
         consent_dir,
+        ota_root,
+        owner_keys: BTreeMap::from([(
+            owner_id.to_string(),
+            hex::encode(owner_key().verifying_key().to_bytes()),
+        )]),
+        ota_rules: Default::default(),
     }
 }
 
@@ -31,8 +43,17 @@ fn ota_and_evolve_require_explicit_consent() -> Result<(), Box<dyn std::error::E
 
     let owner_id = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
 
+    // Package published at a trusted file:// source.
+    let source_dir = tmp.path().join("vendor");
+    std::fs::create_dir_all(&source_dir)?;
+    let payload_path = tmp.path().join("payload.bin");
+    std::fs::write(&payload_path, b"ota_core v2 payload")?;
+    let package_hash = sha256_file(&payload_path)?;
+    std::fs::rename(&payload_path, source_dir.join(format!("{}.pkg", package_hash)))?;
+    let source = format!("file://{}", source_dir.display());
+
     // Policy + controllers.
-    let policy = make_policy(consent_dir.clone(), owner_id);
+    let policy = make_policy(consent_dir.clone(), tmp.path().join("ota"), &source, owner_id);
     let mut logger = AuditLogger::new(tmp.path().join("audit.log"));
     let caller = Caller {
         module_id: "bf_ota_core".to_string(),
@@ -42,14 +63,19 @@ fn ota_and_evolve_require_explicit_consent() -> Result<(), Box<dyn std::error::E
     let ota = OtaController::new(&policy);
     let evo = EvolutionController::new(&policy);
 
-    let package_hash = "hash_ota_core_v2_abc123";
+    let package_hash = package_hash.as_str();
+
+    // 0) Download, verify and stage need no explicit consent.
+    ota.download_package(&mut io, &caller, &source, package_hash)?;
+    ota.verify_package(&mut io, &caller, package_hash)?;
+    ota.stage_package(&mut io, &caller, package_hash)?;
 
     // 1) OTA Commit with NO .cobj -> expect Err.
     let res_no_consent = ota.commit_package(&mut io, &caller, package_hash);
     assert!(res_no_consent.is_err(), "Commit without consent should fail");
 
     // 2) Add COMMIT .cobj, rerun -> expect Ok.
-    let commit_cobj = ConsentObject {
+    let mut commit_cobj = ConsentObject {
         id: "cobj_ota_core_v2_commit".to_string(),
         token: AwarenessToken::Commit,
         target_id: package_hash.to_string(),
@@ -57,7 +83,9 @@ fn ota_and_evolve_require_explicit_consent() -> Result<(), Box<dyn std::error::E
         created_at: Utc::now(),
         valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
         revoked: false,
+        signature: None,
     };
+    commit_cobj.sign(&owner_key());
     let commit_path = consent_dir.join("ota_commit_ota_core_v2.cobj");
     std::fs::write(
         &commit_path,
@@ -75,7 +103,7 @@ fn ota_and_evolve_require_explicit_consent() -> Result<(), Box<dyn std::error::E
     );
 
     // 4) Add EVOLVE .cobj, rerun -> expect Ok.
-    let evolve_cobj = ConsentObject {
+    let mut evolve_cobj = ConsentObject {
         id: "cobj_evolve_bf_ota_core".to_string(),
         token: AwarenessToken::Evolve,
         target_id: "bf_ota_core".to_string(),
@@ -83,7 +111,9 @@ fn ota_and_evolve_require_explicit_consent() -> Result<(), Box<dyn std::error::E
         created_at: Utc::now(),
         valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
         revoked: false,
+        signature: None,
     };
+    evolve_cobj.sign(&owner_key());
     let evolve_path = consent_dir.join("evolve_bf_ota_core.cobj");
     std::fs::write(
         &evolve_path,
@@ -97,11 +127,16 @@ fn ota_and_evolve_require_explicit_consent() -> Result<(), Box<dyn std::error::E
     );
 
     // 5) Optional: simulate expired consent to confirm deny.
+    ota.download_package(&mut io, &caller, &source, package_hash)?;
+    ota.verify_package(&mut io, &caller, package_hash)?;
+    ota.stage_package(&mut io, &caller, package_hash)?;
     let mut expired_cobj = commit_cobj;
     expired_cobj.valid_until = Some(
         Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+            .single()
             .expect("valid past datetime"),
     );
+    expired_cobj.sign(&owner_key());
     std::fs::write(
         &commit_path,
         serde_json::to_string_pretty(&expired_cobj)?,
@@ -0,0 +1,222 @@
+#![forbid(unsafe_code)]
+
+use chrono::{Duration, Utc};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tempfile::TempDir;
+
+use neuro_pc::sovereignty::audit::AuditLogger;
+use neuro_pc::sovereignty::ota_io::{Caller, OtaAction, SovereignOtaIo};
+use neuro_pc::sovereignty::ota_rules::{glob_match, OtaRules};
+use neuro_pc::sovereignty::policy::NrmlPolicy;
+use neuro_pc::sovereignty::policy_loader::{load_policy, parse_policy, PolicyLoadError};
+
+const OWNER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn caller(module_id: &str) -> Caller {
+    Caller {
+        module_id: module_id.to_string(),
+        instance_id: None,
+    }
+}
+
+fn rules(toml_text: &str) -> OtaRules {
+    toml::from_str(toml_text).expect("valid rule table")
+}
+
+#[test]
+fn glob_supports_prefix_suffix_and_infix() {
+    assert!(glob_match("https://vendor.example/*", "https://vendor.example/ota/v2"));
+    assert!(!glob_match("https://vendor.example/*", "https://evil.example/ota"));
+    assert!(glob_match("bf_*_core", "bf_ota_core"));
+    assert!(glob_match("*", ""));
+    assert!(!glob_match("bf_ota", "bf_ota_core"));
+}
+
+#[test]
+fn rules_restrict_callers_actions_and_sources() {
+    let table = rules(
+        r#"
+        default_deny = true
+
+        [[rules]]
+        id = "no_rollback_for_plugins"
+        caller = "plugin_*"
+        actions = ["Rollback"]
+        effect = "Deny"
+
+        [[rules]]
+        id = "plugins_fetch_from_mirror"
+        caller = "plugin_*"
+        actions = ["Download"]
+        sources = ["https://mirror.example/plugins/*"]
+        effect = "Allow"
+
+        [[rules]]
+        id = "plugins_install"
+        caller = "plugin_*"
+        actions = ["Verify", "Stage", "Commit"]
+        effect = "Allow"
+        "#,
+    );
+    let now = Utc::now();
+    let plugin = caller("plugin_dictation");
+    let download = |source: &str| OtaAction::Download {
+        source: source.to_string(),
+        package_hash: "h".to_string(),
+    };
+
+    let v = table.evaluate(&plugin, &download("https://mirror.example/plugins/a.pkg"), now);
+    assert!(v.allowed);
+    assert_eq!(v.rule.as_deref(), Some("plugins_fetch_from_mirror"));
+
+    // Source outside the caller's allowed prefix falls through to default deny.
+    let v = table.evaluate(&plugin, &download("https://mirror.example/core/a.pkg"), now);
+    assert!(!v.allowed);
+    assert_eq!(v.rule, None);
+
+    let rollback = OtaAction::Rollback {
+        target_version: "factory".to_string(),
+    };
+    let v = table.evaluate(&plugin, &rollback, now);
+    assert!(!v.allowed);
+    assert_eq!(v.rule.as_deref(), Some("no_rollback_for_plugins"));
+
+    // Unknown caller: nothing matches, deny by default.
+    let stage = OtaAction::Stage {
+        package_hash: "h".to_string(),
+    };
+    assert!(!table.evaluate(&caller("bf_ota_core"), &stage, now).allowed);
+    assert!(table.evaluate(&plugin, &stage, now).allowed);
+}
+
+#[test]
+fn commit_rate_limit_is_per_caller_and_window() {
+    let table = rules(
+        r#"
+        [[rules]]
+        id = "commit_quota"
+        caller = "*"
+        actions = ["Commit"]
+        effect = "Allow"
+        commit_rate_limit = { max = 2, window_secs = 3600 }
+        "#,
+    );
+    let commit = OtaAction::Commit {
+        package_hash: "h".to_string(),
+    };
+    let t0 = Utc::now();
+    let a = caller("module_a");
+    // Evaluate, then record the commit as the I/O façade does once it goes ahead.
+    let commit_at = |who: &Caller, at| {
+        let v = table.evaluate(who, &commit, at);
+        if v.allowed {
+            table.record_commit(who, v.rule.as_deref().unwrap(), at).unwrap();
+        }
+        v
+    };
+
+    // Evaluation alone does not use up the quota.
+    for _ in 0..3 {
+        assert!(table.evaluate(&a, &commit, t0).allowed);
+    }
+    assert!(commit_at(&a, t0).allowed);
+    assert!(commit_at(&a, t0 + Duration::minutes(1)).allowed);
+    let v = commit_at(&a, t0 + Duration::minutes(2));
+    assert!(!v.allowed);
+    assert_eq!(v.rule.as_deref(), Some("commit_quota"));
+    assert!(table.record_commit(&a, "commit_quota", t0 + Duration::minutes(2)).is_err());
+
+    // Other callers have their own window; the window slides.
+    assert!(commit_at(&caller("module_b"), t0).allowed);
+    assert!(commit_at(&a, t0 + Duration::minutes(61)).allowed);
+}
+
+#[test]
+fn commits_denied_after_the_rule_table_use_no_quota() {
+    let tmp = TempDir::new().unwrap();
+    let policy = NrmlPolicy {
+        owner_id: OWNER.to_string(),
+        trusted_ota_sources: Vec::new(),
+        sovereignty_core_enabled_flag: true,
+        user_control_channel_flag: true,
+        consent_dir: tmp.path().to_path_buf(),
+        ota_root: tmp.path().join("ota"),
+        owner_keys: BTreeMap::new(),
+        ota_rules: rules(
+            r#"
+            [[rules]]
+            id = "commit_quota"
+            caller = "*"
+            actions = ["Commit"]
+            effect = "Allow"
+            commit_rate_limit = { max = 2, window_secs = 3600 }
+            "#,
+        ),
+    };
+    let mut logger = AuditLogger::new(tmp.path().join("audit.log"));
+    let mut io = SovereignOtaIo::new(&policy, &mut logger);
+    let a = caller("module_a");
+    let commit = OtaAction::Commit {
+        package_hash: "h".to_string(),
+    };
+
+    // No consent objects: every commit fails the consent invariant.
+    for _ in 0..3 {
+        let d = io.request(&a, &commit);
+        assert!(!d.allowed);
+        assert!(!d.reason.contains("rate limit"), "{}", d.reason);
+    }
+    let now = Utc::now();
+    assert!(policy.ota_rules.record_commit(&a, "commit_quota", now).is_ok());
+    assert!(policy.ota_rules.record_commit(&a, "commit_quota", now).is_ok());
+    assert!(policy.ota_rules.record_commit(&a, "commit_quota", now).is_err());
+}
+
+#[test]
+fn rate_limit_only_loads_on_allow_rules_covering_commit() {
+    let tmp = TempDir::new().unwrap();
+    let issues_for = |rule: &str| {
+        let text = format!(
+            "schema = \"neuro_pc.nrml\"\nversion = 1\nowner_id = \"{OWNER}\"\nstake_file = \"stake.aln\"\n\
+             trusted_ota_sources = []\nsovereignty_core_enabled_flag = true\n\
+             user_control_channel_flag = true\nconsent_dir = \".\"\nota_root = \"ota\"\n\n\
+             [[ota_rules.rules]]\nid = \"r\"\ncaller = \"*\"\n{rule}\n\
+             commit_rate_limit = {{ max = 1, window_secs = 60 }}\n"
+        );
+        match parse_policy(&text, tmp.path()) {
+            Err(PolicyLoadError::Invalid(issues)) => issues,
+            other => panic!("expected Invalid (no stake file), got {:?}", other.map(|_| ())),
+        }
+    };
+    let flagged = |issues: Vec<String>| issues.iter().any(|i| i.contains("commit_rate_limit"));
+
+    assert!(flagged(issues_for("effect = \"Deny\"\nactions = [\"Commit\"]")));
+    assert!(flagged(issues_for("effect = \"Allow\"\nactions = [\"Stage\"]")));
+    assert!(!flagged(issues_for("effect = \"Allow\"\nactions = [\"Commit\"]")));
+    assert!(!flagged(issues_for("effect = \"Allow\"")));
+}
+
+#[test]
+fn shipped_policy_names_matching_rule() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("policies/neuro_pc.nrml");
+    let policy = load_policy(&path).expect("shipped .nrml validates");
+
+    let d = policy.evaluate_ota(
+        &caller("bf_ota_core"),
+        &OtaAction::Discover {
+            source: "https://trusted.vendor.example/ota".to_string(),
+        },
+    );
+    assert!(d.allowed);
+    assert_eq!(d.rule.as_deref(), Some("ota_core_fetch"));
+
+    let d = policy.evaluate_ota(
+        &caller("unknown_module"),
+        &OtaAction::Verify {
+            package_hash: "h".to_string(),
+        },
+    );
+    assert!(!d.allowed);
+    assert!(d.reason.contains("deny by default"));
+}
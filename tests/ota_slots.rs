@@ -0,0 +1,172 @@
+#![forbid(unsafe_code)]
+
+use chrono::Utc;
+use ed25519_dalek::SigningKey;
+use std::collections::BTreeMap;
+use std::path::Path;
+use tempfile::TempDir;
+
+use neuro_pc::ota::controller::OtaController;
+use neuro_pc::ota::slots::{sha256_file, FileFetcher, SlotEngine, SlotError, SlotId, FACTORY_VERSION};
+use neuro_pc::sovereignty::audit::{self, AuditLogger};
+use neuro_pc::sovereignty::consent::{AwarenessToken, ConsentObject};
+use neuro_pc::sovereignty::ota_io::{Caller, SovereignOtaIo};
+use neuro_pc::sovereignty::policy::NrmlPolicy;
+
+const OWNER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn owner_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn publish(source_dir: &Path, bytes: &[u8]) -> String {
+    let tmp = source_dir.join("incoming.bin");
+    std::fs::write(&tmp, bytes).unwrap();
+    let hash = sha256_file(&tmp).unwrap();
+    std::fs::rename(&tmp, source_dir.join(format!("{}.pkg", hash))).unwrap();
+    hash
+}
+
+fn grant_commit(consent_dir: &Path, target_id: &str) {
+    let mut cobj = ConsentObject {
+        id: format!("cobj_{}", target_id),
+        token: AwarenessToken::Commit,
+        target_id: target_id.to_string(),
+        owner_id: OWNER.to_string(),
+        created_at: Utc::now(),
+        valid_until: Some(Utc::now() + chrono::Duration::hours(1)),
+        revoked: false,
+        signature: None,
+    };
+    cobj.sign(&owner_key());
+    std::fs::write(
+        consent_dir.join(format!("{}.cobj", target_id)),
+        serde_json::to_string_pretty(&cobj).unwrap(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn install_commit_and_rollback_across_slots() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let consent_dir = tmp.path().join("consent");
+    let source_dir = tmp.path().join("vendor");
+    std::fs::create_dir_all(&consent_dir)?;
+    std::fs::create_dir_all(&source_dir)?;
+    let source = format!("file://{}", source_dir.display());
+
+    let policy = NrmlPolicy {
+        owner_id: OWNER.to_string(),
+        trusted_ota_sources: vec![source.clone()],
+        sovereignty_core_enabled_flag: true,
+        user_control_channel_flag: true,
+        consent_dir: consent_dir.clone(),
+        ota_root: tmp.path().join("ota"),
+        owner_keys: BTreeMap::from([(
+            OWNER.to_string(),
+            hex::encode(owner_key().verifying_key().to_bytes()),
+        )]),
+        ota_rules: Default::default(),
+    };
+    let audit_path = tmp.path().join("audit.log");
+    let mut logger = AuditLogger::new(audit_path.clone());
+    let mut io = SovereignOtaIo::new(&policy, &mut logger);
+    let ota = OtaController::new(&policy);
+    let slots = SlotEngine::new(&policy.ota_root);
+    let caller = Caller {
+        module_id: "bf_ota_core".to_string(),
+        instance_id: None,
+    };
+
+    // v1 -> slot A, rollback target is factory.
+    let v1 = publish(&source_dir, b"v1");
+    ota.download_package(&mut io, &caller, &source, &v1)?;
+    ota.verify_package(&mut io, &caller, &v1)?;
+    assert_eq!(ota.stage_package(&mut io, &caller, &v1)?, SlotId::A);
+    grant_commit(&consent_dir, &v1);
+    ota.commit_package(&mut io, &caller, &v1)?;
+    assert_eq!(slots.pointer()?.active, Some(SlotId::A));
+    assert_eq!(slots.rollback_version()?, FACTORY_VERSION);
+    std::fs::remove_file(consent_dir.join(format!("{}.cobj", v1)))?;
+
+    // v2 -> slot B, v1 becomes the rollback target.
+    let v2 = publish(&source_dir, b"v2");
+    ota.download_package(&mut io, &caller, &source, &v2)?;
+    ota.verify_package(&mut io, &caller, &v2)?;
+    assert_eq!(ota.stage_package(&mut io, &caller, &v2)?, SlotId::B);
+    grant_commit(&consent_dir, &v2);
+    ota.commit_package(&mut io, &caller, &v2)?;
+    assert_eq!(slots.pointer()?.active, Some(SlotId::B));
+    assert_eq!(slots.rollback_version()?, v1);
+    assert!(policy.rollback_path_available());
+
+    // Rollback needs consent on the target version and lands on slot A.
+    assert!(ota.rollback_to(&mut io, &caller, &v1).is_err());
+    grant_commit(&consent_dir, &v1);
+    ota.rollback_to(&mut io, &caller, &v1)?;
+    assert_eq!(slots.pointer()?.active, Some(SlotId::A));
+
+    // Every phase is in the audit trail and the chain verifies.
+    let replay = audit::replay(&audit_path)?;
+    assert!(replay.report.is_intact());
+    assert_eq!(replay.records.len(), 10);
+    Ok(())
+}
+
+#[test]
+fn verify_rejects_bad_hash_and_corrupt_slot_disables_rollback() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let source_dir = tmp.path().join("vendor");
+    std::fs::create_dir_all(&source_dir)?;
+    let source = format!("file://{}", source_dir.display());
+    let slots = SlotEngine::new(tmp.path().join("ota"));
+
+    // A package whose bytes do not match its advertised hash.
+    let claimed = "0".repeat(64);
+    std::fs::write(source_dir.join(format!("{}.pkg", claimed)), b"tampered")?;
+    slots.download(&neuro_pc::ota::slots::FileFetcher, &source, &claimed)?;
+    assert!(slots.verify(&claimed).is_err());
+    assert!(slots.stage(&claimed).is_err());
+
+    // Two good installs, then corrupt the rollback slot.
+    for bytes in [&b"v1"[..], &b"v2"[..]] {
+        let hash = publish(&source_dir, bytes);
+        slots.download(&neuro_pc::ota::slots::FileFetcher, &source, &hash)?;
+        slots.verify(&hash)?;
+        slots.stage(&hash)?;
+        slots.commit(&hash)?;
+    }
+    assert!(slots.rollback_available());
+    std::fs::write(tmp.path().join("ota/slot_a/package.pkg"), b"bitrot")?;
+    assert!(!slots.rollback_available());
+    Ok(())
+}
+
+#[test]
+fn package_hash_must_be_lowercase_sha256_hex() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = TempDir::new()?;
+    let source_dir = tmp.path().join("vendor");
+    std::fs::create_dir_all(&source_dir)?;
+    let source = format!("file://{}", source_dir.display());
+    let slots = SlotEngine::new(tmp.path().join("ota"));
+
+    // A package planted outside the source directory.
+    std::fs::write(tmp.path().join("escape.pkg"), b"outside")?;
+    let good = "a".repeat(64);
+    for bad in [
+        "../escape".to_string(),
+        format!("../{}", &good[3..]),
+        good.to_uppercase(),
+        good[1..].to_string(),
+        String::new(),
+    ] {
+        assert!(matches!(
+            slots.download(&FileFetcher, &source, &bad),
+            Err(SlotError::InvalidHash(_))
+        ));
+        assert!(matches!(slots.verify(&bad), Err(SlotError::InvalidHash(_))));
+        assert!(matches!(slots.stage(&bad), Err(SlotError::InvalidHash(_))));
+    }
+    assert!(!tmp.path().join("ota/staging").exists());
+    Ok(())
+}